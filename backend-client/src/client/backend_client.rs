@@ -0,0 +1,301 @@
+// 必要なクレートのインポート
+use reqwest::{header, Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+// 操作のインポート
+use crate::client::operations::{self, Operation};
+// エラーのインポート
+use crate::errors::client_error::ClientError;
+// モデルのインポート
+use crate::models::{
+    ApiKeyResponse, AuthResponseV2, ChangePasswordRequest, CreateApiKeyRequest, CreatedApiKeyResponse,
+    DeleteAccountRequest, ErrorResponse, NewUser, RecoveryCodesResponse, SessionResponse, SignInCredentials,
+    TwoFactorConfirmRequest, TwoFactorSetupResponse, TwoFactorVerifyRequest, UserProfileUpdate, UserResponse, UserUpdate,
+};
+
+// リクエストIDのヘッダー
+const REQUEST_ID_HEADER: &str = "x-request-id";
+// 冪等キーのヘッダー
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+// APIのクライアント
+//
+// クローンしたクライアントはHTTPの接続とトークンを共有する
+#[derive(Clone, Debug)]
+pub struct BackendClient {
+    // HTTPクライアント
+    http: reqwest::Client,
+    // APIのベースURL(例: http://localhost:3000)
+    base_url: String,
+    // Bearerトークン(サインインで設定し、サインアウトで破棄する)
+    token: Arc<RwLock<Option<String>>>,
+}
+
+// BackendClientの実装
+impl BackendClient {
+    // 新しいクライアントを作成する関数
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_http_client(base_url, reqwest::Client::new())
+    }
+
+    // HTTPクライアント(タイムアウトなど)を指定してクライアントを作成する関数
+    pub fn with_http_client(base_url: impl Into<String>, http: reqwest::Client) -> Self {
+        Self {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    // 発行済みのトークンを設定したクライアントを返す関数
+    pub fn with_token(self, token: impl Into<String>) -> Self {
+        self.set_token(Some(token.into()));
+        self
+    }
+
+    // 現在のトークンを取得する関数
+    pub fn token(&self) -> Option<String> {
+        self.token.read().unwrap().clone()
+    }
+
+    // トークンを設定(Noneで破棄)する関数
+    pub fn set_token(&self, token: Option<String>) {
+        *self.token.write().unwrap() = token;
+    }
+
+    // ユーザーの一覧を取得する関数
+    pub async fn list_users(&self) -> Result<Vec<UserResponse>, ClientError> {
+        let response = self.send(self.request(&operations::LIST_USERS, &[])).await?;
+        decode(response).await
+    }
+
+    // 特定のユーザーを取得する関数
+    pub async fn get_user(&self, id: Uuid) -> Result<UserResponse, ClientError> {
+        let id = id.to_string();
+        let response = self.send(self.request(&operations::GET_USER, &[&id])).await?;
+        decode(response).await
+    }
+
+    // ユーザーを作成する関数
+    pub async fn create_user(&self, new_user: &NewUser) -> Result<UserResponse, ClientError> {
+        let request = self.request(&operations::CREATE_USER, &[]).json(new_user);
+        decode(self.send(request).await?).await
+    }
+
+    // 冪等キーを付けてユーザーを作成する関数(同じキーでの再試行は最初のレスポンスを返す)
+    pub async fn create_user_with_idempotency_key(
+        &self,
+        new_user: &NewUser,
+        idempotency_key: &str,
+    ) -> Result<UserResponse, ClientError> {
+        let request = self
+            .request(&operations::CREATE_USER, &[])
+            .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
+            .json(new_user);
+        decode(self.send(request).await?).await
+    }
+
+    // ユーザーを更新する関数(パスワードは変更しない)
+    pub async fn update_user(&self, id: Uuid, user: &UserUpdate) -> Result<UserResponse, ClientError> {
+        let id = id.to_string();
+        let request = self.request(&operations::UPDATE_USER, &[&id]).json(user);
+        decode(self.send(request).await?).await
+    }
+
+    // ユーザーを削除する関数
+    pub async fn delete_user(&self, id: Uuid) -> Result<(), ClientError> {
+        let id = id.to_string();
+        self.send(self.request(&operations::DELETE_USER, &[&id])).await?;
+        Ok(())
+    }
+
+    // 自分のプロフィールを取得する関数
+    pub async fn get_me(&self) -> Result<UserResponse, ClientError> {
+        self.require_token()?;
+        decode(self.send(self.request(&operations::GET_ME, &[])).await?).await
+    }
+
+    // 自分のユーザー名・メールアドレスを更新する関数
+    pub async fn update_me(&self, profile: &UserProfileUpdate) -> Result<UserResponse, ClientError> {
+        self.require_token()?;
+        let request = self.request(&operations::UPDATE_ME, &[]).json(profile);
+        decode(self.send(request).await?).await
+    }
+
+    // 自分のアカウントを削除し、クライアントのトークンを破棄する関数
+    pub async fn delete_me(&self, password: &str) -> Result<(), ClientError> {
+        self.require_token()?;
+        let body = DeleteAccountRequest { password: password.to_string() };
+        self.send(self.request(&operations::DELETE_ME, &[]).json(&body)).await?;
+        self.set_token(None);
+        Ok(())
+    }
+
+    // 自分のパスワードを変更し、クライアントのトークンを破棄する関数
+    //
+    // 変更前に発行されたトークンはサーバーで無効になるため、新しいパスワードで再度サインインする
+    pub async fn change_password(&self, current_password: &str, new_password: &str) -> Result<(), ClientError> {
+        self.require_token()?;
+        let body = ChangePasswordRequest {
+            current_password: current_password.to_string(),
+            new_password: new_password.to_string(),
+        };
+        self.send(self.request(&operations::CHANGE_PASSWORD, &[]).json(&body)).await?;
+        self.set_token(None);
+        Ok(())
+    }
+
+    // 二段階認証の設定を開始する関数(認証アプリに登録する秘密鍵を返す)
+    pub async fn setup_two_factor(&self) -> Result<TwoFactorSetupResponse, ClientError> {
+        self.require_token()?;
+        decode(self.send(self.request(&operations::SETUP_TWO_FACTOR, &[])).await?).await
+    }
+
+    // 認証アプリのコードで二段階認証を有効にし、リカバリーコードを返す関数
+    pub async fn confirm_two_factor(&self, code: &str) -> Result<RecoveryCodesResponse, ClientError> {
+        self.require_token()?;
+        let body = TwoFactorConfirmRequest { code: code.to_string() };
+        decode(self.send(self.request(&operations::CONFIRM_TWO_FACTOR, &[]).json(&body)).await?).await
+    }
+
+    // ユーザーの二段階認証をリセットする関数(管理者のトークンが必要)
+    pub async fn reset_two_factor(&self, id: Uuid) -> Result<(), ClientError> {
+        self.require_token()?;
+        let id = id.to_string();
+        self.send(self.request(&operations::RESET_TWO_FACTOR, &[&id])).await?;
+        Ok(())
+    }
+
+    // 自分のAPIキーの一覧を取得する関数(サインインしたユーザーのトークンが必要)
+    pub async fn list_api_keys(&self) -> Result<Vec<ApiKeyResponse>, ClientError> {
+        self.require_token()?;
+        decode(self.send(self.request(&operations::LIST_API_KEYS, &[])).await?).await
+    }
+
+    // APIキーを発行する関数(キーは返されたレスポンスでのみ取得できる)
+    pub async fn create_api_key(&self, request: &CreateApiKeyRequest) -> Result<CreatedApiKeyResponse, ClientError> {
+        self.require_token()?;
+        decode(self.send(self.request(&operations::CREATE_API_KEY, &[]).json(request)).await?).await
+    }
+
+    // 自分のAPIキーを削除する関数
+    pub async fn delete_api_key(&self, id: Uuid) -> Result<(), ClientError> {
+        self.require_token()?;
+        let id = id.to_string();
+        self.send(self.request(&operations::DELETE_API_KEY, &[&id])).await?;
+        Ok(())
+    }
+
+    // 自分のサインインのセッションの一覧を取得する関数(サインインしたユーザーのトークンが必要)
+    pub async fn list_sessions(&self) -> Result<Vec<SessionResponse>, ClientError> {
+        self.require_token()?;
+        decode(self.send(self.request(&operations::LIST_SESSIONS, &[])).await?).await
+    }
+
+    // 自分のサインインのセッションを削除する関数(以降そのセッションのトークンは使えない)
+    pub async fn delete_session(&self, id: Uuid) -> Result<(), ClientError> {
+        self.require_token()?;
+        let id = id.to_string();
+        self.send(self.request(&operations::DELETE_SESSION, &[&id])).await?;
+        Ok(())
+    }
+
+    // サインインし、発行されたトークンをクライアントに設定する関数
+    //
+    // 二段階認証が有効なユーザーはClientError::TwoFactorRequiredを返すため、
+    // チャレンジトークンとコードでverify_two_factorを呼ぶ
+    pub async fn sign_in(&self, credentials: &SignInCredentials) -> Result<AuthResponseV2, ClientError> {
+        let request = self.request(&operations::SIGN_IN, &[]).json(credentials);
+        let response = self.send(request).await?;
+        if response.status() == StatusCode::ACCEPTED {
+            return Err(ClientError::TwoFactorRequired(decode(response).await?));
+        }
+
+        let response: AuthResponseV2 = decode(response).await?;
+        self.set_token(Some(response.token.clone()));
+        Ok(response)
+    }
+
+    // 二段階認証のコード(またはリカバリーコード)を検証し、発行されたトークンをクライアントに設定する関数
+    pub async fn verify_two_factor(&self, challenge_token: &str, code: &str) -> Result<AuthResponseV2, ClientError> {
+        let body = TwoFactorVerifyRequest {
+            challenge_token: challenge_token.to_string(),
+            code: code.to_string(),
+        };
+        let request = self.request(&operations::VERIFY_TWO_FACTOR, &[]).json(&body);
+        let response: AuthResponseV2 = decode(self.send(request).await?).await?;
+        self.set_token(Some(response.token.clone()));
+        Ok(response)
+    }
+
+    // トークンのユーザーを取得する関数(認証状態チェック)
+    pub async fn check_auth(&self) -> Result<UserResponse, ClientError> {
+        self.require_token()?;
+        let response = self.send(self.request(&operations::CHECK_AUTH, &[])).await?;
+        decode(response).await
+    }
+
+    // サインアウトし、クライアントのトークンを破棄する関数
+    pub async fn sign_out(&self) -> Result<(), ClientError> {
+        self.send(self.request(&operations::SIGN_OUT, &[])).await?;
+        self.set_token(None);
+        Ok(())
+    }
+
+    // トークンが設定されていることを確認する関数(なければリクエストを送らない)
+    fn require_token(&self) -> Result<(), ClientError> {
+        match self.token() {
+            Some(_) => Ok(()),
+            None => Err(ClientError::MissingToken),
+        }
+    }
+
+    // 操作のリクエストを作成する関数(トークンがあればAuthorizationヘッダーを付ける)
+    fn request(&self, operation: &Operation, params: &[&str]) -> RequestBuilder {
+        let method = Method::from_bytes(operation.method.as_bytes()).expect("無効なHTTPメソッドです");
+        let url = format!("{}{}", self.base_url, operation.url_path(params));
+        let request = self.http.request(method, url);
+        match self.token() {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    // リクエストを送信し、エラーレスポンスをClientErrorに変換する関数
+    async fn send(&self, request: RequestBuilder) -> Result<Response, ClientError> {
+        let response = request.header(header::ACCEPT, "application/json").send().await?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        // ボディにリクエストIDがなければヘッダーから取得する
+        let header_request_id = response
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = response.text().await?;
+        // ErrorResponseとして解析し、できなければ本文をメッセージにする
+        let error = match serde_json::from_str::<ErrorResponse>(&body) {
+            Ok(error) => ClientError::Api {
+                status,
+                message: error.message,
+                request_id: Some(error.request_id),
+            },
+            Err(_) => ClientError::Api {
+                status,
+                message: body,
+                request_id: header_request_id,
+            },
+        };
+        Err(error)
+    }
+}
+
+// レスポンスのボディをデコードする関数
+async fn decode<T: DeserializeOwned>(response: Response) -> Result<T, ClientError> {
+    let body = response.bytes().await?;
+    serde_json::from_slice(&body).map_err(|e| ClientError::Decode(e.to_string()))
+}
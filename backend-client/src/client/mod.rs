@@ -0,0 +1,5 @@
+// クライアントのモジュールの宣言
+pub mod backend_client;
+pub mod operations;
+
+// クライアントのエントリーポイント
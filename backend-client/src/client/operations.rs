@@ -0,0 +1,99 @@
+// クライアントが呼び出すAPIの操作
+//
+// パスはOpenAPIドキュメントの表記({id}など)のまま保持し、呼び出し時にパラメータを埋め込む
+
+// APIの操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operation {
+    // HTTPメソッド(大文字)
+    pub method: &'static str,
+    // パス(OpenAPIの表記)
+    pub path: &'static str,
+}
+
+// Operationの実装
+impl Operation {
+    // パスのパラメータを先頭から順に埋め込む関数
+    pub fn url_path(&self, params: &[&str]) -> String {
+        let mut path = String::with_capacity(self.path.len());
+        let mut params = params.iter();
+        let mut rest = self.path;
+        // {...} をパラメータで置き換える
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..].find('}').map(|i| start + i).unwrap_or(rest.len() - 1);
+            path.push_str(&rest[..start]);
+            path.push_str(params.next().expect("パスのパラメータが不足しています"));
+            rest = &rest[end + 1..];
+        }
+        path.push_str(rest);
+        path
+    }
+}
+
+// ユーザーの一覧を取得
+pub const LIST_USERS: Operation = Operation { method: "GET", path: "/v2/users" };
+// ユーザーを作成
+pub const CREATE_USER: Operation = Operation { method: "POST", path: "/v2/users" };
+// 特定のユーザーを取得
+pub const GET_USER: Operation = Operation { method: "GET", path: "/v2/users/{id}" };
+// ユーザーを更新
+pub const UPDATE_USER: Operation = Operation { method: "PUT", path: "/v2/users/{id}" };
+// ユーザーを削除
+pub const DELETE_USER: Operation = Operation { method: "DELETE", path: "/v2/users/{id}" };
+// 自分のプロフィールを取得
+pub const GET_ME: Operation = Operation { method: "GET", path: "/v2/me" };
+// 自分のプロフィールを更新
+pub const UPDATE_ME: Operation = Operation { method: "PATCH", path: "/v2/me" };
+// 自分のアカウントを削除
+pub const DELETE_ME: Operation = Operation { method: "DELETE", path: "/v2/me" };
+// 自分のパスワードを変更
+pub const CHANGE_PASSWORD: Operation = Operation { method: "POST", path: "/v2/me/password" };
+// 二段階認証の設定を開始
+pub const SETUP_TWO_FACTOR: Operation = Operation { method: "POST", path: "/v2/me/2fa/setup" };
+// 二段階認証を有効化
+pub const CONFIRM_TWO_FACTOR: Operation = Operation { method: "POST", path: "/v2/me/2fa/confirm" };
+// ユーザーの二段階認証をリセット(管理者)
+pub const RESET_TWO_FACTOR: Operation = Operation { method: "DELETE", path: "/v2/users/{id}/2fa" };
+// 自分のAPIキーの一覧を取得
+pub const LIST_API_KEYS: Operation = Operation { method: "GET", path: "/v2/me/api-keys" };
+// APIキーを発行
+pub const CREATE_API_KEY: Operation = Operation { method: "POST", path: "/v2/me/api-keys" };
+// 自分のAPIキーを削除
+pub const DELETE_API_KEY: Operation = Operation { method: "DELETE", path: "/v2/me/api-keys/{id}" };
+// 自分のサインインのセッションの一覧を取得
+pub const LIST_SESSIONS: Operation = Operation { method: "GET", path: "/v2/me/sessions" };
+// 自分のサインインのセッションを削除
+pub const DELETE_SESSION: Operation = Operation { method: "DELETE", path: "/v2/me/sessions/{id}" };
+// サインイン
+pub const SIGN_IN: Operation = Operation { method: "POST", path: "/v2/auth/signin" };
+// 認証状態チェック
+pub const CHECK_AUTH: Operation = Operation { method: "GET", path: "/v2/auth/check" };
+// サインアウト
+pub const SIGN_OUT: Operation = Operation { method: "POST", path: "/v2/auth/signout" };
+// 二段階認証のコードを検証(サインインの二段階目)
+pub const VERIFY_TWO_FACTOR: Operation = Operation { method: "POST", path: "/v2/auth/2fa/verify" };
+
+// クライアントが対応している全ての操作
+pub const OPERATIONS: &[Operation] = &[
+    LIST_USERS,
+    CREATE_USER,
+    GET_USER,
+    UPDATE_USER,
+    DELETE_USER,
+    GET_ME,
+    UPDATE_ME,
+    DELETE_ME,
+    CHANGE_PASSWORD,
+    SETUP_TWO_FACTOR,
+    CONFIRM_TWO_FACTOR,
+    RESET_TWO_FACTOR,
+    LIST_API_KEYS,
+    CREATE_API_KEY,
+    DELETE_API_KEY,
+    LIST_SESSIONS,
+    DELETE_SESSION,
+    SIGN_IN,
+    CHECK_AUTH,
+    SIGN_OUT,
+    VERIFY_TWO_FACTOR,
+];
@@ -0,0 +1,52 @@
+// 必要なクレートのインポート
+use reqwest::StatusCode;
+use thiserror::Error;
+// モデルのインポート
+use crate::models::TwoFactorChallenge;
+
+// クライアントのエラーの列挙型
+#[derive(Error, Debug)]
+pub enum ClientError {
+    // APIがエラーレスポンスを返した(ボディはErrorResponse)
+    #[error("APIエラー({status}): {message}")]
+    Api {
+        // ステータスコード
+        status: StatusCode,
+        // エラーメッセージ
+        message: String,
+        // リクエストID(問い合わせ時にサーバーのログと照合する)
+        request_id: Option<String>,
+    },
+    // 通信エラー
+    #[error("通信エラー: {0}")]
+    Transport(#[from] reqwest::Error),
+    // レスポンスのデコードエラー
+    #[error("デコードエラー: {0}")]
+    Decode(String),
+    // 認証が必要な操作でトークンが設定されていない
+    #[error("トークンが設定されていません")]
+    MissingToken,
+    // 二段階認証のコードが必要(チャレンジトークンをverify_two_factorに渡す)
+    #[error("二段階認証のコードが必要です")]
+    TwoFactorRequired(TwoFactorChallenge),
+}
+
+// ClientErrorの実装
+impl ClientError {
+    // APIのエラーレスポンスのステータスコードを取得する関数
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::Api { status, .. } => Some(*status),
+            ClientError::Transport(error) => error.status(),
+            _ => None,
+        }
+    }
+
+    // リクエストIDを取得する関数
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            ClientError::Api { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+}
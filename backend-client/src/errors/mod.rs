@@ -0,0 +1,4 @@
+// クライアントのエラーモジュールの宣言
+pub mod client_error;
+
+// エラーのエントリーポイント
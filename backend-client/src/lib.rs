@@ -0,0 +1,13 @@
+// APIのクライアント
+//
+// APIサーバー(backend)のv2のOpenAPIドキュメント(/api-docs/v2/openapi.json)に合わせて保守する。
+// ドキュメントとの差分は tests/client_integration_01_test.rs で検出する
+
+// モジュールの宣言と公開
+pub mod client;
+pub mod errors;
+pub mod models;
+
+// よく使う型をre-export
+pub use client::backend_client::BackendClient;
+pub use errors::client_error::ClientError;
@@ -0,0 +1,55 @@
+// 必要なクレートのインポート
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// APIキーの権限(スキーマ: ApiKeyScope)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiKeyScope {
+    // 参照
+    Read,
+    // 更新
+    Write,
+    // 管理者向けのAPI
+    Admin,
+}
+
+// APIキーの発行のリクエスト(スキーマ: CreateApiKeyRequest)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    // 名前
+    pub name: String,
+    // 権限
+    pub scopes: Vec<ApiKeyScope>,
+    // 有効日数(Noneの場合はサーバーの既定値)
+    pub expires_in_days: Option<u32>,
+}
+
+// APIキー(スキーマ: ApiKeyResponse、キーそのものは含まない)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKeyResponse {
+    // APIキーのID
+    pub id: Uuid,
+    // 名前
+    pub name: String,
+    // キーの先頭(識別用)
+    pub prefix: String,
+    // 権限
+    pub scopes: Vec<ApiKeyScope>,
+    // 有効期限
+    pub expires_at: NaiveDateTime,
+    // 最後に使われた日時
+    pub last_used_at: Option<NaiveDateTime>,
+    // 作成日時
+    pub created_at: NaiveDateTime,
+}
+
+// APIキーの発行のレスポンス(スキーマ: CreatedApiKeyResponse)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreatedApiKeyResponse {
+    // APIキー(このレスポンスでのみ返される)
+    pub key: String,
+    // 発行したAPIキー
+    pub api_key: ApiKeyResponse,
+}
@@ -0,0 +1,22 @@
+// 必要なクレートのインポート
+use serde::{Deserialize, Serialize};
+// ユーザーモデルのインポート
+use crate::models::users::UserResponse;
+
+// サインインの資格情報(スキーマ: SignInCredentials)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignInCredentials {
+    // メールアドレス
+    pub email: String,
+    // パスワード
+    pub password: String,
+}
+
+// サインインのレスポンス(スキーマ: AuthResponseV2)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthResponseV2 {
+    // JWTトークン
+    pub token: String,
+    // ユーザー
+    pub user: UserResponse,
+}
@@ -0,0 +1,11 @@
+// 必要なクレートのインポート
+use serde::{Deserialize, Serialize};
+
+// エラーレスポンス(スキーマ: ErrorResponse)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    // エラーメッセージ
+    pub message: String,
+    // リクエストID
+    pub request_id: String,
+}
@@ -0,0 +1,19 @@
+// モデルのモジュールの宣言
+pub mod api_key;
+pub mod auth;
+pub mod common;
+pub mod session;
+pub mod two_factor;
+pub mod users;
+
+// 共通の型をre-export
+pub use api_key::{ApiKeyResponse, ApiKeyScope, CreateApiKeyRequest, CreatedApiKeyResponse};
+pub use auth::{AuthResponseV2, SignInCredentials};
+pub use common::ErrorResponse;
+pub use session::SessionResponse;
+pub use two_factor::{
+    RecoveryCodesResponse, TwoFactorChallenge, TwoFactorConfirmRequest, TwoFactorSetupResponse, TwoFactorVerifyRequest,
+};
+pub use users::{ChangePasswordRequest, DeleteAccountRequest, NewUser, UserProfileUpdate, UserResponse, UserUpdate};
+
+// モデルのエントリーポイント
@@ -0,0 +1,23 @@
+// 必要なクレートのインポート
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// サインインのセッション(スキーマ: SessionResponse)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionResponse {
+    // セッションのID
+    pub id: Uuid,
+    // サインインした端末のUser-Agent
+    pub user_agent: Option<String>,
+    // サインインした端末のIPアドレス
+    pub ip_address: Option<String>,
+    // サインインした日時
+    pub created_at: NaiveDateTime,
+    // 最後にアクセスした日時
+    pub last_seen_at: NaiveDateTime,
+    // 有効期限
+    pub expires_at: NaiveDateTime,
+    // クライアントのトークンのセッションか
+    pub current: bool,
+}
@@ -0,0 +1,43 @@
+// 必要なクレートのインポート
+use serde::{Deserialize, Serialize};
+
+// 二段階認証の設定の開始のレスポンス(スキーマ: TwoFactorSetupResponse)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TwoFactorSetupResponse {
+    // 秘密鍵(Base32)
+    pub secret: String,
+    // 認証アプリに登録するURI
+    pub otpauth_uri: String,
+}
+
+// 二段階認証の有効化の確認(スキーマ: TwoFactorConfirmRequest)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TwoFactorConfirmRequest {
+    // 認証アプリに表示されたコード
+    pub code: String,
+}
+
+// リカバリーコード(スキーマ: RecoveryCodesResponse)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryCodesResponse {
+    // リカバリーコード(それぞれ1回だけ使える)
+    pub recovery_codes: Vec<String>,
+}
+
+// 二段階認証のチャレンジ(スキーマ: TwoFactorChallenge)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TwoFactorChallenge {
+    // verify_two_factorに渡すチャレンジトークン
+    pub challenge_token: String,
+    // チャレンジの有効秒数
+    pub expires_in: u64,
+}
+
+// 二段階認証のコードの検証(スキーマ: TwoFactorVerifyRequest)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TwoFactorVerifyRequest {
+    // サインインで返されたチャレンジトークン
+    pub challenge_token: String,
+    // 認証アプリに表示されたコード、またはリカバリーコード
+    pub code: String,
+}
@@ -0,0 +1,66 @@
+// 必要なクレートのインポート
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ユーザー(スキーマ: UserResponse、パスワードを含まない)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserResponse {
+    // ユーザーID
+    pub id: Uuid,
+    // ユーザー名
+    pub username: String,
+    // メールアドレス
+    pub email: String,
+    // 作成日時
+    pub created_at: NaiveDateTime,
+    // 更新日時
+    pub updated_at: NaiveDateTime,
+}
+
+// ユーザーの作成の内容(スキーマ: NewUser)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewUser {
+    // ユーザー名
+    pub username: String,
+    // メールアドレス
+    pub email: String,
+    // パスワード
+    pub password: String,
+}
+
+// ユーザーの更新の内容(スキーマ: UserUpdate、パスワードはchange_passwordで変更する)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserUpdate {
+    // ユーザー名
+    pub username: String,
+    // メールアドレス
+    pub email: String,
+}
+
+// プロフィールの更新(スキーマ: UserProfileUpdate、Noneの項目は変更しない)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserProfileUpdate {
+    // ユーザー名
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    // メールアドレス
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+// アカウントの削除(スキーマ: DeleteAccountRequest)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeleteAccountRequest {
+    // 現在のパスワード
+    pub password: String,
+}
+
+// パスワードの変更(スキーマ: ChangePasswordRequest)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangePasswordRequest {
+    // 現在のパスワード
+    pub current_password: String,
+    // 新しいパスワード
+    pub new_password: String,
+}
@@ -0,0 +1,443 @@
+// 必要なクレートのインポート
+use backend::{
+    middleware::versioning::api_version::ApiVersion,
+    routes::{create_routes, openapi_for},
+    services::auth::totp::Totp,
+    state::AppState,
+};
+use backend_client::{
+    client::operations::OPERATIONS,
+    models::{
+        ApiKeyResponse, ApiKeyScope, AuthResponseV2, ChangePasswordRequest, CreateApiKeyRequest, CreatedApiKeyResponse,
+        DeleteAccountRequest, ErrorResponse, NewUser, RecoveryCodesResponse, SessionResponse, SignInCredentials,
+        TwoFactorChallenge, TwoFactorConfirmRequest, TwoFactorSetupResponse, TwoFactorVerifyRequest, UserProfileUpdate,
+        UserResponse, UserUpdate,
+    },
+    BackendClient, ClientError,
+};
+// 必要なクレートのインポート
+use chrono::NaiveDate;
+use reqwest::StatusCode;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{collections::BTreeSet, net::SocketAddr, sync::Arc};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+// 存在するユーザーのID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// 存在しないユーザーのID
+const MISSING_ID: &str = "00000000-0000-0000-0000-000000000002";
+// サインインで作成するセッションのID
+const SESSION_ID: &str = "00000000-0000-0000-0000-0000000000e1";
+// テスト用のユーザーのパスワード
+const PASSWORD: &str = "password123";
+
+// テスト用のユーザーのJSON(Supabaseが返す行)
+fn test_user_row() -> Value {
+    json!({
+        "id": USER_ID,
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": bcrypt::hash(PASSWORD, 4).unwrap(),
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    })
+}
+
+// 作成の入力
+fn new_user() -> NewUser {
+    NewUser {
+        username: "test_user".to_string(),
+        email: "test@example.com".to_string(),
+        password: PASSWORD.to_string(),
+    }
+}
+
+// 更新の入力
+fn user_update() -> UserUpdate {
+    UserUpdate {
+        username: "test_user".to_string(),
+        email: "test@example.com".to_string(),
+    }
+}
+
+// テスト用のセッションのJSON(Supabaseが返す行)
+fn test_session_row() -> Value {
+    json!({
+        "id": SESSION_ID,
+        "user_id": USER_ID,
+        "user_agent": null,
+        "ip_address": "127.0.0.1",
+        "created_at": "2024-01-01T00:00:00",
+        "last_seen_at": "2024-01-01T00:00:00",
+        "expires_at": "2099-01-01T00:00:00",
+    })
+}
+
+// サインインのセッションのモックを設定(作成・取得・一覧・最終アクセス日時の更新・削除)
+async fn setup_session_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let mut mocks = Vec::new();
+    for (method, path) in [
+        ("POST", "/rest/v1/rpc/create_user_session".to_string()),
+        ("GET", format!("/rest/v1/trans_user_sessions?id=eq.{}", SESSION_ID)),
+        ("GET", format!("/rest/v1/trans_user_sessions?user_id=eq.{}&order=created_at.desc", USER_ID)),
+        ("POST", "/rest/v1/rpc/touch_user_session".to_string()),
+        ("POST", "/rest/v1/rpc/revoke_session_with_audit".to_string()),
+    ] {
+        mocks.push(mock_server
+            .mock(method, path.as_str())
+            .with_status(200)
+            .with_body(json!([test_session_row()]).to_string())
+            .create_async()
+            .await);
+    }
+    mocks
+}
+
+// Supabaseのモックを設定
+async fn setup_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let mut mocks = Vec::new();
+
+    // 一覧・1件・メールアドレスでの取得
+    for (path, body) in [
+        ("/rest/v1/trans_users".to_string(), json!([test_user_row()])),
+        (format!("/rest/v1/trans_users?id=eq.{}", USER_ID), json!([test_user_row()])),
+        (format!("/rest/v1/trans_users?id=eq.{}", MISSING_ID), json!([])),
+        ("/rest/v1/trans_users?email=eq.test%40example.com".to_string(), json!([test_user_row()])),
+    ] {
+        mocks.push(mock_server
+            .mock("GET", path.as_str())
+            .with_status(200)
+            .with_body(body.to_string())
+            .create_async()
+            .await);
+    }
+
+    // 作成・更新・削除
+    for function in [
+        "create_user_with_audit",
+        "update_user_with_audit",
+        "update_user_profile_with_audit",
+        "delete_user_with_audit",
+    ] {
+        mocks.push(mock_server
+            .mock("POST", format!("/rest/v1/rpc/{}", function).as_str())
+            .with_status(200)
+            .with_body(json!([test_user_row()]).to_string())
+            .create_async()
+            .await);
+    }
+
+    // サインインのセッション
+    mocks.extend(setup_session_mocks(mock_server).await);
+
+    mocks
+}
+
+// APIサーバーを同じプロセスで起動し、ベースURLを返す
+async fn spawn_server(supabase_url: String) -> String {
+    let app = create_routes(Arc::new(AppState::new(supabase_url, "test_key".to_string())));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+            .await
+            .unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+// v2のOpenAPIドキュメントをJSONで取得
+fn v2_document() -> Value {
+    serde_json::to_value(openapi_for(ApiVersion::V2)).unwrap()
+}
+
+// スキーマのプロパティ名を取得
+fn schema_properties(document: &Value, name: &str) -> BTreeSet<String> {
+    document["components"]["schemas"][name]["properties"]
+        .as_object()
+        .unwrap_or_else(|| panic!("スキーマ {} がありません", name))
+        .keys()
+        .cloned()
+        .collect()
+}
+
+// モデルのフィールド名を取得
+fn model_fields<T: Serialize>(model: &T) -> BTreeSet<String> {
+    serde_json::to_value(model).unwrap().as_object().unwrap().keys().cloned().collect()
+}
+
+// クライアントがv2の全ての操作に対応していることを確認
+#[test]
+fn test_client_covers_every_v2_operation() {
+    let document = v2_document();
+
+    // ドキュメントに記載された操作
+    let mut documented = BTreeSet::new();
+    for (path, item) in document["paths"].as_object().unwrap() {
+        for method in item.as_object().unwrap().keys() {
+            documented.insert((method.to_uppercase(), path.clone()));
+        }
+    }
+
+    // クライアントの操作
+    let implemented: BTreeSet<(String, String)> = OPERATIONS
+        .iter()
+        .map(|operation| (operation.method.to_string(), operation.path.to_string()))
+        .collect();
+
+    assert_eq!(implemented, documented);
+}
+
+// クライアントのモデルがスキーマと同じフィールドを持つことを確認
+#[test]
+fn test_models_match_schemas() {
+    let document = v2_document();
+    let timestamp = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    let user = UserResponse {
+        id: Uuid::nil(),
+        username: String::new(),
+        email: String::new(),
+        created_at: timestamp,
+        updated_at: timestamp,
+    };
+
+    let api_key = ApiKeyResponse {
+        id: Uuid::nil(),
+        name: String::new(),
+        prefix: String::new(),
+        scopes: vec![ApiKeyScope::Read],
+        expires_at: timestamp,
+        last_used_at: Some(timestamp),
+        created_at: timestamp,
+    };
+
+    let cases = [
+        ("UserResponse", model_fields(&user)),
+        ("NewUser", model_fields(&new_user())),
+        ("UserUpdate", model_fields(&user_update())),
+        ("SignInCredentials", model_fields(&SignInCredentials { email: String::new(), password: String::new() })),
+        ("AuthResponseV2", model_fields(&AuthResponseV2 { token: String::new(), user })),
+        ("UserProfileUpdate", model_fields(&UserProfileUpdate { username: Some(String::new()), email: Some(String::new()) })),
+        ("ChangePasswordRequest", model_fields(&ChangePasswordRequest { current_password: String::new(), new_password: String::new() })),
+        ("DeleteAccountRequest", model_fields(&DeleteAccountRequest { password: String::new() })),
+        ("ErrorResponse", model_fields(&ErrorResponse { message: String::new(), request_id: String::new() })),
+        ("TwoFactorSetupResponse", model_fields(&TwoFactorSetupResponse { secret: String::new(), otpauth_uri: String::new() })),
+        ("TwoFactorConfirmRequest", model_fields(&TwoFactorConfirmRequest { code: String::new() })),
+        ("RecoveryCodesResponse", model_fields(&RecoveryCodesResponse { recovery_codes: Vec::new() })),
+        ("TwoFactorChallenge", model_fields(&TwoFactorChallenge { challenge_token: String::new(), expires_in: 0 })),
+        ("TwoFactorVerifyRequest", model_fields(&TwoFactorVerifyRequest { challenge_token: String::new(), code: String::new() })),
+        ("CreateApiKeyRequest", model_fields(&CreateApiKeyRequest { name: String::new(), scopes: Vec::new(), expires_in_days: Some(1) })),
+        ("CreatedApiKeyResponse", model_fields(&CreatedApiKeyResponse { key: String::new(), api_key: api_key.clone() })),
+        ("ApiKeyResponse", model_fields(&api_key)),
+        ("SessionResponse", model_fields(&SessionResponse {
+            id: Uuid::nil(),
+            user_agent: Some(String::new()),
+            ip_address: Some(String::new()),
+            created_at: timestamp,
+            last_seen_at: timestamp,
+            expires_at: timestamp,
+            current: true,
+        })),
+    ];
+    for (name, fields) in cases {
+        assert_eq!(fields, schema_properties(&document, name), "{} のフィールドが異なります", name);
+    }
+}
+
+// ユーザーのCRUD操作をクライアントで実行できることを確認
+#[tokio::test]
+async fn test_user_operations() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mocks = setup_mocks(&mut mock_server).await;
+    let client = BackendClient::new(spawn_server(mock_server.url()).await);
+    let user_id = Uuid::parse_str(USER_ID).unwrap();
+
+    // 一覧の取得
+    let users = client.list_users().await.unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].id, user_id);
+
+    // 1件の取得
+    let user = client.get_user(user_id).await.unwrap();
+    assert_eq!(user.email, "test@example.com");
+
+    // 作成(冪等キーありとなし)
+    assert_eq!(client.create_user(&new_user()).await.unwrap().id, user_id);
+    let key = Uuid::new_v4().to_string();
+    let first = client.create_user_with_idempotency_key(&new_user(), &key).await.unwrap();
+    let replayed = client.create_user_with_idempotency_key(&new_user(), &key).await.unwrap();
+    assert_eq!(first, replayed);
+
+    // 更新
+    assert_eq!(client.update_user(user_id, &user_update()).await.unwrap().id, user_id);
+
+    // 削除
+    client.delete_user(user_id).await.unwrap();
+}
+
+// エラーレスポンスがClientErrorに変換されることを確認
+#[tokio::test]
+async fn test_error_responses() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mocks = setup_mocks(&mut mock_server).await;
+    let client = BackendClient::new(spawn_server(mock_server.url()).await);
+
+    // 存在しないユーザー
+    let error = client.get_user(Uuid::parse_str(MISSING_ID).unwrap()).await.unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+    assert!(matches!(&error, ClientError::Api { message, .. } if !message.is_empty()));
+    assert!(error.request_id().is_some_and(|id| !id.is_empty()));
+
+    // 誤ったパスワード
+    let error = client
+        .sign_in(&SignInCredentials { email: "test@example.com".to_string(), password: "wrong".to_string() })
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::UNAUTHORIZED));
+    assert!(client.token().is_none());
+
+    // トークンなしの認証状態チェックはリクエストを送らない
+    assert!(matches!(client.check_auth().await, Err(ClientError::MissingToken)));
+
+    // 無効なトークン
+    let client = client.with_token("invalid");
+    assert_eq!(client.check_auth().await.unwrap_err().status(), Some(StatusCode::UNAUTHORIZED));
+}
+
+// サインインで設定したトークンで認証され、サインアウトで破棄されることを確認
+#[tokio::test]
+async fn test_token_lifecycle() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mocks = setup_mocks(&mut mock_server).await;
+    let client = BackendClient::new(spawn_server(mock_server.url()).await);
+
+    // サインイン
+    let response = client
+        .sign_in(&SignInCredentials { email: "test@example.com".to_string(), password: PASSWORD.to_string() })
+        .await
+        .unwrap();
+    assert_eq!(client.token().as_deref(), Some(response.token.as_str()));
+
+    // クローンしたクライアントもトークンを共有する
+    let user = client.clone().check_auth().await.unwrap();
+    assert_eq!(user, response.user);
+    assert_eq!(client.get_me().await.unwrap(), response.user);
+
+    // サインアウト
+    client.sign_out().await.unwrap();
+    assert!(client.token().is_none());
+}
+
+// サインインのセッションの一覧と削除ができることを確認
+#[tokio::test]
+async fn test_session_operations() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mocks = setup_mocks(&mut mock_server).await;
+    let client = BackendClient::new(spawn_server(mock_server.url()).await);
+
+    // トークンなしではリクエストを送らない
+    assert!(matches!(client.list_sessions().await, Err(ClientError::MissingToken)));
+
+    client
+        .sign_in(&SignInCredentials { email: "test@example.com".to_string(), password: PASSWORD.to_string() })
+        .await
+        .unwrap();
+
+    // 一覧には呼び出し元のセッションが含まれる
+    let sessions = client.list_sessions().await.unwrap();
+    let session_id = Uuid::parse_str(SESSION_ID).unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id, session_id);
+    assert!(sessions[0].current);
+
+    // 削除
+    client.delete_session(session_id).await.unwrap();
+}
+
+// 自分のプロフィールの更新とアカウントの削除ができることを確認
+#[tokio::test]
+async fn test_me_operations() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mocks = setup_mocks(&mut mock_server).await;
+    let client = BackendClient::new(spawn_server(mock_server.url()).await);
+
+    // トークンなしではリクエストを送らない
+    assert!(matches!(client.get_me().await, Err(ClientError::MissingToken)));
+
+    client
+        .sign_in(&SignInCredentials { email: "test@example.com".to_string(), password: PASSWORD.to_string() })
+        .await
+        .unwrap();
+
+    // 誤った現在のパスワードでは変更できない
+    let error = client.change_password("wrong", "new_password456").await.unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::FORBIDDEN));
+
+    // プロフィールの更新
+    let profile = UserProfileUpdate { username: Some("renamed".to_string()), ..Default::default() };
+    assert_eq!(client.update_me(&profile).await.unwrap().id, Uuid::parse_str(USER_ID).unwrap());
+
+    // 誤ったパスワードでは削除できない
+    let error = client.delete_me("wrong").await.unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::FORBIDDEN));
+    assert!(client.token().is_some());
+
+    // 削除するとトークンを破棄する
+    client.delete_me(PASSWORD).await.unwrap();
+    assert!(client.token().is_none());
+}
+
+// 二段階認証が有効なユーザーは、チャレンジの検証でサインインが完了することを確認
+#[tokio::test]
+async fn test_two_factor_sign_in() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let secret = "JBSWY3DPEHPK3PXP";
+    let mut user = test_user_row();
+    user["two_factor_enabled"] = json!(true);
+    let _user_mock = mock_server
+        .mock("GET", "/rest/v1/trans_users?email=eq.test%40example.com")
+        .with_status(200)
+        .with_body(json!([user.clone()]).to_string())
+        .create_async()
+        .await;
+    let _user_by_id_mock = mock_server
+        .mock("GET", format!("/rest/v1/trans_users?id=eq.{}", USER_ID).as_str())
+        .with_status(200)
+        .with_body(json!([user]).to_string())
+        .create_async()
+        .await;
+    let _two_factor_mock = mock_server
+        .mock("GET", format!("/rest/v1/trans_user_two_factor?user_id=eq.{}", USER_ID).as_str())
+        .with_status(200)
+        .with_body(json!([{
+            "user_id": USER_ID,
+            "secret": secret,
+            "last_used_step": null,
+            "confirmed_at": "2024-01-01T00:00:00",
+        }]).to_string())
+        .create_async()
+        .await;
+    let _record_step_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/record_two_factor_step")
+        .with_status(200)
+        .with_body(json!([{ "user_id": USER_ID }]).to_string())
+        .create_async()
+        .await;
+    let _session_mocks = setup_session_mocks(&mut mock_server).await;
+    let client = BackendClient::new(spawn_server(mock_server.url()).await);
+
+    // サインインはトークンの代わりにチャレンジを返す
+    let credentials = SignInCredentials { email: "test@example.com".to_string(), password: PASSWORD.to_string() };
+    let challenge = match client.sign_in(&credentials).await {
+        Err(ClientError::TwoFactorRequired(challenge)) => challenge,
+        other => panic!("unexpected result: {:?}", other),
+    };
+    assert!(client.token().is_none());
+
+    // 認証アプリのコードで検証するとトークンを保持する
+    let code = Totp::from_base32(secret).unwrap().code_at(chrono::Utc::now().timestamp());
+    let response = client.verify_two_factor(&challenge.challenge_token, &code).await.unwrap();
+    assert_eq!(client.token().as_deref(), Some(response.token.as_str()));
+}
@@ -0,0 +1,16 @@
+// 必要なクレートのインポート
+use std::time::Duration;
+use async_trait::async_trait;
+
+// キャッシュの保存先のトレイト
+//
+// 値はシリアライズ済みのバイト列で受け渡すため、Redisなどの外部ストアにも差し替えられる
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    // 値を取得する(期限切れ・未登録の場合はNone)
+    async fn get(&self, key: &str) -> Option<Vec<u8>>;
+    // 値を有効期限つきで保存する
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration);
+    // 値を削除する
+    async fn delete(&self, key: &str);
+}
@@ -0,0 +1,128 @@
+// 必要なクレートのインポート
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+use async_trait::async_trait;
+use tokio::time::Instant;
+// キャッシュの保存先のトレイトのインポート
+use crate::cache::cache_backend::CacheBackend;
+
+// エントリ
+struct Entry {
+    // 保存した値
+    value: Vec<u8>,
+    // 有効期限
+    expires_at: Instant,
+    // 最後に使われた順番
+    last_used: u64,
+}
+
+// キャッシュの中身
+#[derive(Default)]
+struct LruState {
+    // キーごとのエントリ
+    entries: HashMap<String, Entry>,
+    // 使われた順番ごとのキー(先頭が最も古い)
+    order: BTreeMap<u64, String>,
+    // 次に割り当てる順番
+    tick: u64,
+}
+
+// LruStateの実装
+impl LruState {
+    // 次の順番を取得する関数
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    // エントリを削除する関数
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.last_used);
+        }
+    }
+}
+
+// メモリ上に値を保持するLRUキャッシュ(容量を超えると最も使われていない値から削除する)
+pub struct InMemoryLruCache {
+    // 保持する最大件数
+    capacity: usize,
+    // キャッシュの中身
+    state: Mutex<LruState>,
+}
+
+// InMemoryLruCacheの実装
+impl InMemoryLruCache {
+    // コンストラクタ
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    // 保持している件数を取得する関数(期限切れのエントリを含む)
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).entries.len()
+    }
+
+    // 空か確認する関数
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// トレイト実装
+#[async_trait]
+impl CacheBackend for InMemoryLruCache {
+    async fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        // 期限切れのエントリは削除
+        let expired = state.entries.get(key)?.expires_at <= Instant::now();
+        if expired {
+            state.remove(key);
+            return None;
+        }
+
+        // 最後に使われた順番を更新
+        let tick = state.next_tick();
+        let entry = state.entries.get_mut(key)?;
+        let previous = std::mem::replace(&mut entry.last_used, tick);
+        let value = entry.value.clone();
+        state.order.remove(&previous);
+        state.order.insert(tick, key.to_string());
+
+        Some(value)
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) {
+        // 容量が0の場合は保存しない
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.remove(key);
+
+        // 容量を超える場合は最も使われていないエントリから削除
+        while state.entries.len() >= self.capacity {
+            let Some((_, oldest)) = state.order.pop_first() else { break };
+            state.entries.remove(&oldest);
+        }
+
+        // 新しいエントリを登録
+        let tick = state.next_tick();
+        state.order.insert(tick, key.to_string());
+        state.entries.insert(key.to_string(), Entry {
+            value,
+            expires_at: Instant::now() + ttl,
+            last_used: tick,
+        });
+    }
+
+    async fn delete(&self, key: &str) {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).remove(key);
+    }
+}
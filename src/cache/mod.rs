@@ -0,0 +1,7 @@
+// キャッシュのモジュールの宣言
+pub mod cache_backend;
+pub mod lru_cache;
+pub mod user_cache;
+
+#[cfg(test)]
+mod tests;
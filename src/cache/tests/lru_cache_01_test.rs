@@ -0,0 +1,73 @@
+use std::time::Duration;
+// キャッシュのインポート
+use crate::cache::cache_backend::CacheBackend;
+use crate::cache::lru_cache::InMemoryLruCache;
+
+// 保存・取得・削除のテスト
+#[tokio::test]
+async fn test_set_get_and_delete() {
+    let cache = InMemoryLruCache::new(10);
+    let ttl = Duration::from_secs(60);
+
+    // 未登録のキーはNone
+    assert_eq!(cache.get("a").await, None);
+
+    // 保存した値を取得できる
+    cache.set("a", b"1".to_vec(), ttl).await;
+    assert_eq!(cache.get("a").await, Some(b"1".to_vec()));
+
+    // 上書きすると新しい値を返す
+    cache.set("a", b"2".to_vec(), ttl).await;
+    assert_eq!(cache.get("a").await, Some(b"2".to_vec()));
+    assert_eq!(cache.len(), 1);
+
+    // 削除すると取得できない
+    cache.delete("a").await;
+    assert_eq!(cache.get("a").await, None);
+    assert!(cache.is_empty());
+}
+
+// 有効期限が切れた値は返さないことのテスト
+#[tokio::test(start_paused = true)]
+async fn test_expired_entries_are_not_returned() {
+    let cache = InMemoryLruCache::new(10);
+    cache.set("a", b"1".to_vec(), Duration::from_secs(30)).await;
+
+    // 期限内は取得できる
+    tokio::time::advance(Duration::from_secs(29)).await;
+    assert_eq!(cache.get("a").await, Some(b"1".to_vec()));
+
+    // 期限切れは取得できず、エントリも削除される
+    tokio::time::advance(Duration::from_secs(2)).await;
+    assert_eq!(cache.get("a").await, None);
+    assert!(cache.is_empty());
+}
+
+// 容量を超えると最も使われていない値から削除されることのテスト
+#[tokio::test]
+async fn test_evicts_least_recently_used() {
+    let cache = InMemoryLruCache::new(2);
+    let ttl = Duration::from_secs(60);
+
+    cache.set("a", b"1".to_vec(), ttl).await;
+    cache.set("b", b"2".to_vec(), ttl).await;
+
+    // aを使うとbが最も古くなる
+    assert!(cache.get("a").await.is_some());
+    cache.set("c", b"3".to_vec(), ttl).await;
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get("b").await, None);
+    assert_eq!(cache.get("a").await, Some(b"1".to_vec()));
+    assert_eq!(cache.get("c").await, Some(b"3".to_vec()));
+}
+
+// 容量が0の場合は保存しないことのテスト
+#[tokio::test]
+async fn test_zero_capacity_stores_nothing() {
+    let cache = InMemoryLruCache::new(0);
+    cache.set("a", b"1".to_vec(), Duration::from_secs(60)).await;
+
+    assert_eq!(cache.get("a").await, None);
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,2 @@
+pub mod lru_cache_01_test;
+pub mod user_cache_01_test;
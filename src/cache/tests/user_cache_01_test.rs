@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use uuid::Uuid;
+// キャッシュのインポート
+use crate::cache::lru_cache::InMemoryLruCache;
+use crate::cache::user_cache::UserCache;
+// モデルのインポート
+use crate::models::users::users::User;
+
+// テスト用のキャッシュを作成
+fn create_test_cache() -> UserCache {
+    UserCache::new(Arc::new(InMemoryLruCache::new(10)), Duration::from_secs(60))
+}
+
+// テスト用のユーザー
+fn test_user(username: &str) -> User {
+    User {
+        id: Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap(),
+        username: username.to_string(),
+        email: "test@example.com".to_string(),
+        password: "hashed_password".to_string(),
+        two_factor_enabled: false,
+        created_at: Utc::now().naive_utc(),
+        updated_at: Utc::now().naive_utc(),
+    }
+}
+
+// 保存したユーザーを取得でき、無効化すると取得できないことのテスト
+#[tokio::test]
+async fn test_store_and_invalidate() {
+    let cache = create_test_cache();
+    let user = test_user("test_user");
+
+    cache.store(&user, cache.generation(user.id)).await;
+    assert_eq!(cache.get(user.id).await.unwrap().username, "test_user");
+
+    cache.invalidate(user.id).await;
+    assert!(cache.get(user.id).await.is_none());
+}
+
+// 取得中に無効化された場合、取得前の世代の古い行は保存しないことのテスト
+#[tokio::test]
+async fn test_stale_store_after_invalidate_is_ignored() {
+    let cache = create_test_cache();
+    let stale = test_user("stale_user");
+
+    // 取得を始めた後に更新され、無効化された
+    let generation = cache.generation(stale.id);
+    cache.invalidate(stale.id).await;
+    cache.store(&stale, generation).await;
+    assert!(cache.get(stale.id).await.is_none());
+
+    // 無効化の後に始めた取得の結果は保存する
+    let fresh = test_user("fresh_user");
+    cache.store(&fresh, cache.generation(fresh.id)).await;
+    assert_eq!(cache.get(fresh.id).await.unwrap().username, "fresh_user");
+}
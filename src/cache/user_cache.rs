@@ -0,0 +1,91 @@
+// 必要なクレートのインポート
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use uuid::Uuid;
+// ユーザー
+use crate::models::users::users::User;
+// キャッシュの保存先
+use crate::cache::cache_backend::CacheBackend;
+
+// キャッシュ名(キーの接頭辞)
+const CACHE_NAME: &str = "user";
+// 世代のカウンターの数(ユーザーIDで振り分け、ユーザー数によらずメモリを一定にする)
+const GENERATION_SLOTS: usize = 1024;
+
+// ユーザーのキャッシュ(全てのコンテナで共有する、無効化の唯一の窓口)
+//
+// 無効化のたびにユーザーの世代を進め、取得を始めた時点から世代が変わった結果は保存しない。
+// 取得中に更新・削除された場合に、古い行で上書きするのを防ぐ
+pub struct UserCache {
+    // キャッシュの保存先
+    backend: Arc<dyn CacheBackend>,
+    // キャッシュを保持する時間
+    ttl: Duration,
+    // 世代のカウンター
+    generations: Vec<AtomicU64>,
+}
+
+// 実装
+impl UserCache {
+    // コンストラクタ
+    pub fn new(backend: Arc<dyn CacheBackend>, ttl: Duration) -> Self {
+        Self {
+            backend,
+            ttl,
+            generations: (0..GENERATION_SLOTS).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    // キャッシュのキーを作成
+    fn cache_key(id: Uuid) -> String {
+        format!("{}:{}", CACHE_NAME, id)
+    }
+
+    // ユーザーの世代のカウンター
+    fn slot(&self, id: Uuid) -> &AtomicU64 {
+        &self.generations[(id.as_u128() % GENERATION_SLOTS as u128) as usize]
+    }
+
+    // 現在の世代を取得する(取得を始める前に呼び出し、storeに渡す)
+    pub fn generation(&self, id: Uuid) -> u64 {
+        self.slot(id).load(Ordering::SeqCst)
+    }
+
+    // キャッシュしたユーザーを取得する(デコードできない値は削除してNone)
+    pub async fn get(&self, id: Uuid) -> Option<User> {
+        let key = Self::cache_key(id);
+        let value = self.backend.get(&key).await?;
+        match serde_json::from_slice::<User>(&value) {
+            Ok(user) => Some(user),
+            Err(_) => {
+                self.backend.delete(&key).await;
+                None
+            }
+        }
+    }
+
+    // ユーザーを保存する(generationから世代が進んでいる場合は保存しない)
+    pub async fn store(&self, user: &User, generation: u64) {
+        if self.generation(user.id) != generation {
+            return;
+        }
+        // シリアライズできない場合は保存しない
+        let Ok(value) = serde_json::to_vec(user) else {
+            return;
+        };
+        let key = Self::cache_key(user.id);
+        self.backend.set(&key, value, self.ttl).await;
+
+        // 保存の間に無効化された場合は、保存した値を消す
+        if self.generation(user.id) != generation {
+            self.backend.delete(&key).await;
+        }
+    }
+
+    // ユーザーのキャッシュを無効化する(世代を進めてから削除する)
+    pub async fn invalidate(&self, id: Uuid) {
+        self.slot(id).fetch_add(1, Ordering::SeqCst);
+        self.backend.delete(&Self::cache_key(id)).await;
+    }
+}
@@ -0,0 +1,34 @@
+// 必要なクレートのインポート
+use std::collections::HashSet;
+use uuid::Uuid;
+
+// 管理者の設定を管理する構造体
+#[derive(Clone, Debug, Default)]
+pub struct AdminConfig {
+    // 管理者のユーザーID(空の場合は管理者向けのAPIを誰も呼び出せない)
+    pub user_ids: HashSet<Uuid>,
+}
+
+// AdminConfigの実装
+impl AdminConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - ADMIN_USER_IDS: 管理者のユーザーIDのカンマ区切り(既定値: なし、不正なIDは無視する)
+    pub fn from_env() -> Self {
+        Self {
+            user_ids: std::env::var("ADMIN_USER_IDS")
+                .map(|value| {
+                    value
+                        .split(',')
+                        .filter_map(|id| Uuid::parse_str(id.trim()).ok())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    // 管理者かどうかを判定する関数
+    pub fn is_admin(&self, user_id: Uuid) -> bool {
+        self.user_ids.contains(&user_id)
+    }
+}
@@ -0,0 +1,70 @@
+// 必要なクレートのインポート
+use std::time::Duration;
+
+// 1ユーザーが発行できるAPIキーの数の既定値
+const DEFAULT_MAX_KEYS_PER_USER: usize = 10;
+// 有効日数を指定しない場合の有効日数の既定値
+const DEFAULT_TTL_DAYS: u32 = 90;
+// 指定できる有効日数の上限の既定値
+const DEFAULT_MAX_TTL_DAYS: u32 = 365;
+// 最終使用日時を更新する間隔の既定値(1分)
+const DEFAULT_LAST_USED_INTERVAL_SECS: u64 = 60;
+
+// APIキーの設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct ApiKeyConfig {
+    // 1ユーザーが発行できるAPIキーの数(期限切れのキーを含む)
+    pub max_keys_per_user: usize,
+    // 有効日数を指定しない場合の有効日数
+    pub default_ttl_days: u32,
+    // 指定できる有効日数の上限
+    pub max_ttl_days: u32,
+    // 最終使用日時を更新する間隔(リクエストごとに書き込まないようにする)
+    pub last_used_interval: Duration,
+}
+
+// ApiKeyConfigの実装
+impl ApiKeyConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - API_KEY_MAX_PER_USER: 1ユーザーが発行できるAPIキーの数(既定値: 10)
+    // - API_KEY_DEFAULT_TTL_DAYS: 有効日数を指定しない場合の有効日数(既定値: 90)
+    // - API_KEY_MAX_TTL_DAYS: 指定できる有効日数の上限(既定値: 365)
+    // - API_KEY_LAST_USED_INTERVAL_SECS: 最終使用日時を更新する間隔の秒数(既定値: 60)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        Self {
+            max_keys_per_user: std::env::var("API_KEY_MAX_PER_USER")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.max_keys_per_user),
+            default_ttl_days: std::env::var("API_KEY_DEFAULT_TTL_DAYS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.default_ttl_days),
+            max_ttl_days: std::env::var("API_KEY_MAX_TTL_DAYS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.max_ttl_days),
+            last_used_interval: std::env::var("API_KEY_LAST_USED_INTERVAL_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default.last_used_interval),
+        }
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for ApiKeyConfig {
+    fn default() -> Self {
+        Self {
+            max_keys_per_user: DEFAULT_MAX_KEYS_PER_USER,
+            default_ttl_days: DEFAULT_TTL_DAYS,
+            max_ttl_days: DEFAULT_MAX_TTL_DAYS,
+            last_used_interval: Duration::from_secs(DEFAULT_LAST_USED_INTERVAL_SECS),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// 必要なクレートのインポート
+use std::time::Duration;
+
+// ユーザーのキャッシュを保持する時間の既定値(60秒)
+const DEFAULT_TTL_SECS: u64 = 60;
+// キャッシュに保持するユーザー数の既定値
+const DEFAULT_CAPACITY: usize = 1000;
+
+// ユーザーの読み取りキャッシュの設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct CacheConfig {
+    // キャッシュを有効にするか
+    pub enabled: bool,
+    // キャッシュを保持する時間(他のインスタンスでの更新はこの時間だけ遅れて反映される)
+    pub ttl: Duration,
+    // キャッシュに保持する最大件数
+    pub capacity: usize,
+}
+
+// CacheConfigの実装
+impl CacheConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - USER_CACHE_ENABLED: キャッシュを有効にするか(既定値: true)
+    // - USER_CACHE_TTL_SECS: キャッシュを保持する秒数(既定値: 60)
+    // - USER_CACHE_CAPACITY: キャッシュに保持する最大件数(既定値: 1000)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        Self {
+            enabled: std::env::var("USER_CACHE_ENABLED")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.enabled),
+            ttl: std::env::var("USER_CACHE_TTL_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default.ttl),
+            capacity: std::env::var("USER_CACHE_CAPACITY")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.capacity),
+        }
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl: Duration::from_secs(DEFAULT_TTL_SECS),
+            capacity: DEFAULT_CAPACITY,
+        }
+    }
+}
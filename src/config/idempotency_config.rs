@@ -0,0 +1,50 @@
+// 必要なクレートのインポート
+use std::time::Duration;
+
+// 保存したレスポンスを保持する時間の既定値(24時間)
+const DEFAULT_TTL_SECS: u64 = 24 * 60 * 60;
+// 保存するレスポンスボディの最大サイズの既定値(1MiB)
+const DEFAULT_MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+
+// 冪等キー(Idempotency-Key)の設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct IdempotencyConfig {
+    // 最初のレスポンスを保持する時間
+    pub ttl: Duration,
+    // 保存するレスポンスボディの最大サイズ(超える場合は保存しない)
+    pub max_response_bytes: usize,
+}
+
+// IdempotencyConfigの実装
+impl IdempotencyConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - IDEMPOTENCY_TTL_SECS: レスポンスを保持する秒数(既定値: 86400)
+    // - IDEMPOTENCY_MAX_RESPONSE_BYTES: 保存するレスポンスボディの最大サイズ(既定値: 1048576)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        Self {
+            ttl: std::env::var("IDEMPOTENCY_TTL_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default.ttl),
+            max_response_bytes: std::env::var("IDEMPOTENCY_MAX_RESPONSE_BYTES")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.max_response_bytes),
+        }
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(DEFAULT_TTL_SECS),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        }
+    }
+}
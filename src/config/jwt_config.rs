@@ -0,0 +1,105 @@
+// 必要なクレートのインポート
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::Algorithm;
+use ring::rand::{SecureRandom, SystemRandom};
+
+// トークンの署名の設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct JwtConfig {
+    // 署名のアルゴリズム(HS256・RS256・EdDSA)
+    pub algorithm: Algorithm,
+    // JWTシークレット(HS256の場合に必須)
+    pub secret: Option<String>,
+    // 署名の秘密鍵(PEM、RS256・EdDSAの場合に必須)
+    pub signing_key: Option<String>,
+    // 署名の鍵ID(kid、Noneの場合は公開鍵のサムプリント)
+    pub signing_key_id: Option<String>,
+    // 検証のみに使う公開鍵(JWKSのJSON、ローテーション前の鍵を残す場合に使う)
+    pub verification_keys: Option<String>,
+    // 発行者(iss、Noneの場合はトークンに含めず検証もしない)
+    pub issuer: Option<String>,
+    // 対象者(aud、Noneの場合はトークンに含めず検証もしない)
+    pub audience: Option<String>,
+}
+
+// JwtConfigの実装
+impl JwtConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - JWT_ALGORITHM: 署名のアルゴリズム(HS256・RS256・EdDSA、既定値: HS256)
+    // - JWT_SECRET / JWT_SECRET_FILE: HS256のJWTシークレット、またはそのファイルのパス(既定値: 起動ごとにランダムに生成)
+    // - JWT_SIGNING_KEY / JWT_SIGNING_KEY_FILE: 署名の秘密鍵のPEM、またはそのファイルのパス(既定値: なし)
+    // - JWT_SIGNING_KEY_ID: 署名の鍵ID(既定値: 公開鍵のサムプリント)
+    // - JWT_VERIFICATION_KEYS / JWT_VERIFICATION_KEYS_FILE: 検証のみに使う公開鍵のJWKS、またはそのファイルのパス(既定値: なし)
+    // - JWT_ISSUER: 発行者(既定値: なし)
+    // - JWT_AUDIENCE: 対象者(既定値: なし)
+    //
+    // 不正なアルゴリズムの場合やファイルを読み込めない場合は起動しない
+    // (意図しないHS256で署名しないよう、既定値として扱わない)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        let algorithm = var("JWT_ALGORITHM")
+            .map(|value| parse_algorithm(&value).unwrap_or_else(|| panic!("JWT_ALGORITHM({})に対応していません", value)))
+            .unwrap_or(default.algorithm);
+
+        Self {
+            algorithm,
+            secret: Some(var_or_file("JWT_SECRET").unwrap_or_else(|| {
+                // 複数のサーバーで共有できず、再起動するとトークンが無効になる
+                if algorithm == Algorithm::HS256 {
+                    tracing::warn!("JWT_SECRET is not set; using a random secret for this process");
+                }
+                random_secret()
+            })),
+            signing_key: var_or_file("JWT_SIGNING_KEY"),
+            signing_key_id: var("JWT_SIGNING_KEY_ID"),
+            verification_keys: var_or_file("JWT_VERIFICATION_KEYS"),
+            issuer: var("JWT_ISSUER"),
+            audience: var("JWT_AUDIENCE"),
+        }
+    }
+}
+
+// アルゴリズムの名前を変換する関数(対応していない場合はNone)
+pub fn parse_algorithm(value: &str) -> Option<Algorithm> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "HS256" => Some(Algorithm::HS256),
+        "RS256" => Some(Algorithm::RS256),
+        "EDDSA" => Some(Algorithm::EdDSA),
+        _ => None,
+    }
+}
+
+// ランダムなJWTシークレットを生成する関数
+fn random_secret() -> String {
+    let mut bytes = [0u8; 32];
+    SystemRandom::new().fill(&mut bytes).expect("乱数を生成できません");
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+// 環境変数の値、または<NAME>_FILEのファイルの内容を読み込む関数
+fn var_or_file(name: &str) -> Option<String> {
+    if let Some(value) = std::env::var(name).ok().filter(|value| !value.is_empty()) {
+        return Some(value);
+    }
+    let path = std::env::var(format!("{}_FILE", name)).ok().filter(|value| !value.is_empty())?;
+    Some(std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}_FILE({})を読み込めません: {}", name, path, e)))
+}
+
+// Defaultトレイトの実装
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: Algorithm::HS256,
+            secret: None,
+            signing_key: None,
+            signing_key_id: None,
+            verification_keys: None,
+            issuer: None,
+            audience: None,
+        }
+    }
+}
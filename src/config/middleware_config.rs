@@ -0,0 +1,104 @@
+// 必要なクレートのインポート
+use std::collections::HashMap;
+use std::time::Duration;
+// APIのバージョンのインポート
+use crate::middleware::versioning::api_version::ApiVersion;
+
+// リクエストボディの最大サイズの既定値(1MiB)
+const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+// リクエストのタイムアウトの既定値(30秒)
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+// ミドルウェアの設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct MiddlewareConfig {
+    // CORSで許可するオリジン(空の場合はCORSヘッダーを付与しない)
+    pub cors_allowed_origins: Vec<String>,
+    // レスポンスの圧縮(gzip/br)を有効にするか
+    pub compression_enabled: bool,
+    // リクエストボディの最大サイズ(バイト)
+    pub max_body_bytes: usize,
+    // リクエストのタイムアウトの既定値
+    pub default_timeout: Duration,
+    // ルートごとのタイムアウト("METHOD /path"をキーとする)
+    pub route_timeouts: HashMap<String, Duration>,
+}
+
+// MiddlewareConfigの実装
+impl MiddlewareConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - CORS_ALLOWED_ORIGINS: 許可するオリジンのカンマ区切り
+    // - COMPRESSION_ENABLED: レスポンスを圧縮するか(既定値: true)
+    // - MAX_BODY_BYTES: リクエストボディの最大サイズ(既定値: 1048576)
+    // - REQUEST_TIMEOUT_SECS: タイムアウトの既定値(既定値: 30)
+    // - ROUTE_TIMEOUTS: ルートごとのタイムアウト(例: "POST /users=10,GET /di/users=5"、/v1・/v2のパスにも適用)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        Self {
+            cors_allowed_origins: std::env::var("CORS_ALLOWED_ORIGINS")
+                .map(|value| parse_list(&value))
+                .unwrap_or(default.cors_allowed_origins),
+            compression_enabled: std::env::var("COMPRESSION_ENABLED")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.compression_enabled),
+            max_body_bytes: std::env::var("MAX_BODY_BYTES")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.max_body_bytes),
+            default_timeout: std::env::var("REQUEST_TIMEOUT_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default.default_timeout),
+            route_timeouts: std::env::var("ROUTE_TIMEOUTS")
+                .map(|value| parse_route_timeouts(&value))
+                .unwrap_or(default.route_timeouts),
+        }
+    }
+
+    // ルートに適用するタイムアウトを取得する関数(バージョンの接頭辞は除いて照合する)
+    pub fn timeout_for(&self, method: &str, route: &str) -> Duration {
+        self.route_timeouts
+            .get(&format!("{} {}", method, ApiVersion::unversioned_path(route)))
+            .copied()
+            .unwrap_or(self.default_timeout)
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for MiddlewareConfig {
+    fn default() -> Self {
+        Self {
+            cors_allowed_origins: Vec::new(),
+            compression_enabled: true,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            default_timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            route_timeouts: HashMap::new(),
+        }
+    }
+}
+
+// カンマ区切りの文字列をリストに変換する関数
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+// "METHOD /path=秒数"のカンマ区切りをルートごとのタイムアウトに変換する関数
+fn parse_route_timeouts(value: &str) -> HashMap<String, Duration> {
+    parse_list(value)
+        .into_iter()
+        .filter_map(|item| {
+            let (route, secs) = item.rsplit_once('=')?;
+            let secs = secs.trim().parse().ok()?;
+            Some((route.trim().to_string(), Duration::from_secs(secs)))
+        })
+        .collect()
+}
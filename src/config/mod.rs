@@ -0,0 +1,19 @@
+// 設定のモジュールの宣言
+pub mod admin_config;
+pub mod api_key_config;
+pub mod cache_config;
+pub mod idempotency_config;
+pub mod jwt_config;
+pub mod middleware_config;
+pub mod oidc_config;
+pub mod outbound_config;
+pub mod password_hash_config;
+pub mod password_policy_config;
+pub mod rate_limit_config;
+pub mod session_config;
+pub mod session_cookie_config;
+pub mod supabase_auth_config;
+pub mod two_factor_config;
+pub mod versioning_config;
+
+// 設定のエントリーポイント
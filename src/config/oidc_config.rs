@@ -0,0 +1,127 @@
+// 必要なクレートのインポート
+use std::collections::HashMap;
+use std::time::Duration;
+
+// 要求するスコープの既定値
+const DEFAULT_SCOPES: &str = "openid email profile";
+// サインインの開始からコールバックまでの有効期間の既定値(10分)
+const DEFAULT_LOGIN_TTL_SECS: u64 = 10 * 60;
+// ディスカバリー・JWKSをキャッシュする期間の既定値(1時間)
+const DEFAULT_METADATA_TTL_SECS: u64 = 60 * 60;
+// IdPへの1回の呼び出しのタイムアウトの既定値
+const DEFAULT_HTTP_TIMEOUT_MS: u64 = 10_000;
+
+// OpenID ConnectのIdP(プロバイダー)ごとの設定
+#[derive(Clone, Debug)]
+pub struct OidcProviderConfig {
+    // プロバイダー名(パスの:providerに使う)
+    pub name: String,
+    // 発行者(ディスカバリーのURLとIDトークンのissの検証に使う)
+    pub issuer: String,
+    // クライアントID(IDトークンのaudの検証に使う)
+    pub client_id: String,
+    // クライアントシークレット(公開クライアントの場合はNone)
+    pub client_secret: Option<String>,
+    // IdPに登録したコールバックのURL(/auth/oidc/{provider}/callback)
+    pub redirect_uri: String,
+    // 要求するスコープ(空白区切り、openidとemailを含める)
+    pub scopes: String,
+}
+
+// OpenID Connectの設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct OidcConfig {
+    // プロバイダー名ごとの設定(空の場合はOIDCのサインインを使わない)
+    pub providers: HashMap<String, OidcProviderConfig>,
+    // サインインの開始からコールバックまでの有効期間
+    pub login_ttl: Duration,
+    // ディスカバリー・JWKSをキャッシュする期間
+    pub metadata_ttl: Duration,
+    // IdPへの1回の呼び出しのタイムアウト
+    pub http_timeout: Duration,
+}
+
+// OidcConfigの実装
+impl OidcConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - OIDC_PROVIDERS: プロバイダー名のカンマ区切り(既定値: なし)
+    // - OIDC_<NAME>_ISSUER: 発行者のURL(必須)
+    // - OIDC_<NAME>_CLIENT_ID: クライアントID(必須)
+    // - OIDC_<NAME>_CLIENT_SECRET: クライアントシークレット(既定値: なし)
+    // - OIDC_<NAME>_REDIRECT_URI: コールバックのURL(必須)
+    // - OIDC_<NAME>_SCOPES: 要求するスコープ(既定値: openid email profile)
+    // - OIDC_LOGIN_TTL_SECS: サインインの開始からコールバックまでの有効秒数(既定値: 600)
+    // - OIDC_METADATA_TTL_SECS: ディスカバリー・JWKSをキャッシュする秒数(既定値: 3600)
+    // - OIDC_HTTP_TIMEOUT_MS: IdPへの1回の呼び出しのタイムアウト(既定値: 10000)
+    //
+    // <NAME>はプロバイダー名を大文字にし、-を_に置き換えたもの。必須の項目がないプロバイダーは無視する
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        let providers = std::env::var("OIDC_PROVIDERS")
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|name| name.trim().to_ascii_lowercase())
+                    .filter(|name| !name.is_empty())
+                    .filter_map(|name| provider_from_env(&name).map(|provider| (name, provider)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            providers,
+            login_ttl: env_secs("OIDC_LOGIN_TTL_SECS").unwrap_or(default.login_ttl),
+            metadata_ttl: env_secs("OIDC_METADATA_TTL_SECS").unwrap_or(default.metadata_ttl),
+            http_timeout: std::env::var("OIDC_HTTP_TIMEOUT_MS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(default.http_timeout),
+        }
+    }
+
+    // プロバイダーの設定を取得する関数
+    pub fn provider(&self, name: &str) -> Option<&OidcProviderConfig> {
+        self.providers.get(name)
+    }
+}
+
+// 環境変数からプロバイダーの設定を読み込む関数(必須の項目がない場合はNone)
+fn provider_from_env(name: &str) -> Option<OidcProviderConfig> {
+    let prefix = format!("OIDC_{}_", name.to_ascii_uppercase().replace('-', "_"));
+    let var = |key: &str| std::env::var(format!("{}{}", prefix, key)).ok().filter(|value| !value.is_empty());
+
+    let (Some(issuer), Some(client_id), Some(redirect_uri)) = (var("ISSUER"), var("CLIENT_ID"), var("REDIRECT_URI")) else {
+        tracing::warn!(provider = name, "OIDC provider is missing ISSUER, CLIENT_ID or REDIRECT_URI and is ignored");
+        return None;
+    };
+
+    Some(OidcProviderConfig {
+        name: name.to_string(),
+        issuer,
+        client_id,
+        client_secret: var("CLIENT_SECRET"),
+        redirect_uri,
+        scopes: var("SCOPES").unwrap_or_else(|| DEFAULT_SCOPES.to_string()),
+    })
+}
+
+// 環境変数から秒数を読み込む関数
+fn env_secs(name: &str) -> Option<Duration> {
+    std::env::var(name).ok().and_then(|value| value.parse().ok()).map(Duration::from_secs)
+}
+
+// Defaultトレイトの実装
+impl Default for OidcConfig {
+    fn default() -> Self {
+        Self {
+            providers: HashMap::new(),
+            login_ttl: Duration::from_secs(DEFAULT_LOGIN_TTL_SECS),
+            metadata_ttl: Duration::from_secs(DEFAULT_METADATA_TTL_SECS),
+            http_timeout: Duration::from_millis(DEFAULT_HTTP_TIMEOUT_MS),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// 必要なクレートのインポート
+use std::time::Duration;
+
+// Supabase(PostgREST)への呼び出しの設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct OutboundConfig {
+    // 1回の呼び出しのタイムアウト
+    pub timeout: Duration,
+    // 再試行の最大回数
+    pub max_retries: u32,
+    // 再試行の待機時間の基準値
+    pub base_backoff: Duration,
+    // 再試行の待機時間の上限
+    pub max_backoff: Duration,
+    // サーキットブレーカーが開くまでの連続失敗回数
+    pub failure_threshold: u32,
+    // サーキットブレーカーが開いている時間
+    pub open_duration: Duration,
+}
+
+// OutboundConfigの実装
+impl OutboundConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - SUPABASE_TIMEOUT_MS: 1回の呼び出しのタイムアウト(既定値: 10000)
+    // - SUPABASE_MAX_RETRIES: 読み取り(GET/HEAD)の再試行の最大回数(既定値: 2、書き込みは再試行しない)
+    // - SUPABASE_RETRY_BASE_MS: 再試行の待機時間の基準値(既定値: 100)
+    // - SUPABASE_RETRY_MAX_MS: 再試行の待機時間の上限(既定値: 2000)
+    // - SUPABASE_BREAKER_THRESHOLD: サーキットブレーカーが開くまでの連続失敗回数(既定値: 5)
+    // - SUPABASE_BREAKER_OPEN_SECS: サーキットブレーカーが開いている秒数(既定値: 30)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        Self {
+            timeout: env_millis("SUPABASE_TIMEOUT_MS").unwrap_or(default.timeout),
+            max_retries: env_parse("SUPABASE_MAX_RETRIES").unwrap_or(default.max_retries),
+            base_backoff: env_millis("SUPABASE_RETRY_BASE_MS").unwrap_or(default.base_backoff),
+            max_backoff: env_millis("SUPABASE_RETRY_MAX_MS").unwrap_or(default.max_backoff),
+            failure_threshold: env_parse("SUPABASE_BREAKER_THRESHOLD").unwrap_or(default.failure_threshold),
+            open_duration: env_parse("SUPABASE_BREAKER_OPEN_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.open_duration),
+        }
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for OutboundConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            max_retries: 2,
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+// 環境変数を数値として読み込む関数
+fn env_parse<T: std::str::FromStr>(name: &str) -> Option<T> {
+    std::env::var(name).ok().and_then(|value| value.parse().ok())
+}
+
+// 環境変数をミリ秒として読み込む関数
+fn env_millis(name: &str) -> Option<Duration> {
+    env_parse(name).map(Duration::from_millis)
+}
@@ -0,0 +1,91 @@
+// 必要なクレートのインポート
+use std::str::FromStr;
+
+// bcryptのコストの既定値
+const DEFAULT_BCRYPT_COST: u32 = 12;
+// Argon2idのメモリ使用量(KiB)の既定値(OWASPの推奨値)
+const DEFAULT_ARGON2_MEMORY_KIB: u32 = 19 * 1024;
+// Argon2idの反復回数の既定値
+const DEFAULT_ARGON2_ITERATIONS: u32 = 2;
+// Argon2idの並列度の既定値
+const DEFAULT_ARGON2_PARALLELISM: u32 = 1;
+
+// パスワードのハッシュ化のアルゴリズム
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PasswordHashAlgorithm {
+    // bcrypt
+    Bcrypt,
+    // Argon2id
+    Argon2id,
+}
+
+// 文字列からアルゴリズムへの変換(大文字・小文字を区別しない)
+impl FromStr for PasswordHashAlgorithm {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "bcrypt" => Ok(Self::Bcrypt),
+            "argon2id" | "argon2" => Ok(Self::Argon2id),
+            other => Err(format!("Unknown password hash algorithm: {}", other)),
+        }
+    }
+}
+
+// パスワードのハッシュ化の設定を管理する構造体
+//
+// 新しいハッシュは選択したアルゴリズムで作成し、もう一方のアルゴリズムのハッシュは照合のみ行う
+#[derive(Clone, Debug)]
+pub struct PasswordHashConfig {
+    // 新しいハッシュに使うアルゴリズム
+    pub algorithm: PasswordHashAlgorithm,
+    // bcryptのコスト(4〜31)
+    pub bcrypt_cost: u32,
+    // Argon2idのメモリ使用量(KiB)
+    pub argon2_memory_kib: u32,
+    // Argon2idの反復回数
+    pub argon2_iterations: u32,
+    // Argon2idの並列度
+    pub argon2_parallelism: u32,
+}
+
+// PasswordHashConfigの実装
+impl PasswordHashConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - PASSWORD_HASH_ALGORITHM: 新しいハッシュに使うアルゴリズム(argon2id または bcrypt、既定値: argon2id)
+    // - PASSWORD_BCRYPT_COST: bcryptのコスト(既定値: 12)
+    // - PASSWORD_ARGON2_MEMORY_KIB: Argon2idのメモリ使用量(KiB、既定値: 19456)
+    // - PASSWORD_ARGON2_ITERATIONS: Argon2idの反復回数(既定値: 2)
+    // - PASSWORD_ARGON2_PARALLELISM: Argon2idの並列度(既定値: 1)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        Self {
+            algorithm: parse_env("PASSWORD_HASH_ALGORITHM").unwrap_or(default.algorithm),
+            bcrypt_cost: parse_env("PASSWORD_BCRYPT_COST").unwrap_or(default.bcrypt_cost),
+            argon2_memory_kib: parse_env("PASSWORD_ARGON2_MEMORY_KIB").unwrap_or(default.argon2_memory_kib),
+            argon2_iterations: parse_env("PASSWORD_ARGON2_ITERATIONS").unwrap_or(default.argon2_iterations),
+            argon2_parallelism: parse_env("PASSWORD_ARGON2_PARALLELISM").unwrap_or(default.argon2_parallelism),
+        }
+    }
+}
+
+// 環境変数を読み込んで変換する関数(未設定・変換できない場合はNone)
+fn parse_env<T: FromStr>(name: &str) -> Option<T> {
+    std::env::var(name).ok().and_then(|value| value.parse().ok())
+}
+
+// Defaultトレイトの実装
+impl Default for PasswordHashConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: PasswordHashAlgorithm::Argon2id,
+            bcrypt_cost: DEFAULT_BCRYPT_COST,
+            argon2_memory_kib: DEFAULT_ARGON2_MEMORY_KIB,
+            argon2_iterations: DEFAULT_ARGON2_ITERATIONS,
+            argon2_parallelism: DEFAULT_ARGON2_PARALLELISM,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+// 再利用を禁止する過去のパスワードの数の既定値
+const DEFAULT_HISTORY_SIZE: usize = 5;
+
+// パスワードのポリシーの設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct PasswordPolicyConfig {
+    // 再利用を禁止する過去のパスワードの数(現在のパスワードは含まない、0で履歴を確認しない)
+    pub history_size: usize,
+}
+
+// PasswordPolicyConfigの実装
+impl PasswordPolicyConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - PASSWORD_HISTORY_SIZE: 再利用を禁止する過去のパスワードの数(既定値: 5)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        Self {
+            history_size: std::env::var("PASSWORD_HISTORY_SIZE")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.history_size),
+        }
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for PasswordPolicyConfig {
+    fn default() -> Self {
+        Self {
+            history_size: DEFAULT_HISTORY_SIZE,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// 必要なクレートのインポート
+use std::collections::HashMap;
+use std::time::Duration;
+// APIのバージョンのインポート
+use crate::middleware::versioning::api_version::ApiVersion;
+
+// OIDCのサインインの開始のルート(認証なしで呼び出せ、呼び出しごとにサインインを保存するため既定で制限する)
+const OIDC_START_ROUTE: &str = "GET /auth/oidc/:provider/start";
+// OIDCのサインインの開始のポリシーの既定値(クライアントごとに1分あたり20回)
+const DEFAULT_OIDC_START_POLICY: RateLimitPolicy = RateLimitPolicy {
+    capacity: 20,
+    window: Duration::from_secs(60),
+};
+
+// レート制限のポリシー(ウィンドウごとに許可するリクエスト数)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimitPolicy {
+    // バケットの容量(ウィンドウごとに許可するリクエスト数)
+    pub capacity: u32,
+    // バケットが空から満杯まで回復する時間
+    pub window: Duration,
+}
+
+// RateLimitPolicyの実装
+impl RateLimitPolicy {
+    // RateLimitPolicyの新しいインスタンスを作成する関数
+    pub fn new(capacity: u32, window: Duration) -> Self {
+        Self { capacity, window }
+    }
+
+    // "回数/秒数"形式の文字列からポリシーを作成する関数
+    pub fn parse(value: &str) -> Option<Self> {
+        let (capacity, secs) = value.trim().split_once('/')?;
+        let capacity: u32 = capacity.trim().parse().ok()?;
+        let secs: u64 = secs.trim().parse().ok()?;
+        if capacity == 0 || secs == 0 {
+            return None;
+        }
+        Some(Self::new(capacity, Duration::from_secs(secs)))
+    }
+
+    // 1秒あたりに回復するトークン数
+    pub fn refill_per_sec(&self) -> f64 {
+        self.capacity as f64 / self.window.as_secs_f64()
+    }
+}
+
+// レート制限の設定を管理する構造体
+#[derive(Clone, Debug, Default)]
+pub struct RateLimitConfig {
+    // ルートごとのポリシーがない場合に適用するポリシー
+    pub default_policy: Option<RateLimitPolicy>,
+    // ルートごとのポリシー("METHOD /path"をキーとする)
+    pub route_policies: HashMap<String, RateLimitPolicy>,
+}
+
+// RateLimitConfigの実装
+impl RateLimitConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - RATE_LIMIT_DEFAULT: 全ルートに適用するポリシー(例: "100/60")
+    // - RATE_LIMIT_POLICIES: ルートごとのポリシー(例: "POST /users=5/60,POST /auth/signin=10/60"、/v1・/v2のパスにも適用)
+    //
+    // OIDCのサインインの開始(GET /auth/oidc/:provider/start)は、RATE_LIMIT_POLICIESで指定しない場合も20/60で制限する
+    pub fn from_env() -> Self {
+        let mut route_policies = HashMap::from([(OIDC_START_ROUTE.to_string(), DEFAULT_OIDC_START_POLICY)]);
+        if let Ok(value) = std::env::var("RATE_LIMIT_POLICIES") {
+            route_policies.extend(parse_route_policies(&value));
+        }
+
+        Self {
+            default_policy: std::env::var("RATE_LIMIT_DEFAULT")
+                .ok()
+                .and_then(|value| RateLimitPolicy::parse(&value)),
+            route_policies,
+        }
+    }
+
+    // ルートに適用するポリシーを取得する関数
+    //
+    // バージョンの接頭辞(/v1・/v2)は除いて照合し、全てのバージョンで同じバケットを使う
+    pub fn policy_for(&self, method: &str, route: &str) -> Option<(String, RateLimitPolicy)> {
+        // ルートごとのポリシーを優先し、なければ既定のポリシーを適用
+        let key = format!("{} {}", method, ApiVersion::unversioned_path(route));
+        match self.route_policies.get(&key) {
+            Some(policy) => Some((key, *policy)),
+            None => self.default_policy.map(|policy| ("*".to_string(), policy)),
+        }
+    }
+}
+
+// "METHOD /path=回数/秒数"のカンマ区切りをルートごとのポリシーに変換する関数
+fn parse_route_policies(value: &str) -> HashMap<String, RateLimitPolicy> {
+    value
+        .split(',')
+        .filter_map(|item| {
+            let (route, policy) = item.split_once('=')?;
+            Some((route.trim().to_string(), RateLimitPolicy::parse(policy)?))
+        })
+        .collect()
+}
@@ -0,0 +1,40 @@
+// 必要なクレートのインポート
+use std::time::Duration;
+
+// 最後にアクセスした日時を更新する間隔の既定値(1分)
+const DEFAULT_LAST_SEEN_INTERVAL_SECS: u64 = 60;
+
+// サインインのセッション(発行したトークン)の設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct SessionConfig {
+    // 最後にアクセスした日時を更新する間隔(リクエストごとに書き込まないようにする)
+    pub last_seen_interval: Duration,
+}
+
+// SessionConfigの実装
+impl SessionConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - SESSION_LAST_SEEN_INTERVAL_SECS: 最後にアクセスした日時を更新する間隔の秒数(既定値: 60)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        Self {
+            last_seen_interval: std::env::var("SESSION_LAST_SEEN_INTERVAL_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default.last_seen_interval),
+        }
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            last_seen_interval: Duration::from_secs(DEFAULT_LAST_SEEN_INTERVAL_SECS),
+        }
+    }
+}
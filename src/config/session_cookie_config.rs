@@ -0,0 +1,100 @@
+// セッションのCookieの名前の既定値
+const DEFAULT_COOKIE_NAME: &str = "session";
+// CSRFトークンのCookieの名前の既定値
+const DEFAULT_CSRF_COOKIE_NAME: &str = "csrf_token";
+
+// CookieのSameSite属性
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SameSite {
+    // 同じサイトからのリクエストのみ送信
+    Strict,
+    // 同じサイトからのリクエストと、別のサイトからのトップレベルの遷移(GET)で送信
+    Lax,
+    // 全てのリクエストで送信(Secure属性が必要)
+    None,
+}
+
+// SameSiteの実装
+impl SameSite {
+    // 設定値から変換する関数(大文字・小文字は区別しない)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "strict" => Some(Self::Strict),
+            "lax" => Some(Self::Lax),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    // Set-Cookieヘッダーの属性の値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Strict => "Strict",
+            Self::Lax => "Lax",
+            Self::None => "None",
+        }
+    }
+}
+
+// ブラウザー向けのCookieのセッションの設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct SessionCookieConfig {
+    // サインインでセッションのCookieを設定するか(有効な場合はCookieのトークンでも認証し、CSRFトークンを検証する)
+    pub enabled: bool,
+    // セッションのCookie(HttpOnly、トークンを保持する)の名前
+    pub cookie_name: String,
+    // CSRFトークンのCookie(JavaScriptから読み、X-CSRF-Tokenヘッダーで送り返す)の名前
+    pub csrf_cookie_name: String,
+    // Secure属性を付けるか(HTTPSでのみ送信する)
+    pub secure: bool,
+    // SameSite属性
+    pub same_site: SameSite,
+    // Domain属性(Noneの場合は付けず、設定したホストのみに送信する)
+    pub domain: Option<String>,
+}
+
+// SessionCookieConfigの実装
+impl SessionCookieConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - SESSION_COOKIE_ENABLED: サインインでセッションのCookieを設定するか(既定値: false)
+    // - SESSION_COOKIE_NAME: セッションのCookieの名前(既定値: session)
+    // - SESSION_CSRF_COOKIE_NAME: CSRFトークンのCookieの名前(既定値: csrf_token)
+    // - SESSION_COOKIE_SECURE: Secure属性を付けるか(既定値: true)
+    // - SESSION_COOKIE_SAME_SITE: SameSite属性(Strict、Lax、None、既定値: Lax)
+    // - SESSION_COOKIE_DOMAIN: Domain属性(既定値: なし)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        Self {
+            enabled: var("SESSION_COOKIE_ENABLED")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.enabled),
+            cookie_name: var("SESSION_COOKIE_NAME").unwrap_or(default.cookie_name),
+            csrf_cookie_name: var("SESSION_CSRF_COOKIE_NAME").unwrap_or(default.csrf_cookie_name),
+            secure: var("SESSION_COOKIE_SECURE")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.secure),
+            same_site: var("SESSION_COOKIE_SAME_SITE")
+                .and_then(|value| SameSite::parse(&value))
+                .unwrap_or(default.same_site),
+            domain: var("SESSION_COOKIE_DOMAIN"),
+        }
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for SessionCookieConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cookie_name: DEFAULT_COOKIE_NAME.to_string(),
+            csrf_cookie_name: DEFAULT_CSRF_COOKIE_NAME.to_string(),
+            secure: true,
+            same_site: SameSite::Lax,
+            domain: None,
+        }
+    }
+}
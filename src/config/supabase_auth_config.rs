@@ -0,0 +1,113 @@
+// 必要なクレートのインポート
+use std::time::Duration;
+
+// GoTrueのトークンのaudの既定値
+const DEFAULT_AUDIENCE: &str = "authenticated";
+// JWKSをキャッシュする期間の既定値(10分)
+const DEFAULT_JWKS_TTL_SECS: u64 = 10 * 60;
+// 未知の鍵(kid)でJWKSを取得し直す最短の間隔の既定値
+const DEFAULT_JWKS_MIN_REFRESH_SECS: u64 = 30;
+// JWKSの取得に失敗した後、取得し直さずに失敗を返す期間の既定値
+const DEFAULT_JWKS_FAILURE_BACKOFF_SECS: u64 = 5;
+// JWKSの取得のタイムアウトの既定値
+const DEFAULT_HTTP_TIMEOUT_MS: u64 = 5_000;
+
+// Supabase Auth(GoTrue)が発行したトークンを受け付ける設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct SupabaseAuthConfig {
+    // GoTrueのトークンを受け付けるか(有効な場合はトークンをPostgRESTに転送し、RLSを適用する)
+    pub enabled: bool,
+    // プロジェクトのJWTシークレット(HS256で署名されたトークンの検証に使う、Noneの場合はJWKSのみ)
+    pub jwt_secret: Option<String>,
+    // JWKSのURL(Noneの場合は{SUPABASE_URL}/auth/v1/.well-known/jwks.json)
+    pub jwks_url: Option<String>,
+    // 発行者(Noneの場合は{SUPABASE_URL}/auth/v1)
+    pub issuer: Option<String>,
+    // 受け付けるaud
+    pub audience: String,
+    // JWKSをキャッシュする期間
+    pub jwks_ttl: Duration,
+    // 未知の鍵(kid)でJWKSを取得し直す最短の間隔(前回の取得からこの期間はInvalidToken)
+    pub jwks_min_refresh_interval: Duration,
+    // JWKSの取得に失敗した後、取得し直さずにInvalidTokenを返す期間
+    pub jwks_failure_backoff: Duration,
+    // JWKSの取得のタイムアウト
+    pub http_timeout: Duration,
+}
+
+// SupabaseAuthConfigの実装
+impl SupabaseAuthConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - SUPABASE_AUTH_ENABLED: GoTrueのトークンを受け付けるか(既定値: false)
+    // - SUPABASE_JWT_SECRET: プロジェクトのJWTシークレット(既定値: なし)
+    // - SUPABASE_JWKS_URL: JWKSのURL(既定値: {SUPABASE_URL}/auth/v1/.well-known/jwks.json)
+    // - SUPABASE_JWT_ISSUER: 発行者(既定値: {SUPABASE_URL}/auth/v1)
+    // - SUPABASE_JWT_AUDIENCE: 受け付けるaud(既定値: authenticated)
+    // - SUPABASE_JWKS_TTL_SECS: JWKSをキャッシュする秒数(既定値: 600)
+    // - SUPABASE_JWKS_MIN_REFRESH_SECS: 未知の鍵でJWKSを取得し直す最短の間隔の秒数(既定値: 30)
+    // - SUPABASE_JWKS_FAILURE_BACKOFF_SECS: JWKSの取得に失敗した後、取得し直さない秒数(既定値: 5)
+    // - SUPABASE_JWKS_TIMEOUT_MS: JWKSの取得のタイムアウト(既定値: 5000)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        Self {
+            enabled: var("SUPABASE_AUTH_ENABLED")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.enabled),
+            jwt_secret: var("SUPABASE_JWT_SECRET"),
+            jwks_url: var("SUPABASE_JWKS_URL"),
+            issuer: var("SUPABASE_JWT_ISSUER"),
+            audience: var("SUPABASE_JWT_AUDIENCE").unwrap_or(default.audience),
+            jwks_ttl: var("SUPABASE_JWKS_TTL_SECS")
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default.jwks_ttl),
+            jwks_min_refresh_interval: var("SUPABASE_JWKS_MIN_REFRESH_SECS")
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default.jwks_min_refresh_interval),
+            jwks_failure_backoff: var("SUPABASE_JWKS_FAILURE_BACKOFF_SECS")
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default.jwks_failure_backoff),
+            http_timeout: var("SUPABASE_JWKS_TIMEOUT_MS")
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(default.http_timeout),
+        }
+    }
+
+    // 発行者を取得する関数(設定がない場合はSupabaseのURLから作る)
+    pub fn issuer(&self, supabase_url: &str) -> String {
+        self.issuer
+            .clone()
+            .unwrap_or_else(|| format!("{}/auth/v1", supabase_url.trim_end_matches('/')))
+    }
+
+    // JWKSのURLを取得する関数(設定がない場合はSupabaseのURLから作る)
+    pub fn jwks_url(&self, supabase_url: &str) -> String {
+        self.jwks_url
+            .clone()
+            .unwrap_or_else(|| format!("{}/auth/v1/.well-known/jwks.json", supabase_url.trim_end_matches('/')))
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for SupabaseAuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            jwt_secret: None,
+            jwks_url: None,
+            issuer: None,
+            audience: DEFAULT_AUDIENCE.to_string(),
+            jwks_ttl: Duration::from_secs(DEFAULT_JWKS_TTL_SECS),
+            jwks_min_refresh_interval: Duration::from_secs(DEFAULT_JWKS_MIN_REFRESH_SECS),
+            jwks_failure_backoff: Duration::from_secs(DEFAULT_JWKS_FAILURE_BACKOFF_SECS),
+            http_timeout: Duration::from_millis(DEFAULT_HTTP_TIMEOUT_MS),
+        }
+    }
+}
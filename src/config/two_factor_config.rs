@@ -0,0 +1,98 @@
+// 必要なクレートのインポート
+use std::time::Duration;
+
+// 認証アプリに表示する発行者名の既定値
+const DEFAULT_ISSUER: &str = "backend";
+// チャレンジの有効期間の既定値(5分)
+const DEFAULT_CHALLENGE_TTL_SECS: u64 = 5 * 60;
+// 1つのチャレンジで試せるコードの数の既定値
+const DEFAULT_MAX_CHALLENGE_ATTEMPTS: u32 = 5;
+// ユーザーごとに許容するコードの失敗の数(チャレンジをまたいで数える)の既定値
+const DEFAULT_MAX_USER_FAILURES: u32 = 10;
+// コードの失敗を数える期間の既定値(15分)
+const DEFAULT_FAILURE_WINDOW_SECS: u64 = 15 * 60;
+// 有効にしたときに発行するリカバリーコードの数の既定値
+const DEFAULT_RECOVERY_CODE_COUNT: usize = 10;
+// 時刻のずれとして許容する前後のステップ数(1ステップ30秒)の既定値
+const DEFAULT_ALLOWED_SKEW_STEPS: i64 = 1;
+
+// 二段階認証(TOTP)の設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct TwoFactorConfig {
+    // 認証アプリに表示する発行者名
+    pub issuer: String,
+    // サインインのチャレンジの有効期間
+    pub challenge_ttl: Duration,
+    // 1つのチャレンジで試せるコードの数(超えた場合はサインインからやり直す)
+    pub max_challenge_attempts: u32,
+    // ユーザーごとに許容するコードの失敗の数(超えた場合はfailure_windowの間、チャレンジの発行と検証を拒否する)
+    pub max_user_failures: u32,
+    // コードの失敗を数える期間(最初の失敗から数える)
+    pub failure_window: Duration,
+    // 有効にしたときに発行するリカバリーコードの数
+    pub recovery_code_count: usize,
+    // 時刻のずれとして許容する前後のステップ数
+    pub allowed_skew_steps: i64,
+}
+
+// TwoFactorConfigの実装
+impl TwoFactorConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - TWO_FACTOR_ISSUER: 認証アプリに表示する発行者名(既定値: backend)
+    // - TWO_FACTOR_CHALLENGE_TTL_SECS: サインインのチャレンジの有効秒数(既定値: 300)
+    // - TWO_FACTOR_MAX_CHALLENGE_ATTEMPTS: 1つのチャレンジで試せるコードの数(既定値: 5)
+    // - TWO_FACTOR_MAX_USER_FAILURES: ユーザーごとに許容するコードの失敗の数(既定値: 10)
+    // - TWO_FACTOR_FAILURE_WINDOW_SECS: コードの失敗を数える秒数(既定値: 900)
+    // - TWO_FACTOR_RECOVERY_CODE_COUNT: 発行するリカバリーコードの数(既定値: 10)
+    // - TWO_FACTOR_ALLOWED_SKEW_STEPS: 許容する前後のステップ数(既定値: 1)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        Self {
+            issuer: std::env::var("TWO_FACTOR_ISSUER").unwrap_or(default.issuer),
+            challenge_ttl: std::env::var("TWO_FACTOR_CHALLENGE_TTL_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default.challenge_ttl),
+            max_challenge_attempts: std::env::var("TWO_FACTOR_MAX_CHALLENGE_ATTEMPTS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.max_challenge_attempts),
+            max_user_failures: std::env::var("TWO_FACTOR_MAX_USER_FAILURES")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.max_user_failures),
+            failure_window: std::env::var("TWO_FACTOR_FAILURE_WINDOW_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(default.failure_window),
+            recovery_code_count: std::env::var("TWO_FACTOR_RECOVERY_CODE_COUNT")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.recovery_code_count),
+            allowed_skew_steps: std::env::var("TWO_FACTOR_ALLOWED_SKEW_STEPS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.allowed_skew_steps),
+        }
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for TwoFactorConfig {
+    fn default() -> Self {
+        Self {
+            issuer: DEFAULT_ISSUER.to_string(),
+            challenge_ttl: Duration::from_secs(DEFAULT_CHALLENGE_TTL_SECS),
+            max_challenge_attempts: DEFAULT_MAX_CHALLENGE_ATTEMPTS,
+            max_user_failures: DEFAULT_MAX_USER_FAILURES,
+            failure_window: Duration::from_secs(DEFAULT_FAILURE_WINDOW_SECS),
+            recovery_code_count: DEFAULT_RECOVERY_CODE_COUNT,
+            allowed_skew_steps: DEFAULT_ALLOWED_SKEW_STEPS,
+        }
+    }
+}
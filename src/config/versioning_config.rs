@@ -0,0 +1,54 @@
+// 必要なクレートのインポート
+use chrono::{DateTime, Utc};
+
+// APIのバージョンの設定を管理する構造体
+#[derive(Clone, Debug)]
+pub struct VersioningConfig {
+    // Acceptヘッダーでバージョンを選択できるようにするか
+    pub negotiation_enabled: bool,
+    // v1を非推奨にした日時(Deprecationヘッダー、未設定の場合は"true")
+    pub v1_deprecated_at: Option<DateTime<Utc>>,
+    // v1の提供を終了する日時(Sunsetヘッダー、未設定の場合は送らない)
+    pub v1_sunset: Option<DateTime<Utc>>,
+}
+
+// VersioningConfigの実装
+impl VersioningConfig {
+    // 環境変数から設定を読み込む関数
+    //
+    // - API_VERSION_NEGOTIATION: Acceptヘッダーでバージョンを選択できるようにするか(既定値: true)
+    // - API_V1_DEPRECATED_AT: v1を非推奨にした日時(RFC 3339形式、例: 2025-01-01T00:00:00Z)
+    // - API_V1_SUNSET: v1の提供を終了する日時(RFC 3339形式)
+    pub fn from_env() -> Self {
+        // 既定値を取得
+        let default = Self::default();
+
+        Self {
+            negotiation_enabled: std::env::var("API_VERSION_NEGOTIATION")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.negotiation_enabled),
+            v1_deprecated_at: parse_datetime_env("API_V1_DEPRECATED_AT").or(default.v1_deprecated_at),
+            v1_sunset: parse_datetime_env("API_V1_SUNSET").or(default.v1_sunset),
+        }
+    }
+}
+
+// 環境変数からRFC 3339形式の日時を読み込む関数
+fn parse_datetime_env(name: &str) -> Option<DateTime<Utc>> {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+        .map(|value| value.with_timezone(&Utc))
+}
+
+// Defaultトレイトの実装
+impl Default for VersioningConfig {
+    fn default() -> Self {
+        Self {
+            negotiation_enabled: true,
+            v1_deprecated_at: None,
+            v1_sunset: None,
+        }
+    }
+}
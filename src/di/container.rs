@@ -1,10 +1,30 @@
 // 必要なクレートのインポート
 use std::sync::Arc;
-use reqwest::Client;
+use std::time::Duration;
 use crate::di::repositories::user_repository::{UserRepositoryTrait, UserRepository};
+use crate::di::repositories::cached_user_repository::CachedUserRepository;
 use crate::di::services::user_di_service::UserDIService;
-use crate::di::handlers::user_handler::UserHandler;
+use crate::di::repositories::two_factor_repository::TwoFactorRepository;
+use crate::di::services::two_factor_service::TwoFactorService;
+use crate::di::repositories::user_identity_repository::UserIdentityRepository;
+use crate::di::services::user_identity_service::UserIdentityService;
+use crate::di::repositories::api_key_repository::ApiKeyRepository;
+use crate::di::services::api_key_service::ApiKeyService;
+use crate::di::repositories::session_repository::SessionRepository;
+use crate::di::services::session_service::SessionService;
+use crate::di::handlers::user_handler::{ResponseMode, UserHandler};
 use crate::di::routers::user_router::UserRouter;
+use crate::di::repositories::repository::Resource;
+use crate::di::repositories::supabase_repository::SupabaseRepository;
+use crate::di::services::crud_service::CrudService;
+use crate::di::handlers::crud_handler::CrudHandler;
+use crate::di::routers::crud_router::CrudRouter;
+use crate::supabase::supabase_client::SupabaseClient;
+use crate::cache::cache_backend::CacheBackend;
+use crate::cache::user_cache::UserCache;
+use crate::cache::lru_cache::InMemoryLruCache;
+use crate::config::cache_config::CacheConfig;
+use crate::password::password_hasher::PasswordHashers;
 
 // コンテナ
 #[allow(dead_code)]
@@ -12,43 +32,173 @@ pub struct Container {
     user_repository: Arc<dyn UserRepositoryTrait>,
     user_service: Arc<UserDIService>,
     user_handler: Arc<UserHandler>,
+    legacy_user_handler: Arc<UserHandler>,
     user_router: Arc<UserRouter>,
+    password_hashers: Arc<PasswordHashers>,
+    two_factor_service: Arc<TwoFactorService>,
+    user_identity_service: Arc<UserIdentityService>,
+    api_key_service: Arc<ApiKeyService>,
+    session_service: Arc<SessionService>,
+    user_cache: Option<Arc<UserCache>>,
+    supabase: SupabaseClient,
 }
 
 // メソッド
 impl Container {
-    // コンストラクタ
+    // コンストラクタ(キャッシュが有効な場合はメモリ上のLRUキャッシュを使う)
     pub fn new(
-        client: Client,
-        supabase_url: String,
-        supabase_anon_key: String,
+        supabase: SupabaseClient,
+        cache_config: &CacheConfig,
+        password_hashers: Arc<PasswordHashers>,
+    ) -> Self {
+        let cache = cache_config.enabled.then(|| {
+            Arc::new(InMemoryLruCache::new(cache_config.capacity)) as Arc<dyn CacheBackend>
+        });
+        Self::with_cache(supabase, cache, cache_config.ttl, password_hashers)
+    }
+
+    // キャッシュの保存先を指定するコンストラクタ(Noneの場合はキャッシュしない)
+    pub fn with_cache(
+        supabase: SupabaseClient,
+        cache: Option<Arc<dyn CacheBackend>>,
+        cache_ttl: Duration,
+        password_hashers: Arc<PasswordHashers>,
+    ) -> Self {
+        let user_cache = cache.map(|cache| Arc::new(UserCache::new(cache, cache_ttl)));
+        Self::build(supabase, user_cache, true, password_hashers)
+    }
+
+    // コンテナを組み立てる関数
+    //
+    // ユーザーの書き込みは、キャッシュの取得の有無によらず共有のキャッシュ(user_cache)で無効化する
+    fn build(
+        supabase: SupabaseClient,
+        user_cache: Option<Arc<UserCache>>,
+        read_cached: bool,
+        password_hashers: Arc<PasswordHashers>,
     ) -> Self {
         // リポジトリの初期化
-        let user_repository = Arc::new(UserRepository::new(
-            client,
-            supabase_url,
-            supabase_anon_key,
+        let user_repository: Arc<dyn UserRepositoryTrait> = Arc::new(UserRepository::with_password_hashers(
+            supabase.clone(),
+            password_hashers.clone(),
         ));
+        let user_repository: Arc<dyn UserRepositoryTrait> = match (&user_cache, read_cached) {
+            (Some(cache), true) => Arc::new(CachedUserRepository::new(user_repository, cache.clone())),
+            (Some(cache), false) => Arc::new(CachedUserRepository::invalidate_only(user_repository, cache.clone())),
+            (None, _) => user_repository,
+        };
         
         // サービスの初期化
-        let user_service = Arc::new(UserDIService::new(user_repository.clone()));
+        let user_service = Arc::new(UserDIService::with_password_hashers(
+            user_repository.clone(),
+            password_hashers.clone(),
+        ));
         
         // ハンドラーの初期化
         let user_handler = Arc::new(UserHandler::new(user_service.clone()));
+        // 以前の/usersのレスポンス形式を保つハンドラー(サービスとリポジトリは共通)
+        let legacy_user_handler = Arc::new(UserHandler::with_mode(user_service.clone(), ResponseMode::Legacy));
         
         // ルーターの初期化
         let user_router = Arc::new(UserRouter::new(user_handler.clone()));
 
+        // 二段階認証のサービスの初期化
+        let two_factor_service = Arc::new(TwoFactorService::with_user_cache(
+            Arc::new(TwoFactorRepository::new(supabase.clone())),
+            user_cache.clone(),
+        ));
+
+        // 外部のIdPのアカウントの紐付けのサービスの初期化
+        let user_identity_service = Arc::new(UserIdentityService::new(
+            Arc::new(UserIdentityRepository::new(supabase.clone())),
+            user_repository.clone(),
+        ));
+
+        // APIキーのサービスの初期化
+        let api_key_service = Arc::new(ApiKeyService::new(Arc::new(ApiKeyRepository::new(supabase.clone()))));
+
+        // サインインのセッションのサービスの初期化
+        let session_service = Arc::new(SessionService::new(Arc::new(SessionRepository::new(supabase.clone()))));
+
         Self {
             user_repository,
             user_service,
             user_handler,
+            legacy_user_handler,
             user_router,
+            password_hashers,
+            two_factor_service,
+            user_identity_service,
+            api_key_service,
+            session_service,
+            user_cache,
+            supabase,
         }
     }
 
+    // Authorizationヘッダーにユーザーのトークンを設定したコンテナを作成する関数
+    //
+    // PostgRESTのRLSがユーザーごとに適用されるため、結果を他のユーザーと共有しないよう取得にキャッシュは使わない。
+    // 書き込みは共有のキャッシュを無効化する
+    pub fn with_auth_token(&self, token: impl Into<String>) -> Self {
+        Self::build(
+            self.supabase.with_auth_token(token),
+            self.user_cache.clone(),
+            false,
+            self.password_hashers.clone(),
+        )
+    }
+
+    // ユーザーのリポジトリ(キャッシュが有効な場合はキャッシュつき)
+    pub fn user_repository(&self) -> Arc<dyn UserRepositoryTrait> {
+        self.user_repository.clone()
+    }
+
+    // 標準のハンドラー(/di/users・/v2/users)
+    pub fn user_handler(&self) -> Arc<UserHandler> {
+        self.user_handler.clone()
+    }
+
+    // 互換モードのハンドラー(/users)
+    pub fn legacy_user_handler(&self) -> Arc<UserHandler> {
+        self.legacy_user_handler.clone()
+    }
+
     // ルーター
     pub fn user_router(&self) -> Arc<UserRouter> {
         self.user_router.clone()
     }
-}
\ No newline at end of file
+
+    // パスワードのハッシュ化(サインイン時の照合とハッシュの作り直しに使う)
+    pub fn password_hashers(&self) -> Arc<PasswordHashers> {
+        self.password_hashers.clone()
+    }
+
+    // 二段階認証のサービス
+    pub fn two_factor_service(&self) -> Arc<TwoFactorService> {
+        self.two_factor_service.clone()
+    }
+
+    // 外部のIdPのアカウントの紐付けのサービス
+    pub fn user_identity_service(&self) -> Arc<UserIdentityService> {
+        self.user_identity_service.clone()
+    }
+
+    // APIキーのサービス
+    pub fn api_key_service(&self) -> Arc<ApiKeyService> {
+        self.api_key_service.clone()
+    }
+
+    // サインインのセッションのサービス
+    pub fn session_service(&self) -> Arc<SessionService> {
+        self.session_service.clone()
+    }
+
+    // 汎用CRUDリソースのルーター(リポジトリ・サービス・ハンドラーを組み立てる)
+    pub fn resource<T: Resource>(&self) -> CrudRouter<T> {
+        let repository = Arc::new(SupabaseRepository::<T>::new(self.supabase.clone()));
+        let service = Arc::new(CrudService::new(repository));
+        let handler = Arc::new(CrudHandler::new(service));
+        CrudRouter::new(handler)
+    }
+}
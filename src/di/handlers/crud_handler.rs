@@ -0,0 +1,77 @@
+use axum::{
+    extract::Json,
+    http::StatusCode,
+};
+use std::sync::Arc;
+// リポジトリ
+use crate::di::repositories::repository::Resource;
+// サービス
+use crate::di::services::crud_service::CrudService;
+
+// 汎用CRUDハンドラー
+pub struct CrudHandler<T: Resource> {
+    service: Arc<CrudService<T>>,
+}
+
+// メソッド
+impl<T: Resource> CrudHandler<T> {
+    // コンストラクタ
+    pub fn new(service: Arc<CrudService<T>>) -> Self {
+        Self { service }
+    }
+
+    pub async fn list(
+        &self,
+    ) -> Result<Json<Vec<T>>, (StatusCode, String)> {
+        self.service
+            .get_all()
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    pub async fn get(
+        &self,
+        id: T::Id,
+    ) -> Result<Json<T>, (StatusCode, String)> {
+        self.service
+            .get_by_id(id)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    pub async fn create(
+        &self,
+        Json(input): Json<T::Input>,
+    ) -> Result<Json<T>, (StatusCode, String)> {
+        self.service
+            .create(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    pub async fn update(
+        &self,
+        id: T::Id,
+        Json(input): Json<T::Input>,
+    ) -> Result<Json<T>, (StatusCode, String)> {
+        self.service
+            .update(id, input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    pub async fn delete(
+        &self,
+        id: T::Id,
+    ) -> Result<StatusCode, (StatusCode, String)> {
+        self.service
+            .delete(id)
+            .await
+            .map(|_| StatusCode::NO_CONTENT)
+            .map_err(Into::into)
+    }
+}
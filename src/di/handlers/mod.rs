@@ -1 +1,2 @@
-pub mod user_handler;
\ No newline at end of file
+pub mod user_handler;
+pub mod crud_handler;
\ No newline at end of file
@@ -1,26 +1,43 @@
 use axum::{
     extract::Json,
     http::StatusCode,
+    response::{IntoResponse, Response},
 };
 use std::sync::Arc;
 use uuid::Uuid;
 // ユーザー
-use crate::models::users::users::{User, NewUser};
-// エラー
-use crate::errors::users::user_error::UserError;
+use crate::models::users::users::{User, NewUser, UserUpdate, UserProfileUpdate, DeleteAccountRequest, ChangePasswordRequest};
 // サービス
 use crate::di::services::user_di_service::UserDIService;
 
+// 削除成功時のメッセージ(互換モード)
+pub const LEGACY_DELETE_MESSAGE: &str = "User deleted successfully";
+
+// レスポンスの形式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponseMode {
+    // 標準(削除は204 No Content)
+    Standard,
+    // 互換モード(以前の/usersと同じく、削除は200とメッセージのJSON)
+    Legacy,
+}
+
 // ハンドラー
 pub struct UserHandler {
     service: Arc<UserDIService>,
+    mode: ResponseMode,
 }
 
 // メソッド
 impl UserHandler {
     // コンストラクタ
     pub fn new(service: Arc<UserDIService>) -> Self {
-        Self { service }
+        Self::with_mode(service, ResponseMode::Standard)
+    }
+
+    // レスポンスの形式を指定するコンストラクタ
+    pub fn with_mode(service: Arc<UserDIService>, mode: ResponseMode) -> Self {
+        Self { service, mode }
     }
 
     pub async fn get_users(
@@ -30,15 +47,7 @@ impl UserHandler {
             .get_all_users()
             .await
             .map(Json)
-            .map_err(|e| {
-                match e {
-                    UserError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                    UserError::UserNotFound => (StatusCode::NOT_FOUND, "ユーザーが見つかりません".to_string()),
-                    UserError::InvalidData(msg) => (StatusCode::BAD_REQUEST, msg),
-                    UserError::PasswordError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                    UserError::JsonError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                }
-            })
+            .map_err(Into::into)
     }
 
     pub async fn get_user(
@@ -49,15 +58,7 @@ impl UserHandler {
             .get_user_by_id(id)
             .await
             .map(Json)
-            .map_err(|e| {
-                match e {
-                    UserError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                    UserError::UserNotFound => (StatusCode::NOT_FOUND, "ユーザーが見つかりません".to_string()),
-                    UserError::InvalidData(msg) => (StatusCode::BAD_REQUEST, msg),
-                    UserError::PasswordError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                    UserError::JsonError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                }
-            })
+            .map_err(Into::into)
     }
 
     pub async fn create_user(
@@ -68,53 +69,68 @@ impl UserHandler {
             .create_user(user)
             .await
             .map(Json)
-            .map_err(|e| {
-                match e {
-                    UserError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                    UserError::UserNotFound => (StatusCode::NOT_FOUND, "ユーザーが見つかりません".to_string()),
-                    UserError::InvalidData(msg) => (StatusCode::BAD_REQUEST, msg),
-                    UserError::PasswordError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                    UserError::JsonError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                }
-            })
+            .map_err(Into::into)
     }
 
     pub async fn update_user(
         &self,
         id: Uuid,
-        Json(user): Json<NewUser>,
+        Json(user): Json<UserUpdate>,
     ) -> Result<Json<User>, (StatusCode, String)> {
         self.service
             .update_user(id, user)
             .await
             .map(Json)
-            .map_err(|e| {
-                match e {
-                    UserError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                    UserError::UserNotFound => (StatusCode::NOT_FOUND, "ユーザーが見つかりません".to_string()),
-                    UserError::InvalidData(msg) => (StatusCode::BAD_REQUEST, msg),
-                    UserError::PasswordError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                    UserError::JsonError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                }
-            })
+            .map_err(Into::into)
+    }
+
+    pub async fn update_profile(
+        &self,
+        id: Uuid,
+        Json(profile): Json<UserProfileUpdate>,
+    ) -> Result<Json<User>, (StatusCode, String)> {
+        self.service
+            .update_profile(id, profile)
+            .await
+            .map(Json)
+            .map_err(Into::into)
     }
 
     pub async fn delete_user(
         &self,
         id: Uuid,
+    ) -> Result<Response, (StatusCode, String)> {
+        self.service.delete_user(id).await?;
+        Ok(self.deleted_response())
+    }
+
+    pub async fn delete_account(
+        &self,
+        id: Uuid,
+        Json(request): Json<DeleteAccountRequest>,
+    ) -> Result<Response, (StatusCode, String)> {
+        self.service.delete_account(id, &request.password).await?;
+        Ok(self.deleted_response())
+    }
+
+    pub async fn change_password(
+        &self,
+        id: Uuid,
+        Json(request): Json<ChangePasswordRequest>,
+        history_size: usize,
     ) -> Result<StatusCode, (StatusCode, String)> {
         self.service
-            .delete_user(id)
+            .change_password(id, &request.current_password, &request.new_password, history_size)
             .await
             .map(|_| StatusCode::NO_CONTENT)
-            .map_err(|e| {
-                match e {
-                    UserError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                    UserError::UserNotFound => (StatusCode::NOT_FOUND, "ユーザーが見つかりません".to_string()),
-                    UserError::InvalidData(msg) => (StatusCode::BAD_REQUEST, msg),
-                    UserError::PasswordError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                    UserError::JsonError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-                }
-            })
+            .map_err(Into::into)
     }
-}
\ No newline at end of file
+
+    // 削除成功時のレスポンス(レスポンスの形式に応じて返す)
+    fn deleted_response(&self) -> Response {
+        match self.mode {
+            ResponseMode::Standard => StatusCode::NO_CONTENT.into_response(),
+            ResponseMode::Legacy => Json(LEGACY_DELETE_MESSAGE.to_string()).into_response(),
+        }
+    }
+}
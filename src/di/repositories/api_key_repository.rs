@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// エラー
+use crate::errors::auth::auth_error::AuthError;
+// モデル
+use crate::models::auth::api_key::{ApiKeyResponse, ApiKeyScope};
+// Supabaseのクライアント
+use crate::supabase::supabase_client::SupabaseClient;
+// PostgRESTのクエリビルダー
+use crate::supabase::postgrest_query::{Order, PostgrestQuery};
+
+// APIキー(trans_user_api_keysの行)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApiKeyRecord {
+    // APIキーのID
+    pub id: Uuid,
+    // 発行したユーザーのID
+    pub user_id: Uuid,
+    // 名前
+    pub name: String,
+    // キーの先頭(識別用、一意)
+    pub prefix: String,
+    // キー全体のハッシュ(SHA-256)
+    pub key_hash: String,
+    // 権限
+    pub scopes: Vec<ApiKeyScope>,
+    // 有効期限
+    pub expires_at: NaiveDateTime,
+    // 最後に使われた日時
+    pub last_used_at: Option<NaiveDateTime>,
+    // 作成日時
+    pub created_at: NaiveDateTime,
+}
+
+// レスポンスへの変換(ハッシュを含めない)
+impl From<ApiKeyRecord> for ApiKeyResponse {
+    fn from(record: ApiKeyRecord) -> Self {
+        Self {
+            id: record.id,
+            name: record.name,
+            prefix: record.prefix,
+            scopes: record.scopes,
+            expires_at: record.expires_at,
+            last_used_at: record.last_used_at,
+            created_at: record.created_at,
+        }
+    }
+}
+
+// トレイト
+#[async_trait]
+pub trait ApiKeyRepositoryTrait: Send + Sync {
+    // ユーザーのAPIキーを新しい順に取得する
+    async fn list(&self, user_id: Uuid) -> Result<Vec<ApiKeyRecord>, AuthError>;
+    // キーの先頭からAPIキーを取得する(該当するキーがない場合はNone)
+    async fn find_by_prefix(&self, prefix: &str) -> Result<Option<ApiKeyRecord>, AuthError>;
+    // APIキーを保存する(ユーザーのキーの数が上限に達している場合はNone)
+    async fn create(&self, args: CreateApiKeyArgs) -> Result<Option<ApiKeyRecord>, AuthError>;
+    // ユーザーのAPIキーを削除する(該当するキーがない場合はfalseを返す)
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, AuthError>;
+    // 最終使用日時を現在時刻に更新する
+    async fn touch(&self, id: Uuid) -> Result<(), AuthError>;
+}
+
+// APIキーのテーブル
+pub const API_KEYS_TABLE: &str = "trans_user_api_keys";
+
+// 発行(監査ログ付き)のRPC
+pub const CREATE_API_KEY_RPC: &str = "create_api_key_with_audit";
+// 削除(監査ログ付き)のRPC
+pub const DELETE_API_KEY_RPC: &str = "delete_api_key_with_audit";
+// 最終使用日時の更新のRPC
+pub const TOUCH_API_KEY_RPC: &str = "touch_api_key";
+
+// 発行のRPCの引数
+#[derive(Serialize, Debug, Clone)]
+pub struct CreateApiKeyArgs {
+    // ユーザーID
+    pub p_user_id: Uuid,
+    // 名前
+    pub p_name: String,
+    // キーの先頭
+    pub p_prefix: String,
+    // キー全体のハッシュ(SHA-256)
+    pub p_key_hash: String,
+    // 権限
+    pub p_scopes: Vec<ApiKeyScope>,
+    // 有効期限
+    pub p_expires_at: NaiveDateTime,
+    // ユーザーが発行できるキーの数
+    pub p_max_keys: usize,
+}
+
+// リポジトリ
+pub struct ApiKeyRepository {
+    // Supabaseのクライアント
+    supabase: SupabaseClient,
+}
+
+// メソッド
+impl ApiKeyRepository {
+    // コンストラクタ
+    pub fn new(supabase: SupabaseClient) -> Self {
+        Self { supabase }
+    }
+}
+
+// トレイト実装
+//
+// 書き込みは全てRPCで行う(manuals/transaction.md)
+#[async_trait]
+impl ApiKeyRepositoryTrait for ApiKeyRepository {
+    async fn list(&self, user_id: Uuid) -> Result<Vec<ApiKeyRecord>, AuthError> {
+        let query = PostgrestQuery::new(API_KEYS_TABLE)
+            .eq("user_id", user_id)
+            .order("created_at", Order::Desc);
+        Ok(self.supabase.select("list_api_keys", &query).await?)
+    }
+
+    async fn find_by_prefix(&self, prefix: &str) -> Result<Option<ApiKeyRecord>, AuthError> {
+        let records: Vec<ApiKeyRecord> = self.supabase
+            .select("find_api_key", &PostgrestQuery::new(API_KEYS_TABLE).eq("prefix", prefix))
+            .await?;
+
+        Ok(records.into_iter().next())
+    }
+
+    async fn create(&self, args: CreateApiKeyArgs) -> Result<Option<ApiKeyRecord>, AuthError> {
+        let records: Vec<ApiKeyRecord> = self.supabase.rpc("create_api_key", CREATE_API_KEY_RPC, &args).await?;
+        Ok(records.into_iter().next())
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, AuthError> {
+        let args = json!({ "p_user_id": user_id, "p_id": id });
+        let rows: Vec<serde_json::Value> = self.supabase.rpc("delete_api_key", DELETE_API_KEY_RPC, &args).await?;
+        Ok(!rows.is_empty())
+    }
+
+    async fn touch(&self, id: Uuid) -> Result<(), AuthError> {
+        let args = json!({ "p_id": id });
+        let _: Vec<serde_json::Value> = self.supabase.rpc("touch_api_key", TOUCH_API_KEY_RPC, &args).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+// ユーザー
+use crate::models::users::users::{User, NewUser, UserProfileUpdate, UserUpdate};
+// エラー
+use crate::errors::users::user_error::UserError;
+// リポジトリ
+use crate::di::repositories::user_repository::UserRepositoryTrait;
+// ユーザーのキャッシュ
+use crate::cache::user_cache::UserCache;
+// メトリクス
+use crate::metrics::app_metrics::metrics;
+
+// メトリクスのキャッシュ名
+const CACHE_NAME: &str = "user";
+
+// IDでの取得結果をキャッシュするリポジトリ(他のリポジトリを包むデコレーター)
+//
+// 更新・削除の後はキャッシュを無効化する。一覧の取得はキャッシュしない
+pub struct CachedUserRepository {
+    // 包むリポジトリ
+    inner: Arc<dyn UserRepositoryTrait>,
+    // ユーザーのキャッシュ(全てのコンテナで共有する)
+    cache: Arc<UserCache>,
+    // 取得にキャッシュを使うか(falseの場合は書き込みの無効化のみ行う)
+    read_cached: bool,
+}
+
+// 実装
+impl CachedUserRepository {
+    // コンストラクタ
+    pub fn new(inner: Arc<dyn UserRepositoryTrait>, cache: Arc<UserCache>) -> Self {
+        Self { inner, cache, read_cached: true }
+    }
+
+    // 取得にキャッシュを使わず、書き込みでキャッシュを無効化するだけのリポジトリを作成
+    //
+    // ユーザーのトークンで呼び出す場合(RLSで結果が変わるため共有できない)に使う
+    pub fn invalidate_only(inner: Arc<dyn UserRepositoryTrait>, cache: Arc<UserCache>) -> Self {
+        Self { inner, cache, read_cached: false }
+    }
+}
+
+// トレイト実装
+#[async_trait]
+impl UserRepositoryTrait for CachedUserRepository {
+    async fn find_all(&self) -> Result<Vec<User>, UserError> {
+        self.inner.find_all().await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<User, UserError> {
+        if !self.read_cached {
+            return self.inner.find_by_id(id).await;
+        }
+
+        // キャッシュにあればそれを返す
+        if let Some(user) = self.cache.get(id).await {
+            metrics().record_cache_lookup(CACHE_NAME, true);
+            return Ok(user);
+        }
+        metrics().record_cache_lookup(CACHE_NAME, false);
+
+        self.find_by_id_uncached(id).await
+    }
+
+    async fn find_by_id_uncached(&self, id: Uuid) -> Result<User, UserError> {
+        // 取得を始める前の世代を記録し、取得中に無効化された場合は保存しない
+        let generation = self.cache.generation(id);
+
+        // 取得できたユーザーのみキャッシュ(見つからない結果はキャッシュしない)
+        let user = self.inner.find_by_id(id).await?;
+        if self.read_cached {
+            self.cache.store(&user, generation).await;
+        }
+        Ok(user)
+    }
+
+    async fn create(&self, user: NewUser) -> Result<User, UserError> {
+        let created = self.inner.create(user).await?;
+        if self.read_cached {
+            self.cache.store(&created, self.cache.generation(created.id)).await;
+        }
+        Ok(created)
+    }
+
+    async fn update(&self, id: Uuid, user: UserUpdate) -> Result<User, UserError> {
+        // 失敗した場合も書き込まれた可能性があるため、結果に関わらず無効化
+        let result = self.inner.update(id, user).await;
+        self.cache.invalidate(id).await;
+        result
+    }
+
+    async fn update_profile(&self, id: Uuid, profile: UserProfileUpdate) -> Result<User, UserError> {
+        // 失敗した場合も書き込まれた可能性があるため、結果に関わらず無効化
+        let result = self.inner.update_profile(id, profile).await;
+        self.cache.invalidate(id).await;
+        result
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), UserError> {
+        // 失敗した場合も削除された可能性があるため、結果に関わらず無効化
+        let result = self.inner.delete(id).await;
+        self.cache.invalidate(id).await;
+        result
+    }
+
+    async fn password_history(&self, id: Uuid, limit: usize) -> Result<Vec<String>, UserError> {
+        self.inner.password_history(id, limit).await
+    }
+
+    async fn change_password(&self, id: Uuid, password_hash: String, history_size: usize) -> Result<User, UserError> {
+        // 失敗した場合も書き込まれた可能性があるため、結果に関わらず無効化
+        let result = self.inner.change_password(id, password_hash, history_size).await;
+        self.cache.invalidate(id).await;
+        result
+    }
+
+    async fn rehash_password(&self, id: Uuid, current_hash: String, new_hash: String) -> Result<bool, UserError> {
+        // 失敗した場合も書き込まれた可能性があるため、結果に関わらず無効化
+        let result = self.inner.rehash_password(id, current_hash, new_hash).await;
+        self.cache.invalidate(id).await;
+        result
+    }
+}
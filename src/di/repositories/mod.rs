@@ -1,4 +1,11 @@
 pub mod user_repository;
+pub mod cached_user_repository;
+pub mod repository;
+pub mod supabase_repository;
+pub mod two_factor_repository;
+pub mod user_identity_repository;
+pub mod api_key_repository;
+pub mod session_repository;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,51 @@
+use std::fmt::Display;
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use utoipa::openapi::{schema::Schema, Ref, RefOr};
+use utoipa::ToSchema;
+
+// エラー
+use crate::errors::resources::resource_error::ResourceError;
+
+// リソース(PostgRESTのテーブル1つに対応するモデル)
+//
+// モデルにこのトレイトを実装すると、汎用のリポジトリ・サービス・ハンドラー・ルーターで
+// `/di/<RESOURCE>` のCRUD APIとOpenAPIのドキュメントを提供できる
+pub trait Resource: Serialize + DeserializeOwned + for<'s> ToSchema<'s> + Send + Sync + 'static {
+    // 主キーの型
+    type Id: Display + DeserializeOwned + Send + Sync + 'static;
+    // 作成・更新時に受け取る入力の型
+    type Input: Serialize + DeserializeOwned + for<'s> ToSchema<'s> + Send + Sync + 'static;
+
+    // テーブル名
+    const TABLE: &'static str;
+    // ルートのパス(/di/<RESOURCE>)とOpenAPIのタグ
+    const RESOURCE: &'static str;
+    // 主キーの列名
+    const ID_COLUMN: &'static str = "id";
+
+    // 主キーのOpenAPIのスキーマ
+    fn id_schema() -> RefOr<Schema> {
+        Ref::from_schema_name("UuidWrapper").into()
+    }
+
+    // 作成時に保存する行を組み立てる(デフォルトは入力をそのまま保存)
+    fn to_insert(input: Self::Input) -> Result<serde_json::Value, ResourceError> {
+        serde_json::to_value(input).map_err(|e| ResourceError::InvalidData(e.to_string()))
+    }
+
+    // 更新時に保存する値を組み立てる(デフォルトは入力をそのまま保存)
+    fn to_update(input: Self::Input) -> Result<serde_json::Value, ResourceError> {
+        serde_json::to_value(input).map_err(|e| ResourceError::InvalidData(e.to_string()))
+    }
+}
+
+// 汎用リポジトリのトレイト
+#[async_trait]
+pub trait Repository<T: Resource, Id>: Send + Sync {
+    async fn find_all(&self) -> Result<Vec<T>, ResourceError>;
+    async fn find_by_id(&self, id: Id) -> Result<T, ResourceError>;
+    async fn create(&self, input: T::Input) -> Result<T, ResourceError>;
+    async fn update(&self, id: Id, input: T::Input) -> Result<T, ResourceError>;
+    async fn delete(&self, id: Id) -> Result<(), ResourceError>;
+}
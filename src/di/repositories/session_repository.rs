@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// エラー
+use crate::errors::auth::auth_error::AuthError;
+// Supabaseのクライアント
+use crate::supabase::supabase_client::SupabaseClient;
+// PostgRESTのクエリビルダー
+use crate::supabase::postgrest_query::{Order, PostgrestQuery};
+
+// サインインのセッション(trans_user_sessionsの行)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SessionRecord {
+    // セッションのID(トークンのsid)
+    pub id: Uuid,
+    // ユーザーID
+    pub user_id: Uuid,
+    // サインインした端末のUser-Agent
+    pub user_agent: Option<String>,
+    // サインインした端末のIPアドレス
+    pub ip_address: Option<String>,
+    // サインインした日時
+    pub created_at: NaiveDateTime,
+    // 最後にアクセスした日時
+    pub last_seen_at: NaiveDateTime,
+    // 有効期限
+    pub expires_at: NaiveDateTime,
+}
+
+// トレイト
+#[async_trait]
+pub trait SessionRepositoryTrait: Send + Sync {
+    // ユーザーのセッションを新しい順に取得する
+    async fn list(&self, user_id: Uuid) -> Result<Vec<SessionRecord>, AuthError>;
+    // IDでセッションを取得する(該当するセッションがない場合はNone)
+    async fn find(&self, id: Uuid) -> Result<Option<SessionRecord>, AuthError>;
+    // セッションを保存する(ユーザーの期限切れのセッションは削除する)
+    async fn create(&self, args: CreateSessionArgs) -> Result<SessionRecord, AuthError>;
+    // ユーザーのセッションを削除する(該当するセッションがない場合はfalseを返す)
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, AuthError>;
+    // ユーザーの全てのセッションを削除する
+    async fn delete_all(&self, user_id: Uuid) -> Result<(), AuthError>;
+    // 最後にアクセスした日時を現在時刻に更新する
+    async fn touch(&self, id: Uuid) -> Result<(), AuthError>;
+}
+
+// セッションのテーブル
+pub const SESSIONS_TABLE: &str = "trans_user_sessions";
+
+// 作成のRPC
+pub const CREATE_SESSION_RPC: &str = "create_user_session";
+// 削除(監査ログ付き)のRPC
+pub const REVOKE_SESSION_RPC: &str = "revoke_session_with_audit";
+// ユーザーの全てのセッションの削除のRPC
+pub const REVOKE_USER_SESSIONS_RPC: &str = "revoke_user_sessions";
+// 最後にアクセスした日時の更新のRPC
+pub const TOUCH_SESSION_RPC: &str = "touch_user_session";
+
+// 作成のRPCの引数
+#[derive(Serialize, Debug, Clone)]
+pub struct CreateSessionArgs {
+    // ユーザーID
+    pub p_user_id: Uuid,
+    // サインインした端末のUser-Agent
+    pub p_user_agent: Option<String>,
+    // サインインした端末のIPアドレス
+    pub p_ip_address: Option<String>,
+    // 有効期限
+    pub p_expires_at: NaiveDateTime,
+}
+
+// リポジトリ
+pub struct SessionRepository {
+    // Supabaseのクライアント
+    supabase: SupabaseClient,
+}
+
+// メソッド
+impl SessionRepository {
+    // コンストラクタ
+    pub fn new(supabase: SupabaseClient) -> Self {
+        Self { supabase }
+    }
+}
+
+// トレイト実装
+//
+// 書き込みは全てRPCで行う(manuals/transaction.md)
+#[async_trait]
+impl SessionRepositoryTrait for SessionRepository {
+    async fn list(&self, user_id: Uuid) -> Result<Vec<SessionRecord>, AuthError> {
+        let query = PostgrestQuery::new(SESSIONS_TABLE)
+            .eq("user_id", user_id)
+            .order("created_at", Order::Desc);
+        Ok(self.supabase.select("list_sessions", &query).await?)
+    }
+
+    async fn find(&self, id: Uuid) -> Result<Option<SessionRecord>, AuthError> {
+        let records: Vec<SessionRecord> = self.supabase
+            .select("find_session", &PostgrestQuery::new(SESSIONS_TABLE).eq("id", id))
+            .await?;
+
+        Ok(records.into_iter().next())
+    }
+
+    async fn create(&self, args: CreateSessionArgs) -> Result<SessionRecord, AuthError> {
+        let records: Vec<SessionRecord> = self.supabase.rpc("create_session", CREATE_SESSION_RPC, &args).await?;
+        records
+            .into_iter()
+            .next()
+            .ok_or(AuthError::DatabaseError("Session creation failed".to_string()))
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, AuthError> {
+        let args = json!({ "p_user_id": user_id, "p_id": id });
+        let rows: Vec<serde_json::Value> = self.supabase.rpc("revoke_session", REVOKE_SESSION_RPC, &args).await?;
+        Ok(!rows.is_empty())
+    }
+
+    async fn delete_all(&self, user_id: Uuid) -> Result<(), AuthError> {
+        let args = json!({ "p_user_id": user_id });
+        let _: Vec<serde_json::Value> = self.supabase.rpc("revoke_user_sessions", REVOKE_USER_SESSIONS_RPC, &args).await?;
+        Ok(())
+    }
+
+    async fn touch(&self, id: Uuid) -> Result<(), AuthError> {
+        let args = json!({ "p_id": id });
+        let _: Vec<serde_json::Value> = self.supabase.rpc("touch_session", TOUCH_SESSION_RPC, &args).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use std::marker::PhantomData;
+use async_trait::async_trait;
+
+// リポジトリ
+use crate::di::repositories::repository::{Repository, Resource};
+// エラー
+use crate::errors::resources::resource_error::ResourceError;
+// Supabaseのクライアント
+use crate::supabase::supabase_client::SupabaseClient;
+// PostgRESTのクエリビルダー
+use crate::supabase::postgrest_query::PostgrestQuery;
+
+// PostgRESTのテーブルを操作する汎用リポジトリ
+pub struct SupabaseRepository<T> {
+    // Supabaseのクライアント
+    supabase: SupabaseClient,
+    // 対象のリソース
+    resource: PhantomData<fn() -> T>,
+}
+
+// メソッド
+impl<T: Resource> SupabaseRepository<T> {
+    // コンストラクタ
+    pub fn new(supabase: SupabaseClient) -> Self {
+        Self {
+            // Supabaseのクライアント
+            supabase,
+            // 対象のリソース
+            resource: PhantomData,
+        }
+    }
+
+    // 主キーで絞り込むクエリ
+    fn by_id(id: &T::Id) -> PostgrestQuery {
+        PostgrestQuery::new(T::TABLE).eq(T::ID_COLUMN, id)
+    }
+}
+
+// トレイト実装
+#[async_trait]
+impl<T: Resource> Repository<T, T::Id> for SupabaseRepository<T> {
+    // 全件取得
+    async fn find_all(&self) -> Result<Vec<T>, ResourceError> {
+        Ok(self.supabase
+            .select("find_all", &PostgrestQuery::new(T::TABLE))
+            .await?)
+    }
+
+    // 1件取得
+    async fn find_by_id(&self, id: T::Id) -> Result<T, ResourceError> {
+        let rows: Vec<T> = self.supabase
+            .select("find_by_id", &Self::by_id(&id))
+            .await?;
+
+        rows.into_iter()
+            .next()
+            .ok_or(ResourceError::NotFound)
+    }
+
+    // 作成
+    async fn create(&self, input: T::Input) -> Result<T, ResourceError> {
+        let row = T::to_insert(input)?;
+        let rows: Vec<T> = self.supabase
+            .insert("create", &PostgrestQuery::new(T::TABLE), &row)
+            .await?;
+
+        rows.into_iter()
+            .next()
+            .ok_or(ResourceError::DatabaseError("Resource creation failed".to_string()))
+    }
+
+    // 更新
+    async fn update(&self, id: T::Id, input: T::Input) -> Result<T, ResourceError> {
+        let values = T::to_update(input)?;
+        let rows: Vec<T> = self.supabase
+            .update("update", &Self::by_id(&id), &values)
+            .await?;
+
+        rows.into_iter()
+            .next()
+            .ok_or(ResourceError::NotFound)
+    }
+
+    // 削除
+    async fn delete(&self, id: T::Id) -> Result<(), ResourceError> {
+        let rows: Vec<serde_json::Value> = self.supabase
+            .delete("delete", &Self::by_id(&id))
+            .await?;
+
+        if rows.is_empty() {
+            return Err(ResourceError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use async_trait::async_trait;
+use uuid::Uuid;
+// リポジトリのインポート
+use crate::di::repositories::user_repository::UserRepositoryTrait;
+use crate::di::repositories::cached_user_repository::CachedUserRepository;
+// キャッシュのインポート
+use crate::cache::lru_cache::InMemoryLruCache;
+use crate::cache::user_cache::UserCache;
+// モデルのインポート
+use crate::models::users::users::{User, NewUser, UserProfileUpdate, UserUpdate};
+// エラーのインポート
+use crate::errors::users::user_error::UserError;
+// ヘルパーのインポート
+use super::helpers::{create_test_user, create_test_user_update, create_test_user_with_id};
+
+// 呼び出し回数を数えるテスト用のリポジトリ
+struct CountingRepository {
+    // 返すユーザー(Noneの場合は見つからない)
+    user: Option<User>,
+    // find_by_idの呼び出し回数
+    find_calls: AtomicUsize,
+}
+
+// トレイト実装
+#[async_trait]
+impl UserRepositoryTrait for CountingRepository {
+    async fn find_all(&self) -> Result<Vec<User>, UserError> {
+        Ok(self.user.clone().into_iter().collect())
+    }
+
+    async fn find_by_id(&self, _id: Uuid) -> Result<User, UserError> {
+        self.find_calls.fetch_add(1, Ordering::SeqCst);
+        self.user.clone().ok_or(UserError::UserNotFound)
+    }
+
+    async fn create(&self, _user: NewUser) -> Result<User, UserError> {
+        self.user.clone().ok_or(UserError::DatabaseError("User creation failed".to_string()))
+    }
+
+    async fn update(&self, _id: Uuid, user: UserUpdate) -> Result<User, UserError> {
+        let mut updated = self.user.clone().ok_or(UserError::UserNotFound)?;
+        updated.username = user.username;
+        Ok(updated)
+    }
+
+    async fn update_profile(&self, _id: Uuid, profile: UserProfileUpdate) -> Result<User, UserError> {
+        let mut updated = self.user.clone().ok_or(UserError::UserNotFound)?;
+        updated.username = profile.username.unwrap_or(updated.username);
+        Ok(updated)
+    }
+
+    async fn delete(&self, _id: Uuid) -> Result<(), UserError> {
+        self.user.as_ref().map(|_| ()).ok_or(UserError::UserNotFound)
+    }
+
+    async fn password_history(&self, _id: Uuid, _limit: usize) -> Result<Vec<String>, UserError> {
+        Ok(Vec::new())
+    }
+
+    async fn change_password(&self, _id: Uuid, password_hash: String, _history_size: usize) -> Result<User, UserError> {
+        let mut updated = self.user.clone().ok_or(UserError::UserNotFound)?;
+        updated.password = password_hash;
+        Ok(updated)
+    }
+
+    async fn rehash_password(&self, _id: Uuid, _current_hash: String, _new_hash: String) -> Result<bool, UserError> {
+        Ok(self.user.is_some())
+    }
+}
+
+// テスト用のリポジトリを作成
+fn create_test_repository(user: Option<User>) -> (Arc<CountingRepository>, CachedUserRepository) {
+    let inner = Arc::new(CountingRepository {
+        user,
+        find_calls: AtomicUsize::new(0),
+    });
+    let repository = CachedUserRepository::new(
+        inner.clone(),
+        Arc::new(UserCache::new(Arc::new(InMemoryLruCache::new(10)), Duration::from_secs(60))),
+    );
+    (inner, repository)
+}
+
+// 2回目以降の取得はキャッシュから返すことのテスト
+#[tokio::test]
+async fn test_find_by_id_is_cached() {
+    let user = create_test_user_with_id();
+    let (inner, repository) = create_test_repository(Some(user.clone()));
+
+    let first = repository.find_by_id(user.id).await.unwrap();
+    let second = repository.find_by_id(user.id).await.unwrap();
+
+    assert_eq!(first.id, user.id);
+    assert_eq!(second.email, user.email);
+    assert_eq!(inner.find_calls.load(Ordering::SeqCst), 1);
+}
+
+// 有効期限が切れると取得し直すことのテスト
+#[tokio::test(start_paused = true)]
+async fn test_find_by_id_refetches_after_ttl() {
+    let user = create_test_user_with_id();
+    let (inner, repository) = create_test_repository(Some(user.clone()));
+
+    repository.find_by_id(user.id).await.unwrap();
+    tokio::time::advance(Duration::from_secs(61)).await;
+    repository.find_by_id(user.id).await.unwrap();
+
+    assert_eq!(inner.find_calls.load(Ordering::SeqCst), 2);
+}
+
+// 見つからない結果はキャッシュしないことのテスト
+#[tokio::test]
+async fn test_not_found_is_not_cached() {
+    let (inner, repository) = create_test_repository(None);
+    let id = Uuid::new_v4();
+
+    for _ in 0..2 {
+        let result = repository.find_by_id(id).await;
+        assert!(matches!(result, Err(UserError::UserNotFound)));
+    }
+    assert_eq!(inner.find_calls.load(Ordering::SeqCst), 2);
+}
+
+// 更新するとキャッシュが無効化されることのテスト
+#[tokio::test]
+async fn test_update_invalidates_cache() {
+    let user = create_test_user_with_id();
+    let (inner, repository) = create_test_repository(Some(user.clone()));
+
+    repository.find_by_id(user.id).await.unwrap();
+    repository.update(user.id, create_test_user_update()).await.unwrap();
+    repository.find_by_id(user.id).await.unwrap();
+
+    assert_eq!(inner.find_calls.load(Ordering::SeqCst), 2);
+}
+
+// 削除するとキャッシュが無効化されることのテスト
+#[tokio::test]
+async fn test_delete_invalidates_cache() {
+    let user = create_test_user_with_id();
+    let (inner, repository) = create_test_repository(Some(user.clone()));
+
+    repository.find_by_id(user.id).await.unwrap();
+    repository.delete(user.id).await.unwrap();
+    repository.find_by_id(user.id).await.unwrap();
+
+    assert_eq!(inner.find_calls.load(Ordering::SeqCst), 2);
+}
+
+// 作成したユーザーはキャッシュから返すことのテスト
+#[tokio::test]
+async fn test_create_populates_cache() {
+    let user = create_test_user_with_id();
+    let (inner, repository) = create_test_repository(Some(user.clone()));
+
+    let created = repository.create(create_test_user()).await.unwrap();
+    let found = repository.find_by_id(created.id).await.unwrap();
+
+    assert_eq!(found.id, user.id);
+    assert_eq!(inner.find_calls.load(Ordering::SeqCst), 0);
+}
+
+// キャッシュを使わない取得は常に包むリポジトリから取得することのテスト
+#[tokio::test]
+async fn test_find_by_id_uncached_skips_cache() {
+    let user = create_test_user_with_id();
+    let (inner, repository) = create_test_repository(Some(user.clone()));
+
+    repository.find_by_id(user.id).await.unwrap();
+    repository.find_by_id_uncached(user.id).await.unwrap();
+    repository.find_by_id_uncached(user.id).await.unwrap();
+
+    assert_eq!(inner.find_calls.load(Ordering::SeqCst), 3);
+}
+
+// 取得にキャッシュを使わないリポジトリの書き込みも、共有のキャッシュを無効化することのテスト
+#[tokio::test]
+async fn test_invalidate_only_repository_invalidates_shared_cache() {
+    let user = create_test_user_with_id();
+    let inner = Arc::new(CountingRepository {
+        user: Some(user.clone()),
+        find_calls: AtomicUsize::new(0),
+    });
+    let cache = Arc::new(UserCache::new(Arc::new(InMemoryLruCache::new(10)), Duration::from_secs(60)));
+    let shared = CachedUserRepository::new(inner.clone(), cache.clone());
+    let scoped = CachedUserRepository::invalidate_only(inner.clone(), cache);
+
+    // キャッシュに保存
+    shared.find_by_id(user.id).await.unwrap();
+    // 取得はキャッシュを使わず、保存もしない
+    scoped.find_by_id(user.id).await.unwrap();
+    assert_eq!(inner.find_calls.load(Ordering::SeqCst), 2);
+
+    // 書き込むと共有のキャッシュが無効化される
+    scoped.update(user.id, create_test_user_update()).await.unwrap();
+    shared.find_by_id(user.id).await.unwrap();
+    assert_eq!(inner.find_calls.load(Ordering::SeqCst), 3);
+}
@@ -3,16 +3,25 @@ use uuid::Uuid;
 use chrono::Utc;
 use serde_json::json;
 // モデルのインポート
-use crate::models::users::users::{User, NewUser};
+use crate::models::users::users::{User, NewUser, UserUpdate};
 
 // モックのハンドル
 pub struct MockHandles {
-    // 開始モック
-    pub begin_mock: Mock,
-    // 作成モック
-    pub create_mock: Mock,
-    // コミットモック
-    pub commit_mock: Mock,
+    // RPCのモック
+    pub rpc_mock: Mock,
+    // 呼ばれてはいけない書き込みのモック(テーブルへの直接の書き込み・以前のトランザクション関数)
+    pub forbidden_mocks: Vec<Mock>,
+}
+
+// MockHandlesの実装
+impl MockHandles {
+    // RPCが1回だけ呼ばれ、それ以外の書き込みがないことを検証
+    pub async fn assert_single_rpc_write(&self) {
+        self.rpc_mock.assert_async().await;
+        for mock in &self.forbidden_mocks {
+            mock.assert_async().await;
+        }
+    }
 }
 
 // テストユーザーの作成
@@ -25,6 +34,14 @@ pub fn create_test_user() -> NewUser {
     }
 }
 
+// テストユーザーの更新内容の作成
+pub fn create_test_user_update() -> UserUpdate {
+    UserUpdate {
+        username: "test_user".to_string(),
+        email: "test@example.com".to_string(),
+    }
+}
+
 // IDつきテストユーザーの作成
 pub fn create_test_user_with_id() -> User {
     // テストユーザーの作成
@@ -33,107 +50,99 @@ pub fn create_test_user_with_id() -> User {
         username: "test_user".to_string(),
         email: "test@example.com".to_string(),
         password: "hashed_password".to_string(),
+        two_factor_enabled: false,
         created_at: Utc::now().naive_utc(),
         updated_at: Utc::now().naive_utc(),
     }
 }
 
-// モックのセットアップ
-pub async fn setup_mocks(mock_server: &mut mockito::Server, created_user: &User) -> MockHandles {
-    let begin_mock = mock_server
-        .mock("POST", "/rest/v1/rpc/begin_transaction")
-        .match_header("apikey", "test_key")
-        .match_header("Content-Type", "application/json")
-        .match_body(mockito::Matcher::Any)
-        .with_status(200)
-        .with_body(json!({"transaction_id": "test-tx"}).to_string())
-        .create_async()
-        .await;
+// 呼ばれてはいけない書き込みのモックのセットアップ
+//
+// ユーザーの書き込みはRPC1回で行うため、テーブルへの直接の書き込みや
+// 複数のリクエストにまたがるトランザクション関数の呼び出しがあればテストが失敗する
+pub async fn setup_forbidden_write_mocks(mock_server: &mut mockito::Server) -> Vec<Mock> {
+    let mut mocks = Vec::new();
+
+    // テーブルへの直接の書き込み
+    for method in ["POST", "PATCH", "DELETE"] {
+        mocks.push(mock_server
+            .mock(method, mockito::Matcher::Regex(r"^/rest/v1/trans_users".to_string()))
+            .expect(0)
+            .create_async()
+            .await);
+    }
 
-    // ユーザー作成のモック
-    let create_mock = mock_server
-        .mock("POST", "/rest/v1/trans_users")
-        .match_header("apikey", "test_key")
-        .match_header("Content-Type", "application/json")
-        .match_header("Transaction-Id", "test-tx")
-        .match_header("Prefer", "return=representation")
-        .match_body(mockito::Matcher::Any)
-        .with_status(200)
-        .with_body(json!([created_user]).to_string())
-        .create_async()
-        .await;
+    // 以前のトランザクション関数
+    for function in ["begin_transaction", "commit_transaction", "rollback_transaction"] {
+        mocks.push(mock_server
+            .mock("POST", format!("/rest/v1/rpc/{}", function).as_str())
+            .expect(0)
+            .create_async()
+            .await);
+    }
+
+    mocks
+}
 
-    // コミットモック
-    let commit_mock = mock_server
-        .mock("POST", "/rest/v1/rpc/commit_transaction")
+// RPCのモックのセットアップ
+async fn setup_rpc_mock(
+    mock_server: &mut mockito::Server,
+    function: &str,
+    body: mockito::Matcher,
+    status: usize,
+    response: serde_json::Value,
+) -> MockHandles {
+    let rpc_mock = mock_server
+        .mock("POST", format!("/rest/v1/rpc/{}", function).as_str())
         .match_header("apikey", "test_key")
         .match_header("Content-Type", "application/json")
-        .match_body(mockito::Matcher::JsonString(
-            json!({"transaction_id": "test-tx"}).to_string()
-        ))
-        .with_status(200)
-        .with_body("{}")
+        .match_body(body)
+        .with_status(status)
+        .with_body(response.to_string())
+        .expect(1)
         .create_async()
         .await;
 
-    // モックハンドルの返却
     MockHandles {
-        begin_mock,
-        create_mock,
-        commit_mock,
+        rpc_mock,
+        forbidden_mocks: setup_forbidden_write_mocks(mock_server).await,
     }
 }
 
+// 作成用のモックセットアップ
+pub async fn setup_mocks(mock_server: &mut mockito::Server, created_user: &User) -> MockHandles {
+    setup_rpc_mock(
+        mock_server,
+        "create_user_with_audit",
+        mockito::Matcher::PartialJson(json!({
+            "p_username": created_user.username,
+            "p_email": created_user.email,
+        })),
+        200,
+        json!([created_user]),
+    )
+    .await
+}
+
 // 更新用のモックセットアップ
 pub async fn setup_update_mocks(
     mock_server: &mut mockito::Server,
     user_id: &Uuid,
     updated_user: &User
 ) -> MockHandles {
-    // 開始モック
-    let begin_mock = mock_server
-        .mock("POST", "/rest/v1/rpc/begin_transaction")
-        .match_header("apikey", "test_key")
-        .match_header("Content-Type", "application/json")
-        .match_body(mockito::Matcher::Any)
-        .with_status(200)
-        .with_body(json!({"transaction_id": "test-tx"}).to_string())
-        .create_async()
-        .await;
-
-    // ユーザー更新のモック
-    let path = format!("/rest/v1/trans_users?id=eq.{}", user_id);
-    let update_mock = mock_server
-        .mock("PATCH", path.as_str())
-        .match_header("apikey", "test_key")
-        .match_header("Content-Type", "application/json")
-        .match_header("Prefer", "return=representation")
-        .match_header("Transaction-Id", "test-tx")
-        .match_body(mockito::Matcher::Any)
-        .with_status(200)
-        .with_body(json!([updated_user]).to_string())
-        .create_async()
-        .await;
-
-    // コミットモック
-    let commit_mock = mock_server
-        .mock("POST", "/rest/v1/rpc/commit_transaction")
-        .match_header("apikey", "test_key")
-        .match_header("Content-Type", "application/json")
-        .match_body(mockito::Matcher::JsonString(
-            json!({"transaction_id": "test-tx"}).to_string()
-        ))
-        .with_status(200)
-        .with_body("{}")
-        .create_async()
-        .await;
-
-    // モックハンドルの返却
-    MockHandles {
-        begin_mock,
-        create_mock: update_mock,  // 更新用のモックを割り当て
-        commit_mock,
-    }
+    setup_rpc_mock(
+        mock_server,
+        "update_user_with_audit",
+        // パスワードを送らないことも確認する
+        mockito::Matcher::Json(json!({
+            "p_id": user_id,
+            "p_username": updated_user.username,
+            "p_email": updated_user.email,
+        })),
+        200,
+        json!([updated_user]),
+    )
+    .await
 }
 
 // 削除用のモックセットアップ
@@ -141,46 +150,43 @@ pub async fn setup_delete_mocks(
     mock_server: &mut mockito::Server,
     user_id: &Uuid
 ) -> MockHandles {
-    // 開始モック
-    let begin_mock = mock_server
-        .mock("POST", "/rest/v1/rpc/begin_transaction")
-        .match_header("apikey", "test_key")
-        .match_header("Content-Type", "application/json")
-        .match_body(mockito::Matcher::Any)
-        .with_status(200)
-        .with_body(json!({"transaction_id": "test-tx"}).to_string())
-        .create_async()
-        .await;
-
-    // ユーザー削除のパス
-    let path = format!("/rest/v1/trans_users?id=eq.{}", user_id);
-    let delete_mock = mock_server
-        .mock("DELETE", path.as_str())
-        .match_header("apikey", "test_key")
-        .match_header("Transaction-Id", "test-tx")
-        .with_status(204)
-        .create_async()
-        .await;
+    let mut deleted_user = create_test_user_with_id();
+    deleted_user.id = *user_id;
+
+    setup_rpc_mock(
+        mock_server,
+        "delete_user_with_audit",
+        mockito::Matcher::Json(json!({ "p_id": user_id })),
+        200,
+        json!([deleted_user]),
+    )
+    .await
+}
 
-    // コミットモック
-    let commit_mock = mock_server
-        .mock("POST", "/rest/v1/rpc/commit_transaction")
-        .match_header("apikey", "test_key")
-        .match_header("Content-Type", "application/json")
-        .match_body(mockito::Matcher::JsonString(
-            json!({"transaction_id": "test-tx"}).to_string()
-        ))
-        .with_status(200)
-        .with_body("{}")
-        .create_async()
-        .await;
+// 該当するユーザーがいない書き込み用のモックセットアップ
+pub async fn setup_missing_user_mocks(
+    mock_server: &mut mockito::Server,
+    function: &str,
+    user_id: &Uuid,
+) -> MockHandles {
+    setup_rpc_mock(
+        mock_server,
+        function,
+        mockito::Matcher::PartialJson(json!({ "p_id": user_id })),
+        200,
+        json!([]),
+    )
+    .await
+}
 
-    // モックハンドルの返却
-    MockHandles {
-        begin_mock,
-        create_mock: delete_mock,  // 削除用のモックを割り当て
-        commit_mock,
-    }
+// 失敗する書き込み用のモックセットアップ
+pub async fn setup_failed_write_mocks(
+    mock_server: &mut mockito::Server,
+    function: &str,
+    status: usize,
+    error: serde_json::Value,
+) -> MockHandles {
+    setup_rpc_mock(mock_server, function, mockito::Matcher::Any, status, error).await
 }
 
 // 存在しないユーザー検索用のモックセットアップ
@@ -198,48 +204,20 @@ pub async fn setup_not_found_mock(
         .await
 }
 
-// 作成エラー用のモックセットアップ
+// 作成エラー用のモックセットアップ(監査ログの書き込みが失敗し、関数全体がロールバックされる)
 pub async fn setup_create_error_mock(
     mock_server: &mut mockito::Server,
 ) -> MockHandles {
-    let begin_mock = mock_server
-        .mock("POST", "/rest/v1/rpc/begin_transaction")
-        .match_header("apikey", "test_key")
-        .match_header("Content-Type", "application/json")
-        .match_body(mockito::Matcher::Any)
-        .with_status(200)
-        .with_body(json!({"transaction_id": "test-tx"}).to_string())
-        .create_async()
-        .await;
-
-    let create_mock = mock_server
-        .mock("POST", "/rest/v1/trans_users")
-        .match_header("apikey", "test_key")
-        .match_header("Content-Type", "application/json")
-        .match_header("Transaction-Id", "test-tx")
-        .match_body(mockito::Matcher::Any)
-        .with_status(400)
-        .with_body(json!({
-            "message": "Invalid user data"
-        }).to_string())
-        .create_async()
-        .await;
-
-    let commit_mock = mock_server
-        .mock("POST", "/rest/v1/rpc/commit_transaction")
-        .match_header("apikey", "test_key")
-        .match_header("Content-Type", "application/json")
-        .match_body(mockito::Matcher::JsonString(
-            json!({"transaction_id": "test-tx"}).to_string()
-        ))
-        .with_status(200)
-        .with_body("{}")
-        .create_async()
-        .await;
-
-    MockHandles {
-        begin_mock,
-        create_mock,
-        commit_mock,
-    }
-}
\ No newline at end of file
+    setup_failed_write_mocks(
+        mock_server,
+        "create_user_with_audit",
+        400,
+        json!({
+            "code": "23502",
+            "message": "null value in column \"payload\" of relation \"trans_user_audit_logs\" violates not-null constraint",
+            "details": null,
+            "hint": null
+        }),
+    )
+    .await
+}
@@ -1,4 +1,7 @@
 #[cfg(test)]
 pub mod helpers;
 pub mod user_repository_01_test;
-pub mod user_repository_02_test;
\ No newline at end of file
+pub mod user_repository_02_test;
+pub mod user_repository_03_test;
+pub mod cached_user_repository_01_test;
+
@@ -1,17 +1,22 @@
-use std::time::Duration;
 use reqwest::Client;
 use serde_json::json;
 use chrono::Utc;
 use uuid::Uuid;
+use std::sync::Arc;
 // リポジトリのインポート
 use crate::di::repositories::user_repository::{UserRepository, UserRepositoryTrait};
+// 呼び出しポリシーのインポート
+use crate::outbound::outbound_policy::OutboundPolicy;
+// Supabaseのクライアントのインポート
+use crate::supabase::supabase_client::SupabaseClient;
 // エラーのインポート
 use crate::errors::users::user_error::UserError;
 // モデルのインポート
-use crate::models::users::users::{User, NewUser};
+use crate::models::users::users::{User, NewUser, UserUpdate};
 // ヘルパーのインポート
 use super::helpers::{
     create_test_user,
+    create_test_user_update,
     create_test_user_with_id,
     setup_mocks,
     setup_update_mocks,
@@ -44,11 +49,12 @@ async fn test_find_all() {
     // HTTPクライアントの初期化
     let client = Client::new();
     // リポジトリの初期化
-    let repository = UserRepository::new(
+    let repository = UserRepository::new(SupabaseClient::new(
         client,
         mock_url,
         "test_key".to_string(),
-    );
+        Arc::new(OutboundPolicy::default()),
+    ));
 
     // find_allのテスト
     let result = repository.find_all().await;
@@ -94,11 +100,12 @@ async fn test_find_by_id() {
     // HTTPクライアントの初期化
     let client = Client::new();
     // リポジトリの初期化
-    let repository = UserRepository::new(
+    let repository = UserRepository::new(SupabaseClient::new(
         client,
         mock_url,
         "test_key".to_string(),
-    );
+        Arc::new(OutboundPolicy::default()),
+    ));
 
     // find_by_idのテスト
     let result = repository.find_by_id(user_id).await;
@@ -131,11 +138,12 @@ async fn test_create() {
     let mocks = setup_mocks(&mut mock_server, &created_user).await;
     
     let client = Client::new();
-    let repository = UserRepository::new(
+    let repository = UserRepository::new(SupabaseClient::new(
         client,
         mock_url.clone(),
         "test_key".to_string(),
-    );
+        Arc::new(OutboundPolicy::default()),
+    ));
    
     println!("Executing create operation...");
     let result = repository.create(new_user.clone()).await;
@@ -150,12 +158,13 @@ async fn test_update() {
     let mock_url = mock_server.url();
 
     let user_id = Uuid::new_v4();
-    let update_user = create_test_user();
+    let update_user = create_test_user_update();
     let updated_user = User {
         id: user_id,
         username: update_user.username.clone(),
         email: update_user.email.clone(),
-        password: "hashed_new_password".to_string(),
+        password: "hashed_password".to_string(),
+        two_factor_enabled: false,
         created_at: Utc::now().naive_utc(),
         updated_at: Utc::now().naive_utc(),
     };
@@ -164,11 +173,12 @@ async fn test_update() {
     let mocks = setup_update_mocks(&mut mock_server, &user_id, &updated_user).await;
 
     let client = Client::new();
-    let repository = UserRepository::new(
+    let repository = UserRepository::new(SupabaseClient::new(
         client,
         mock_url,
         "test_key".to_string(),
-    );
+        Arc::new(OutboundPolicy::default()),
+    ));
 
     println!("Executing update operation...");
     let result = repository.update(user_id, update_user.clone()).await;
@@ -187,11 +197,12 @@ async fn test_delete() {
     let mocks = setup_delete_mocks(&mut mock_server, &user_id).await;
 
     let client = Client::new();
-    let repository = UserRepository::new(
+    let repository = UserRepository::new(SupabaseClient::new(
         client,
         mock_url,
         "test_key".to_string(),
-    );
+        Arc::new(OutboundPolicy::default()),
+    ));
 
     println!("Executing delete operation...");
     let result = repository.delete(user_id).await;
@@ -206,16 +217,10 @@ async fn verify_add_result(result: &Result<User, UserError>, new_user: &NewUser,
         Ok(user) => println!("Create succeeded: {:?}", user),
         Err(e) => println!("Create failed with error: {:?}", e),
     }
-   
-    // モックの待機
-    println!("Waiting for async operations to complete...");
-    tokio::time::sleep(Duration::from_millis(1000)).await;
-   
-    // モックの検証
+
+    // モックの検証(RPC1回のみで書き込む)
     println!("Verifying mocks...");
-    mocks.begin_mock.assert_async().await;
-    mocks.create_mock.assert_async().await;
-    mocks.commit_mock.assert_async().await;
+    mocks.assert_single_rpc_write().await;
    
     // 結果の検証
     assert!(result.is_ok(), "Create operation failed: {:?}", result.as_ref().err());
@@ -233,7 +238,7 @@ async fn verify_add_result(result: &Result<User, UserError>, new_user: &NewUser,
 async fn verify_update_result(
     result: &Result<User, UserError>,
     user_id: Uuid,
-    update_user: &NewUser,
+    update_user: &UserUpdate,
     mocks: &MockHandles
 ) {
     // 結果のログ
@@ -242,11 +247,9 @@ async fn verify_update_result(
         Err(e) => println!("Update failed with error: {:?}", e),
     }
 
-    // モックの検証
+    // モックの検証(RPC1回のみで書き込む)
     println!("Verifying mocks...");
-    mocks.begin_mock.assert_async().await;
-    mocks.create_mock.assert_async().await;
-    mocks.commit_mock.assert_async().await;
+    mocks.assert_single_rpc_write().await;
 
     // 結果の検証
     assert!(result.is_ok(), "Update operation failed: {:?}", result.as_ref().err());
@@ -259,8 +262,6 @@ async fn verify_update_result(
         assert_eq!(user.username, update_user.username);
         // メールアドレスの検証
         assert_eq!(user.email, update_user.email);
-        // パスワードの検証
-        assert_ne!(user.password, update_user.password);
     }
 }
 
@@ -272,11 +273,9 @@ async fn verify_delete_result(result: &Result<(), UserError>, mocks: &MockHandle
         Err(e) => println!("Delete failed with error: {:?}", e),
     }
 
-    // モックの検証
+    // モックの検証(RPC1回のみで書き込む)
     println!("Verifying mocks...");
-    mocks.begin_mock.assert_async().await;
-    mocks.create_mock.assert_async().await;
-    mocks.commit_mock.assert_async().await;
+    mocks.assert_single_rpc_write().await;
 
     // 結果の検証
     assert!(result.is_ok(), "Delete operation failed: {:?}", result.as_ref().err());
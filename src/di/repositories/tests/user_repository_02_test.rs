@@ -1,8 +1,13 @@
 use mockito::Mock;
 use reqwest::Client;
 use uuid::Uuid;
+use std::sync::Arc;
 // リポジトリのインポート
 use crate::di::repositories::user_repository::{UserRepository, UserRepositoryTrait};
+// 呼び出しポリシーのインポート
+use crate::outbound::outbound_policy::OutboundPolicy;
+// Supabaseのクライアントのインポート
+use crate::supabase::supabase_client::SupabaseClient;
 // エラーのインポート
 use crate::errors::users::user_error::UserError;
 // モデルのインポート
@@ -31,11 +36,12 @@ async fn test_find_by_id_not_found() {
     // クライアントの作成
     let client = Client::new();
     // リポジトリの作成
-    let repository = UserRepository::new(
+    let repository = UserRepository::new(SupabaseClient::new(
         client,
         mock_url,
         "test_key".to_string(),
-    );
+        Arc::new(OutboundPolicy::default()),
+    ));
     // ユーザーIDを使用してユーザーを検索
     println!("Executing find_by_id operation...");
     let result = repository.find_by_id(user_id).await;
@@ -59,11 +65,12 @@ async fn test_create_error() {
     // クライアントの作成
     let client = Client::new();
     // リポジトリの作成
-    let repository = UserRepository::new(
+    let repository = UserRepository::new(SupabaseClient::new(
         client,
         mock_url,
         "test_key".to_string(),
-    );
+        Arc::new(OutboundPolicy::default()),
+    ));
     // 無効なデータを使用してユーザーを作成
     println!("Executing create operation with invalid data...");
     let result = repository.create(new_user).await;
@@ -106,8 +113,7 @@ async fn verify_create_error_result(
 
     // モックの検証
     println!("Verifying mocks...");
-    mocks.begin_mock.assert_async().await;
-    mocks.create_mock.assert_async().await;
+    mocks.assert_single_rpc_write().await;
 
     assert!(result.is_err(), "Expected creation error");
     if let Err(e) = result {
@@ -0,0 +1,89 @@
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+use std::sync::Arc;
+// リポジトリのインポート
+use crate::di::repositories::user_repository::{UserRepository, UserRepositoryTrait};
+// 呼び出しポリシーのインポート
+use crate::outbound::outbound_policy::OutboundPolicy;
+// Supabaseのクライアントのインポート
+use crate::supabase::supabase_client::SupabaseClient;
+// エラーのインポート
+use crate::errors::users::user_error::UserError;
+// ヘルパーのインポート
+use super::helpers::{
+    create_test_user,
+    create_test_user_update,
+    setup_create_error_mock,
+    setup_failed_write_mocks,
+    setup_missing_user_mocks,
+};
+
+// テスト用のリポジトリを作成
+fn create_test_repository(mock_url: String) -> UserRepository {
+    UserRepository::new(SupabaseClient::new(
+        Client::new(),
+        mock_url,
+        "test_key".to_string(),
+        Arc::new(OutboundPolicy::default()),
+    ))
+}
+
+// 監査ログの書き込みが失敗した場合、ユーザーの行も書き込まれないことのテスト
+#[tokio::test]
+async fn test_create_failure_leaves_no_partial_write() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let mocks = setup_create_error_mock(&mut mock_server).await;
+    let repository = create_test_repository(mock_server.url());
+
+    let result = repository.create(create_test_user()).await;
+
+    // 制約違反は不正なデータとして返る
+    assert!(matches!(result, Err(UserError::InvalidData(_))), "{:?}", result);
+    // RPC1回のみで、テーブルへの直接の書き込みやロールバックの呼び出しがないことを確認
+    mocks.assert_single_rpc_write().await;
+}
+
+// 一時的なエラーでも書き込みのRPCは再試行されないことのテスト
+#[tokio::test]
+async fn test_write_rpc_is_not_retried() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let user_id = Uuid::new_v4();
+    let mocks = setup_failed_write_mocks(
+        &mut mock_server,
+        "update_user_with_audit",
+        503,
+        json!({ "message": "Service Unavailable" }),
+    )
+    .await;
+    let repository = create_test_repository(mock_server.url());
+
+    let result = repository.update(user_id, create_test_user_update()).await;
+
+    // エラーが返り、RPCは1回しか呼ばれない(二重に書き込まれない)
+    assert!(matches!(result, Err(UserError::DatabaseError(_))), "{:?}", result);
+    mocks.assert_single_rpc_write().await;
+}
+
+// 存在しないユーザーの更新・削除は何も書き込まずに404になることのテスト
+#[tokio::test]
+async fn test_missing_user_update_and_delete() {
+    // 更新
+    let mut mock_server = mockito::Server::new_async().await;
+    let user_id = Uuid::new_v4();
+    let mocks = setup_missing_user_mocks(&mut mock_server, "update_user_with_audit", &user_id).await;
+    let repository = create_test_repository(mock_server.url());
+
+    let result = repository.update(user_id, create_test_user_update()).await;
+    assert!(matches!(result, Err(UserError::UserNotFound)), "{:?}", result);
+    mocks.assert_single_rpc_write().await;
+
+    // 削除
+    let mut mock_server = mockito::Server::new_async().await;
+    let mocks = setup_missing_user_mocks(&mut mock_server, "delete_user_with_audit", &user_id).await;
+    let repository = create_test_repository(mock_server.url());
+
+    let result = repository.delete(user_id).await;
+    assert!(matches!(result, Err(UserError::UserNotFound)), "{:?}", result);
+    mocks.assert_single_rpc_write().await;
+}
@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// エラー
+use crate::errors::auth::auth_error::AuthError;
+// Supabaseのクライアント
+use crate::supabase::supabase_client::SupabaseClient;
+// PostgRESTのクエリビルダー
+use crate::supabase::postgrest_query::PostgrestQuery;
+
+// 二段階認証の設定(trans_user_two_factorの行)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TwoFactorRecord {
+    // ユーザーID
+    pub user_id: Uuid,
+    // TOTPの秘密鍵(Base32)
+    pub secret: String,
+    // 最後に受け付けたコードのステップ(同じコードの再利用を防ぐ)
+    pub last_used_step: Option<i64>,
+    // 有効にした日時(Noneの場合は設定中)
+    pub confirmed_at: Option<NaiveDateTime>,
+}
+
+// TwoFactorRecordの実装
+impl TwoFactorRecord {
+    // 二段階認証が有効か
+    pub fn is_enabled(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+}
+
+// トレイト
+#[async_trait]
+pub trait TwoFactorRepositoryTrait: Send + Sync {
+    // ユーザーの二段階認証の設定を取得する(未設定の場合はNone)
+    async fn find(&self, user_id: Uuid) -> Result<Option<TwoFactorRecord>, AuthError>;
+    // 設定を開始する(秘密鍵を保存し、有効な場合は何もせずfalseを返す)
+    async fn begin_setup(&self, user_id: Uuid, secret: String) -> Result<bool, AuthError>;
+    // 有効にする(受け付けたステップとリカバリーコードのハッシュを保存し、有効な場合はfalseを返す)
+    async fn enable(&self, user_id: Uuid, step: i64, recovery_code_hashes: Vec<String>) -> Result<bool, AuthError>;
+    // 受け付けたステップを記録する(記録済みのステップ以前の場合はfalseを返す)
+    async fn record_step(&self, user_id: Uuid, step: i64) -> Result<bool, AuthError>;
+    // 未使用のリカバリーコードを使用済みにする(該当するコードがない場合はfalseを返す)
+    async fn use_recovery_code(&self, user_id: Uuid, code_hash: String) -> Result<bool, AuthError>;
+    // 設定とリカバリーコードを削除して無効にする(ユーザーがいない場合はfalseを返す)
+    async fn reset(&self, user_id: Uuid) -> Result<bool, AuthError>;
+}
+
+// 二段階認証の設定のテーブル
+pub const TWO_FACTOR_TABLE: &str = "trans_user_two_factor";
+
+// 設定の開始のRPC
+pub const BEGIN_TWO_FACTOR_SETUP_RPC: &str = "begin_two_factor_setup";
+// 有効化(監査ログ付き)のRPC
+pub const ENABLE_TWO_FACTOR_RPC: &str = "enable_two_factor_with_audit";
+// 受け付けたステップの記録のRPC
+pub const RECORD_TWO_FACTOR_STEP_RPC: &str = "record_two_factor_step";
+// リカバリーコードの使用のRPC
+pub const USE_RECOVERY_CODE_RPC: &str = "use_two_factor_recovery_code";
+// 無効化(監査ログ付き)のRPC
+pub const RESET_TWO_FACTOR_RPC: &str = "reset_two_factor_with_audit";
+
+// 有効化のRPCの引数
+#[derive(Serialize, Debug)]
+pub struct EnableTwoFactorArgs {
+    // ユーザーID
+    pub p_user_id: Uuid,
+    // 確認に使ったコードのステップ
+    pub p_step: i64,
+    // リカバリーコードのハッシュ(SHA-256)
+    pub p_recovery_code_hashes: Vec<String>,
+}
+
+// リポジトリ
+pub struct TwoFactorRepository {
+    // Supabaseのクライアント
+    supabase: SupabaseClient,
+}
+
+// メソッド
+impl TwoFactorRepository {
+    // コンストラクタ
+    pub fn new(supabase: SupabaseClient) -> Self {
+        Self { supabase }
+    }
+
+    // RPCを呼び出し、行が返ったか(更新・削除した行があるか)を返す関数
+    async fn rpc_affected<B: Serialize + Sync + ?Sized>(
+        &self,
+        operation: &'static str,
+        function: &str,
+        args: &B,
+    ) -> Result<bool, AuthError> {
+        let rows: Vec<serde_json::Value> = self.supabase.rpc(operation, function, args).await?;
+        Ok(!rows.is_empty())
+    }
+}
+
+// トレイト実装
+//
+// 書き込みは全てRPCで行う(manuals/transaction.md)
+#[async_trait]
+impl TwoFactorRepositoryTrait for TwoFactorRepository {
+    async fn find(&self, user_id: Uuid) -> Result<Option<TwoFactorRecord>, AuthError> {
+        let records: Vec<TwoFactorRecord> = self.supabase
+            .select("find_two_factor", &PostgrestQuery::new(TWO_FACTOR_TABLE).eq("user_id", user_id))
+            .await?;
+
+        Ok(records.into_iter().next())
+    }
+
+    async fn begin_setup(&self, user_id: Uuid, secret: String) -> Result<bool, AuthError> {
+        let args = json!({ "p_user_id": user_id, "p_secret": secret });
+        self.rpc_affected("begin_two_factor_setup", BEGIN_TWO_FACTOR_SETUP_RPC, &args).await
+    }
+
+    async fn enable(&self, user_id: Uuid, step: i64, recovery_code_hashes: Vec<String>) -> Result<bool, AuthError> {
+        let args = EnableTwoFactorArgs {
+            p_user_id: user_id,
+            p_step: step,
+            p_recovery_code_hashes: recovery_code_hashes,
+        };
+        self.rpc_affected("enable_two_factor", ENABLE_TWO_FACTOR_RPC, &args).await
+    }
+
+    async fn record_step(&self, user_id: Uuid, step: i64) -> Result<bool, AuthError> {
+        let args = json!({ "p_user_id": user_id, "p_step": step });
+        self.rpc_affected("record_two_factor_step", RECORD_TWO_FACTOR_STEP_RPC, &args).await
+    }
+
+    async fn use_recovery_code(&self, user_id: Uuid, code_hash: String) -> Result<bool, AuthError> {
+        let args = json!({ "p_user_id": user_id, "p_code_hash": code_hash });
+        self.rpc_affected("use_recovery_code", USE_RECOVERY_CODE_RPC, &args).await
+    }
+
+    async fn reset(&self, user_id: Uuid) -> Result<bool, AuthError> {
+        let args = json!({ "p_user_id": user_id });
+        self.rpc_affected("reset_two_factor", RESET_TWO_FACTOR_RPC, &args).await
+    }
+}
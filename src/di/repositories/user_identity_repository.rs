@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+
+// ユーザー
+use crate::models::users::users::User;
+// エラー
+use crate::errors::auth::auth_error::AuthError;
+// Supabaseのクライアント
+use crate::supabase::supabase_client::SupabaseClient;
+// PostgRESTのクエリビルダー
+use crate::supabase::postgrest_query::PostgrestQuery;
+
+// 外部のIdPのアカウントとユーザーの紐付け(trans_user_identitiesの行)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UserIdentity {
+    // プロバイダー名
+    pub provider: String,
+    // IdPのユーザーID(IDトークンのsub)
+    pub subject: String,
+    // ユーザーID
+    pub user_id: Uuid,
+}
+
+// トレイト
+#[async_trait]
+pub trait UserIdentityRepositoryTrait: Send + Sync {
+    // IdPのアカウントに紐付いたユーザーIDを取得する(紐付いていない場合はNone)
+    async fn find_user_id(&self, provider: &str, subject: &str) -> Result<Option<Uuid>, AuthError>;
+    // メールアドレスが一致するユーザーにIdPのアカウントを紐付け、ユーザーを返す(該当するユーザーがいない場合はNone)
+    async fn link_by_email(&self, provider: &str, subject: &str, email: &str) -> Result<Option<User>, AuthError>;
+}
+
+// 紐付けのテーブル
+pub const USER_IDENTITIES_TABLE: &str = "trans_user_identities";
+
+// 紐付け(監査ログ付き)のRPC
+pub const LINK_USER_IDENTITY_RPC: &str = "link_user_identity_with_audit";
+
+// 紐付けのRPCの引数
+#[derive(Serialize, Debug)]
+pub struct LinkUserIdentityArgs<'a> {
+    // プロバイダー名
+    pub p_provider: &'a str,
+    // IdPのユーザーID
+    pub p_subject: &'a str,
+    // IdPが確認したメールアドレス
+    pub p_email: &'a str,
+}
+
+// リポジトリ
+pub struct UserIdentityRepository {
+    // Supabaseのクライアント
+    supabase: SupabaseClient,
+}
+
+// メソッド
+impl UserIdentityRepository {
+    // コンストラクタ
+    pub fn new(supabase: SupabaseClient) -> Self {
+        Self { supabase }
+    }
+}
+
+// トレイト実装
+//
+// 書き込みは全てRPCで行う(manuals/transaction.md)
+#[async_trait]
+impl UserIdentityRepositoryTrait for UserIdentityRepository {
+    async fn find_user_id(&self, provider: &str, subject: &str) -> Result<Option<Uuid>, AuthError> {
+        let query = PostgrestQuery::new(USER_IDENTITIES_TABLE)
+            .eq("provider", provider)
+            .eq("subject", subject);
+        let identities: Vec<UserIdentity> = self.supabase.select("find_user_identity", &query).await?;
+
+        Ok(identities.into_iter().next().map(|identity| identity.user_id))
+    }
+
+    async fn link_by_email(&self, provider: &str, subject: &str, email: &str) -> Result<Option<User>, AuthError> {
+        let args = LinkUserIdentityArgs {
+            p_provider: provider,
+            p_subject: subject,
+            p_email: email,
+        };
+        let users: Vec<User> = self.supabase
+            .rpc("link_user_identity", LINK_USER_IDENTITY_RPC, &args)
+            .await?;
+
+        // 該当するユーザーがいない場合は空の配列が返る
+        Ok(users.into_iter().next())
+    }
+}
@@ -1,45 +1,159 @@
+use std::sync::Arc;
 use async_trait::async_trait;
 use uuid::Uuid;
-use chrono::Utc;
-use reqwest::Client;
-use bcrypt::{hash, DEFAULT_COST};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 // ユーザー
-use crate::models::users::users::{User, NewUser};
+use crate::models::users::users::{User, NewUser, UserProfileUpdate, UserUpdate};
 // エラー
 use crate::errors::users::user_error::UserError;
+// Supabaseのクライアント
+use crate::supabase::supabase_client::SupabaseClient;
+// PostgRESTのクエリビルダー
+use crate::supabase::postgrest_query::{Order, PostgrestQuery};
+// パスワードのハッシュ化
+use crate::password::password_hasher::PasswordHashers;
 
 // トレイト
 #[async_trait]
 pub trait UserRepositoryTrait: Send + Sync {
     async fn find_all(&self) -> Result<Vec<User>, UserError>;
     async fn find_by_id(&self, id: Uuid) -> Result<User, UserError>;
+    // キャッシュを使わない取得(パスワードの照合・二段階認証の判定など、最新の行が必要な場合)
+    async fn find_by_id_uncached(&self, id: Uuid) -> Result<User, UserError> {
+        self.find_by_id(id).await
+    }
     async fn create(&self, user: NewUser) -> Result<User, UserError>;
-    async fn update(&self, id: Uuid, user: NewUser) -> Result<User, UserError>;
+    // ユーザー名・メールアドレスの置き換え(パスワードは変更しない)
+    async fn update(&self, id: Uuid, user: UserUpdate) -> Result<User, UserError>;
+    // ユーザー名・メールアドレスのみの更新(パスワードは変更しない)
+    async fn update_profile(&self, id: Uuid, profile: UserProfileUpdate) -> Result<User, UserError>;
     async fn delete(&self, id: Uuid) -> Result<(), UserError>;
+    // 過去のパスワードのハッシュ(新しい順、現在のパスワードは含まない)
+    async fn password_history(&self, id: Uuid, limit: usize) -> Result<Vec<String>, UserError>;
+    // パスワードの変更(ハッシュ化済みのパスワードを書き込み、以前のパスワードを履歴に残す)
+    async fn change_password(&self, id: Uuid, password_hash: String, history_size: usize) -> Result<User, UserError>;
+    // パスワードのハッシュの作り直し(現在のハッシュがcurrent_hashのままの場合のみ書き込み、書き込んだかを返す)
+    async fn rehash_password(&self, id: Uuid, current_hash: String, new_hash: String) -> Result<bool, UserError>;
+}
+
+// ユーザーの作成のRPCの引数
+//
+// 各RPCはユーザーの行と監査ログの行を1つのトランザクションで書き込む(manuals/transaction.md)
+#[derive(Serialize, Debug)]
+pub struct UserWriteArgs {
+    // ユーザー名
+    pub p_username: String,
+    // メールアドレス
+    pub p_email: String,
+    // ハッシュ化済みのパスワード
+    pub p_password: String,
+}
+
+// UserWriteArgsの実装
+impl UserWriteArgs {
+    // 入力のパスワードを現在のアルゴリズムでハッシュ化して引数を作成する関数
+    pub fn from_new_user(user: NewUser, password_hashers: &PasswordHashers) -> Result<Self, UserError> {
+        let hashed_password = password_hashers.hash(&user.password)?;
+
+        Ok(Self {
+            p_username: user.username,
+            p_email: user.email,
+            p_password: hashed_password,
+        })
+    }
+}
+
+// 更新のRPCの引数(パスワードは含まない)
+#[derive(Serialize, Debug)]
+pub struct UserUpdateArgs {
+    // 更新対象のユーザーID
+    pub p_id: Uuid,
+    // ユーザー名
+    pub p_username: String,
+    // メールアドレス
+    pub p_email: String,
 }
 
+// プロフィール更新のRPCの引数(Noneの項目は変更しない)
+#[derive(Serialize, Debug)]
+pub struct UserProfileArgs {
+    // 更新対象のユーザーID
+    pub p_id: Uuid,
+    // ユーザー名
+    pub p_username: Option<String>,
+    // メールアドレス
+    pub p_email: Option<String>,
+}
+
+// パスワード変更のRPCの引数
+#[derive(Serialize, Debug)]
+pub struct PasswordChangeArgs {
+    // 変更対象のユーザーID
+    pub p_id: Uuid,
+    // ハッシュ化済みの新しいパスワード
+    pub p_password: String,
+    // 残す履歴の数
+    pub p_history_size: usize,
+}
+
+// パスワードのハッシュの作り直しのRPCの引数
+#[derive(Serialize, Debug)]
+pub struct PasswordRehashArgs {
+    // 対象のユーザーID
+    pub p_id: Uuid,
+    // 照合に使った現在のハッシュ(他の変更と競合した場合は書き込まない)
+    pub p_current_password: String,
+    // 作り直したハッシュ
+    pub p_password: String,
+}
+
+// パスワードの履歴の行
+#[derive(Deserialize, Debug)]
+struct PasswordHistoryRow {
+    // ハッシュ化済みのパスワード
+    password_hash: String,
+}
+
+// パスワードの履歴のテーブル
+pub const PASSWORD_HISTORY_TABLE: &str = "trans_user_password_history";
+
+// ユーザー作成(監査ログ付き)のRPC
+pub const CREATE_USER_RPC: &str = "create_user_with_audit";
+// ユーザー更新(監査ログ付き)のRPC
+pub const UPDATE_USER_RPC: &str = "update_user_with_audit";
+// プロフィール更新(監査ログ付き)のRPC
+pub const UPDATE_USER_PROFILE_RPC: &str = "update_user_profile_with_audit";
+// パスワード変更(履歴・監査ログ付き)のRPC
+pub const CHANGE_USER_PASSWORD_RPC: &str = "change_user_password_with_audit";
+// パスワードのハッシュの作り直しのRPC
+pub const REHASH_USER_PASSWORD_RPC: &str = "rehash_user_password";
+// ユーザー削除(監査ログ付き)のRPC
+pub const DELETE_USER_RPC: &str = "delete_user_with_audit";
+
 // リポジトリ
 pub struct UserRepository {
-    // クライアント 
-    client: Client,
-    // SupabaseのURL
-    supabase_url: String,
-    // Supabaseの匿名キー
-    supabase_anon_key: String,
+    // Supabaseのクライアント
+    supabase: SupabaseClient,
+    // パスワードのハッシュ化
+    password_hashers: Arc<PasswordHashers>,
 }
 
 // メソッド
 impl UserRepository {
-    // コンストラクタ
-    pub fn new(client: Client, supabase_url: String, supabase_anon_key: String) -> Self {
+    // コンストラクタ(既定の設定でパスワードをハッシュ化する)
+    pub fn new(supabase: SupabaseClient) -> Self {
+        Self::with_password_hashers(supabase, Arc::new(PasswordHashers::default()))
+    }
+
+    // パスワードのハッシュ化を指定するコンストラクタ
+    pub fn with_password_hashers(supabase: SupabaseClient, password_hashers: Arc<PasswordHashers>) -> Self {
         Self {
-            // クライアント
-            client,
-            // SupabaseのURL
-            supabase_url,
-            // Supabaseの匿名キー
-            supabase_anon_key,
+            // Supabaseのクライアント
+            supabase,
+            // パスワードのハッシュ化
+            password_hashers,
         }
     }
 }
@@ -49,278 +163,140 @@ impl UserRepository {
 impl UserRepositoryTrait for UserRepository {
     // 全件取得
     async fn find_all(&self) -> Result<Vec<User>, UserError> {
-        let response = self.client
-            .get(format!("{}/rest/v1/trans_users", self.supabase_url))
-            .header("apikey", &self.supabase_anon_key)
-            .send()
-            .await
-            .map_err(|e| UserError::DatabaseError(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(UserError::DatabaseError("Failed to retrieve user list.".to_string()));
-        }
-
-        let users: Vec<User> = response.json()
-            .await
-            .map_err(|e| UserError::JsonError(e.to_string()))?;
-
-        Ok(users)
+        Ok(self.supabase
+            .select("find_all", &PostgrestQuery::new("trans_users"))
+            .await?)
     }
 
     // 1件取得
     async fn find_by_id(&self, id: Uuid) -> Result<User, UserError> {
-        let response = self.client
-            .get(format!("{}/rest/v1/trans_users?id=eq.{}", self.supabase_url, id))
-            .header("apikey", &self.supabase_anon_key)
-            .send()
-            .await
-            .map_err(|e| UserError::DatabaseError(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(UserError::DatabaseError("User acquisition failed.".to_string()));
-        }
-
-        let users: Vec<User> = response.json()
-            .await
-            .map_err(|e| UserError::JsonError(e.to_string()))?;
+        let users: Vec<User> = self.supabase
+            .select("find_by_id", &PostgrestQuery::new("trans_users").eq("id", id))
+            .await?;
 
         users.into_iter()
             .next()
             .ok_or(UserError::UserNotFound)
     }
 
-    // 作成
+    // 作成(ユーザーと監査ログを1回のRPCで書き込む)
     async fn create(&self, new_user: NewUser) -> Result<User, UserError> {
-        // トランザクション開始
-        let transaction_response = self.client
-            .post(&format!("{}/rest/v1/rpc/begin_transaction", self.supabase_url))
-            .header("apikey", &self.supabase_anon_key)
-            .header("Content-Type", "application/json")
-            .json(&serde_json::json!({}))
-            .send()
-            .await
-            .map_err(|e| UserError::DatabaseError(format!("Failed to start transaction: {}", e)))?;
-
-        if !transaction_response.status().is_success() {
-            return Err(UserError::DatabaseError("Failed to start transaction".to_string()));
-        }
+        let args = UserWriteArgs::from_new_user(new_user, &self.password_hashers)?;
+
+        let created_users: Vec<User> = self.supabase
+            .rpc("create", CREATE_USER_RPC, &args)
+            .await?;
+
+        created_users
+            .into_iter()
+            .next()
+            .ok_or(UserError::DatabaseError("User creation failed".to_string()))
+    }
 
-        // トランザクションIDを取得
-        let transaction_data: serde_json::Value = transaction_response.json()
-            .await
-            .map_err(|e| UserError::DatabaseError(format!("Failed to parse transaction response: {}", e)))?;
-
-        let transaction_id = transaction_data["transaction_id"]
-            .as_str()
-            .ok_or_else(|| UserError::DatabaseError("Failed to get transaction ID".to_string()))?;
-
-        // パスワードのハッシュ化
-        let hashed_password = hash(new_user.password.as_bytes(), DEFAULT_COST)
-            .map_err(|e| UserError::DatabaseError(format!("Password hashing failed: {}", e)))?;
-
-        let user = User {
-            id: Uuid::new_v4(),
-            username: new_user.username,
-            email: new_user.email,
-            password: hashed_password,
-            created_at: Utc::now().naive_utc(),
-            updated_at: Utc::now().naive_utc(),
+    // 更新(ユーザーと監査ログを1回のRPCで書き込む、パスワードは変更しない)
+    async fn update(&self, id: Uuid, updated_user: UserUpdate) -> Result<User, UserError> {
+        let args = UserUpdateArgs {
+            p_id: id,
+            p_username: updated_user.username,
+            p_email: updated_user.email,
         };
 
-        let response = self.client
-            .post(format!("{}/rest/v1/trans_users", self.supabase_url))
-            .header("apikey", &self.supabase_anon_key)
-            .header("Content-Type", "application/json")
-            .header("Transaction-Id", transaction_id)
-            .header("Prefer", "return=representation")
-            .json(&user)
-            .send()
-            .await
-            .map_err(|e| {
-                // エラー時はロールバック
-                let _ = self.client
-                    .post(&format!("{}/rest/v1/rpc/rollback_transaction", self.supabase_url))
-                    .header("apikey", &self.supabase_anon_key)
-                    .json(&serde_json::json!({ "transaction_id": transaction_id }))
-                    .send();
-                UserError::DatabaseError(e.to_string())
-            })?;
-
-        match response.status() {
-            status if status.is_success() => {
-                // トランザクションをコミット
-                let commit_response = self.client
-                    .post(&format!("{}/rest/v1/rpc/commit_transaction", self.supabase_url))
-                    .header("apikey", &self.supabase_anon_key)
-                    .header("Content-Type", "application/json")
-                    .json(&serde_json::json!({ "transaction_id": transaction_id }))
-                    .send()
-                    .await
-                    .map_err(|e| UserError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
-
-                if !commit_response.status().is_success() {
-                    return Err(UserError::DatabaseError("Failed to commit transaction".to_string()));
-                }
-
-                let created_users: Vec<User> = response.json()
-                    .await
-                    .map_err(|e| UserError::JsonError(e.to_string()))?;
-                
-                Ok(created_users
-                    .into_iter()
-                    .next()
-                    .ok_or(UserError::DatabaseError("User creation failed".to_string()))?)
-            },
-            status => {
-                // エラー時はロールバック
-                let _ = self.client
-                    .post(&format!("{}/rest/v1/rpc/rollback_transaction", self.supabase_url))
-                    .header("apikey", &self.supabase_anon_key)
-                    .json(&serde_json::json!({ "transaction_id": transaction_id }))
-                    .send()
-                    .await;
-                
-                Err(UserError::DatabaseError(format!("User creation failed. Status: {}", status)))
-            }
-        }
+        let updated_users: Vec<User> = self.supabase
+            .rpc("update", UPDATE_USER_RPC, &args)
+            .await?;
+
+        // 該当するユーザーがいない場合は空の配列が返る
+        updated_users
+            .into_iter()
+            .next()
+            .ok_or(UserError::UserNotFound)
     }
 
-    // 更新
-    async fn update(&self, id: Uuid, updated_user: NewUser) -> Result<User, UserError> {
-        // まず、ユーザーが存在するか確認
-        let check_response = self.client
-            .get(format!("{}/rest/v1/trans_users?id=eq.{}", self.supabase_url, id))
-            .header("apikey", &self.supabase_anon_key)
-            .header("Prefer", "return=representation")
-            .send()
-            .await
-            .map_err(|e| UserError::DatabaseError(e.to_string()))?;
-
-        let users: Vec<User> = check_response
-            .json()
-            .await
-            .map_err(|e| UserError::DatabaseError(e.to_string()))?;
-
-        if users.is_empty() {
-            return Err(UserError::UserNotFound);
-        }
+    // プロフィール更新(パスワードを読み書きせず、ユーザーと監査ログを1回のRPCで書き込む)
+    async fn update_profile(&self, id: Uuid, profile: UserProfileUpdate) -> Result<User, UserError> {
+        let args = UserProfileArgs {
+            p_id: id,
+            p_username: profile.username,
+            p_email: profile.email,
+        };
 
-        // トランザクション開始
-        let transaction_response = self.client
-            .post(&format!("{}/rest/v1/rpc/begin_transaction", self.supabase_url))
-            .header("apikey", &self.supabase_anon_key)
-            .header("Content-Type", "application/json")
-            .json(&serde_json::json!({}))
-            .send()
-            .await
-            .map_err(|e| UserError::DatabaseError(format!("Failed to start transaction: {}", e)))?;
-
-        if !transaction_response.status().is_success() {
-            return Err(UserError::DatabaseError("Failed to start transaction".to_string()));
-        }
+        let updated_users: Vec<User> = self.supabase
+            .rpc("update_profile", UPDATE_USER_PROFILE_RPC, &args)
+            .await?;
 
-        // パスワードのハッシュ化
-        let hashed_password = hash(updated_user.password.as_bytes(), DEFAULT_COST)
-            .map_err(|e| UserError::DatabaseError(format!("Password hashing failed: {}", e)))?;
-
-        let update_data = serde_json::json!({
-            "username": updated_user.username,
-            "email": updated_user.email,
-            "password": hashed_password,
-            "updated_at": Utc::now().naive_utc()
-        });
-
-        let response = self.client
-            .patch(&format!("{}/rest/v1/trans_users?id=eq.{}", self.supabase_url, id))
-            .header("apikey", &self.supabase_anon_key)
-            .header("Content-Type", "application/json")
-            .header("Prefer", "tx=commit")
-            .header("Prefer", "return=representation")
-            .json(&update_data)
-            .send()
-            .await
-            .map_err(|e| UserError::DatabaseError(e.to_string()))?;
-
-        let status = response.status();
-        let response_text = response.text().await
-            .map_err(|e| UserError::DatabaseError(e.to_string()))?;
-
-        match status {
-            s if s.as_u16() == 200 => {
-                let updated_user: Vec<User> = serde_json::from_str(&response_text)
-                    .map_err(|e| UserError::JsonError(e.to_string()))?;
-                
-                Ok(updated_user
-                    .into_iter()
-                    .next()
-                    .ok_or(UserError::UserNotFound)?)
-            },
-            _ => {
-                // エラー時はロールバック
-                let _ = self.client
-                    .post(&format!("{}/rest/v1/rpc/rollback_transaction", self.supabase_url))
-                    .header("apikey", &self.supabase_anon_key)
-                    .send()
-                    .await;
-                
-                Err(UserError::DatabaseError(format!("Failed to update user. Status: {}. Response: {}", status, response_text)))
-            }
-        }
+        // 該当するユーザーがいない場合は空の配列が返る
+        updated_users
+            .into_iter()
+            .next()
+            .ok_or(UserError::UserNotFound)
     }
 
-    // 削除
+    // 削除(ユーザーの削除と監査ログを1回のRPCで書き込む)
     async fn delete(&self, id: Uuid) -> Result<(), UserError> {
-        // まず、ユーザーが存在するか確認
-        let check_response = self.client
-            .get(format!("{}/rest/v1/trans_users?id=eq.{}", self.supabase_url, id))
-            .header("apikey", &self.supabase_anon_key)
-            .header("Prefer", "return=representation")
-            .send()
-            .await
-            .map_err(|e| UserError::DatabaseError(e.to_string()))?;
-
-        let users: Vec<User> = check_response
-            .json()
-            .await
-            .map_err(|e| UserError::DatabaseError(e.to_string()))?;
-
-        if users.is_empty() {
+        let deleted_users: Vec<User> = self.supabase
+            .rpc("delete", DELETE_USER_RPC, &json!({ "p_id": id }))
+            .await?;
+
+        // 該当するユーザーがいない場合は空の配列が返る
+        if deleted_users.is_empty() {
             return Err(UserError::UserNotFound);
         }
 
-        // トランザクション開始
-        let transaction_response = self.client
-            .post(&format!("{}/rest/v1/rpc/begin_transaction", self.supabase_url))
-            .header("apikey", &self.supabase_anon_key)
-            .header("Content-Type", "application/json")
-            .json(&serde_json::json!({}))
-            .send()
-            .await
-            .map_err(|e| UserError::DatabaseError(format!("Failed to start transaction: {}", e)))?;
-
-        if !transaction_response.status().is_success() {
-            return Err(UserError::DatabaseError("Failed to start transaction".to_string()));
-        }
+        Ok(())
+    }
 
-        let response = self.client
-            .delete(&format!("{}/rest/v1/trans_users?id=eq.{}", self.supabase_url, id))
-            .header("apikey", &self.supabase_anon_key)
-            .header("Prefer", "tx=commit")
-            .send()
-            .await
-            .map_err(|e| UserError::DatabaseError(e.to_string()))?;
-
-        if !response.status().is_success() {
-            // エラー時はロールバック
-            let _ = self.client
-                .post(&format!("{}/rest/v1/rpc/rollback_transaction", self.supabase_url))
-                .header("apikey", &self.supabase_anon_key)
-                .send()
-                .await;
-            
-            return Err(UserError::DatabaseError("Failed to delete user".to_string()));
+    // 過去のパスワードのハッシュ(新しい順)
+    async fn password_history(&self, id: Uuid, limit: usize) -> Result<Vec<String>, UserError> {
+        if limit == 0 {
+            return Ok(Vec::new());
         }
 
-        Ok(())
+        let rows: Vec<PasswordHistoryRow> = self.supabase
+            .select(
+                "password_history",
+                &PostgrestQuery::new(PASSWORD_HISTORY_TABLE)
+                    .select("password_hash")
+                    .eq("user_id", id)
+                    .order("created_at", Order::Desc)
+                    .limit(limit as u64),
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(|row| row.password_hash).collect())
+    }
+
+    // パスワードの変更(パスワード・履歴・監査ログを1回のRPCで書き込む)
+    async fn change_password(&self, id: Uuid, password_hash: String, history_size: usize) -> Result<User, UserError> {
+        let args = PasswordChangeArgs {
+            p_id: id,
+            p_password: password_hash,
+            p_history_size: history_size,
+        };
+
+        let updated_users: Vec<User> = self.supabase
+            .rpc("change_password", CHANGE_USER_PASSWORD_RPC, &args)
+            .await?;
+
+        // 該当するユーザーがいない場合は空の配列が返る
+        updated_users
+            .into_iter()
+            .next()
+            .ok_or(UserError::UserNotFound)
+    }
+
+    // パスワードのハッシュの作り直し(監査ログは残さない)
+    async fn rehash_password(&self, id: Uuid, current_hash: String, new_hash: String) -> Result<bool, UserError> {
+        let args = PasswordRehashArgs {
+            p_id: id,
+            p_current_password: current_hash,
+            p_password: new_hash,
+        };
+
+        let updated_users: Vec<User> = self.supabase
+            .rpc("rehash_password", REHASH_USER_PASSWORD_RPC, &args)
+            .await?;
+
+        // 現在のハッシュが変わっていた場合は空の配列が返る
+        Ok(!updated_users.is_empty())
     }
 }
@@ -0,0 +1,223 @@
+use axum::{
+    http::Method,
+    Router,
+    extract::{Path, Json, State},
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use utoipa::openapi::{
+    path::{OperationBuilder, ParameterBuilder, ParameterIn, PathItemBuilder},
+    request_body::RequestBodyBuilder,
+    schema::{ArrayBuilder, ComponentsBuilder},
+    tag::TagBuilder,
+    ContentBuilder, OpenApi, OpenApiBuilder, PathItemType, PathsBuilder, Ref, Required,
+    ResponseBuilder,
+};
+use utoipa::ToSchema;
+// リポジトリ
+use crate::di::repositories::repository::Resource;
+// ハンドラー
+use crate::di::handlers::crud_handler::CrudHandler;
+// エラーレスポンス
+use crate::models::ErrorResponse;
+// ルートの一覧
+use crate::routes::route_table::RouteTable;
+
+// 汎用CRUDルーター
+pub struct CrudRouter<T: Resource> {
+    handler: Arc<CrudHandler<T>>,
+}
+
+// メソッド
+impl<T: Resource> CrudRouter<T> {
+    // コンストラクタ
+    pub fn new(handler: Arc<CrudHandler<T>>) -> Self {
+        Self { handler }
+    }
+
+    // 一覧のパス
+    pub fn collection_path() -> String {
+        format!("/di/{}", T::RESOURCE)
+    }
+
+    // 1件のパス
+    pub fn item_path() -> String {
+        format!("/di/{}/:id", T::RESOURCE)
+    }
+
+    // ルート
+    pub fn routes(&self) -> Router {
+        self.route_table().into_router()
+    }
+
+    // ルート(登録したルートの一覧つき)
+    pub fn route_table(&self) -> RouteTable {
+        let handler = Arc::clone(&self.handler);
+
+        async fn list_handler<T: Resource>(
+            State(handler): State<Arc<CrudHandler<T>>>,
+        ) -> impl IntoResponse {
+            handler.list().await
+        }
+
+        async fn get_handler<T: Resource>(
+            State(handler): State<Arc<CrudHandler<T>>>,
+            Path(id): Path<T::Id>,
+        ) -> impl IntoResponse {
+            handler.get(id).await
+        }
+
+        async fn create_handler<T: Resource>(
+            State(handler): State<Arc<CrudHandler<T>>>,
+            Json(input): Json<T::Input>,
+        ) -> impl IntoResponse {
+            handler.create(Json(input)).await
+        }
+
+        async fn update_handler<T: Resource>(
+            State(handler): State<Arc<CrudHandler<T>>>,
+            Path(id): Path<T::Id>,
+            Json(input): Json<T::Input>,
+        ) -> impl IntoResponse {
+            handler.update(id, Json(input)).await
+        }
+
+        async fn delete_handler<T: Resource>(
+            State(handler): State<Arc<CrudHandler<T>>>,
+            Path(id): Path<T::Id>,
+        ) -> impl IntoResponse {
+            handler.delete(id).await
+        }
+
+        RouteTable::new()
+            .route(Method::GET, &Self::collection_path(), list_handler::<T>)
+            .route(Method::POST, &Self::collection_path(), create_handler::<T>)
+            .route(Method::GET, &Self::item_path(), get_handler::<T>)
+            .route(Method::PUT, &Self::item_path(), update_handler::<T>)
+            .route(Method::DELETE, &Self::item_path(), delete_handler::<T>)
+            .with_state(handler)
+    }
+
+    // OpenAPIのドキュメント
+    pub fn openapi() -> OpenApi {
+        // スキーマ名
+        let (model_name, model_schema) = T::schema();
+        let (input_name, input_schema) = T::Input::schema();
+        let (error_name, error_schema) = ErrorResponse::schema();
+
+        // レスポンス・リクエストボディの定義
+        let json = |schema: utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>| {
+            ContentBuilder::new().schema(schema).build()
+        };
+        let model = || json(Ref::from_schema_name(model_name).into());
+        let error = |description: &str| {
+            ResponseBuilder::new()
+                .description(description)
+                .content("application/json", json(Ref::from_schema_name(error_name).into()))
+                .build()
+        };
+        let request_body = || {
+            RequestBodyBuilder::new()
+                .content("application/json", json(Ref::from_schema_name(input_name).into()))
+                .required(Some(Required::True))
+                .build()
+        };
+        let id_parameter = || {
+            ParameterBuilder::new()
+                .name("id")
+                .parameter_in(ParameterIn::Path)
+                .required(Required::True)
+                .description(Some(format!("{}のID", T::RESOURCE)))
+                .schema(Some(T::id_schema()))
+                .build()
+        };
+        let operation = |name: &str| {
+            OperationBuilder::new()
+                .operation_id(Some(format!("{}_{}", name, T::RESOURCE)))
+                .tag(T::RESOURCE)
+        };
+
+        // 一覧のパス
+        let collection = PathItemBuilder::new()
+            .operation(
+                PathItemType::Get,
+                operation("list")
+                    .summary(Some(format!("{}の一覧を取得", T::RESOURCE)))
+                    .response("200", ResponseBuilder::new()
+                        .description("取得成功")
+                        .content("application/json", json(ArrayBuilder::new()
+                            .items(Ref::from_schema_name(model_name))
+                            .into()))
+                        .build())
+                    .response("500", error("サーバーエラー")),
+            )
+            .operation(
+                PathItemType::Post,
+                operation("create")
+                    .summary(Some(format!("{}を作成", T::RESOURCE)))
+                    .request_body(Some(request_body()))
+                    .response("200", ResponseBuilder::new()
+                        .description("作成成功")
+                        .content("application/json", model())
+                        .build())
+                    .response("400", error("無効なリクエストデータ"))
+                    .response("409", error("競合"))
+                    .response("500", error("サーバーエラー")),
+            )
+            .build();
+
+        // 1件のパス
+        let item = PathItemBuilder::new()
+            .operation(
+                PathItemType::Get,
+                operation("get")
+                    .summary(Some(format!("{}を1件取得", T::RESOURCE)))
+                    .parameter(id_parameter())
+                    .response("200", ResponseBuilder::new()
+                        .description("取得成功")
+                        .content("application/json", model())
+                        .build())
+                    .response("404", error("見つかりません"))
+                    .response("500", error("サーバーエラー")),
+            )
+            .operation(
+                PathItemType::Put,
+                operation("update")
+                    .summary(Some(format!("{}を更新", T::RESOURCE)))
+                    .parameter(id_parameter())
+                    .request_body(Some(request_body()))
+                    .response("200", ResponseBuilder::new()
+                        .description("更新成功")
+                        .content("application/json", model())
+                        .build())
+                    .response("400", error("無効なリクエストデータ"))
+                    .response("404", error("見つかりません"))
+                    .response("500", error("サーバーエラー")),
+            )
+            .operation(
+                PathItemType::Delete,
+                operation("delete")
+                    .summary(Some(format!("{}を削除", T::RESOURCE)))
+                    .parameter(id_parameter())
+                    .response("204", ResponseBuilder::new().description("削除成功").build())
+                    .response("404", error("見つかりません"))
+                    .response("500", error("サーバーエラー")),
+            )
+            .build();
+
+        OpenApiBuilder::new()
+            .paths(PathsBuilder::new()
+                .path(Self::collection_path(), collection)
+                .path(format!("/di/{}/{{id}}", T::RESOURCE), item))
+            .components(Some(ComponentsBuilder::new()
+                .schema(model_name, model_schema)
+                .schema(input_name, input_schema)
+                .schema(error_name, error_schema)
+                .build()))
+            .tags(Some([TagBuilder::new()
+                .name(T::RESOURCE)
+                .description(Some(format!("{}の汎用CRUD API", T::RESOURCE)))
+                .build()]))
+            .build()
+    }
+}
@@ -1 +1,2 @@
-pub mod user_router;
\ No newline at end of file
+pub mod user_router;
+pub mod crud_router;
\ No newline at end of file
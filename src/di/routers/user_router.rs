@@ -1,15 +1,17 @@
 use axum::{
-    routing::{get, post, put, delete},
+    http::{Method, StatusCode},
     Router,
     extract::{Path, Json, State},
-    response::IntoResponse,
+    response::Response,
 };
 use std::sync::Arc;
 use uuid::Uuid;
 // ハンドラー
 use crate::di::handlers::user_handler::UserHandler;
 // ユーザー
-use crate::models::users::users::{NewUser};
+use crate::models::users::users::{User, NewUser, UserUpdate};
+// ルートの一覧
+use crate::routes::route_table::RouteTable;
 
 // ルーター
 pub struct UserRouter {
@@ -23,51 +25,139 @@ impl UserRouter {
         Self { handler }
     }
 
+    // ルート(登録したルートの一覧つき)
+    pub fn route_table(&self) -> RouteTable {
+        RouteTable::new()
+            .route(Method::GET, "/di/users", get_users_handler)
+            .route(Method::GET, "/di/users/:id", get_user_handler)
+            .route(Method::POST, "/di/users", create_user_handler)
+            .route(Method::PUT, "/di/users/:id", update_user_handler)
+            .route(Method::DELETE, "/di/users/:id", delete_user_handler)
+            .with_state(Arc::clone(&self.handler))
+    }
+
     // ルート
     pub fn routes(&self) -> Router {
-        let handler = Arc::clone(&self.handler);
-        
-        async fn get_users_handler(
-            State(handler): State<Arc<UserHandler>>,
-        ) -> impl IntoResponse {
-            handler.get_users().await
-        }
+        self.route_table().into_router()
+    }
+}
 
-        async fn get_user_handler(
-            State(handler): State<Arc<UserHandler>>,
-            Path(id): Path<Uuid>,
-        ) -> impl IntoResponse {
-            handler.get_user(id).await
-        }
+// ユーザーの一覧を取得する関数
+#[utoipa::path(
+    get,
+    path = "/di/users",
+    security(()),
+    responses(
+        (status = 200, description = "ユーザー一覧を取得成功", body = Vec<User>),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "di-users"
+)]
+pub async fn get_users_handler(
+    State(handler): State<Arc<UserHandler>>,
+) -> Result<Json<Vec<User>>, (StatusCode, String)> {
+    handler.get_users().await
+}
 
-        async fn create_user_handler(
-            State(handler): State<Arc<UserHandler>>,
-            Json(user): Json<NewUser>,
-        ) -> impl IntoResponse {
-            handler.create_user(Json(user)).await
-        }
+// 特定のユーザーを取得する関数
+#[utoipa::path(
+    get,
+    path = "/di/users/{id}",
+    params(
+        ("id" = crate::models::UuidWrapper, Path, description = "ユーザーID")
+    ),
+    security(()),
+    responses(
+        (status = 200, description = "ユーザー取得成功", body = User),
+        (status = 400, description = "無効なユーザーID", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "di-users"
+)]
+pub async fn get_user_handler(
+    State(handler): State<Arc<UserHandler>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<User>, (StatusCode, String)> {
+    handler.get_user(id).await
+}
 
-        async fn update_user_handler(
-            State(handler): State<Arc<UserHandler>>,
-            Path(id): Path<Uuid>,
-            Json(user): Json<NewUser>,
-        ) -> impl IntoResponse {
-            handler.update_user(id, Json(user)).await
-        }
+// 新しいユーザーを作成する関数
+#[utoipa::path(
+    post,
+    path = "/di/users",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "再試行時に同じレスポンスを返すための冪等キー")
+    ),
+    request_body = NewUser,
+    security(()),
+    responses(
+        (status = 200, description = "ユーザー作成成功", body = User),
+        (status = 400, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 409, description = "メールアドレスが使用済み、または同じ冪等キーのリクエストが処理中", body = ErrorResponse),
+        (status = 422, description = "冪等キーが別のリクエストで使用済み", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "di-users"
+)]
+pub async fn create_user_handler(
+    State(handler): State<Arc<UserHandler>>,
+    Json(user): Json<NewUser>,
+) -> Result<Json<User>, (StatusCode, String)> {
+    handler.create_user(Json(user)).await
+}
 
-        async fn delete_user_handler(
-            State(handler): State<Arc<UserHandler>>,
-            Path(id): Path<Uuid>,
-        ) -> impl IntoResponse {
-            handler.delete_user(id).await
-        }
+// ユーザーを更新する関数
+#[utoipa::path(
+    put,
+    path = "/di/users/{id}",
+    params(
+        ("id" = crate::models::UuidWrapper, Path, description = "更新対象のユーザーID")
+    ),
+    request_body = UserUpdate,
+    security(()),
+    responses(
+        (status = 200, description = "ユーザー更新成功", body = User),
+        (status = 400, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 409, description = "メールアドレスが使用済み", body = ErrorResponse),
+        (status = 422, description = "変更できない項目(パスワードなど)を含む、パスワードは/me/passwordで変更する", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "di-users"
+)]
+pub async fn update_user_handler(
+    State(handler): State<Arc<UserHandler>>,
+    Path(id): Path<Uuid>,
+    Json(user): Json<UserUpdate>,
+) -> Result<Json<User>, (StatusCode, String)> {
+    handler.update_user(id, Json(user)).await
+}
 
-        Router::new()
-            .route("/di/users", get(get_users_handler))
-            .route("/di/users/:id", get(get_user_handler))
-            .route("/di/users", post(create_user_handler))
-            .route("/di/users/:id", put(update_user_handler))
-            .route("/di/users/:id", delete(delete_user_handler))
-            .with_state(handler)
-    }
-}
\ No newline at end of file
+// ユーザーを削除する関数
+#[utoipa::path(
+    delete,
+    path = "/di/users/{id}",
+    params(
+        ("id" = crate::models::UuidWrapper, Path, description = "削除対象のユーザーID")
+    ),
+    security(()),
+    responses(
+        (status = 204, description = "ユーザー削除成功"),
+        (status = 400, description = "無効なユーザーID", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "di-users"
+)]
+pub async fn delete_user_handler(
+    State(handler): State<Arc<UserHandler>>,
+    Path(id): Path<Uuid>,
+) -> Result<Response, (StatusCode, String)> {
+    handler.delete_user(id).await
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use chrono::{Duration, Utc};
+use rand::distributions::{Alphanumeric, DistString};
+use ring::constant_time::verify_slices_are_equal;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use tokio::time::Instant;
+use uuid::Uuid;
+// リポジトリ
+use crate::di::repositories::api_key_repository::{ApiKeyRecord, ApiKeyRepositoryTrait, CreateApiKeyArgs};
+// モデル
+use crate::models::auth::api_key::{ApiKeyResponse, CreateApiKeyRequest, CreatedApiKeyResponse};
+// 設定
+use crate::config::api_key_config::ApiKeyConfig;
+// エラー
+use crate::errors::auth::auth_error::AuthError;
+
+// APIキーの接頭辞(JWTと区別し、漏えい時にスキャナーで検出しやすくする)
+pub const API_KEY_PREFIX: &str = "bk_";
+// キーの先頭の識別子の文字数
+const API_KEY_ID_LEN: usize = 8;
+// キーの秘密の部分の文字数(英数字、約238ビット)
+const API_KEY_SECRET_LEN: usize = 40;
+// 名前の最大文字数
+const MAX_NAME_LEN: usize = 100;
+// 検証に成功したキーを覚えておく期間
+const VERIFIED_KEY_TTL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+// 検証に成功したキーを覚えておく数の上限
+const MAX_VERIFIED_KEYS: usize = 10_000;
+
+// APIキーのサービス
+pub struct ApiKeyService {
+    repository: Arc<dyn ApiKeyRepositoryTrait>,
+    // 検証に成功したキーのハッシュごとのキーのIDと期限(レート制限でDBを呼び出さずにキーを識別するために使う)
+    verified: Mutex<HashMap<String, (Uuid, Instant)>>,
+}
+
+// メソッド
+impl ApiKeyService {
+    // コンストラクタ
+    pub fn new(repository: Arc<dyn ApiKeyRepositoryTrait>) -> Self {
+        Self {
+            repository,
+            verified: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // APIキーを発行する(キーはハッシュのみ保存し、平文はこのレスポンスでのみ返す)
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        request: CreateApiKeyRequest,
+        config: &ApiKeyConfig,
+    ) -> Result<CreatedApiKeyResponse, AuthError> {
+        let name = request.name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+            return Err(AuthError::InvalidApiKeyRequest(format!("name must be 1 to {} characters", MAX_NAME_LEN)));
+        }
+        if request.scopes.is_empty() {
+            return Err(AuthError::InvalidApiKeyRequest("at least one scope is required".to_string()));
+        }
+        let ttl_days = request.expires_in_days.unwrap_or(config.default_ttl_days);
+        if ttl_days == 0 || ttl_days > config.max_ttl_days {
+            return Err(AuthError::InvalidApiKeyRequest(format!(
+                "expires_in_days must be 1 to {}",
+                config.max_ttl_days
+            )));
+        }
+
+        // 重複した権限はまとめる
+        let mut scopes = request.scopes;
+        scopes.sort();
+        scopes.dedup();
+
+        let (prefix, key) = generate_api_key();
+        let args = CreateApiKeyArgs {
+            p_user_id: user_id,
+            p_name: name,
+            p_prefix: prefix,
+            p_key_hash: hash_api_key(&key),
+            p_scopes: scopes,
+            p_expires_at: (Utc::now() + Duration::days(i64::from(ttl_days))).naive_utc(),
+            p_max_keys: config.max_keys_per_user,
+        };
+        let record = self.repository.create(args).await?.ok_or(AuthError::ApiKeyLimitReached)?;
+
+        Ok(CreatedApiKeyResponse { key, api_key: record.into() })
+    }
+
+    // ユーザーのAPIキーを新しい順に取得する
+    pub async fn list(&self, user_id: Uuid) -> Result<Vec<ApiKeyResponse>, AuthError> {
+        let records = self.repository.list(user_id).await?;
+        Ok(records.into_iter().map(Into::into).collect())
+    }
+
+    // ユーザーのAPIキーを削除する(以降そのキーは使えない)
+    pub async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<(), AuthError> {
+        let deleted = self.repository.delete(user_id, id).await?;
+        self.verified
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|_, (key_id, _)| *key_id != id);
+        if !deleted {
+            return Err(AuthError::ApiKeyNotFound);
+        }
+        Ok(())
+    }
+
+    // Bearerトークンとして渡されたAPIキーを検証し、キーの行を返す
+    //
+    // 最終使用日時は設定の間隔ごとに更新し、更新に失敗しても認証は失敗させない
+    pub async fn authenticate(&self, key: &str, config: &ApiKeyConfig) -> Result<ApiKeyRecord, AuthError> {
+        let record = self.verify(key).await?;
+
+        let now = Utc::now().naive_utc();
+        let interval = Duration::from_std(config.last_used_interval).unwrap_or(Duration::zero());
+        if record.last_used_at.is_none_or(|last_used_at| now - last_used_at >= interval) {
+            if let Err(e) = self.repository.touch(record.id).await {
+                tracing::warn!(api_key_id = %record.id, error = ?e, "updating API key last_used_at failed");
+            }
+        }
+
+        Ok(record)
+    }
+
+    // APIキーを検証し、キーの行を返す(最終使用日時は更新しない)
+    //
+    // 形式が正しくない・存在しない・ハッシュが一致しない・期限切れのキーはInvalidToken
+    pub async fn verify(&self, key: &str) -> Result<ApiKeyRecord, AuthError> {
+        let prefix = parse_prefix(key).ok_or(AuthError::InvalidToken)?;
+        let record = self.repository.find_by_prefix(prefix).await?.ok_or(AuthError::InvalidToken)?;
+
+        // ハッシュの比較は一定時間で行う
+        if verify_slices_are_equal(hash_api_key(key).as_bytes(), record.key_hash.as_bytes()).is_err() {
+            return Err(AuthError::InvalidToken);
+        }
+        if record.expires_at <= Utc::now().naive_utc() {
+            return Err(AuthError::InvalidToken);
+        }
+        self.remember_verified(key, record.id);
+
+        Ok(record)
+    }
+
+    // 最近検証に成功したキーのIDを返す(DBを呼び出さない、検証の代わりには使わない)
+    //
+    // レート制限でキーごとのバケットを使うためのもので、覚えていないキーはNone
+    pub fn recently_verified(&self, key: &str) -> Option<Uuid> {
+        let verified = self.verified.lock().unwrap_or_else(|e| e.into_inner());
+        verified
+            .get(&hash_api_key(key))
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(id, _)| *id)
+    }
+
+    // 検証に成功したキーを覚える(上限に達した場合は期限切れのものを削除し、それでも空かない場合は覚えない)
+    fn remember_verified(&self, key: &str, id: Uuid) {
+        let now = Instant::now();
+        let mut verified = self.verified.lock().unwrap_or_else(|e| e.into_inner());
+        if verified.len() >= MAX_VERIFIED_KEYS {
+            verified.retain(|_, (_, expires_at)| *expires_at > now);
+        }
+        if verified.len() < MAX_VERIFIED_KEYS {
+            verified.insert(hash_api_key(key), (id, now + VERIFIED_KEY_TTL));
+        }
+    }
+}
+
+// APIキーを生成する関数(キーの先頭とキー全体を返す)
+//
+// 例: bk_9fQ2xLmA_Jx8c0VtR4yPq1sZkW7nBd3HfG6uLe2MoTa9Ci5Xr(先頭はbk_9fQ2xLmA)
+pub fn generate_api_key() -> (String, String) {
+    let mut rng = rand::rngs::OsRng;
+    let prefix = format!("{}{}", API_KEY_PREFIX, Alphanumeric.sample_string(&mut rng, API_KEY_ID_LEN));
+    let key = format!("{}_{}", prefix, Alphanumeric.sample_string(&mut rng, API_KEY_SECRET_LEN));
+    (prefix, key)
+}
+
+// APIキーの形式か判定する関数(JWTと区別するために使う)
+pub fn is_api_key(token: &str) -> bool {
+    token.starts_with(API_KEY_PREFIX)
+}
+
+// APIキーから先頭(識別用の部分)を取り出す関数(形式が正しくない場合はNone)
+pub fn parse_prefix(key: &str) -> Option<&str> {
+    let rest = key.strip_prefix(API_KEY_PREFIX)?;
+    let (id, secret) = rest.split_once('_')?;
+    let valid = |part: &str, len: usize| part.len() == len && part.bytes().all(|b| b.is_ascii_alphanumeric());
+    if !valid(id, API_KEY_ID_LEN) || !valid(secret, API_KEY_SECRET_LEN) {
+        return None;
+    }
+    Some(&key[..API_KEY_PREFIX.len() + API_KEY_ID_LEN])
+}
+
+// APIキーのハッシュ(SHA-256)を計算する関数
+//
+// キーは十分にランダムなため、パスワードのような低速なハッシュは使わない
+pub fn hash_api_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+// リポジトリ
+use crate::di::repositories::repository::{Repository, Resource};
+// エラー
+use crate::errors::resources::resource_error::ResourceError;
+
+// 汎用CRUDサービス
+pub struct CrudService<T: Resource> {
+    repository: Arc<dyn Repository<T, T::Id>>,
+}
+
+// メソッド
+impl<T: Resource> CrudService<T> {
+    // コンストラクタ
+    pub fn new(repository: Arc<dyn Repository<T, T::Id>>) -> Self {
+        Self { repository }
+    }
+
+    // 全件取得
+    pub async fn get_all(&self) -> Result<Vec<T>, ResourceError> {
+        self.repository.find_all().await
+    }
+
+    // 1件取得
+    pub async fn get_by_id(&self, id: T::Id) -> Result<T, ResourceError> {
+        self.repository.find_by_id(id).await
+    }
+
+    // 作成
+    pub async fn create(&self, input: T::Input) -> Result<T, ResourceError> {
+        self.repository.create(input).await
+    }
+
+    // 更新
+    pub async fn update(&self, id: T::Id, input: T::Input) -> Result<T, ResourceError> {
+        self.repository.update(id, input).await
+    }
+
+    // 削除
+    pub async fn delete(&self, id: T::Id) -> Result<(), ResourceError> {
+        self.repository.delete(id).await
+    }
+}
@@ -1 +1,9 @@
-pub mod user_di_service;
\ No newline at end of file
+pub mod user_di_service;
+pub mod crud_service;
+pub mod two_factor_service;
+pub mod user_identity_service;
+pub mod api_key_service;
+pub mod session_service;
+
+#[cfg(test)]
+mod tests;
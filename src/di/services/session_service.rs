@@ -0,0 +1,105 @@
+use std::sync::Arc;
+use chrono::{Duration, NaiveDateTime, Utc};
+use uuid::Uuid;
+// リポジトリ
+use crate::di::repositories::session_repository::{CreateSessionArgs, SessionRecord, SessionRepositoryTrait};
+// モデル
+use crate::models::auth::session::{DeviceInfo, SessionResponse};
+// 設定
+use crate::config::session_config::SessionConfig;
+// エラー
+use crate::errors::auth::auth_error::AuthError;
+
+// 保存するUser-Agentの最大文字数(超えた部分は切り捨てる)
+const MAX_USER_AGENT_LEN: usize = 512;
+
+// サインインのセッションのサービス
+pub struct SessionService {
+    repository: Arc<dyn SessionRepositoryTrait>,
+}
+
+// メソッド
+impl SessionService {
+    // コンストラクタ
+    pub fn new(repository: Arc<dyn SessionRepositoryTrait>) -> Self {
+        Self { repository }
+    }
+
+    // トークンを発行するセッションを作成する(有効期限はトークンと同じ)
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        device: &DeviceInfo,
+        expires_at: NaiveDateTime,
+    ) -> Result<SessionRecord, AuthError> {
+        let args = CreateSessionArgs {
+            p_user_id: user_id,
+            p_user_agent: device
+                .user_agent
+                .as_ref()
+                .map(|user_agent| user_agent.chars().take(MAX_USER_AGENT_LEN).collect()),
+            p_ip_address: device.ip_address.clone(),
+            p_expires_at: expires_at,
+        };
+        self.repository.create(args).await
+    }
+
+    // ユーザーの有効なセッションを新しい順に取得する(currentは呼び出し元のトークンのセッション)
+    pub async fn list(&self, user_id: Uuid, current: Option<Uuid>) -> Result<Vec<SessionResponse>, AuthError> {
+        let now = Utc::now().naive_utc();
+        let records = self.repository.list(user_id).await?;
+
+        Ok(records
+            .into_iter()
+            .filter(|record| record.expires_at > now)
+            .map(|record| SessionResponse {
+                current: Some(record.id) == current,
+                id: record.id,
+                user_agent: record.user_agent,
+                ip_address: record.ip_address,
+                created_at: record.created_at,
+                last_seen_at: record.last_seen_at,
+                expires_at: record.expires_at,
+            })
+            .collect())
+    }
+
+    // ユーザーのセッションを削除する(以降そのセッションのトークンは使えない)
+    pub async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<(), AuthError> {
+        if !self.repository.delete(user_id, id).await? {
+            return Err(AuthError::SessionNotFound);
+        }
+        Ok(())
+    }
+
+    // ユーザーの全てのセッションを削除する(パスワードの変更時)
+    pub async fn delete_all(&self, user_id: Uuid) -> Result<(), AuthError> {
+        self.repository.delete_all(user_id).await
+    }
+
+    // トークンのセッションが有効か確認する(削除・期限切れ・別のユーザーのセッションの場合はInvalidToken)
+    //
+    // 最後にアクセスした日時は設定の間隔ごとに更新し、更新に失敗しても認証は失敗させない
+    pub async fn authenticate(&self, id: Uuid, user_id: Uuid, config: &SessionConfig) -> Result<(), AuthError> {
+        let record = self
+            .repository
+            .find(id)
+            .await?
+            .filter(|record| record.user_id == user_id)
+            .ok_or(AuthError::InvalidToken)?;
+
+        let now = Utc::now().naive_utc();
+        if record.expires_at <= now {
+            return Err(AuthError::InvalidToken);
+        }
+
+        let interval = Duration::from_std(config.last_seen_interval).unwrap_or(Duration::zero());
+        if now - record.last_seen_at >= interval {
+            if let Err(e) = self.repository.touch(record.id).await {
+                tracing::warn!(session_id = %record.id, error = ?e, "updating session last_seen_at failed");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+// サービスのインポート
+use crate::di::services::api_key_service::{generate_api_key, hash_api_key, parse_prefix, ApiKeyService};
+// リポジトリのインポート
+use crate::di::repositories::api_key_repository::{ApiKeyRecord, ApiKeyRepositoryTrait, CreateApiKeyArgs};
+// モデルのインポート
+use crate::models::auth::api_key::{ApiKeyScope, CreateApiKeyRequest};
+// 設定のインポート
+use crate::config::api_key_config::ApiKeyConfig;
+// エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+
+// メモリ上にキーを保持するリポジトリ(RPCと同じ条件で更新する)
+#[derive(Default)]
+struct FakeApiKeyRepository {
+    // 保存したキー
+    records: Mutex<Vec<ApiKeyRecord>>,
+    // 最終使用日時を更新した回数
+    touches: Mutex<usize>,
+}
+
+// トレイト実装
+#[async_trait]
+impl ApiKeyRepositoryTrait for FakeApiKeyRepository {
+    async fn list(&self, user_id: Uuid) -> Result<Vec<ApiKeyRecord>, AuthError> {
+        let records = self.records.lock().unwrap();
+        Ok(records.iter().rev().filter(|record| record.user_id == user_id).cloned().collect())
+    }
+
+    async fn find_by_prefix(&self, prefix: &str) -> Result<Option<ApiKeyRecord>, AuthError> {
+        Ok(self.records.lock().unwrap().iter().find(|record| record.prefix == prefix).cloned())
+    }
+
+    async fn create(&self, args: CreateApiKeyArgs) -> Result<Option<ApiKeyRecord>, AuthError> {
+        let mut records = self.records.lock().unwrap();
+        if records.iter().filter(|record| record.user_id == args.p_user_id).count() >= args.p_max_keys {
+            return Ok(None);
+        }
+        let record = ApiKeyRecord {
+            id: Uuid::new_v4(),
+            user_id: args.p_user_id,
+            name: args.p_name,
+            prefix: args.p_prefix,
+            key_hash: args.p_key_hash,
+            scopes: args.p_scopes,
+            expires_at: args.p_expires_at,
+            last_used_at: None,
+            created_at: Utc::now().naive_utc(),
+        };
+        records.push(record.clone());
+        Ok(Some(record))
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, AuthError> {
+        let mut records = self.records.lock().unwrap();
+        let before = records.len();
+        records.retain(|record| !(record.user_id == user_id && record.id == id));
+        Ok(records.len() < before)
+    }
+
+    async fn touch(&self, id: Uuid) -> Result<(), AuthError> {
+        *self.touches.lock().unwrap() += 1;
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.iter_mut().find(|record| record.id == id) {
+            record.last_used_at = Some(Utc::now().naive_utc());
+        }
+        Ok(())
+    }
+}
+
+// 発行のリクエスト
+fn request(scopes: Vec<ApiKeyScope>) -> CreateApiKeyRequest {
+    CreateApiKeyRequest { name: " nightly-batch ".to_string(), scopes, expires_in_days: None }
+}
+
+// サービスとリポジトリを作成
+fn service() -> (ApiKeyService, Arc<FakeApiKeyRepository>) {
+    let repository = Arc::new(FakeApiKeyRepository::default());
+    (ApiKeyService::new(repository.clone()), repository)
+}
+
+// 生成したキーの形式と先頭の取り出しのテスト
+#[test]
+fn test_generated_key_format() {
+    let (prefix, key) = generate_api_key();
+    assert!(prefix.starts_with("bk_"));
+    assert!(key.starts_with(&format!("{}_", prefix)));
+    assert_eq!(parse_prefix(&key), Some(prefix.as_str()));
+
+    // 形式が正しくないキー
+    assert_eq!(parse_prefix("bk_short_secret"), None);
+    assert_eq!(parse_prefix(&key.replacen("bk_", "xx_", 1)), None);
+    assert_eq!(parse_prefix(&format!("{}!", key)), None);
+    assert_ne!(generate_api_key().1, key);
+}
+
+// 発行したキーで認証でき、ハッシュのみ保存されることのテスト
+#[tokio::test]
+async fn test_create_and_authenticate() {
+    let (service, repository) = service();
+    let user_id = Uuid::new_v4();
+    let config = ApiKeyConfig::default();
+
+    let created = service
+        .create(user_id, request(vec![ApiKeyScope::Write, ApiKeyScope::Read, ApiKeyScope::Read]), &config)
+        .await
+        .unwrap();
+    assert_eq!(created.api_key.name, "nightly-batch");
+    assert_eq!(created.api_key.scopes, vec![ApiKeyScope::Read, ApiKeyScope::Write]);
+    assert!(created.key.starts_with(&created.api_key.prefix));
+    let stored = repository.records.lock().unwrap()[0].clone();
+    assert_eq!(stored.key_hash, hash_api_key(&created.key));
+    assert!(!stored.key_hash.contains(&created.key));
+    let days = (stored.expires_at - Utc::now().naive_utc()).num_days();
+    assert!((89..=90).contains(&days));
+
+    let record = service.authenticate(&created.key, &config).await.unwrap();
+    assert_eq!(record.user_id, user_id);
+
+    // 先頭が同じで秘密の部分が異なるキー
+    let forged = format!("{}_{}", created.api_key.prefix, "A".repeat(40));
+    assert!(matches!(service.authenticate(&forged, &config).await, Err(AuthError::InvalidToken)));
+}
+
+// 最終使用日時は間隔ごとに更新することのテスト
+#[tokio::test]
+async fn test_last_used_is_throttled() {
+    let (service, repository) = service();
+    let config = ApiKeyConfig::default();
+    let created = service.create(Uuid::new_v4(), request(vec![ApiKeyScope::Read]), &config).await.unwrap();
+
+    for _ in 0..3 {
+        service.authenticate(&created.key, &config).await.unwrap();
+    }
+    assert_eq!(*repository.touches.lock().unwrap(), 1);
+
+    let every_time = ApiKeyConfig { last_used_interval: Duration::ZERO, ..config };
+    service.authenticate(&created.key, &every_time).await.unwrap();
+    assert_eq!(*repository.touches.lock().unwrap(), 2);
+
+    // 検証のみでは最終使用日時を更新しない
+    let record = service.verify(&created.key).await.unwrap();
+    assert_eq!(record.key_hash, hash_api_key(&created.key));
+    assert_eq!(*repository.touches.lock().unwrap(), 2);
+}
+
+// 期限切れ・削除したキーを拒否することのテスト
+#[tokio::test]
+async fn test_expired_and_deleted_keys_are_rejected() {
+    let (service, repository) = service();
+    let user_id = Uuid::new_v4();
+    let config = ApiKeyConfig::default();
+    let created = service.create(user_id, request(vec![ApiKeyScope::Read]), &config).await.unwrap();
+
+    repository.records.lock().unwrap()[0].expires_at = Utc::now().naive_utc() - chrono::Duration::seconds(1);
+    assert!(matches!(service.authenticate(&created.key, &config).await, Err(AuthError::InvalidToken)));
+
+    // 他のユーザーのキーは削除できない
+    assert!(matches!(service.delete(Uuid::new_v4(), created.api_key.id).await, Err(AuthError::ApiKeyNotFound)));
+    service.delete(user_id, created.api_key.id).await.unwrap();
+    assert!(service.list(user_id).await.unwrap().is_empty());
+    assert!(matches!(service.authenticate(&created.key, &config).await, Err(AuthError::InvalidToken)));
+}
+
+// 検証に成功したキーのみ覚え、削除すると忘れることのテスト(レート制限での識別)
+#[tokio::test]
+async fn test_recently_verified_keys() {
+    let (service, _) = service();
+    let user_id = Uuid::new_v4();
+    let config = ApiKeyConfig::default();
+    let created = service.create(user_id, request(vec![ApiKeyScope::Read]), &config).await.unwrap();
+
+    // 検証前・検証に失敗したキーは覚えていない
+    assert_eq!(service.recently_verified(&created.key), None);
+    let forged = format!("{}_{}", created.api_key.prefix, "A".repeat(40));
+    assert!(service.verify(&forged).await.is_err());
+    assert_eq!(service.recently_verified(&forged), None);
+
+    service.authenticate(&created.key, &config).await.unwrap();
+    assert_eq!(service.recently_verified(&created.key), Some(created.api_key.id));
+
+    service.delete(user_id, created.api_key.id).await.unwrap();
+    assert_eq!(service.recently_verified(&created.key), None);
+}
+
+// 不正な発行のリクエストと上限のテスト
+#[tokio::test]
+async fn test_create_validation_and_limit() {
+    let (service, _) = service();
+    let user_id = Uuid::new_v4();
+    let config = ApiKeyConfig { max_keys_per_user: 1, ..ApiKeyConfig::default() };
+
+    let invalid = [
+        CreateApiKeyRequest { name: "  ".to_string(), ..request(vec![ApiKeyScope::Read]) },
+        request(Vec::new()),
+        CreateApiKeyRequest { expires_in_days: Some(0), ..request(vec![ApiKeyScope::Read]) },
+        CreateApiKeyRequest { expires_in_days: Some(366), ..request(vec![ApiKeyScope::Read]) },
+    ];
+    for request in invalid {
+        assert!(matches!(
+            service.create(user_id, request, &config).await,
+            Err(AuthError::InvalidApiKeyRequest(_))
+        ));
+    }
+
+    service.create(user_id, request(vec![ApiKeyScope::Read]), &config).await.unwrap();
+    assert!(matches!(
+        service.create(user_id, request(vec![ApiKeyScope::Read]), &config).await,
+        Err(AuthError::ApiKeyLimitReached)
+    ));
+}
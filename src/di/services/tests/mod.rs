@@ -0,0 +1,5 @@
+pub mod user_di_service_01_test;
+pub mod user_di_service_02_test;
+pub mod two_factor_service_01_test;
+pub mod api_key_service_01_test;
+pub mod session_service_01_test;
@@ -0,0 +1,171 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use uuid::Uuid;
+// サービスのインポート
+use crate::di::services::session_service::SessionService;
+// リポジトリのインポート
+use crate::di::repositories::session_repository::{CreateSessionArgs, SessionRecord, SessionRepositoryTrait};
+// モデルのインポート
+use crate::models::auth::session::DeviceInfo;
+// 設定のインポート
+use crate::config::session_config::SessionConfig;
+// エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+
+// メモリ上にセッションを保持するリポジトリ(RPCと同じ条件で更新する)
+#[derive(Default)]
+struct FakeSessionRepository {
+    // 保存したセッション
+    records: Mutex<Vec<SessionRecord>>,
+    // 最後にアクセスした日時を更新した回数
+    touches: Mutex<usize>,
+}
+
+// トレイト実装
+#[async_trait]
+impl SessionRepositoryTrait for FakeSessionRepository {
+    async fn list(&self, user_id: Uuid) -> Result<Vec<SessionRecord>, AuthError> {
+        let records = self.records.lock().unwrap();
+        Ok(records.iter().rev().filter(|record| record.user_id == user_id).cloned().collect())
+    }
+
+    async fn find(&self, id: Uuid) -> Result<Option<SessionRecord>, AuthError> {
+        Ok(self.records.lock().unwrap().iter().find(|record| record.id == id).cloned())
+    }
+
+    async fn create(&self, args: CreateSessionArgs) -> Result<SessionRecord, AuthError> {
+        let now = Utc::now().naive_utc();
+        let mut records = self.records.lock().unwrap();
+        records.retain(|record| !(record.user_id == args.p_user_id && record.expires_at <= now));
+        let record = SessionRecord {
+            id: Uuid::new_v4(),
+            user_id: args.p_user_id,
+            user_agent: args.p_user_agent,
+            ip_address: args.p_ip_address,
+            created_at: now,
+            last_seen_at: now,
+            expires_at: args.p_expires_at,
+        };
+        records.push(record.clone());
+        Ok(record)
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, AuthError> {
+        let mut records = self.records.lock().unwrap();
+        let before = records.len();
+        records.retain(|record| !(record.user_id == user_id && record.id == id));
+        Ok(records.len() < before)
+    }
+
+    async fn delete_all(&self, user_id: Uuid) -> Result<(), AuthError> {
+        self.records.lock().unwrap().retain(|record| record.user_id != user_id);
+        Ok(())
+    }
+
+    async fn touch(&self, id: Uuid) -> Result<(), AuthError> {
+        *self.touches.lock().unwrap() += 1;
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.iter_mut().find(|record| record.id == id) {
+            record.last_seen_at = Utc::now().naive_utc();
+        }
+        Ok(())
+    }
+}
+
+// サインインした端末
+fn device() -> DeviceInfo {
+    DeviceInfo {
+        user_agent: Some("Mozilla/5.0".to_string()),
+        ip_address: Some("203.0.113.10".to_string()),
+    }
+}
+
+// 現在時刻から指定した秒数後の日時
+fn in_secs(secs: i64) -> NaiveDateTime {
+    (Utc::now() + chrono::Duration::seconds(secs)).naive_utc()
+}
+
+// サービスとリポジトリを作成
+fn service() -> (SessionService, Arc<FakeSessionRepository>) {
+    let repository = Arc::new(FakeSessionRepository::default());
+    (SessionService::new(repository.clone()), repository)
+}
+
+// 作成したセッションが端末の情報とともに一覧に含まれることのテスト
+#[tokio::test]
+async fn test_create_and_list() {
+    let (service, _) = service();
+    let user_id = Uuid::new_v4();
+    let first = service.create(user_id, &device(), in_secs(3600)).await.unwrap();
+    let long_agent = DeviceInfo { user_agent: Some("x".repeat(1000)), ip_address: None };
+    let second = service.create(user_id, &long_agent, in_secs(3600)).await.unwrap();
+    service.create(Uuid::new_v4(), &device(), in_secs(3600)).await.unwrap();
+
+    let sessions = service.list(user_id, Some(first.id)).await.unwrap();
+    assert_eq!(sessions.iter().map(|session| session.id).collect::<Vec<_>>(), vec![second.id, first.id]);
+    assert_eq!(sessions[1].user_agent.as_deref(), Some("Mozilla/5.0"));
+    assert_eq!(sessions[1].ip_address.as_deref(), Some("203.0.113.10"));
+    assert!(sessions[1].current && !sessions[0].current);
+    // 長いUser-Agentは切り捨てて保存する
+    assert_eq!(sessions[0].user_agent.as_ref().unwrap().len(), 512);
+}
+
+// 削除・期限切れ・別のユーザーのセッションを拒否することのテスト
+#[tokio::test]
+async fn test_authenticate_rejects_revoked_sessions() {
+    let (service, repository) = service();
+    let user_id = Uuid::new_v4();
+    let config = SessionConfig::default();
+    let session = service.create(user_id, &device(), in_secs(3600)).await.unwrap();
+
+    service.authenticate(session.id, user_id, &config).await.unwrap();
+    assert!(matches!(service.authenticate(session.id, Uuid::new_v4(), &config).await, Err(AuthError::InvalidToken)));
+    assert!(matches!(service.authenticate(Uuid::new_v4(), user_id, &config).await, Err(AuthError::InvalidToken)));
+
+    repository.records.lock().unwrap()[0].expires_at = in_secs(-1);
+    assert!(matches!(service.authenticate(session.id, user_id, &config).await, Err(AuthError::InvalidToken)));
+    assert!(service.list(user_id, None).await.unwrap().is_empty());
+
+    // 他のユーザーのセッションは削除できない
+    let session = service.create(user_id, &device(), in_secs(3600)).await.unwrap();
+    assert!(matches!(service.delete(Uuid::new_v4(), session.id).await, Err(AuthError::SessionNotFound)));
+    service.delete(user_id, session.id).await.unwrap();
+    assert!(matches!(service.authenticate(session.id, user_id, &config).await, Err(AuthError::InvalidToken)));
+    assert!(matches!(service.delete(user_id, session.id).await, Err(AuthError::SessionNotFound)));
+}
+
+// 最後にアクセスした日時は間隔ごとに更新することのテスト
+#[tokio::test]
+async fn test_last_seen_is_throttled() {
+    let (service, repository) = service();
+    let user_id = Uuid::new_v4();
+    let config = SessionConfig::default();
+    let session = service.create(user_id, &device(), in_secs(3600)).await.unwrap();
+
+    for _ in 0..3 {
+        service.authenticate(session.id, user_id, &config).await.unwrap();
+    }
+    assert_eq!(*repository.touches.lock().unwrap(), 0);
+
+    let every_time = SessionConfig { last_seen_interval: Duration::ZERO };
+    service.authenticate(session.id, user_id, &every_time).await.unwrap();
+    assert_eq!(*repository.touches.lock().unwrap(), 1);
+}
+
+// パスワードの変更時にユーザーの全てのセッションを削除することのテスト
+#[tokio::test]
+async fn test_delete_all() {
+    let (service, _) = service();
+    let user_id = Uuid::new_v4();
+    let other_user_id = Uuid::new_v4();
+    for _ in 0..2 {
+        service.create(user_id, &device(), in_secs(3600)).await.unwrap();
+    }
+    let other = service.create(other_user_id, &device(), in_secs(3600)).await.unwrap();
+
+    service.delete_all(user_id).await.unwrap();
+    assert!(service.list(user_id, None).await.unwrap().is_empty());
+    service.authenticate(other.id, other_user_id, &SessionConfig::default()).await.unwrap();
+}
@@ -0,0 +1,190 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+// サービスのインポート
+use crate::di::services::two_factor_service::{hash_recovery_code, TwoFactorService};
+// リポジトリのインポート
+use crate::di::repositories::two_factor_repository::{TwoFactorRecord, TwoFactorRepositoryTrait};
+// 設定のインポート
+use crate::config::two_factor_config::TwoFactorConfig;
+// エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+// TOTPのインポート
+use crate::services::auth::totp::Totp;
+// キャッシュのインポート
+use crate::cache::lru_cache::InMemoryLruCache;
+use crate::cache::user_cache::UserCache;
+// モデルのインポート
+use crate::models::users::users::User;
+
+// メモリ上に設定を保持するリポジトリ(RPCと同じ条件で更新する)
+#[derive(Default)]
+struct FakeTwoFactorRepository {
+    // ユーザーごとの設定
+    records: Mutex<HashMap<Uuid, TwoFactorRecord>>,
+    // 未使用のリカバリーコードのハッシュ
+    recovery_codes: Mutex<HashSet<(Uuid, String)>>,
+}
+
+// トレイト実装
+#[async_trait]
+impl TwoFactorRepositoryTrait for FakeTwoFactorRepository {
+    async fn find(&self, user_id: Uuid) -> Result<Option<TwoFactorRecord>, AuthError> {
+        Ok(self.records.lock().unwrap().get(&user_id).cloned())
+    }
+
+    async fn begin_setup(&self, user_id: Uuid, secret: String) -> Result<bool, AuthError> {
+        let mut records = self.records.lock().unwrap();
+        if records.get(&user_id).is_some_and(TwoFactorRecord::is_enabled) {
+            return Ok(false);
+        }
+        records.insert(user_id, TwoFactorRecord { user_id, secret, last_used_step: None, confirmed_at: None });
+        Ok(true)
+    }
+
+    async fn enable(&self, user_id: Uuid, step: i64, recovery_code_hashes: Vec<String>) -> Result<bool, AuthError> {
+        let mut records = self.records.lock().unwrap();
+        let Some(record) = records.get_mut(&user_id).filter(|record| !record.is_enabled()) else {
+            return Ok(false);
+        };
+        record.last_used_step = Some(step);
+        record.confirmed_at = Some(Utc::now().naive_utc());
+        let mut codes = self.recovery_codes.lock().unwrap();
+        codes.extend(recovery_code_hashes.into_iter().map(|hash| (user_id, hash)));
+        Ok(true)
+    }
+
+    async fn record_step(&self, user_id: Uuid, step: i64) -> Result<bool, AuthError> {
+        let mut records = self.records.lock().unwrap();
+        let Some(record) = records.get_mut(&user_id) else {
+            return Ok(false);
+        };
+        if record.last_used_step.is_some_and(|last| step <= last) {
+            return Ok(false);
+        }
+        record.last_used_step = Some(step);
+        Ok(true)
+    }
+
+    async fn use_recovery_code(&self, user_id: Uuid, code_hash: String) -> Result<bool, AuthError> {
+        Ok(self.recovery_codes.lock().unwrap().remove(&(user_id, code_hash)))
+    }
+
+    async fn reset(&self, user_id: Uuid) -> Result<bool, AuthError> {
+        self.recovery_codes.lock().unwrap().retain(|(id, _)| *id != user_id);
+        Ok(self.records.lock().unwrap().remove(&user_id).is_some())
+    }
+}
+
+// 現在のコードを計算する関数
+fn current_code(secret: &str) -> String {
+    Totp::from_base32(secret).unwrap().code_at(Utc::now().timestamp())
+}
+
+// 設定を開始して確認するまでのテスト
+#[tokio::test]
+async fn test_setup_and_confirm() {
+    let service = TwoFactorService::new(Arc::new(FakeTwoFactorRepository::default()));
+    let config = TwoFactorConfig::default();
+    let user_id = Uuid::new_v4();
+
+    // 開始前の確認は拒否
+    assert!(matches!(
+        service.confirm_setup(user_id, "000000", &config).await,
+        Err(AuthError::TwoFactorNotSetUp)
+    ));
+
+    let setup = service.begin_setup(user_id, "user@example.com", &config).await.unwrap();
+    assert!(setup.otpauth_uri.starts_with("otpauth://totp/backend:user%40example%2Ecom?secret="));
+
+    // 誤ったコード
+    assert!(matches!(
+        service.confirm_setup(user_id, "abcdef", &config).await,
+        Err(AuthError::InvalidTwoFactorCode)
+    ));
+
+    let recovery = service.confirm_setup(user_id, &current_code(&setup.secret), &config).await.unwrap();
+    assert_eq!(recovery.recovery_codes.len(), config.recovery_code_count);
+
+    // 有効にした後は設定し直せない
+    assert!(matches!(
+        service.begin_setup(user_id, "user@example.com", &config).await,
+        Err(AuthError::TwoFactorAlreadyEnabled)
+    ));
+}
+
+// 同じコードの再利用と使用済みのリカバリーコードを拒否することのテスト
+#[tokio::test]
+async fn test_verify_rejects_replayed_codes() {
+    let service = TwoFactorService::new(Arc::new(FakeTwoFactorRepository::default()));
+    let config = TwoFactorConfig::default();
+    let user_id = Uuid::new_v4();
+
+    let setup = service.begin_setup(user_id, "user@example.com", &config).await.unwrap();
+    let code = current_code(&setup.secret);
+    let recovery = service.confirm_setup(user_id, &code, &config).await.unwrap();
+
+    // 確認に使ったコードはサインインに使えない
+    assert!(matches!(
+        service.verify(user_id, &code, &config).await,
+        Err(AuthError::InvalidTwoFactorCode)
+    ));
+
+    // リカバリーコードは1回のみ使える(大文字・区切りの省略を許容)
+    let recovery_code = &recovery.recovery_codes[0];
+    let typed = recovery_code.replace('-', "").to_uppercase();
+    assert!(service.verify(user_id, &typed, &config).await.is_ok());
+    assert!(matches!(
+        service.verify(user_id, recovery_code, &config).await,
+        Err(AuthError::InvalidTwoFactorCode)
+    ));
+
+    // リセット後は検証できない
+    service.reset(user_id).await.unwrap();
+    assert!(matches!(
+        service.verify(user_id, &recovery.recovery_codes[1], &config).await,
+        Err(AuthError::InvalidTwoFactorCode)
+    ));
+    assert!(matches!(service.reset(user_id).await, Err(AuthError::UserNotFound)));
+}
+
+// 有効化・リセットでユーザーのキャッシュ(two_factor_enabled)を無効化することのテスト
+#[tokio::test]
+async fn test_enable_and_reset_invalidate_user_cache() {
+    let cache = Arc::new(UserCache::new(Arc::new(InMemoryLruCache::new(10)), Duration::from_secs(60)));
+    let service = TwoFactorService::with_user_cache(Arc::new(FakeTwoFactorRepository::default()), Some(cache.clone()));
+    let config = TwoFactorConfig::default();
+    let user = User {
+        id: Uuid::new_v4(),
+        username: "test_user".to_string(),
+        email: "user@example.com".to_string(),
+        password: "hashed_password".to_string(),
+        two_factor_enabled: false,
+        created_at: Utc::now().naive_utc(),
+        updated_at: Utc::now().naive_utc(),
+    };
+
+    // 有効化
+    cache.store(&user, cache.generation(user.id)).await;
+    let setup = service.begin_setup(user.id, &user.email, &config).await.unwrap();
+    service.confirm_setup(user.id, &current_code(&setup.secret), &config).await.unwrap();
+    assert!(cache.get(user.id).await.is_none());
+
+    // リセット
+    cache.store(&user, cache.generation(user.id)).await;
+    service.reset(user.id).await.unwrap();
+    assert!(cache.get(user.id).await.is_none());
+}
+
+// リカバリーコードのハッシュが入力の揺れを吸収することのテスト
+#[test]
+fn test_hash_recovery_code_normalizes_input() {
+    let hash = hash_recovery_code("k3m9x-7pq2d");
+
+    assert_eq!(hash.len(), 64);
+    assert_eq!(hash_recovery_code("K3M9X 7PQ2D"), hash);
+    assert_ne!(hash_recovery_code("k3m9x-7pq2e"), hash);
+}
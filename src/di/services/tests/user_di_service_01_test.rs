@@ -0,0 +1,130 @@
+use reqwest::Client;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+// サービスのインポート
+use crate::di::services::user_di_service::UserDIService;
+// リポジトリのインポート
+use crate::di::repositories::user_repository::UserRepository;
+use crate::di::repositories::cached_user_repository::CachedUserRepository;
+// キャッシュのインポート
+use crate::cache::lru_cache::InMemoryLruCache;
+use crate::cache::user_cache::UserCache;
+// 呼び出しポリシーのインポート
+use crate::outbound::outbound_policy::OutboundPolicy;
+// Supabaseのクライアントのインポート
+use crate::supabase::supabase_client::SupabaseClient;
+// モデルのインポート
+use crate::models::users::users::{NewUser, User, UserUpdate};
+// エラーのインポート
+use crate::errors::users::user_error::UserError;
+
+// テスト用のサービスを作成
+fn create_test_service(mock_url: String) -> UserDIService {
+    UserDIService::new(Arc::new(UserRepository::new(SupabaseClient::new(
+        Client::new(),
+        mock_url,
+        "test_key".to_string(),
+        Arc::new(OutboundPolicy::default()),
+    ))))
+}
+
+// 正しい入力
+fn valid_user() -> NewUser {
+    NewUser {
+        username: "test_user".to_string(),
+        email: "test@example.com".to_string(),
+        password: "password123".to_string(),
+    }
+}
+
+// 不正な入力と期待するメッセージ
+fn invalid_users() -> Vec<(NewUser, &'static str)> {
+    vec![
+        (NewUser { username: String::new(), ..valid_user() }, "Username cannot be empty"),
+        (NewUser { email: "invalid".to_string(), ..valid_user() }, "Invalid email format"),
+        (NewUser { password: "short".to_string(), ..valid_user() }, "Password must be at least 8 characters"),
+        (NewUser { password: "a".repeat(73), ..valid_user() }, "Password must be at most 72 bytes"),
+    ]
+}
+
+// 入力の検証のテスト
+#[test]
+fn test_validate() {
+    assert!(UserDIService::validate(&valid_user()).is_ok());
+
+    for (user, expected) in invalid_users() {
+        match UserDIService::validate(&user) {
+            Err(UserError::InvalidData(message)) => assert_eq!(message, expected),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
+
+// 不正な入力では作成・更新のRPCを呼ばないことのテスト
+#[tokio::test]
+async fn test_invalid_input_is_rejected_before_write() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let rpc_mock = mock_server
+        .mock("POST", mockito::Matcher::Regex(r"^/rest/v1/rpc/".to_string()))
+        .expect(0)
+        .create_async()
+        .await;
+    let service = create_test_service(mock_server.url());
+
+    for (user, _) in invalid_users() {
+        assert!(matches!(service.create_user(user.clone()).await, Err(UserError::InvalidData(_))));
+    }
+    // 更新はユーザー名とメールアドレスのみ検証する(パスワードは更新の入力にない)
+    for (user, _) in invalid_users().into_iter().take(2) {
+        let update = UserUpdate { username: user.username, email: user.email };
+        assert!(matches!(service.update_user(Uuid::new_v4(), update).await, Err(UserError::InvalidData(_))));
+    }
+
+    rpc_mock.assert_async().await;
+}
+
+// パスワードの確認は、キャッシュの古いハッシュではなく最新の行で行うことのテスト
+#[tokio::test]
+async fn test_password_is_verified_against_fresh_row() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let id = Uuid::new_v4();
+    let row = |password: &str| User {
+        id,
+        username: "test_user".to_string(),
+        email: "test@example.com".to_string(),
+        password: bcrypt::hash(password, 4).unwrap(),
+        two_factor_enabled: false,
+        created_at: chrono::Utc::now().naive_utc(),
+        updated_at: chrono::Utc::now().naive_utc(),
+    };
+    let _find_mock = mock_server
+        .mock("GET", format!("/rest/v1/trans_users?id=eq.{}", id).as_str())
+        .with_status(200)
+        .with_body(json!([row("new_password456")]).to_string())
+        .create_async()
+        .await;
+    let delete_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/delete_user_with_audit")
+        .with_status(200)
+        .with_body(json!([row("new_password456")]).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    // 変更前のパスワードのハッシュがキャッシュに残っている
+    let cache = Arc::new(UserCache::new(Arc::new(InMemoryLruCache::new(10)), Duration::from_secs(60)));
+    cache.store(&row("password123"), cache.generation(id)).await;
+    let inner = Arc::new(UserRepository::new(SupabaseClient::new(
+        Client::new(),
+        mock_server.url(),
+        "test_key".to_string(),
+        Arc::new(OutboundPolicy::default()),
+    )));
+    let service = UserDIService::new(Arc::new(CachedUserRepository::new(inner, cache)));
+
+    assert!(matches!(service.delete_account(id, "password123").await, Err(UserError::PasswordMismatch)));
+    service.delete_account(id, "new_password456").await.unwrap();
+    delete_mock.assert_async().await;
+}
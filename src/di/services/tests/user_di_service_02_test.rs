@@ -0,0 +1,32 @@
+// サービスのインポート
+use crate::di::services::user_di_service::UserDIService;
+// モデルのインポート
+use crate::models::users::users::UserProfileUpdate;
+// エラーのインポート
+use crate::errors::users::user_error::UserError;
+
+// プロフィールの入力の検証のテスト
+#[test]
+fn test_validate_profile() {
+    // 一部の項目のみの指定は有効
+    let valid = [
+        UserProfileUpdate { username: Some("renamed".to_string()), email: None },
+        UserProfileUpdate { username: None, email: Some("new@example.com".to_string()) },
+    ];
+    for profile in valid {
+        assert!(UserDIService::validate_profile(&profile).is_ok());
+    }
+
+    // 不正な入力と期待するメッセージ
+    let invalid = [
+        (UserProfileUpdate::default(), "No fields to update"),
+        (UserProfileUpdate { username: Some(String::new()), email: None }, "Username cannot be empty"),
+        (UserProfileUpdate { username: None, email: Some("invalid".to_string()) }, "Invalid email format"),
+    ];
+    for (profile, expected) in invalid {
+        match UserDIService::validate_profile(&profile) {
+            Err(UserError::InvalidData(message)) => assert_eq!(message, expected),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
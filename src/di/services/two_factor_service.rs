@@ -0,0 +1,177 @@
+use std::sync::Arc;
+use chrono::Utc;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use uuid::Uuid;
+// リポジトリ
+use crate::di::repositories::two_factor_repository::{TwoFactorRecord, TwoFactorRepositoryTrait};
+// モデル
+use crate::models::auth::two_factor::{RecoveryCodesResponse, TwoFactorSetupResponse};
+// 設定
+use crate::config::two_factor_config::TwoFactorConfig;
+// エラー
+use crate::errors::auth::auth_error::AuthError;
+// TOTP
+use crate::services::auth::totp::Totp;
+// ユーザーのキャッシュ
+use crate::cache::user_cache::UserCache;
+
+// リカバリーコードの文字(読み間違えやすい0・1・i・l・oを除く)
+const RECOVERY_CODE_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+// リカバリーコードの区切りの前後の文字数
+const RECOVERY_CODE_GROUP_LEN: usize = 5;
+
+// 二段階認証(TOTP)のサービス
+pub struct TwoFactorService {
+    repository: Arc<dyn TwoFactorRepositoryTrait>,
+    // 有効・無効を切り替えた際に無効化するユーザーのキャッシュ(two_factor_enabledを含むため)
+    user_cache: Option<Arc<UserCache>>,
+}
+
+// メソッド
+impl TwoFactorService {
+    // コンストラクタ
+    pub fn new(repository: Arc<dyn TwoFactorRepositoryTrait>) -> Self {
+        Self::with_user_cache(repository, None)
+    }
+
+    // ユーザーのキャッシュを指定するコンストラクタ
+    pub fn with_user_cache(repository: Arc<dyn TwoFactorRepositoryTrait>, user_cache: Option<Arc<UserCache>>) -> Self {
+        Self { repository, user_cache }
+    }
+
+    // 設定の開始(新しい秘密鍵を発行し、確認されるまでは無効のまま)
+    //
+    // 確認前に再度呼び出した場合は秘密鍵を発行し直す
+    pub async fn begin_setup(
+        &self,
+        user_id: Uuid,
+        account: &str,
+        config: &TwoFactorConfig,
+    ) -> Result<TwoFactorSetupResponse, AuthError> {
+        let totp = Totp::generate();
+        if !self.repository.begin_setup(user_id, totp.to_base32()).await? {
+            return Err(AuthError::TwoFactorAlreadyEnabled);
+        }
+
+        Ok(TwoFactorSetupResponse {
+            secret: totp.to_base32(),
+            otpauth_uri: totp.otpauth_uri(&config.issuer, account),
+        })
+    }
+
+    // 設定の確認(認証アプリのコードが一致した場合に有効にし、リカバリーコードを返す)
+    pub async fn confirm_setup(
+        &self,
+        user_id: Uuid,
+        code: &str,
+        config: &TwoFactorConfig,
+    ) -> Result<RecoveryCodesResponse, AuthError> {
+        let record = self.repository.find(user_id).await?.ok_or(AuthError::TwoFactorNotSetUp)?;
+        if record.is_enabled() {
+            return Err(AuthError::TwoFactorAlreadyEnabled);
+        }
+        let step = Self::verify_totp(&record, code, config).ok_or(AuthError::InvalidTwoFactorCode)?;
+
+        // リカバリーコードはハッシュのみ保存し、平文はこのレスポンスでのみ返す
+        let recovery_codes = generate_recovery_codes(config.recovery_code_count);
+        let hashes = recovery_codes.iter().map(|code| hash_recovery_code(code)).collect();
+        let enabled = self.repository.enable(user_id, step, hashes).await;
+        self.invalidate_user(user_id).await;
+        if !enabled? {
+            return Err(AuthError::TwoFactorAlreadyEnabled);
+        }
+
+        Ok(RecoveryCodesResponse { recovery_codes })
+    }
+
+    // サインインの二段階目の検証(認証アプリのコード、またはリカバリーコード)
+    //
+    // 同じコード(ステップ)と使用済みのリカバリーコードは受け付けない
+    pub async fn verify(&self, user_id: Uuid, code: &str, config: &TwoFactorConfig) -> Result<(), AuthError> {
+        let record = self
+            .repository
+            .find(user_id)
+            .await?
+            .filter(TwoFactorRecord::is_enabled)
+            .ok_or(AuthError::InvalidTwoFactorCode)?;
+
+        // 認証アプリのコード
+        if let Some(step) = Self::verify_totp(&record, code, config) {
+            if record.last_used_step.is_some_and(|last| step <= last) {
+                return Err(AuthError::InvalidTwoFactorCode);
+            }
+            // 同時に同じコードが使われた場合は、先に記録した方のみ受け付ける
+            return match self.repository.record_step(user_id, step).await? {
+                true => Ok(()),
+                false => Err(AuthError::InvalidTwoFactorCode),
+            };
+        }
+
+        // リカバリーコード
+        if self.repository.use_recovery_code(user_id, hash_recovery_code(code)).await? {
+            return Ok(());
+        }
+
+        Err(AuthError::InvalidTwoFactorCode)
+    }
+
+    // 無効化(管理者によるリセット、設定とリカバリーコードを削除する)
+    pub async fn reset(&self, user_id: Uuid) -> Result<(), AuthError> {
+        let reset = self.repository.reset(user_id).await;
+        self.invalidate_user(user_id).await;
+        if !reset? {
+            return Err(AuthError::UserNotFound);
+        }
+        Ok(())
+    }
+
+    // ユーザーのキャッシュを無効化する(書き込みに失敗した場合も、反映されている可能性があるため無効化する)
+    async fn invalidate_user(&self, user_id: Uuid) {
+        if let Some(cache) = &self.user_cache {
+            cache.invalidate(user_id).await;
+        }
+    }
+
+    // 認証アプリのコードを検証し、一致したステップを返す
+    fn verify_totp(record: &TwoFactorRecord, code: &str, config: &TwoFactorConfig) -> Option<i64> {
+        Totp::from_base32(&record.secret)?.verify(code, Utc::now().timestamp(), config.allowed_skew_steps)
+    }
+}
+
+// リカバリーコードを生成する関数(例: k3m9x-7pq2d)
+pub fn generate_recovery_codes(count: usize) -> Vec<String> {
+    let mut rng = rand::rngs::OsRng;
+    (0..count)
+        .map(|_| {
+            let mut code = String::with_capacity(RECOVERY_CODE_GROUP_LEN * 2 + 1);
+            for i in 0..RECOVERY_CODE_GROUP_LEN * 2 {
+                if i == RECOVERY_CODE_GROUP_LEN {
+                    code.push('-');
+                }
+                code.push(RECOVERY_CODE_ALPHABET[rng.gen_range(0..RECOVERY_CODE_ALPHABET.len())] as char);
+            }
+            code
+        })
+        .collect()
+}
+
+// リカバリーコードのハッシュ(SHA-256)を計算する関数
+//
+// 入力の揺れを許容するため、区切り・空白を除いて小文字にしてから計算する。
+// コードは十分にランダムなため、パスワードのような低速なハッシュは使わない
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+
+    Sha256::digest(normalized.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
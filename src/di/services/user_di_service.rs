@@ -3,20 +3,93 @@ use uuid::Uuid;
 // リポジトリ
 use crate::di::repositories::user_repository::{UserRepositoryTrait};
 // ユーザー
-use crate::models::users::users::{User, NewUser};
+use crate::models::users::users::{User, NewUser, UserProfileUpdate, UserUpdate};
 // エラー
 use crate::errors::users::user_error::UserError;
+// パスワードのハッシュ化
+use crate::password::password_hasher::PasswordHashers;
+
+// パスワードの最小文字数
+const MIN_PASSWORD_LENGTH: usize = 8;
+// パスワードの最大バイト数(bcryptは72バイトより後ろを無視するため)
+const MAX_PASSWORD_BYTES: usize = 72;
 
 // サービス
 pub struct UserDIService {
     repository: Arc<dyn UserRepositoryTrait>,
+    password_hashers: Arc<PasswordHashers>,
 }
 
 // メソッド
 impl UserDIService {
-    // コンストラクタ
+    // コンストラクタ(既定の設定でパスワードをハッシュ化する)
     pub fn new(repository: Arc<dyn UserRepositoryTrait>) -> Self {
-        Self { repository }
+        Self::with_password_hashers(repository, Arc::new(PasswordHashers::default()))
+    }
+
+    // パスワードのハッシュ化を指定するコンストラクタ
+    pub fn with_password_hashers(repository: Arc<dyn UserRepositoryTrait>, password_hashers: Arc<PasswordHashers>) -> Self {
+        Self { repository, password_hashers }
+    }
+
+    // 作成の入力の検証
+    pub fn validate(user: &NewUser) -> Result<(), UserError> {
+        Self::validate_identity(&user.username, &user.email)?;
+        Self::validate_password(&user.password)
+    }
+
+    // 更新の入力の検証
+    pub fn validate_update(user: &UserUpdate) -> Result<(), UserError> {
+        Self::validate_identity(&user.username, &user.email)
+    }
+
+    // ユーザー名・メールアドレスの検証(作成・更新で共通)
+    fn validate_identity(username: &str, email: &str) -> Result<(), UserError> {
+        if username.is_empty() {
+            return Err(UserError::InvalidData("Username cannot be empty".to_string()));
+        }
+
+        if !email.contains('@') {
+            return Err(UserError::InvalidData("Invalid email format".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // パスワードのポリシーの検証(作成・更新・パスワードの変更で共通)
+    pub fn validate_password(password: &str) -> Result<(), UserError> {
+        if password.len() < MIN_PASSWORD_LENGTH {
+            return Err(UserError::InvalidData(format!(
+                "Password must be at least {} characters",
+                MIN_PASSWORD_LENGTH
+            )));
+        }
+
+        if password.len() > MAX_PASSWORD_BYTES {
+            return Err(UserError::InvalidData(format!(
+                "Password must be at most {} bytes",
+                MAX_PASSWORD_BYTES
+            )));
+        }
+
+        Ok(())
+    }
+
+    // プロフィールの入力の検証(指定された項目のみ)
+    pub fn validate_profile(profile: &UserProfileUpdate) -> Result<(), UserError> {
+        if profile.username.is_none() && profile.email.is_none() {
+            return Err(UserError::InvalidData("No fields to update".to_string()));
+        }
+
+        if profile.username.as_deref().is_some_and(str::is_empty) {
+            return Err(UserError::InvalidData("Username cannot be empty".to_string()));
+        }
+
+        if profile.email.as_deref().is_some_and(|email| !email.contains('@')) {
+            return Err(UserError::InvalidData("Invalid email format".to_string()));
+        }
+
+        Ok(())
     }
 
     // 全件取得
@@ -31,16 +104,75 @@ impl UserDIService {
 
     // 作成
     pub async fn create_user(&self, user: NewUser) -> Result<User, UserError> {
+        Self::validate(&user)?;
         self.repository.create(user).await
     }
 
-    // 更新
-    pub async fn update_user(&self, id: Uuid, user: NewUser) -> Result<User, UserError> {
+    // 更新(パスワードは変更しない、パスワードの変更はchange_password)
+    pub async fn update_user(&self, id: Uuid, user: UserUpdate) -> Result<User, UserError> {
+        Self::validate_update(&user)?;
         self.repository.update(id, user).await
     }
 
+    // プロフィールの更新(本人による更新、パスワードは変更しない)
+    pub async fn update_profile(&self, id: Uuid, profile: UserProfileUpdate) -> Result<User, UserError> {
+        Self::validate_profile(&profile)?;
+        self.repository.update_profile(id, profile).await
+    }
+
     // 削除
     pub async fn delete_user(&self, id: Uuid) -> Result<(), UserError> {
         self.repository.delete(id).await
     }
-}
\ No newline at end of file
+
+    // アカウントの削除(本人による削除、現在のパスワードで確認する)
+    pub async fn delete_account(&self, id: Uuid, password: &str) -> Result<(), UserError> {
+        // キャッシュの古いハッシュで照合しないよう、最新の行を取得する
+        let user = self.repository.find_by_id_uncached(id).await?;
+
+        // パスワードの確認
+        if !self.verify_password(password, &user.password)? {
+            return Err(UserError::PasswordMismatch);
+        }
+
+        self.repository.delete(id).await
+    }
+
+    // パスワードの変更(本人による変更、現在のパスワードで確認する)
+    //
+    // 現在のパスワードと直近のhistory_size個のパスワードは再利用できない
+    pub async fn change_password(
+        &self,
+        id: Uuid,
+        current_password: &str,
+        new_password: &str,
+        history_size: usize,
+    ) -> Result<User, UserError> {
+        // キャッシュの古いハッシュで照合しないよう、最新の行を取得する
+        let user = self.repository.find_by_id_uncached(id).await?;
+
+        // 現在のパスワードの確認
+        if !self.verify_password(current_password, &user.password)? {
+            return Err(UserError::PasswordMismatch);
+        }
+
+        // 新しいパスワードのポリシーの検証
+        Self::validate_password(new_password)?;
+
+        // 現在・過去のパスワードの再利用を拒否
+        let history = self.repository.password_history(id, history_size).await?;
+        for password_hash in std::iter::once(&user.password).chain(history.iter()) {
+            if self.verify_password(new_password, password_hash)? {
+                return Err(UserError::InvalidData("Password was used recently".to_string()));
+            }
+        }
+
+        let password_hash = self.password_hashers.hash(new_password)?;
+        self.repository.change_password(id, password_hash, history_size).await
+    }
+
+    // パスワードとハッシュを照合(ハッシュのアルゴリズムは問わない)
+    fn verify_password(&self, password: &str, password_hash: &str) -> Result<bool, UserError> {
+        Ok(self.password_hashers.matches(password, password_hash)?)
+    }
+}
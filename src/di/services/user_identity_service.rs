@@ -0,0 +1,45 @@
+use std::sync::Arc;
+// リポジトリ
+use crate::di::repositories::user_identity_repository::UserIdentityRepositoryTrait;
+use crate::di::repositories::user_repository::UserRepositoryTrait;
+// モデル
+use crate::models::users::users::User;
+// エラー
+use crate::errors::auth::auth_error::AuthError;
+// OIDCのクライアント
+use crate::services::auth::oidc_client::VerifiedIdentity;
+
+// 外部のIdPのアカウントとユーザーを紐付けるサービス
+pub struct UserIdentityService {
+    // 紐付けのリポジトリ
+    identities: Arc<dyn UserIdentityRepositoryTrait>,
+    // ユーザーのリポジトリ
+    users: Arc<dyn UserRepositoryTrait>,
+}
+
+// メソッド
+impl UserIdentityService {
+    // コンストラクタ
+    pub fn new(identities: Arc<dyn UserIdentityRepositoryTrait>, users: Arc<dyn UserRepositoryTrait>) -> Self {
+        Self { identities, users }
+    }
+
+    // IdPのアカウントのユーザーを返す
+    //
+    // 紐付いていない場合は、IdPが確認したメールアドレスが一致する既存のユーザーに紐付ける(ユーザーは作成しない)
+    pub async fn resolve(&self, provider: &str, identity: &VerifiedIdentity) -> Result<User, AuthError> {
+        if let Some(user_id) = self.identities.find_user_id(provider, &identity.subject).await? {
+            return Ok(self.users.find_by_id(user_id).await?);
+        }
+
+        let email = identity.email.as_deref().ok_or(AuthError::NoLinkedAccount)?;
+        if !identity.email_verified {
+            return Err(AuthError::EmailNotVerified);
+        }
+
+        self.identities
+            .link_by_email(provider, &identity.subject, email)
+            .await?
+            .ok_or(AuthError::NoLinkedAccount)
+    }
+}
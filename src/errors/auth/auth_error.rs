@@ -1,5 +1,11 @@
 // 必要なクレートのインポート
 use axum::http::StatusCode;
+// Supabaseエラーのインポート
+use crate::errors::supabase::supabase_error::SupabaseError;
+// ユーザーエラーのインポート
+use crate::errors::users::user_error::UserError;
+// パスワードのハッシュ化のエラーのインポート
+use crate::errors::password::password_error::PasswordHashError;
 
 // 認証エラーの列挙型
 #[derive(Debug)]
@@ -14,6 +20,81 @@ pub enum AuthError {
     DatabaseError(String),
     // ユーザーが見つかりません
     UserNotFound,
+    // データベースが一時的に利用できない
+    ServiceUnavailable(String),
+    // 権限がない(管理者向けのAPIなど)
+    Forbidden,
+    // 二段階認証のコードが一致しない
+    InvalidTwoFactorCode,
+    // 二段階認証のチャレンジが無効(期限切れ・試行回数の超過を含む)
+    InvalidChallenge,
+    // 二段階認証のコードの失敗が多すぎる(チャレンジをまたいで数える)
+    TwoFactorLocked,
+    // 二段階認証が有効になっている
+    TwoFactorAlreadyEnabled,
+    // 二段階認証の設定が開始されていない
+    TwoFactorNotSetUp,
+    // 設定されていないOIDCのプロバイダー
+    UnknownProvider,
+    // OIDCのサインインの状態(state)が無効(期限切れ・使用済みを含む)
+    InvalidLoginState,
+    // IdPがサインインを拒否した(コールバックのerror)
+    LoginRejected(String),
+    // IdPの呼び出し・レスポンスのエラー
+    IdentityProviderError(String),
+    // IDトークンの検証エラー
+    InvalidIdToken(String),
+    // IdPがメールアドレスを確認していない
+    EmailNotVerified,
+    // IdPのアカウントに対応するユーザーがいない
+    NoLinkedAccount,
+    // APIキーの発行のリクエストが不正(名前・権限・有効日数)
+    InvalidApiKeyRequest(String),
+    // 発行できるAPIキーの数の上限に達している
+    ApiKeyLimitReached,
+    // APIキーが見つからない
+    ApiKeyNotFound,
+    // APIキーに必要な権限がない
+    InsufficientScope,
+    // APIキーでは実行できない操作(APIキー・二段階認証の管理など)
+    ApiKeyNotAllowed,
+    // Cookieのセッションのリクエストで、CSRFトークンがないまたは一致しない
+    CsrfTokenMismatch,
+    // セッションが見つからない
+    SessionNotFound,
+}
+
+// Supabaseエラーを認証エラーに変換
+impl From<SupabaseError> for AuthError {
+    fn from(error: SupabaseError) -> Self {
+        match error {
+            // サーキットブレーカーによる遮断
+            SupabaseError::Unavailable(msg) => AuthError::ServiceUnavailable(msg),
+            // PostgRESTのエラーはメッセージとヒントを含める
+            SupabaseError::Api { body, .. } => AuthError::DatabaseError(body.describe()),
+            // 通信・デコードエラー
+            error => AuthError::DatabaseError(error.to_string()),
+        }
+    }
+}
+
+// ユーザーエラーを認証エラーに変換(リポジトリ経由でユーザーを取得した場合)
+impl From<UserError> for AuthError {
+    fn from(error: UserError) -> Self {
+        match error {
+            UserError::UserNotFound => AuthError::UserNotFound,
+            UserError::ServiceUnavailable(msg) => AuthError::ServiceUnavailable(msg),
+            UserError::DatabaseError(msg) => AuthError::DatabaseError(msg),
+            error => AuthError::DatabaseError(error.to_string()),
+        }
+    }
+}
+
+// パスワードのハッシュ化のエラーを認証エラーに変換
+impl From<PasswordHashError> for AuthError {
+    fn from(error: PasswordHashError) -> Self {
+        AuthError::DatabaseError(error.to_string())
+    }
 }
 
 // エラーをHTTPステータスコードとメッセージに変換
@@ -30,6 +111,48 @@ impl From<AuthError> for (StatusCode, String) {
             AuthError::DatabaseError(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)),
             // ユーザーが見つかりません
             AuthError::UserNotFound => (StatusCode::NOT_FOUND, "User not found".to_string()),
+            // データベースが一時的に利用できない
+            AuthError::ServiceUnavailable(e) => (StatusCode::SERVICE_UNAVAILABLE, e),
+            // 権限がない
+            AuthError::Forbidden => (StatusCode::FORBIDDEN, "Admin privileges required".to_string()),
+            // 二段階認証のコードが一致しない
+            AuthError::InvalidTwoFactorCode => (StatusCode::UNAUTHORIZED, "Invalid two-factor code".to_string()),
+            // 二段階認証のチャレンジが無効
+            AuthError::InvalidChallenge => (StatusCode::UNAUTHORIZED, "Invalid or expired challenge".to_string()),
+            // 二段階認証のコードの失敗が多すぎる
+            AuthError::TwoFactorLocked => (StatusCode::TOO_MANY_REQUESTS, "Too many failed two-factor attempts, try again later".to_string()),
+            // 二段階認証が有効になっている
+            AuthError::TwoFactorAlreadyEnabled => (StatusCode::CONFLICT, "Two-factor authentication is already enabled".to_string()),
+            // 二段階認証の設定が開始されていない
+            AuthError::TwoFactorNotSetUp => (StatusCode::BAD_REQUEST, "Two-factor setup has not been started".to_string()),
+            // 設定されていないOIDCのプロバイダー
+            AuthError::UnknownProvider => (StatusCode::NOT_FOUND, "Unknown identity provider".to_string()),
+            // OIDCのサインインの状態が無効
+            AuthError::InvalidLoginState => (StatusCode::BAD_REQUEST, "Invalid or expired login state".to_string()),
+            // IdPがサインインを拒否した
+            AuthError::LoginRejected(e) => (StatusCode::UNAUTHORIZED, format!("Sign-in rejected by identity provider: {}", e)),
+            // IdPの呼び出し・レスポンスのエラー
+            AuthError::IdentityProviderError(e) => (StatusCode::BAD_GATEWAY, format!("Identity provider error: {}", e)),
+            // IDトークンの検証エラー
+            AuthError::InvalidIdToken(e) => (StatusCode::UNAUTHORIZED, format!("Invalid ID token: {}", e)),
+            // IdPがメールアドレスを確認していない
+            AuthError::EmailNotVerified => (StatusCode::FORBIDDEN, "Email address is not verified by the identity provider".to_string()),
+            // IdPのアカウントに対応するユーザーがいない
+            AuthError::NoLinkedAccount => (StatusCode::FORBIDDEN, "No account matches this identity".to_string()),
+            // APIキーの発行のリクエストが不正
+            AuthError::InvalidApiKeyRequest(e) => (StatusCode::BAD_REQUEST, format!("Invalid API key request: {}", e)),
+            // 発行できるAPIキーの数の上限に達している
+            AuthError::ApiKeyLimitReached => (StatusCode::CONFLICT, "API key limit reached".to_string()),
+            // APIキーが見つからない
+            AuthError::ApiKeyNotFound => (StatusCode::NOT_FOUND, "API key not found".to_string()),
+            // APIキーに必要な権限がない
+            AuthError::InsufficientScope => (StatusCode::FORBIDDEN, "API key does not have the required scope".to_string()),
+            // APIキーでは実行できない操作
+            AuthError::ApiKeyNotAllowed => (StatusCode::FORBIDDEN, "This operation cannot be performed with an API key".to_string()),
+            // CSRFトークンがないまたは一致しない
+            AuthError::CsrfTokenMismatch => (StatusCode::FORBIDDEN, "CSRF token is missing or does not match".to_string()),
+            // セッションが見つからない
+            AuthError::SessionNotFound => (StatusCode::NOT_FOUND, "Session not found".to_string()),
         }
     }
 }
\ No newline at end of file
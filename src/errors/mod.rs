@@ -2,5 +2,11 @@
 pub mod users;
 // 認証のエラーモジュールのインポート
 pub mod auth;
+// Supabaseのエラーモジュールのインポート
+pub mod supabase;
+// 汎用CRUDリソースのエラーモジュールのインポート
+pub mod resources;
+// パスワードのハッシュ化のエラーモジュールのインポート
+pub mod password;
 
 // エラーのエントリーポイント
\ No newline at end of file
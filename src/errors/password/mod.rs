@@ -0,0 +1,4 @@
+// パスワードのハッシュ化のエラーモジュールの宣言
+pub mod password_error;
+
+// パスワードのハッシュ化のエラーのエントリーポイント
@@ -0,0 +1,19 @@
+// 必要なクレートのインポート
+use thiserror::Error;
+
+// パスワードのハッシュ化のエラーの列挙型
+#[derive(Error, Debug)]
+pub enum PasswordHashError {
+    // ハッシュ化に失敗
+    #[error("Password hashing failed: {0}")]
+    Hash(String),
+    // 照合に失敗(ハッシュの形式が不正など)
+    #[error("Password verification failed: {0}")]
+    Verify(String),
+    // どのアルゴリズムでも扱えないハッシュ
+    #[error("Unsupported password hash format")]
+    UnsupportedFormat,
+    // 不正なパラメータ(コストなど)
+    #[error("Invalid password hash parameters: {0}")]
+    InvalidParams(String),
+}
@@ -0,0 +1,4 @@
+// リソースのエラーモジュールの宣言
+pub mod resource_error;
+
+// リソースエラーのエントリーポイント
@@ -0,0 +1,78 @@
+// 必要なクレートのインポート
+use axum::http::StatusCode;
+use thiserror::Error;
+// Supabaseエラーのインポート
+use crate::errors::supabase::supabase_error::SupabaseError;
+
+// 汎用CRUDリソースのエラーの列挙型
+#[derive(Error, Debug)]
+pub enum ResourceError {
+    // データベースエラー
+    #[error("データベースエラー: {0}")]
+    DatabaseError(String),
+    // リソースが見つかりません
+    #[error("リソースが見つかりません")]
+    NotFound,
+    // 不正なデータ形式
+    #[error("不正なデータ形式: {0}")]
+    InvalidData(String),
+    // JSONエラー
+    #[error("JSONエラー: {0}")]
+    JsonError(String),
+    // データベースが一時的に利用できない
+    #[error("サービス利用不可: {0}")]
+    ServiceUnavailable(String),
+    // 一意制約などの競合
+    #[error("競合: {0}")]
+    Conflict(String),
+}
+
+// Supabaseエラーをリソースエラーに変換
+impl From<SupabaseError> for ResourceError {
+    fn from(error: SupabaseError) -> Self {
+        match error {
+            SupabaseError::Api { status, body } => match body.code.as_deref() {
+                // 一意制約違反
+                Some("23505") => ResourceError::Conflict(body.describe()),
+                // 該当する行がない(単一行の取得)
+                Some("PGRST116") => ResourceError::NotFound,
+                // データ例外・整合性制約違反
+                Some(code) if code.starts_with("22") || code.starts_with("23") => {
+                    ResourceError::InvalidData(body.describe())
+                }
+                // 見つからない
+                _ if status.as_u16() == 404 => ResourceError::NotFound,
+                // リクエストの不備
+                _ if status.as_u16() == 400 => ResourceError::InvalidData(body.describe()),
+                // その他
+                _ => ResourceError::DatabaseError(body.describe()),
+            },
+            // サーキットブレーカーによる遮断
+            SupabaseError::Unavailable(msg) => ResourceError::ServiceUnavailable(msg),
+            // 通信エラー
+            SupabaseError::Transport(msg) => ResourceError::DatabaseError(msg),
+            // デコードエラー
+            SupabaseError::Decode(msg) => ResourceError::JsonError(msg),
+        }
+    }
+}
+
+// エラーをHTTPステータスコードとメッセージに変換
+impl From<ResourceError> for (StatusCode, String) {
+    fn from(err: ResourceError) -> Self {
+        match err {
+            // データベースエラー
+            ResourceError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            // リソースが見つかりません
+            ResourceError::NotFound => (StatusCode::NOT_FOUND, "リソースが見つかりません".to_string()),
+            // 不正なデータ形式
+            ResourceError::InvalidData(msg) => (StatusCode::BAD_REQUEST, msg),
+            // JSONエラー
+            ResourceError::JsonError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            // データベースが一時的に利用できない
+            ResourceError::ServiceUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
+            // 一意制約などの競合
+            ResourceError::Conflict(msg) => (StatusCode::CONFLICT, msg),
+        }
+    }
+}
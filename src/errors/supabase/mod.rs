@@ -0,0 +1,4 @@
+// Supabaseのエラーモジュールの宣言
+pub mod supabase_error;
+
+// Supabaseエラーのエントリーポイント
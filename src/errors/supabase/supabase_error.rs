@@ -0,0 +1,90 @@
+// 必要なクレートのインポート
+use reqwest::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+// 外部HTTP呼び出しのエラーのインポート
+use crate::outbound::outbound_policy::OutboundError;
+
+// PostgRESTのエラーレスポンスのボディ
+#[derive(Debug, Default, Deserialize, Clone)]
+pub struct PostgrestErrorBody {
+    // エラーコード(PostgreSQLのSQLSTATEまたはPGRSTxxx)
+    pub code: Option<String>,
+    // エラーメッセージ
+    #[serde(default)]
+    pub message: String,
+    // 詳細
+    pub details: Option<String>,
+    // ヒント
+    pub hint: Option<String>,
+}
+
+// PostgrestErrorBodyの実装
+impl PostgrestErrorBody {
+    // メッセージにヒントを付けた説明文を作成する関数
+    pub fn describe(&self) -> String {
+        match &self.hint {
+            Some(hint) if !hint.is_empty() => format!("{} (hint: {})", self.message, hint),
+            _ => self.message.clone(),
+        }
+    }
+}
+
+// Supabase(PostgREST)の呼び出しエラーの列挙型
+#[derive(Error, Debug)]
+pub enum SupabaseError {
+    // PostgRESTがエラーレスポンスを返した
+    #[error("PostgRESTエラー({status}): {}", .body.describe())]
+    Api {
+        // ステータスコード
+        status: StatusCode,
+        // エラーボディ
+        body: PostgrestErrorBody,
+    },
+    // 通信エラー
+    #[error("通信エラー: {0}")]
+    Transport(String),
+    // サーキットブレーカーによる遮断
+    #[error("サービス利用不可: {0}")]
+    Unavailable(String),
+    // レスポンスのデコードエラー
+    #[error("デコードエラー: {0}")]
+    Decode(String),
+}
+
+// SupabaseErrorの実装
+impl SupabaseError {
+    // エラーレスポンスのステータスコードとボディからエラーを作成する関数
+    pub fn from_response(status: StatusCode, body: &str) -> Self {
+        // PostgRESTのエラーボディとして解析し、できなければ本文をメッセージにする
+        let body = serde_json::from_str::<PostgrestErrorBody>(body).unwrap_or_else(|_| PostgrestErrorBody {
+            message: if body.is_empty() {
+                status.canonical_reason().unwrap_or_default().to_string()
+            } else {
+                body.to_string()
+            },
+            ..PostgrestErrorBody::default()
+        });
+        SupabaseError::Api { status, body }
+    }
+
+    // PostgRESTのエラーコードを取得する関数
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            SupabaseError::Api { body, .. } => body.code.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+// 外部HTTP呼び出しのエラーをSupabaseエラーに変換
+impl From<OutboundError> for SupabaseError {
+    fn from(error: OutboundError) -> Self {
+        match error {
+            // サーキットブレーカーによる遮断
+            OutboundError::CircuitOpen => SupabaseError::Unavailable(error.to_string()),
+            // 通信エラー
+            OutboundError::Transport(e) => SupabaseError::Transport(e.to_string()),
+        }
+    }
+}
@@ -1,6 +1,10 @@
 // 必要なクレートのインポート
 use axum::http::StatusCode;
 use thiserror::Error;
+// Supabaseエラーのインポート
+use crate::errors::supabase::supabase_error::SupabaseError;
+// パスワードのハッシュ化のエラーのインポート
+use crate::errors::password::password_error::PasswordHashError;
 
 // ユーザーエラーの列挙型
 #[derive(Error, Debug)]
@@ -19,6 +23,52 @@ pub enum UserError {
     // JSONエラー
     #[error("JSONエラー: {0}")]
     JsonError(String),
+    // データベースが一時的に利用できない
+    #[error("サービス利用不可: {0}")]
+    ServiceUnavailable(String),
+    // 一意制約などの競合
+    #[error("競合: {0}")]
+    Conflict(String),
+    // 確認のパスワードが一致しない
+    #[error("パスワードが一致しません")]
+    PasswordMismatch,
+}
+
+// Supabaseエラーをユーザーエラーに変換
+impl From<SupabaseError> for UserError {
+    fn from(error: SupabaseError) -> Self {
+        match error {
+            SupabaseError::Api { status, body } => match body.code.as_deref() {
+                // 一意制約違反
+                Some("23505") => UserError::Conflict(body.describe()),
+                // 該当する行がない(単一行の取得)
+                Some("PGRST116") => UserError::UserNotFound,
+                // データ例外・整合性制約違反
+                Some(code) if code.starts_with("22") || code.starts_with("23") => {
+                    UserError::InvalidData(body.describe())
+                }
+                // 見つからない
+                _ if status.as_u16() == 404 => UserError::UserNotFound,
+                // リクエストの不備
+                _ if status.as_u16() == 400 => UserError::InvalidData(body.describe()),
+                // その他
+                _ => UserError::DatabaseError(body.describe()),
+            },
+            // サーキットブレーカーによる遮断
+            SupabaseError::Unavailable(msg) => UserError::ServiceUnavailable(msg),
+            // 通信エラー
+            SupabaseError::Transport(msg) => UserError::DatabaseError(msg),
+            // デコードエラー
+            SupabaseError::Decode(msg) => UserError::JsonError(msg),
+        }
+    }
+}
+
+// パスワードのハッシュ化のエラーをユーザーエラーに変換
+impl From<PasswordHashError> for UserError {
+    fn from(error: PasswordHashError) -> Self {
+        UserError::PasswordError(error.to_string())
+    }
 }
 
 // エラーをHTTPステータスコードとメッセージに変換
@@ -35,6 +85,12 @@ impl From<UserError> for (StatusCode, String) {
             UserError::PasswordError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
             // JSONエラー
             UserError::JsonError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            // データベースが一時的に利用できない
+            UserError::ServiceUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
+            // 一意制約などの競合
+            UserError::Conflict(msg) => (StatusCode::CONFLICT, msg),
+            // 確認のパスワードが一致しない
+            UserError::PasswordMismatch => (StatusCode::FORBIDDEN, "Password confirmation failed".to_string()),
         }
     }
 }
\ No newline at end of file
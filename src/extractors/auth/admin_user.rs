@@ -0,0 +1,36 @@
+// 必要なクレートのインポート
+use async_trait::async_trait;
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+};
+use std::sync::Arc;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// 認証エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+// APIキーの権限のインポート
+use crate::models::auth::api_key::ApiKeyScope;
+// 認証済みユーザーのエクストラクターのインポート
+use crate::extractors::auth::authenticated_user::AuthenticatedUser;
+
+// 管理者(ADMIN_USER_IDSのユーザー)として認証された呼び出し元
+//
+// トークンが無効な場合は401、管理者でない場合(adminの権限のないAPIキーを含む)は403を返してハンドラーを呼ばない
+#[derive(Debug, Clone)]
+pub struct AdminUser(pub AuthenticatedUser);
+
+// リクエストから管理者を取り出す
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for AdminUser {
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let caller = AuthenticatedUser::from_headers(state, &parts.headers).await?;
+        if !state.admin_config.is_admin(caller.id) {
+            return Err(AuthError::Forbidden.into());
+        }
+        caller.require_scope(ApiKeyScope::Admin)?;
+        Ok(Self(caller))
+    }
+}
@@ -0,0 +1,159 @@
+// 必要なクレートのインポート
+use async_trait::async_trait;
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderMap, Method, StatusCode},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// 認証モデルのインポート
+use crate::models::auth::auth::Claims;
+use crate::models::auth::api_key::ApiKeyScope;
+// 認証エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+// コンテナのインポート
+use crate::di::container::Container;
+// APIキーの判定のインポート
+use crate::di::services::api_key_service::is_api_key;
+// セッションのCookieのインポート
+use crate::services::auth::session_cookie::request_token;
+
+// Authorizationヘッダーのトークン(またはAPIキー)、セッションのCookieのトークンで認証された呼び出し元
+//
+// ハンドラーの引数に指定すると、トークンが無効な場合(パスワードの変更・セッションの削除で無効にされた場合を含む)は
+// 401を返してハンドラーを呼ばない。APIキーの場合は、メソッドに必要な権限がなければ403を返す
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    // ユーザーID(Claims::sub)
+    pub id: Uuid,
+    // トークンのクレーム(APIキーの場合は有効期限・作成日時から作成)
+    pub claims: Claims,
+    // Supabase Auth(GoTrue)が発行したトークンの場合はそのトークン(PostgRESTに転送する)
+    pub supabase_token: Option<String>,
+    // APIキーで認証した場合はキーのIDと権限
+    pub api_key: Option<ApiKeyGrant>,
+}
+
+// APIキーで認証した呼び出し元のキーの情報
+#[derive(Debug, Clone)]
+pub struct ApiKeyGrant {
+    // APIキーのID
+    pub id: Uuid,
+    // 権限
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+// AuthenticatedUserの実装
+impl AuthenticatedUser {
+    // ヘッダーのBearerトークン(またはAPIキー)を検証する関数
+    //
+    // Authorizationヘッダーがない場合は、Cookieのセッションが有効であればセッションのCookieのトークンを検証する
+    pub async fn from_headers(state: &AppState, headers: &HeaderMap) -> Result<Self, AuthError> {
+        // Authorizationヘッダー(またはセッションのCookie)からトークンを取得
+        let token = request_token(&state.session_cookie_config, headers).ok_or(AuthError::InvalidToken)?;
+
+        // APIキー(接頭辞で判定し、JWTとしては検証しない)
+        if is_api_key(token) {
+            return Self::from_api_key(state, token).await;
+        }
+
+        // トークンの検証(このサーバーのトークンでない場合は、有効であればSupabase Authのトークンとして検証する)
+        let (claims, supabase_token) = match state.jwt_keys.verify(&state.jwt_secret, token) {
+            Ok(claims) => (claims, None),
+            Err(_) if state.supabase_auth_config.enabled => {
+                let claims = state.supabase_jwt_verifier.verify(&state.supabase_auth_config, token).await?;
+                (claims, Some(token.to_string()))
+            }
+            Err(_) => return Err(AuthError::InvalidToken),
+        };
+
+        // トークンのユーザーIDを取得
+        let id = Uuid::parse_str(&claims.sub).map_err(|_| AuthError::InvalidToken)?;
+
+        // 無効にされたトークンを拒否
+        if state.token_revocation_store.is_revoked(id, claims.iat).await {
+            return Err(AuthError::InvalidToken);
+        }
+
+        // 削除・期限切れのセッションのトークンを拒否(サインインで発行したセッションのIDを含むトークンのみ)
+        if let Some(session_id) = claims.sid {
+            state
+                .container
+                .session_service()
+                .authenticate(session_id, id, &state.session_config)
+                .await?;
+        }
+
+        Ok(Self { id, claims, supabase_token, api_key: None })
+    }
+
+    // APIキーを検証する関数
+    //
+    // APIキーは削除・期限切れで無効になる(パスワードを変更するとユーザーの全てのキーを削除する)
+    async fn from_api_key(state: &AppState, key: &str) -> Result<Self, AuthError> {
+        let record = state.container.api_key_service().authenticate(key, &state.api_key_config).await?;
+        let claims = Claims {
+            sub: record.user_id.to_string(),
+            exp: record.expires_at.and_utc().timestamp(),
+            iat: record.created_at.and_utc().timestamp(),
+            iss: None,
+            aud: None,
+            sid: None,
+        };
+
+        Ok(Self {
+            id: record.user_id,
+            claims,
+            supabase_token: None,
+            api_key: Some(ApiKeyGrant { id: record.id, scopes: record.scopes }),
+        })
+    }
+
+    // 権限を確認する関数(トークンの場合は常に許可し、APIキーの場合はキーの権限に含まれる場合のみ許可)
+    pub fn require_scope(&self, scope: ApiKeyScope) -> Result<(), AuthError> {
+        match &self.api_key {
+            Some(grant) if !grant.scopes.contains(&scope) => Err(AuthError::InsufficientScope),
+            _ => Ok(()),
+        }
+    }
+
+    // APIキーでの呼び出しを拒否する関数(APIキー・二段階認証の管理など、サインインしたユーザーのみの操作)
+    pub fn reject_api_key(&self) -> Result<(), AuthError> {
+        match self.api_key {
+            Some(_) => Err(AuthError::ApiKeyNotAllowed),
+            None => Ok(()),
+        }
+    }
+
+    // 呼び出し元の権限でSupabaseを呼び出すコンテナを取得する関数
+    //
+    // Supabase Authのトークンの場合はトークンを転送し、RLSのポリシー(auth.uid())を適用する
+    pub fn container(&self, state: &AppState) -> Arc<Container> {
+        match &self.supabase_token {
+            Some(token) => Arc::new(state.container.with_auth_token(token.clone())),
+            None => state.container.clone(),
+        }
+    }
+}
+
+// リクエストから認証済みユーザーを取り出す
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for AuthenticatedUser {
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let caller = Self::from_headers(state, &parts.headers).await?;
+        caller.require_scope(scope_for(&parts.method))?;
+        Ok(caller)
+    }
+}
+
+// メソッドに必要なAPIキーの権限を返す関数(参照はread、それ以外はwrite)
+pub fn scope_for(method: &Method) -> ApiKeyScope {
+    match *method {
+        Method::GET | Method::HEAD | Method::OPTIONS => ApiKeyScope::Read,
+        _ => ApiKeyScope::Write,
+    }
+}
@@ -0,0 +1,32 @@
+// 必要なクレートのインポート
+use async_trait::async_trait;
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header, request::Parts},
+};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+// 端末の情報のインポート
+use crate::models::auth::session::DeviceInfo;
+
+// リクエストからサインインした端末の情報を取り出す(取得できない項目はNone)
+//
+// IPアドレスはレート制限と同じく接続元のアドレスを使う
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for DeviceInfo {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let user_agent = parts
+            .headers
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let ip_address = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|info| info.0.ip().to_string());
+
+        Ok(Self { user_agent, ip_address })
+    }
+}
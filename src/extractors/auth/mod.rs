@@ -0,0 +1,6 @@
+// 認証済みユーザー・管理者・端末の情報のエクストラクターのモジュールの宣言
+pub mod admin_user;
+pub mod authenticated_user;
+pub mod device_info;
+
+// 認証のエクストラクターのエントリーポイント
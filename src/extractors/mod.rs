@@ -0,0 +1,4 @@
+// 認証のエクストラクターモジュールのインポート
+pub mod auth;
+
+// エクストラクターのエントリーポイント
@@ -1,10 +1,18 @@
 // モジュールの宣言と公開
+pub mod cache;
+pub mod config;
 pub mod di;
 pub mod errors;
+pub mod extractors;
+pub mod metrics;
+pub mod middleware;
 pub mod models;
+pub mod outbound;
+pub mod password;
 pub mod routes;
 pub mod services;
 pub mod state;
+pub mod supabase;
 // 必要なクレートのインポート
 use axum::Router;
 use std::sync::Arc;
@@ -1,10 +1,3 @@
-// モジュールのインポート
-mod di;
-mod errors;
-mod models;
-mod routes;
-mod services;
-mod state;
 // 必要なクレートのインポート
 use axum::serve;
 use dotenv::dotenv;
@@ -12,15 +5,22 @@ use std::{env, sync::Arc};
 use std::net::SocketAddr;
 use tokio::net::TcpListener;
 // アプリケーションの状態のインポート
-use crate::state::AppState;
+use backend::state::AppState;
 // ルーティングのインポート
-use crate::routes::create_routes;
+use backend::routes::create_routes;
 
 // メイン関数
 #[tokio::main]
 async fn main() {
     // 環境変数を読み込む
     dotenv().ok();
+    // ログの初期化(RUST_LOGで出力レベルを変更可能)
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
 
     // SupabaseのURLを環境変数から取得
     let supabase_url = env::var("SUPABASE_URL").expect("SUPABASE_URL must be set");
@@ -34,9 +34,12 @@ async fn main() {
     // ソケットアドレスを作成
     let addr = format!("127.0.0.1:{}", port).parse::<SocketAddr>().unwrap();
 
-    println!("Server running at http://127.0.0.1:{}", port);
+    tracing::info!("Server running at http://127.0.0.1:{}", port);
 
     // ソケットアドレスをバインドして起動
     let listener = TcpListener::bind(addr).await.unwrap();
-    serve(listener, app).await.unwrap();
+    // レート制限で接続元のIPアドレスを参照できるようにする
+    serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
 }
\ No newline at end of file
@@ -0,0 +1,168 @@
+// 必要なクレートのインポート
+use std::sync::OnceLock;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+// アプリケーション全体で共有するメトリクス
+static METRICS: OnceLock<AppMetrics> = OnceLock::new();
+
+// アプリケーションのメトリクスを管理する構造体
+pub struct AppMetrics {
+    // メトリクスのレジストリ
+    registry: Registry,
+    // ルートごとのリクエスト数
+    pub http_requests_total: IntCounterVec,
+    // ルートごとのレイテンシ
+    pub http_request_duration_seconds: HistogramVec,
+    // 処理中のリクエスト数
+    pub http_requests_in_flight: IntGaugeVec,
+    // Supabase(PostgREST)への呼び出し数
+    pub supabase_requests_total: IntCounterVec,
+    // Supabase(PostgREST)への呼び出しのレイテンシ
+    pub supabase_request_duration_seconds: HistogramVec,
+    // Supabase(PostgREST)への呼び出しのエラー数
+    pub supabase_request_errors_total: IntCounterVec,
+    // Supabase(PostgREST)への呼び出しの再試行回数
+    pub supabase_request_retries_total: IntCounterVec,
+    // サーキットブレーカーの状態(0: Closed, 1: Open, 2: HalfOpen)
+    pub supabase_circuit_breaker_state: IntGauge,
+    // サインインの結果ごとの回数
+    pub sign_in_total: IntCounterVec,
+    // キャッシュの参照結果(ヒット・ミス)ごとの回数
+    pub cache_requests_total: IntCounterVec,
+}
+
+// AppMetricsの実装
+impl AppMetrics {
+    // AppMetricsの新しいインスタンスを作成する関数
+    pub fn new() -> Self {
+        // レジストリを作成
+        let registry = Registry::new();
+
+        // ルートごとのリクエスト数
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "Total number of HTTP requests"),
+            &["method", "route", "status"],
+        )
+        .expect("metric can be created");
+        // ルートごとのレイテンシ
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request latency in seconds"),
+            &["method", "route"],
+        )
+        .expect("metric can be created");
+        // 処理中のリクエスト数
+        let http_requests_in_flight = IntGaugeVec::new(
+            Opts::new("http_requests_in_flight", "Number of HTTP requests currently being served"),
+            &["method", "route"],
+        )
+        .expect("metric can be created");
+        // Supabaseへの呼び出し数
+        let supabase_requests_total = IntCounterVec::new(
+            Opts::new("supabase_requests_total", "Total number of outbound PostgREST requests"),
+            &["operation", "status"],
+        )
+        .expect("metric can be created");
+        // Supabaseへの呼び出しのレイテンシ
+        let supabase_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "supabase_request_duration_seconds",
+                "Outbound PostgREST request latency in seconds",
+            ),
+            &["operation"],
+        )
+        .expect("metric can be created");
+        // Supabaseへの呼び出しのエラー数
+        let supabase_request_errors_total = IntCounterVec::new(
+            Opts::new("supabase_request_errors_total", "Total number of failed outbound PostgREST requests"),
+            &["operation", "kind"],
+        )
+        .expect("metric can be created");
+        // Supabaseへの呼び出しの再試行回数
+        let supabase_request_retries_total = IntCounterVec::new(
+            Opts::new("supabase_request_retries_total", "Total number of retried outbound PostgREST requests"),
+            &["operation"],
+        )
+        .expect("metric can be created");
+        // サーキットブレーカーの状態
+        let supabase_circuit_breaker_state = IntGauge::new(
+            "supabase_circuit_breaker_state",
+            "State of the PostgREST circuit breaker (0: closed, 1: open, 2: half-open)",
+        )
+        .expect("metric can be created");
+        // サインインの結果ごとの回数
+        let sign_in_total = IntCounterVec::new(
+            Opts::new("sign_in_total", "Total number of sign-in attempts"),
+            &["result"],
+        )
+        .expect("metric can be created");
+        // キャッシュの参照結果ごとの回数
+        let cache_requests_total = IntCounterVec::new(
+            Opts::new("cache_requests_total", "Total number of cache lookups"),
+            &["cache", "result"],
+        )
+        .expect("metric can be created");
+
+        // レジストリにメトリクスを登録
+        registry.register(Box::new(http_requests_total.clone())).expect("metric can be registered");
+        registry.register(Box::new(http_request_duration_seconds.clone())).expect("metric can be registered");
+        registry.register(Box::new(http_requests_in_flight.clone())).expect("metric can be registered");
+        registry.register(Box::new(supabase_requests_total.clone())).expect("metric can be registered");
+        registry.register(Box::new(supabase_request_duration_seconds.clone())).expect("metric can be registered");
+        registry.register(Box::new(supabase_request_errors_total.clone())).expect("metric can be registered");
+        registry.register(Box::new(supabase_request_retries_total.clone())).expect("metric can be registered");
+        registry.register(Box::new(supabase_circuit_breaker_state.clone())).expect("metric can be registered");
+        registry.register(Box::new(sign_in_total.clone())).expect("metric can be registered");
+        registry.register(Box::new(cache_requests_total.clone())).expect("metric can be registered");
+
+        Self {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            http_requests_in_flight,
+            supabase_requests_total,
+            supabase_request_duration_seconds,
+            supabase_request_errors_total,
+            supabase_request_retries_total,
+            supabase_circuit_breaker_state,
+            sign_in_total,
+            cache_requests_total,
+        }
+    }
+
+    // サインインの結果を記録する関数
+    pub fn record_sign_in(&self, success: bool) {
+        let result = if success { "success" } else { "failure" };
+        self.sign_in_total.with_label_values(&[result]).inc();
+    }
+
+    // キャッシュの参照結果を記録する関数
+    pub fn record_cache_lookup(&self, cache: &str, hit: bool) {
+        let result = if hit { "hit" } else { "miss" };
+        self.cache_requests_total.with_label_values(&[cache, result]).inc();
+    }
+
+    // Prometheusのテキスト形式でメトリクスを出力する関数
+    pub fn render(&self) -> Result<String, String> {
+        // メトリクスを収集してエンコード
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|e| e.to_string())?;
+
+        String::from_utf8(buffer).map_err(|e| e.to_string())
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for AppMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// アプリケーション全体で共有するメトリクスを取得する関数
+pub fn metrics() -> &'static AppMetrics {
+    METRICS.get_or_init(AppMetrics::new)
+}
@@ -0,0 +1,4 @@
+// メトリクスのモジュールの宣言
+pub mod app_metrics;
+
+// メトリクスのエントリーポイント
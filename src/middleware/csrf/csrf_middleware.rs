@@ -0,0 +1,40 @@
+// 必要なクレートのインポート
+use std::sync::Arc;
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// 認証エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+// セッションのCookieのインポート
+use crate::services::auth::session_cookie::{bearer_token, session_token, verify_csrf};
+
+// Cookieのセッションで認証される状態を変更するリクエストのCSRFトークン(ダブルサブミット)を検証するミドルウェア
+//
+// セッションのCookieがあり、Authorizationヘッダーがない GET・HEAD・OPTIONS 以外のリクエストは、
+// X-CSRF-TokenヘッダーがCSRFトークンのCookieと一致しない場合に403を返す
+pub async fn enforce_csrf(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let config = &state.session_cookie_config;
+    let headers = request.headers();
+
+    // 参照のリクエストと、ブラウザーが自動で送らない資格情報(Authorizationヘッダー)のリクエストは検証しない
+    let safe = matches!(*request.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    if safe || bearer_token(headers).is_some() || session_token(config, headers).is_none() {
+        return next.run(request).await;
+    }
+
+    if !verify_csrf(config, headers) {
+        tracing::warn!(method = %request.method(), path = %request.uri().path(), "CSRF token mismatch");
+        return <(StatusCode, String)>::from(AuthError::CsrfTokenMismatch).into_response();
+    }
+
+    next.run(request).await
+}
@@ -0,0 +1,4 @@
+// CSRFミドルウェアのモジュールの宣言
+pub mod csrf_middleware;
+
+// CSRFミドルウェアのエントリーポイント
@@ -0,0 +1,233 @@
+// 必要なクレートのインポート
+use std::fmt::Write;
+use std::sync::Arc;
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{Request, State},
+    http::{header, Extensions, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// クライアントの識別のインポート
+use crate::middleware::rate_limit::rate_limit_middleware::client_key;
+// 認証済みユーザーのインポート
+use crate::extractors::auth::authenticated_user::AuthenticatedUser;
+// バージョンの接頭辞を除いたパスのインポート
+use crate::middleware::versioning::api_version::ApiVersion;
+// セッションのCookieのインポート
+use crate::services::auth::session_cookie::request_token;
+// 冪等キーストアのインポート
+use crate::middleware::idempotency::idempotency_store::{
+    IdempotencyOutcome, IdempotencyStore, StoredResponse,
+};
+
+// 冪等キーのヘッダー名
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+// 保存したレスポンスを返したことを示すヘッダー名
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+// 受け付ける冪等キーの最大長
+const MAX_KEY_LENGTH: usize = 255;
+// 冪等キーを扱わないパスの接頭辞(サインインなど、トークンを返す認証のルート)
+const EXCLUDED_PATH_PREFIX: &str = "/auth/";
+
+// POSTリクエストのIdempotency-Keyヘッダーを処理するミドルウェア
+//
+// 同じキーと呼び出し元の最初のレスポンスを保存し、再試行には保存したレスポンスを返す
+pub async fn enforce_idempotency(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // POST以外とキーのないリクエストはそのまま処理
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    // 認証のルートはトークンを返すため、保存・再送しない
+    if ApiVersion::unversioned_path(request.uri().path()).starts_with(EXCLUDED_PATH_PREFIX) {
+        return next.run(request).await;
+    }
+
+    // キーの形式を確認
+    let key = match key.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_string(),
+        _ => {
+            return (StatusCode::BAD_REQUEST, "Invalid Idempotency-Key header".to_string()).into_response();
+        }
+    };
+
+    // 認証した呼び出し元ごとにキーを分ける(認証できない場合は保存・再送せずにそのまま処理)
+    let Some(caller) = caller_scope(&state, request.headers(), request.extensions()).await else {
+        return next.run(request).await;
+    };
+    let store_key = format!("{}|{}", caller, key);
+
+    // リクエストボディを読み込み、リクエストの内容のハッシュを計算
+    let (parts, body) = request.into_parts();
+    let bytes = match to_bytes(body, state.middleware_config.max_body_bytes).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return (StatusCode::PAYLOAD_TOO_LARGE, "Request body too large".to_string()).into_response();
+        }
+    };
+    let fingerprint = fingerprint(&parts.method, parts.uri.path(), &bytes);
+
+    // キーの状態を確認
+    let config = &state.idempotency_config;
+    let store = state.idempotency_store.clone();
+    match store.begin(&store_key, &fingerprint, config.ttl).await {
+        IdempotencyOutcome::Started => {}
+        IdempotencyOutcome::InProgress => {
+            return (
+                StatusCode::CONFLICT,
+                "A request with this Idempotency-Key is already in progress".to_string(),
+            )
+                .into_response();
+        }
+        IdempotencyOutcome::Mismatch => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Idempotency-Key was already used with a different request".to_string(),
+            )
+                .into_response();
+        }
+        IdempotencyOutcome::Replay(stored) => {
+            tracing::info!(idempotency_key = %key, "replaying stored response");
+            return replay(stored);
+        }
+    }
+
+    // 処理が完了しなかった場合(切断・タイムアウトなど)はキーを解放する
+    let mut guard = InProgressGuard {
+        store: store.clone(),
+        key: Some(store_key.clone()),
+    };
+
+    // リクエストを処理
+    let response = next.run(Request::from_parts(parts, Body::from(bytes))).await;
+
+    // サーバーエラーは保存せず、同じキーで再試行できるようにする
+    if response.status().is_server_error() {
+        guard.release().await;
+        return response;
+    }
+
+    // サイズが分からない、または大きすぎるレスポンスは保存しない
+    // Cookieを設定するレスポンス(サインインなど)も、再送でセッションのCookieを再発行しないよう保存しない
+    let cacheable = !response.headers().contains_key(header::SET_COOKIE)
+        && response
+            .body()
+            .size_hint()
+            .exact()
+            .is_some_and(|size| size <= config.max_response_bytes as u64);
+    if !cacheable {
+        guard.release().await;
+        return response;
+    }
+
+    // レスポンスを保存
+    let (parts, body) = response.into_parts();
+    let Ok(body) = to_bytes(body, config.max_response_bytes).await else {
+        return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read response".to_string()).into_response();
+    };
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        headers: parts
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str().to_string(), value.as_bytes().to_vec()))
+            .collect(),
+        body: body.clone(),
+    };
+    store.complete(&store_key, stored, config.ttl).await;
+    guard.disarm();
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+// 保存したレスポンスを共有する呼び出し元を決める関数
+//
+// トークン(またはAPIキー)があれば検証したユーザー(APIキーはキー)ごとに分け、
+// 検証できない場合はNone(別の呼び出し元のレスポンスを返さないよう、保存・再送しない)。
+// トークンがなければ接続元のIPアドレスで分ける
+async fn caller_scope(state: &AppState, headers: &HeaderMap, extensions: &Extensions) -> Option<String> {
+    if request_token(&state.session_cookie_config, headers).is_none() {
+        return Some(client_key(state, headers, extensions));
+    }
+
+    let user = AuthenticatedUser::from_headers(state, headers).await.ok()?;
+    Some(match user.api_key {
+        Some(grant) => format!("key:{}", grant.id),
+        None => format!("user:{}", user.id),
+    })
+}
+
+// リクエストの内容(メソッド・パス・ボディ)のハッシュを計算する関数
+fn fingerprint(method: &Method, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b" ");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+
+    hasher.finalize().iter().fold(String::with_capacity(64), |mut hex, byte| {
+        let _ = write!(hex, "{:02x}", byte);
+        hex
+    })
+}
+
+// 保存したレスポンスを再構築する関数
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::from_bytes(&value)) {
+            response.headers_mut().append(name, value);
+        }
+    }
+    response
+        .headers_mut()
+        .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+// 処理中のキーを、完了しなかった場合に解放するガード
+struct InProgressGuard {
+    // ストア
+    store: Arc<dyn IdempotencyStore>,
+    // 解放するキー(完了した場合はNone)
+    key: Option<String>,
+}
+
+// InProgressGuardの実装
+impl InProgressGuard {
+    // 完了したのでキーを解放しない
+    fn disarm(&mut self) {
+        self.key = None;
+    }
+
+    // キーをすぐに解放する
+    async fn release(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.store.release(&key).await;
+        }
+    }
+}
+
+// Dropトレイトの実装
+impl Drop for InProgressGuard {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let store = self.store.clone();
+            tokio::spawn(async move {
+                store.release(&key).await;
+            });
+        }
+    }
+}
@@ -0,0 +1,130 @@
+// 必要なクレートのインポート
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use async_trait::async_trait;
+use axum::body::Bytes;
+use tokio::time::Instant;
+
+// 期限切れのエントリを掃除するしきい値
+const MAX_ENTRIES_BEFORE_PRUNE: usize = 10_000;
+
+// 保存したレスポンス
+#[derive(Clone, Debug)]
+pub struct StoredResponse {
+    // ステータスコード
+    pub status: u16,
+    // レスポンスヘッダー
+    pub headers: Vec<(String, Vec<u8>)>,
+    // レスポンスボディ
+    pub body: Bytes,
+}
+
+// 冪等キーの処理を開始した結果
+#[derive(Clone, Debug)]
+pub enum IdempotencyOutcome {
+    // 初めてのキーなので処理を実行する
+    Started,
+    // 同じキーのリクエストが処理中
+    InProgress,
+    // 同じキーが別のリクエストボディで使われた
+    Mismatch,
+    // 最初のレスポンスを返す
+    Replay(StoredResponse),
+}
+
+// 冪等キーの状態を保存するストアのトレイト
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    // キーの処理を開始する(フィンガープリントはリクエストの内容のハッシュ)
+    async fn begin(&self, key: &str, fingerprint: &str, ttl: Duration) -> IdempotencyOutcome;
+    // 処理の結果を保存する
+    async fn complete(&self, key: &str, response: StoredResponse, ttl: Duration);
+    // 処理を取り消し、同じキーで再試行できるようにする
+    async fn release(&self, key: &str);
+}
+
+// エントリの状態
+enum EntryState {
+    // 処理中
+    InProgress,
+    // 処理済み
+    Completed(StoredResponse),
+}
+
+// エントリ
+struct Entry {
+    // リクエストの内容のハッシュ
+    fingerprint: String,
+    // 状態
+    state: EntryState,
+    // 有効期限
+    expires_at: Instant,
+}
+
+// メモリ上にエントリを保持するストア
+#[derive(Default)]
+pub struct InMemoryIdempotencyStore {
+    // キーごとのエントリ
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+// InMemoryIdempotencyStoreの実装
+impl InMemoryIdempotencyStore {
+    // コンストラクタ
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// トレイト実装
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn begin(&self, key: &str, fingerprint: &str, ttl: Duration) -> IdempotencyOutcome {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        // エントリが多くなりすぎた場合は期限切れのものを削除
+        if entries.len() >= MAX_ENTRIES_BEFORE_PRUNE {
+            entries.retain(|_, entry| entry.expires_at > now);
+        }
+
+        // 有効なエントリがあれば状態に応じて返す
+        if let Some(entry) = entries.get(key).filter(|entry| entry.expires_at > now) {
+            if entry.fingerprint != fingerprint {
+                return IdempotencyOutcome::Mismatch;
+            }
+            return match &entry.state {
+                EntryState::InProgress => IdempotencyOutcome::InProgress,
+                EntryState::Completed(response) => IdempotencyOutcome::Replay(response.clone()),
+            };
+        }
+
+        // 新しいエントリを処理中として登録
+        entries.insert(key.to_string(), Entry {
+            fingerprint: fingerprint.to_string(),
+            state: EntryState::InProgress,
+            expires_at: now + ttl,
+        });
+        IdempotencyOutcome::Started
+    }
+
+    async fn complete(&self, key: &str, response: StoredResponse, ttl: Duration) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        // 処理中のエントリを処理済みにし、有効期限を延長
+        if let Some(entry) = entries.get_mut(key) {
+            entry.state = EntryState::Completed(response);
+            entry.expires_at = Instant::now() + ttl;
+        }
+    }
+
+    async fn release(&self, key: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        // 処理中のエントリのみ削除
+        if matches!(entries.get(key).map(|entry| &entry.state), Some(EntryState::InProgress)) {
+            entries.remove(key);
+        }
+    }
+}
@@ -0,0 +1,8 @@
+// 冪等キーミドルウェアのモジュールの宣言
+pub mod idempotency_middleware;
+pub mod idempotency_store;
+
+#[cfg(test)]
+mod tests;
+
+// 冪等キーミドルウェアのエントリーポイント
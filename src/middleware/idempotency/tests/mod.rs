@@ -0,0 +1 @@
+pub mod idempotency_store_01_test;
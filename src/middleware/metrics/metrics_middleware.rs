@@ -0,0 +1,57 @@
+// 必要なクレートのインポート
+use std::time::Instant;
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use prometheus::IntGauge;
+// メトリクスのインポート
+use crate::metrics::app_metrics::metrics;
+
+// 処理中のリクエスト数を減らすためのガード
+struct InFlightGuard(IntGauge);
+
+// Dropトレイトの実装
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        // リクエストが中断された場合も必ず減らす
+        self.0.dec();
+    }
+}
+
+// ルートごとのリクエスト数・レイテンシ・処理中のリクエスト数を記録するミドルウェア
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    // マッチしたルートのパターンを取得(パスパラメータでラベルが増えないようにする)
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    // HTTPメソッドを取得
+    let method = request.method().to_string();
+
+    // 処理中のリクエスト数を増やす
+    let gauge = metrics()
+        .http_requests_in_flight
+        .with_label_values(&[&method, &route]);
+    gauge.inc();
+    let _guard = InFlightGuard(gauge);
+
+    // 計測開始
+    let started = Instant::now();
+    // 次の処理を実行
+    let response = next.run(request).await;
+
+    // リクエスト数とレイテンシを記録
+    metrics()
+        .http_requests_total
+        .with_label_values(&[&method, &route, response.status().as_str()])
+        .inc();
+    metrics()
+        .http_request_duration_seconds
+        .with_label_values(&[&method, &route])
+        .observe(started.elapsed().as_secs_f64());
+
+    response
+}
@@ -0,0 +1,4 @@
+// メトリクスミドルウェアのモジュールの宣言
+pub mod metrics_middleware;
+
+// メトリクスミドルウェアのエントリーポイント
@@ -0,0 +1,11 @@
+// ミドルウェアのモジュールの宣言
+pub mod csrf;
+pub mod idempotency;
+pub mod metrics;
+pub mod rate_limit;
+pub mod request_id;
+pub mod stack;
+pub mod timeout;
+pub mod versioning;
+
+// ミドルウェアのエントリーポイント
@@ -0,0 +1,8 @@
+// レート制限ミドルウェアのモジュールの宣言
+pub mod rate_limit_middleware;
+pub mod rate_limit_store;
+
+#[cfg(test)]
+mod tests;
+
+// レート制限ミドルウェアのエントリーポイント
@@ -0,0 +1,105 @@
+// 必要なクレートのインポート
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::{Extensions, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// レート制限ストアのインポート
+use crate::middleware::rate_limit::rate_limit_store::RateLimitDecision;
+// APIキーの判定のインポート
+use crate::di::services::api_key_service::is_api_key;
+// セッションのCookieのインポート
+use crate::services::auth::session_cookie::request_token;
+
+// ルートごとのポリシーに従ってクライアント単位のレート制限を行うミドルウェア
+pub async fn enforce_rate_limit(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // マッチしたルートのパターンからポリシーを決定
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    let Some((policy_key, policy)) = state
+        .rate_limit_config
+        .policy_for(request.method().as_str(), &route)
+    else {
+        // ポリシーがないルートは制限しない
+        return next.run(request).await;
+    };
+
+    // クライアントを識別するキーでバケットからトークンを取得
+    let client = client_key(&state, request.headers(), request.extensions());
+    let decision = state
+        .rate_limit_store
+        .acquire(&format!("{}|{}", policy_key, client), &policy)
+        .await;
+
+    // 制限を超えた場合は429を返す
+    let mut response = if decision.allowed {
+        next.run(request).await
+    } else {
+        tracing::warn!(route = %route, "rate limit exceeded");
+        (StatusCode::TOO_MANY_REQUESTS, "Too many requests".to_string()).into_response()
+    };
+
+    // レート制限のヘッダーを設定
+    set_rate_limit_headers(response.headers_mut(), &decision);
+
+    response
+}
+
+// クライアントを識別するキーを決定する関数(BearerのAPIキー → 認証済みユーザー → IPアドレスの順)
+//
+// 検証できた識別子のみ使い、存在しないキーや不正なトークンはIPアドレスで識別する
+// (毎回異なる値を送って新しいバケットを得ることで制限を回避できないようにする)。
+// 制限を超えたリクエストでDBを呼び出さないよう、ここではDBを使わずに検証できるものだけを使う
+pub fn client_key(state: &AppState, headers: &HeaderMap, extensions: &Extensions) -> String {
+    let bearer = request_token(&state.session_cookie_config, headers);
+
+    match bearer {
+        // BearerのAPIキーは、このインスタンスで最近検証に成功したキーのみキーのIDで識別
+        Some(key) if is_api_key(key) => {
+            if let Some(id) = state.container.api_key_service().recently_verified(key) {
+                return format!("key:{}", id);
+            }
+        }
+        // 有効なBearerトークン(またはセッションのCookieのトークン)があればユーザーIDで識別
+        Some(token) => {
+            if let Ok(claims) = state.jwt_keys.verify(&state.jwt_secret, token) {
+                return format!("user:{}", claims.sub);
+            }
+        }
+        None => {}
+    }
+
+    // それ以外は接続元のIPアドレスで識別(検証していないヘッダーの値は識別に使わない)
+    extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| format!("ip:{}", info.0.ip()))
+        .unwrap_or_else(|| "ip:unknown".to_string())
+}
+
+// RateLimit-*ヘッダーとRetry-Afterヘッダーを設定する関数
+fn set_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert("ratelimit-limit", HeaderValue::from(decision.limit));
+    headers.insert("ratelimit-remaining", HeaderValue::from(decision.remaining));
+    headers.insert("ratelimit-reset", HeaderValue::from(ceil_secs(decision.reset_after)));
+    if let Some(retry_after) = decision.retry_after {
+        headers.insert("retry-after", HeaderValue::from(ceil_secs(retry_after)));
+    }
+}
+
+// 秒数を切り上げる関数
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs_f64().ceil() as u64
+}
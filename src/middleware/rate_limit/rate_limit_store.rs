@@ -0,0 +1,104 @@
+// 必要なクレートのインポート
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use async_trait::async_trait;
+use tokio::time::Instant;
+// 設定のインポート
+use crate::config::rate_limit_config::RateLimitPolicy;
+
+// 古いバケットを掃除するしきい値
+const MAX_BUCKETS_BEFORE_PRUNE: usize = 10_000;
+
+// レート制限の判定結果
+#[derive(Clone, Debug)]
+pub struct RateLimitDecision {
+    // リクエストを許可するか
+    pub allowed: bool,
+    // ウィンドウごとに許可するリクエスト数
+    pub limit: u32,
+    // 残りのリクエスト数
+    pub remaining: u32,
+    // バケットが満杯に戻るまでの時間
+    pub reset_after: Duration,
+    // 次のリクエストが許可されるまでの時間(拒否した場合のみ)
+    pub retry_after: Option<Duration>,
+}
+
+// レート制限の状態を保存するストアのトレイト
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    // キーに対応するバケットからトークンを1つ取得する
+    async fn acquire(&self, key: &str, policy: &RateLimitPolicy) -> RateLimitDecision;
+}
+
+// トークンバケット
+struct TokenBucket {
+    // 残りのトークン数
+    tokens: f64,
+    // 最後に更新した時刻
+    updated_at: Instant,
+}
+
+// メモリ上にバケットを保持するストア
+#[derive(Default)]
+pub struct InMemoryRateLimitStore {
+    // キーごとのバケット
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+// InMemoryRateLimitStoreの実装
+impl InMemoryRateLimitStore {
+    // コンストラクタ
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// トレイト実装
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn acquire(&self, key: &str, policy: &RateLimitPolicy) -> RateLimitDecision {
+        // 現在時刻と回復速度を取得
+        let now = Instant::now();
+        let capacity = policy.capacity as f64;
+        let refill_per_sec = policy.refill_per_sec();
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        // バケットが多くなりすぎた場合は満杯に戻っているものを削除
+        if buckets.len() >= MAX_BUCKETS_BEFORE_PRUNE {
+            buckets.retain(|_, bucket| {
+                now.duration_since(bucket.updated_at) < policy.window
+            });
+        }
+
+        // バケットを取得し、経過時間に応じてトークンを回復
+        let bucket = buckets.entry(key.to_string()).or_insert(TokenBucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.updated_at = now;
+
+        // トークンがあれば消費して許可
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        // 満杯に戻るまでの時間と、次に許可されるまでの時間を計算
+        let reset_after = Duration::from_secs_f64((capacity - bucket.tokens) / refill_per_sec);
+        let retry_after = (!allowed)
+            .then(|| Duration::from_secs_f64((1.0 - bucket.tokens) / refill_per_sec));
+
+        RateLimitDecision {
+            allowed,
+            limit: policy.capacity,
+            remaining: bucket.tokens.floor() as u32,
+            reset_after,
+            retry_after,
+        }
+    }
+}
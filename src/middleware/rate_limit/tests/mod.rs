@@ -0,0 +1 @@
+pub mod rate_limit_store_01_test;
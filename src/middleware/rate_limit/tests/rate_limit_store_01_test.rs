@@ -0,0 +1,62 @@
+use std::time::Duration;
+// 設定のインポート
+use crate::config::rate_limit_config::RateLimitPolicy;
+// レート制限ストアのインポート
+use crate::middleware::rate_limit::rate_limit_store::{InMemoryRateLimitStore, RateLimitStore};
+
+// 容量を使い切ると拒否されることのテスト
+#[tokio::test(start_paused = true)]
+async fn test_acquire_until_exhausted() {
+    // 10秒で2回までのポリシー
+    let store = InMemoryRateLimitStore::new();
+    let policy = RateLimitPolicy::new(2, Duration::from_secs(10));
+
+    // 容量分は許可される
+    let first = store.acquire("client", &policy).await;
+    assert!(first.allowed);
+    assert_eq!(first.limit, 2);
+    assert_eq!(first.remaining, 1);
+
+    let second = store.acquire("client", &policy).await;
+    assert!(second.allowed);
+    assert_eq!(second.remaining, 0);
+
+    // 容量を超えると拒否され、再試行までの時間が返される
+    let third = store.acquire("client", &policy).await;
+    assert!(!third.allowed);
+    assert_eq!(third.retry_after, Some(Duration::from_secs(5)));
+    assert_eq!(third.reset_after, Duration::from_secs(10));
+
+    // 別のクライアントは影響を受けない
+    let other = store.acquire("other", &policy).await;
+    assert!(other.allowed);
+}
+
+// 時間の経過でトークンが回復することのテスト
+#[tokio::test(start_paused = true)]
+async fn test_tokens_refill_over_time() {
+    // 10秒で2回までのポリシー
+    let store = InMemoryRateLimitStore::new();
+    let policy = RateLimitPolicy::new(2, Duration::from_secs(10));
+
+    // 容量を使い切る
+    store.acquire("client", &policy).await;
+    store.acquire("client", &policy).await;
+    assert!(!store.acquire("client", &policy).await.allowed);
+
+    // 5秒経過するとトークンが1つ回復する
+    tokio::time::advance(Duration::from_secs(5)).await;
+    assert!(store.acquire("client", &policy).await.allowed);
+    assert!(!store.acquire("client", &policy).await.allowed);
+}
+
+// ポリシーの文字列解析のテスト
+#[test]
+fn test_parse_policy() {
+    assert_eq!(
+        RateLimitPolicy::parse("5/60"),
+        Some(RateLimitPolicy::new(5, Duration::from_secs(60)))
+    );
+    assert_eq!(RateLimitPolicy::parse("0/60"), None);
+    assert_eq!(RateLimitPolicy::parse("invalid"), None);
+}
@@ -0,0 +1,4 @@
+// リクエストIDミドルウェアのモジュールの宣言
+pub mod request_id_middleware;
+
+// リクエストIDミドルウェアのエントリーポイント
@@ -0,0 +1,149 @@
+// 必要なクレートのインポート
+use axum::{
+    body::{Body, Bytes},
+    extract::Request,
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use futures_util::{stream, StreamExt};
+use tracing::Instrument;
+use uuid::Uuid;
+// 共通モデルのインポート
+use crate::models::ErrorResponse;
+
+// リクエストIDのヘッダー名
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+// 受け付けるリクエストIDの最大長
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+// エラーボディを書き換える際に読み込む最大サイズ
+const MAX_ERROR_BODY_SIZE: usize = 64 * 1024;
+
+// リクエストごとのIDをタスクローカルに保持
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+// 処理中のリクエストのIDを取得する関数
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+// クライアントから受け取ったリクエストIDが使用可能か確認する関数
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LENGTH
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+// リクエストIDを受け取るか生成し、ログ・レスポンスヘッダーに含めるミドルウェア
+//
+// エラーボディへの追加はattach_request_id_to_errorsで行う(圧縮より内側に置く)
+pub async fn propagate_request_id(request: Request, next: Next) -> Response {
+    // ヘッダーからリクエストIDを取得し、なければ生成
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    // リクエストIDを含むスパンを作成
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        uri = %request.uri(),
+    );
+
+    // タスクローカルにリクエストIDを設定して次の処理を実行
+    let response = REQUEST_ID
+        .scope(request_id.clone(), async move {
+            let response = next.run(request).await;
+            tracing::info!(status = response.status().as_u16(), "request completed");
+            response
+        }.instrument(span))
+        .await;
+
+    // レスポンスヘッダーにリクエストIDを設定
+    let mut response = response;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
+
+// エラーレスポンスのボディに処理中のリクエストIDを含めるミドルウェア
+//
+// propagate_request_idより内側、圧縮より内側に置く。圧縮済みのボディは書き換えない
+pub async fn attach_request_id_to_errors(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+
+    let is_error = response.status().is_client_error() || response.status().is_server_error();
+    match current_request_id() {
+        Some(request_id) if is_error && !response.headers().contains_key(header::CONTENT_ENCODING) => {
+            attach_request_id_to_error(response, &request_id).await
+        }
+        _ => response,
+    }
+}
+
+// エラーボディを上限まで読み込む関数
+//
+// 上限を超えた・読み込みに失敗した場合は、読み込んだ部分と残りをつないだ元のボディを返す
+async fn read_error_body(body: Body) -> Result<Bytes, Body> {
+    let mut data = body.into_data_stream();
+    let mut chunks: Vec<Bytes> = Vec::new();
+    let mut size = 0;
+    while let Some(chunk) = data.next().await {
+        match chunk {
+            Ok(bytes) if size + bytes.len() <= MAX_ERROR_BODY_SIZE => {
+                size += bytes.len();
+                chunks.push(bytes);
+            }
+            item => {
+                let read = stream::iter(chunks.into_iter().map(Ok)).chain(stream::once(async { item }));
+                return Err(Body::from_stream(read.chain(data)));
+            }
+        }
+    }
+    Ok(chunks.concat().into())
+}
+
+// エラーレスポンスのボディをリクエストID付きのJSONに変換する関数(上限を超えるボディはそのまま返す)
+async fn attach_request_id_to_error(response: Response, request_id: &str) -> Response {
+    // レスポンスを分解してボディを読み込む
+    let (mut parts, body) = response.into_parts();
+    let bytes = match read_error_body(body).await {
+        Ok(bytes) => bytes,
+        Err(body) => return Response::from_parts(parts, body),
+    };
+
+    // 既にJSONオブジェクトの場合はリクエストIDを追加し、それ以外はメッセージとして包む
+    let body = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(serde_json::Value::Object(mut object)) => {
+            object.insert("request_id".to_string(), serde_json::Value::String(request_id.to_string()));
+            serde_json::Value::Object(object)
+        }
+        _ => {
+            // 空のボディはステータスの説明文をメッセージにする
+            let message = if bytes.is_empty() {
+                parts.status.canonical_reason().unwrap_or_default().to_string()
+            } else {
+                String::from_utf8_lossy(&bytes).into_owned()
+            };
+            serde_json::to_value(ErrorResponse {
+                message,
+                request_id: request_id.to_string(),
+            })
+            .unwrap_or_default()
+        }
+    };
+
+    // JSONとしてレスポンスを再構築
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    Response::from_parts(parts, Body::from(body.to_string()))
+}
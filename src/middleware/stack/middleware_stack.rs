@@ -0,0 +1,106 @@
+// 必要なクレートのインポート
+use std::sync::Arc;
+use axum::{
+    extract::DefaultBodyLimit,
+    http::{header, HeaderValue, Method},
+    middleware,
+    Router,
+};
+use tower_http::{
+    compression::CompressionLayer,
+    cors::{AllowOrigin, CorsLayer},
+    limit::RequestBodyLimitLayer,
+};
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// 冪等キーのヘッダー名のインポート
+use crate::middleware::idempotency::idempotency_middleware::IDEMPOTENCY_KEY_HEADER;
+// 設定のインポート
+use crate::config::middleware_config::MiddlewareConfig;
+// CSRFトークンのヘッダー名のインポート
+use crate::services::auth::session_cookie::CSRF_HEADER;
+// ミドルウェアのインポート
+use crate::middleware::csrf::csrf_middleware::enforce_csrf;
+use crate::middleware::idempotency::idempotency_middleware::{enforce_idempotency, IDEMPOTENT_REPLAYED_HEADER};
+use crate::middleware::metrics::metrics_middleware::track_metrics;
+use crate::middleware::rate_limit::rate_limit_middleware::enforce_rate_limit;
+use crate::middleware::request_id::request_id_middleware::{
+    attach_request_id_to_errors, propagate_request_id, REQUEST_ID_HEADER,
+};
+use crate::middleware::timeout::timeout_middleware::enforce_timeout;
+use crate::middleware::versioning::versioning_middleware::{DEPRECATION_HEADER, SUNSET_HEADER};
+
+// 全ルートに共通のミドルウェアを適用する関数
+//
+// 外側から順に リクエストID → CORS → 圧縮 → エラーボディへのリクエストID → ボディサイズ制限 → メトリクス → レート制限 → CSRF → 冪等キー → タイムアウト の順で処理される
+pub fn apply_middleware_stack(router: Router, state: Arc<AppState>) -> Router {
+    // ミドルウェアの設定を取得
+    let config = &state.middleware_config;
+
+    // ルートごとのタイムアウト・冪等キー・CSRF・レート制限とメトリクス
+    let router = router
+        .layer(middleware::from_fn_with_state(Arc::new(config.clone()), enforce_timeout))
+        .layer(middleware::from_fn_with_state(state.clone(), enforce_idempotency))
+        .layer(middleware::from_fn_with_state(state.clone(), enforce_csrf))
+        .layer(middleware::from_fn_with_state(state.clone(), enforce_rate_limit))
+        .layer(middleware::from_fn(track_metrics));
+
+    // ボディサイズ制限(Jsonエクストラクタの既定の制限は無効にして設定値に統一)
+    let router = router
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(config.max_body_bytes));
+
+    // エラーボディにリクエストIDを含める(圧縮前のボディを書き換えるため圧縮より内側)
+    let router = router.layer(middleware::from_fn(attach_request_id_to_errors));
+
+    // レスポンスの圧縮
+    let router = if config.compression_enabled {
+        router.layer(CompressionLayer::new())
+    } else {
+        router
+    };
+
+    // CORS(許可するオリジンが設定されている場合のみ)
+    let router = match cors_layer(config) {
+        Some(cors) => router.layer(cors),
+        None => router,
+    };
+
+    // リクエストIDを付与し、ログとレスポンスヘッダーに含める
+    router.layer(middleware::from_fn(propagate_request_id))
+}
+
+// 設定からCORSレイヤーを作成する関数
+fn cors_layer(config: &MiddlewareConfig) -> Option<CorsLayer> {
+    // 許可するオリジンをヘッダー値に変換
+    let origins: Vec<HeaderValue> = config
+        .cors_allowed_origins
+        .iter()
+        .filter_map(|origin| HeaderValue::from_str(origin).ok())
+        .collect();
+
+    if origins.is_empty() {
+        return None;
+    }
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
+            .allow_headers([
+                header::AUTHORIZATION,
+                header::CONTENT_TYPE,
+                header::HeaderName::from_static(REQUEST_ID_HEADER),
+                header::HeaderName::from_static(IDEMPOTENCY_KEY_HEADER),
+                header::HeaderName::from_static(CSRF_HEADER),
+            ])
+            .expose_headers([
+                header::HeaderName::from_static(REQUEST_ID_HEADER),
+                header::HeaderName::from_static(IDEMPOTENT_REPLAYED_HEADER),
+                header::HeaderName::from_static(DEPRECATION_HEADER),
+                header::HeaderName::from_static(SUNSET_HEADER),
+                header::LINK,
+            ])
+            .allow_credentials(true),
+    )
+}
@@ -0,0 +1,4 @@
+// ミドルウェアスタックのモジュールの宣言
+pub mod middleware_stack;
+
+// ミドルウェアスタックのエントリーポイント
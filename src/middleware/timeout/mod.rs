@@ -0,0 +1,4 @@
+// タイムアウトミドルウェアのモジュールの宣言
+pub mod timeout_middleware;
+
+// タイムアウトミドルウェアのエントリーポイント
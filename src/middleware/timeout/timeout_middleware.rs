@@ -0,0 +1,34 @@
+// 必要なクレートのインポート
+use std::sync::Arc;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+// 設定のインポート
+use crate::config::middleware_config::MiddlewareConfig;
+
+// ルートごとのタイムアウトを適用し、超過した場合は504を返すミドルウェア
+pub async fn enforce_timeout(
+    State(config): State<Arc<MiddlewareConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // マッチしたルートのパターンとメソッドからタイムアウトを決定
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    let timeout = config.timeout_for(request.method().as_str(), &route);
+
+    // タイムアウト付きで次の処理を実行
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!(route = %route, timeout_ms = timeout.as_millis() as u64, "request timed out");
+            (StatusCode::GATEWAY_TIMEOUT, "Request timed out".to_string()).into_response()
+        }
+    }
+}
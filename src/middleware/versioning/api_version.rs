@@ -0,0 +1,99 @@
+// バージョンを指定するメディアタイプ(例: application/vnd.backend.v2+json)の接頭辞
+pub const VERSIONED_MEDIA_TYPE_PREFIX: &str = "application/vnd.backend.v";
+// バージョンを指定するメディアタイプの接尾辞
+pub const VERSIONED_MEDIA_TYPE_SUFFIX: &str = "+json";
+
+// APIのバージョン
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiVersion {
+    // 最初のバージョン(レスポンスにパスワードを含む、非推奨)
+    V1,
+    // パスワードを含まないレスポンスのバージョン
+    V2,
+}
+
+// ApiVersionの実装
+impl ApiVersion {
+    // 最新のバージョン
+    pub const LATEST: ApiVersion = ApiVersion::V2;
+
+    // バージョン番号から取得する関数
+    pub fn from_number(number: u32) -> Option<Self> {
+        match number {
+            1 => Some(ApiVersion::V1),
+            2 => Some(ApiVersion::V2),
+            _ => None,
+        }
+    }
+
+    // パスの接頭辞(例: /v2)
+    pub fn prefix(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/v1",
+            ApiVersion::V2 => "/v2",
+        }
+    }
+
+    // バージョンごとのOpenAPIドキュメントのパス
+    pub fn openapi_path(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/api-docs/v1/openapi.json",
+            ApiVersion::V2 => "/api-docs/v2/openapi.json",
+        }
+    }
+
+    // 非推奨のバージョンか確認する関数
+    pub fn is_deprecated(self) -> bool {
+        self != Self::LATEST
+    }
+
+    // パスの接頭辞からバージョンを取得する関数(/v1/usersならV1)
+    pub fn from_path(path: &str) -> Option<Self> {
+        let segment = path.strip_prefix('/')?.split('/').next()?;
+        segment.strip_prefix('v')?.parse().ok().and_then(Self::from_number)
+    }
+
+    // パスからバージョンの接頭辞を除く関数(/v2/usersなら/users、接頭辞がなければそのまま)
+    //
+    // ルートごとの設定(レート制限・タイムアウト)は接頭辞のないパスで指定し、全てのバージョンに適用する
+    pub fn unversioned_path(path: &str) -> &str {
+        let rest = Self::from_path(path).and_then(|version| path.strip_prefix(version.prefix()));
+        match rest {
+            Some("") => "/",
+            Some(rest) if rest.starts_with('/') => rest,
+            _ => path,
+        }
+    }
+}
+
+// Acceptヘッダーで要求されたバージョンを取得する関数
+//
+// バージョンを指定するメディアタイプがなければNone、未対応のバージョンはエラー
+pub fn requested_version(accept: &str) -> Result<Option<ApiVersion>, String> {
+    for media_type in accept.split(',') {
+        // パラメーター(;q=0.9など)を除いたメディアタイプ
+        let media_type = media_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        // バージョンを指定するメディアタイプのみ対象
+        let Some(number) = media_type
+            .strip_prefix(VERSIONED_MEDIA_TYPE_PREFIX)
+            .and_then(|rest| rest.strip_suffix(VERSIONED_MEDIA_TYPE_SUFFIX))
+        else {
+            continue;
+        };
+
+        return number
+            .parse()
+            .ok()
+            .and_then(ApiVersion::from_number)
+            .map(Some)
+            .ok_or_else(|| format!("Unsupported API version: {}", media_type));
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,8 @@
+// バージョンミドルウェアのモジュールの宣言
+pub mod api_version;
+pub mod versioning_middleware;
+
+#[cfg(test)]
+mod tests;
+
+// バージョンミドルウェアのエントリーポイント
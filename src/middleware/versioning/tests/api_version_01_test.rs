@@ -0,0 +1,53 @@
+// バージョンのインポート
+use crate::middleware::versioning::api_version::{requested_version, ApiVersion};
+
+// パスの接頭辞からバージョンを取得するテスト
+#[test]
+fn test_from_path() {
+    assert_eq!(ApiVersion::from_path("/v1/users"), Some(ApiVersion::V1));
+    assert_eq!(ApiVersion::from_path("/v2"), Some(ApiVersion::V2));
+    assert_eq!(ApiVersion::from_path("/v2/auth/check"), Some(ApiVersion::V2));
+
+    // 接頭辞がない・未対応のバージョン
+    assert_eq!(ApiVersion::from_path("/users"), None);
+    assert_eq!(ApiVersion::from_path("/v3/users"), None);
+    assert_eq!(ApiVersion::from_path("/vx/users"), None);
+}
+
+// パスからバージョンの接頭辞を除くテスト
+#[test]
+fn test_unversioned_path() {
+    assert_eq!(ApiVersion::unversioned_path("/v1/auth/signin"), "/auth/signin");
+    assert_eq!(ApiVersion::unversioned_path("/v2/users/{id}"), "/users/{id}");
+    assert_eq!(ApiVersion::unversioned_path("/v2"), "/");
+
+    // 接頭辞がない・未対応のバージョンはそのまま
+    assert_eq!(ApiVersion::unversioned_path("/auth/signin"), "/auth/signin");
+    assert_eq!(ApiVersion::unversioned_path("/v3/users"), "/v3/users");
+    assert_eq!(ApiVersion::unversioned_path("/v01/users"), "/v01/users");
+}
+
+// Acceptヘッダーで要求されたバージョンを取得するテスト
+#[test]
+fn test_requested_version() {
+    // バージョンの指定なし
+    assert_eq!(requested_version(""), Ok(None));
+    assert_eq!(requested_version("application/json, */*"), Ok(None));
+
+    // バージョンの指定あり(パラメーターや大文字を含む)
+    assert_eq!(requested_version("application/vnd.backend.v2+json"), Ok(Some(ApiVersion::V2)));
+    assert_eq!(
+        requested_version("application/json;q=0.5, Application/Vnd.Backend.V1+JSON;q=0.9"),
+        Ok(Some(ApiVersion::V1))
+    );
+
+    // 未対応のバージョン
+    assert!(requested_version("application/vnd.backend.v3+json").is_err());
+}
+
+// 最新のバージョンのみ非推奨でないことのテスト
+#[test]
+fn test_is_deprecated() {
+    assert!(ApiVersion::V1.is_deprecated());
+    assert!(!ApiVersion::LATEST.is_deprecated());
+}
@@ -0,0 +1 @@
+pub mod api_version_01_test;
@@ -0,0 +1,111 @@
+// 必要なクレートのインポート
+use std::sync::Arc;
+use axum::{
+    extract::{Request, State},
+    http::{header, uri::PathAndQuery, HeaderValue, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+// 設定のインポート
+use crate::config::versioning_config::VersioningConfig;
+// バージョンのインポート
+use crate::middleware::versioning::api_version::{requested_version, ApiVersion};
+
+// 非推奨を示すヘッダー名
+pub const DEPRECATION_HEADER: &str = "deprecation";
+// 提供終了日時を示すヘッダー名
+pub const SUNSET_HEADER: &str = "sunset";
+// バージョンの対象外のパス(運用・ドキュメント用と、IdPに登録するOIDCのコールバック・JWKSなどの既定の場所)
+const UNVERSIONED_PATHS: [&str; 5] = ["/metrics", "/api-docs", "/swagger-ui", "/auth/oidc", "/.well-known"];
+
+// バージョンの接頭辞がないAPIのパスか確認する関数(運用・ドキュメント用のパスを除く)
+pub fn is_unversioned_api_path(path: &str) -> bool {
+    ApiVersion::from_path(path).is_none()
+        && !UNVERSIONED_PATHS
+            .iter()
+            .any(|prefix| path == *prefix || path.starts_with(&format!("{}/", prefix)))
+}
+
+// パスにバージョンの接頭辞を付ける関数
+fn with_version_prefix(uri: &Uri, version: ApiVersion) -> Option<Uri> {
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{}{}?{}", version.prefix(), uri.path(), query),
+        None => format!("{}{}", version.prefix(), uri.path()),
+    };
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::try_from(path_and_query).ok()?);
+    Uri::from_parts(parts).ok()
+}
+
+// バージョンの接頭辞がないリクエストをAcceptヘッダーのバージョンに振り分けるミドルウェア
+//
+// ルーティングの前に適用する(接頭辞がなくバージョンの指定もない場合はv1として扱う)
+pub async fn negotiate_version(
+    State(config): State<Arc<VersioningConfig>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    // 無効な場合やバージョンの接頭辞がある場合はそのまま処理
+    if !config.negotiation_enabled || !is_unversioned_api_path(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    // Acceptヘッダーで要求されたバージョンを取得
+    let accept = request
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let version = match requested_version(accept) {
+        Ok(version) => version,
+        Err(message) => return (StatusCode::NOT_ACCEPTABLE, message).into_response(),
+    };
+
+    // v1以外が要求された場合はパスにバージョンの接頭辞を付ける
+    if let Some(version) = version.filter(|version| *version != ApiVersion::V1) {
+        if let Some(uri) = with_version_prefix(request.uri(), version) {
+            *request.uri_mut() = uri;
+        }
+    }
+
+    // Acceptヘッダーによってレスポンスが変わることを示す
+    let mut response = next.run(request).await;
+    response.headers_mut().append(header::VARY, HeaderValue::from_static("accept"));
+    response
+}
+
+// 非推奨のバージョンのレスポンスにDeprecation・Sunset・Linkヘッダーを付けるミドルウェア
+pub async fn deprecate_version(
+    State(config): State<Arc<VersioningConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+
+    // 非推奨にした日時(未設定の場合はtrue)
+    let deprecation = config
+        .v1_deprecated_at
+        .map(|at| format!("@{}", at.timestamp()))
+        .unwrap_or_else(|| "true".to_string());
+    if let Ok(value) = HeaderValue::from_str(&deprecation) {
+        headers.insert(DEPRECATION_HEADER, value);
+    }
+
+    // 提供終了日時(HTTP-date形式)
+    if let Some(sunset) = config.v1_sunset {
+        let value = sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(SUNSET_HEADER, value);
+        }
+    }
+
+    // 後継のバージョンのドキュメント
+    let link = format!("<{}>; rel=\"successor-version\"", ApiVersion::LATEST.openapi_path());
+    if let Ok(value) = HeaderValue::from_str(&link) {
+        headers.append(header::LINK, value);
+    }
+
+    response
+}
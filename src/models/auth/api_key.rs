@@ -0,0 +1,69 @@
+// 必要なクレートのインポート
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// APIキーの権限
+//
+// readは参照(GET)、writeは更新(GET以外)、adminは管理者向けのAPI(管理者のユーザーのキーのみ有効)に使える
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiKeyScope {
+    // 参照
+    Read,
+    // 更新
+    Write,
+    // 管理者向けのAPI
+    Admin,
+}
+
+// APIキーの発行のリクエスト(/me/api-keys)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct CreateApiKeyRequest {
+    // 名前(用途の識別用)
+    #[schema(example = "nightly-batch")]
+    pub name: String,
+    // 権限
+    #[schema(example = json!(["read"]))]
+    pub scopes: Vec<ApiKeyScope>,
+    // 有効日数(省略した場合は設定の既定値)
+    #[schema(example = 30)]
+    pub expires_in_days: Option<u32>,
+}
+
+// APIキー(キーそのものとハッシュは含まない)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct ApiKeyResponse {
+    // APIキーのID
+    #[schema(value_type = UuidWrapper)]
+    pub id: Uuid,
+    // 名前
+    #[schema(example = "nightly-batch")]
+    pub name: String,
+    // キーの先頭(識別用、キーはこの文字列から始まる)
+    #[schema(example = "bk_9fQ2xLmA")]
+    pub prefix: String,
+    // 権限
+    #[schema(example = json!(["read"]))]
+    pub scopes: Vec<ApiKeyScope>,
+    // 有効期限
+    #[schema(value_type = NaiveDateTimeWrapper)]
+    pub expires_at: NaiveDateTime,
+    // 最後に使われた日時(使われていない場合はnull)
+    #[schema(value_type = Option<NaiveDateTimeWrapper>)]
+    pub last_used_at: Option<NaiveDateTime>,
+    // 作成日時
+    #[schema(value_type = NaiveDateTimeWrapper)]
+    pub created_at: NaiveDateTime,
+}
+
+// APIキーの発行のレスポンス(キーはこのレスポンスでのみ返す)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct CreatedApiKeyResponse {
+    // APIキー(Authorization: Bearer <キー>で使う)
+    #[schema(example = "bk_9fQ2xLmA_Jx8c0VtR4yPq1sZkW7nBd3HfG6uLe2MoTa9Ci5Xr")]
+    pub key: String,
+    // 発行したAPIキー
+    pub api_key: ApiKeyResponse,
+}
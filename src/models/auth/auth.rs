@@ -4,7 +4,10 @@ use uuid::Uuid;
 use chrono::{Duration, Utc};
 use utoipa::ToSchema;
 // ユーザーモデルのインポート
-use crate::models::users::users::User;
+use crate::models::users::users::{User, UserResponse};
+
+// 発行するトークンの有効秒数(24時間、セッションのCookieの有効期間も同じ)
+pub const TOKEN_TTL_SECS: i64 = 24 * 60 * 60;
 
 // サインイン資格情報
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -18,7 +21,7 @@ pub struct SignInCredentials {
 }
 
 // JWTクレーム
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Claims {
     // ユーザーID
     #[schema(example = "123e4567-e89b-12d3-a456-426614174000")]
@@ -29,6 +32,18 @@ pub struct Claims {
     // 発行時間
     #[schema(example = "1727364545")]
     pub iat: i64,
+    // 発行者(JWT_ISSUERを設定した場合のみ)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "https://api.example.com")]
+    pub iss: Option<String>,
+    // 対象者(JWT_AUDIENCEを設定した場合のみ)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "backend")]
+    pub aud: Option<String>,
+    // セッションのID(サインインで発行したトークンのみ、/me/sessionsで削除すると無効になる)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<UuidWrapper>)]
+    pub sid: Option<Uuid>,
 }
 
 // 認証レスポンス
@@ -42,6 +57,25 @@ pub struct AuthResponse {
     pub user: User,
 }
 
+// 認証レスポンス(v2以降、ユーザーのパスワードを含まない)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthResponseV2 {
+    // JWTトークン
+    pub token: String,
+    // ユーザー
+    pub user: UserResponse,
+}
+
+// v1の認証レスポンスからの変換
+impl From<AuthResponse> for AuthResponseV2 {
+    fn from(response: AuthResponse) -> Self {
+        Self {
+            token: response.token,
+            user: response.user.into(),
+        }
+    }
+}
+
 // JWTクレームの生成
 impl Claims {
     // JWTクレームの新しいインスタンスを作成
@@ -51,8 +85,11 @@ impl Claims {
         // JWTクレームの新しいインスタンスを作成
         Self {
             sub: user_id.to_string(),
-            exp: (now + Duration::hours(24)).timestamp(),
+            exp: (now + Duration::seconds(TOKEN_TTL_SECS)).timestamp(),
             iat: now.timestamp(),
+            iss: None,
+            aud: None,
+            sid: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,19 @@
+// 必要なクレートのインポート
+use jsonwebtoken::jwk::Jwk;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// トークンの検証に使う公開鍵(JWKS、RFC 7517)
+#[derive(Serialize, Debug, ToSchema)]
+pub struct JwksResponse {
+    // 公開鍵(HS256で署名している場合は空)
+    #[schema(value_type = Vec<Object>, example = json!([{
+        "kty": "OKP",
+        "crv": "Ed25519",
+        "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo",
+        "kid": "2024-01",
+        "use": "sig",
+        "alg": "EdDSA"
+    }]))]
+    pub keys: Vec<Jwk>,
+}
@@ -1,4 +1,10 @@
-// 認証モデルのモジュールの宣言
+// 認証モデルのモジュールの宣言(既存のインポートパスを保つため、ディレクトリと同名のモジュールを許可)
+#[allow(clippy::module_inception)]
 pub mod auth;
+pub mod api_key;
+pub mod jwks;
+pub mod oidc;
+pub mod session;
+pub mod two_factor;
 
 // 認証モデルのエントリーポイント
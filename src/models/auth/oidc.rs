@@ -0,0 +1,19 @@
+// 必要なクレートのインポート
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+// OIDCのコールバックのクエリ(/auth/oidc/{provider}/callback)
+//
+// 成功した場合はcodeとstate、IdPがサインインを拒否した場合はerrorとstateが付く
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OidcCallbackParams {
+    // 認可コード
+    pub code: Option<String>,
+    // サインインの開始時に発行した値
+    pub state: Option<String>,
+    // IdPのエラーコード(access_deniedなど)
+    pub error: Option<String>,
+    // IdPのエラーの説明
+    pub error_description: Option<String>,
+}
@@ -0,0 +1,40 @@
+// 必要なクレートのインポート
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// サインインした端末の情報(セッションに記録する)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeviceInfo {
+    // User-Agentヘッダー
+    pub user_agent: Option<String>,
+    // 接続元のIPアドレス
+    pub ip_address: Option<String>,
+}
+
+// サインインのセッション(/me/sessions)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct SessionResponse {
+    // セッションのID
+    #[schema(value_type = UuidWrapper)]
+    pub id: Uuid,
+    // サインインした端末のUser-Agent
+    #[schema(example = "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_5) AppleWebKit/605.1.15")]
+    pub user_agent: Option<String>,
+    // サインインした端末のIPアドレス
+    #[schema(example = "203.0.113.10")]
+    pub ip_address: Option<String>,
+    // サインインした日時
+    #[schema(value_type = NaiveDateTimeWrapper)]
+    pub created_at: NaiveDateTime,
+    // 最後にアクセスした日時
+    #[schema(value_type = NaiveDateTimeWrapper)]
+    pub last_seen_at: NaiveDateTime,
+    // 有効期限(トークンの有効期限と同じ)
+    #[schema(value_type = NaiveDateTimeWrapper)]
+    pub expires_at: NaiveDateTime,
+    // この一覧を取得したトークンのセッションか
+    #[schema(example = true)]
+    pub current: bool,
+}
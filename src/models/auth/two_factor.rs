@@ -0,0 +1,54 @@
+// 必要なクレートのインポート
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// 二段階認証の設定の開始のレスポンス(/me/2fa/setup)
+//
+// 認証アプリに登録した後、表示されたコードで/me/2fa/confirmを呼ぶまで有効にならない
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct TwoFactorSetupResponse {
+    // 秘密鍵(Base32、手入力用)
+    #[schema(example = "JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP")]
+    pub secret: String,
+    // 認証アプリに登録するURI(QRコードにして表示する)
+    #[schema(example = "otpauth://totp/backend:john.doe%40example.com?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP&issuer=backend&algorithm=SHA1&digits=6&period=30")]
+    pub otpauth_uri: String,
+}
+
+// 二段階認証の有効化の確認(/me/2fa/confirm)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct TwoFactorConfirmRequest {
+    // 認証アプリに表示されたコード
+    #[schema(example = "123456")]
+    pub code: String,
+}
+
+// リカバリーコードのレスポンス(有効にしたときに1回だけ返す)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct RecoveryCodesResponse {
+    // リカバリーコード(それぞれ1回だけ使える)
+    #[schema(example = json!(["k3m9x-7pq2d", "a8v4n-2hc6e"]))]
+    pub recovery_codes: Vec<String>,
+}
+
+// 二段階認証のチャレンジ(二段階認証が有効なユーザーのサインイン、202)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct TwoFactorChallenge {
+    // /auth/2fa/verifyに渡すチャレンジトークン
+    #[schema(example = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08")]
+    pub challenge_token: String,
+    // チャレンジの有効秒数
+    #[schema(example = 300)]
+    pub expires_in: u64,
+}
+
+// 二段階認証のコードの検証(/auth/2fa/verify)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct TwoFactorVerifyRequest {
+    // サインインで返したチャレンジトークン
+    #[schema(example = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08")]
+    pub challenge_token: String,
+    // 認証アプリに表示されたコード、またはリカバリーコード
+    #[schema(example = "123456")]
+    pub code: String,
+}
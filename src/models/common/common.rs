@@ -1,5 +1,6 @@
 // 共通モデルのエントリーポイント
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use utoipa::ToSchema;
 
@@ -13,4 +14,15 @@ pub struct NaiveDateTimeWrapper(pub NaiveDateTime);
 #[derive(ToSchema)]
 #[schema(value_type = String, format = "uuid", example = "123e4567-e89b-12d3-a456-426614174000")]
 #[allow(dead_code)]
-pub struct UuidWrapper(pub Uuid);
\ No newline at end of file
+pub struct UuidWrapper(pub Uuid);
+
+// エラーレスポンス
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    // エラーメッセージ
+    #[schema(example = "ユーザーが見つかりません")]
+    pub message: String,
+    // リクエストID
+    #[schema(example = "0d9c6f4e-5b0a-4c1e-9a43-2f4f7c3b8e21")]
+    pub request_id: String,
+}
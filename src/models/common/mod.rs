@@ -1,2 +1,3 @@
-// 共通モデルのモジュールの宣言
+// 共通モデルのモジュールの宣言(既存のインポートパスを保つため、ディレクトリと同名のモジュールを許可)
+#[allow(clippy::module_inception)]
 pub mod common;
\ No newline at end of file
@@ -4,6 +4,6 @@ pub mod auth;
 pub mod common;
 
 // 共通の型をre-export
-pub use common::common::{NaiveDateTimeWrapper, UuidWrapper};
+pub use common::common::{ErrorResponse, NaiveDateTimeWrapper, UuidWrapper};
 
 // モデルのエントリーポイント
@@ -1,4 +1,5 @@
-// モジュールの宣言
+// モジュールの宣言(既存のインポートパスを保つため、ディレクトリと同名のモジュールを許可)
+#[allow(clippy::module_inception)]
 pub mod users;
 
 // ユーザーモデルのエントリーポイント
\ No newline at end of file
@@ -19,6 +19,9 @@ pub struct User {
     // パスワード
     #[schema(example = "password123")]
     pub password: String,
+    // 二段階認証が有効か(列がない行はfalse)
+    #[serde(default)]
+    pub two_factor_enabled: bool,
     // 作成日時
     #[schema(value_type = NaiveDateTimeWrapper)]
     pub created_at: NaiveDateTime,
@@ -40,3 +43,83 @@ pub struct NewUser {
     #[schema(example = "password123")]
     pub password: String,
 }
+
+// ユーザーの更新(PUT /users/{id}、ユーザー名・メールアドレスを置き換える)
+//
+// パスワードは現在のパスワードで確認する/me/passwordでのみ変更できる(passwordを指定すると422)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UserUpdate {
+    // ユーザー名
+    #[schema(example = "John Doe")]
+    pub username: String,
+    // メールアドレス
+    #[schema(example = "john.doe@example.com")]
+    pub email: String,
+}
+
+// ユーザーのレスポンス(v2以降、パスワードを含まない)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct UserResponse {
+    // ユーザーID
+    #[schema(value_type = UuidWrapper)]
+    pub id: Uuid,
+    // ユーザー名
+    #[schema(example = "John Doe")]
+    pub username: String,
+    // メールアドレス
+    #[schema(example = "john.doe@example.com")]
+    pub email: String,
+    // 作成日時
+    #[schema(value_type = NaiveDateTimeWrapper)]
+    pub created_at: NaiveDateTime,
+    // 更新日時
+    #[schema(value_type = NaiveDateTimeWrapper)]
+    pub updated_at: NaiveDateTime,
+}
+
+// ユーザーからレスポンスへの変換
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+// プロフィールの更新(/me、指定した項目のみ更新する)
+//
+// パスワードは変更できない(未知のフィールドは拒否する)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UserProfileUpdate {
+    // ユーザー名
+    #[schema(example = "John Doe")]
+    pub username: Option<String>,
+    // メールアドレス
+    #[schema(example = "john.doe@example.com")]
+    pub email: Option<String>,
+}
+
+// アカウントの削除(/me、確認のため現在のパスワードを指定する)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct DeleteAccountRequest {
+    // 現在のパスワード
+    #[schema(example = "password123")]
+    pub password: String,
+}
+
+// パスワードの変更(/me/password)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct ChangePasswordRequest {
+    // 現在のパスワード
+    #[schema(example = "password123")]
+    pub current_password: String,
+    // 新しいパスワード
+    #[schema(example = "new_password456")]
+    pub new_password: String,
+}
@@ -0,0 +1,159 @@
+// 必要なクレートのインポート
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+// サーキットブレーカーの状態
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CircuitState {
+    // 通常状態(呼び出しを許可)
+    Closed,
+    // 遮断状態(呼び出しを即座に失敗させる)
+    Open,
+    // 試行状態(1回だけ呼び出しを許可して回復を確認)
+    HalfOpen,
+}
+
+// サーキットブレーカーの内部状態
+struct BreakerState {
+    // 現在の状態
+    state: CircuitState,
+    // 連続失敗回数
+    consecutive_failures: u32,
+    // 遮断を開始した時刻
+    opened_at: Option<Instant>,
+    // 試行中の呼び出しがあるか
+    trial_in_flight: bool,
+}
+
+// 連続失敗回数に応じて呼び出しを遮断するサーキットブレーカー
+pub struct CircuitBreaker {
+    // 遮断するまでの連続失敗回数
+    failure_threshold: u32,
+    // 遮断している時間
+    open_duration: Duration,
+    // 内部状態
+    inner: Mutex<BreakerState>,
+}
+
+// CircuitBreakerの実装
+impl CircuitBreaker {
+    // コンストラクタ
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            inner: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                trial_in_flight: false,
+            }),
+        }
+    }
+
+    // 現在の状態を取得する関数
+    pub fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).state
+    }
+
+    // 呼び出しを許可するか判定し、許可した場合は呼び出しの許可を返す関数
+    //
+    // 試行状態で許可した呼び出しの結果を記録せずに許可を破棄した場合(送信前のエラー・キャンセルなど)は、
+    // 試行を解放して次の呼び出しで再度試行できるようにする
+    pub fn try_acquire(&self) -> Option<CallPermit<'_>> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let trial = match inner.state {
+            CircuitState::Closed => false,
+            CircuitState::Open => {
+                // 遮断時間が経過したら試行状態に移行して1回だけ許可
+                let elapsed = inner.opened_at.map(|at| at.elapsed()).unwrap_or_default();
+                if elapsed < self.open_duration {
+                    return None;
+                }
+                inner.state = CircuitState::HalfOpen;
+                inner.trial_in_flight = true;
+                true
+            }
+            CircuitState::HalfOpen => {
+                // 試行中の呼び出しがなければ許可
+                if inner.trial_in_flight {
+                    return None;
+                }
+                inner.trial_in_flight = true;
+                true
+            }
+        };
+        Some(CallPermit { breaker: self, trial, recorded: false })
+    }
+
+    // 呼び出しの成功を記録する関数
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.trial_in_flight = false;
+    }
+
+    // 呼び出しの失敗を記録する関数
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.consecutive_failures += 1;
+        inner.trial_in_flight = false;
+
+        // 試行に失敗した場合、または連続失敗回数がしきい値に達した場合は遮断
+        if inner.state == CircuitState::HalfOpen || inner.consecutive_failures >= self.failure_threshold {
+            if inner.state != CircuitState::Open {
+                tracing::warn!(failures = inner.consecutive_failures, "circuit breaker opened");
+            }
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+
+    // 結果を記録せずに破棄された試行を解放する関数(試行状態のまま次の試行を許可する)
+    fn release_trial(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.state == CircuitState::HalfOpen {
+            inner.trial_in_flight = false;
+        }
+    }
+}
+
+// サーキットブレーカーが許可した1回の呼び出し
+//
+// 結果はrecord_success・record_failureで記録する。記録せずに破棄した場合は試行を解放する
+pub struct CallPermit<'a> {
+    // 許可したサーキットブレーカー
+    breaker: &'a CircuitBreaker,
+    // 試行状態で許可した呼び出しか
+    trial: bool,
+    // 結果を記録したか
+    recorded: bool,
+}
+
+// CallPermitの実装
+impl CallPermit<'_> {
+    // 呼び出しの成功を記録する関数
+    pub fn record_success(mut self) {
+        self.recorded = true;
+        self.breaker.record_success();
+    }
+
+    // 呼び出しの失敗を記録する関数
+    pub fn record_failure(mut self) {
+        self.recorded = true;
+        self.breaker.record_failure();
+    }
+}
+
+// 結果を記録せずに破棄された場合は試行を解放する
+impl Drop for CallPermit<'_> {
+    fn drop(&mut self) {
+        if self.trial && !self.recorded {
+            tracing::warn!("circuit breaker trial released without a result");
+            self.breaker.release_trial();
+        }
+    }
+}
@@ -0,0 +1,9 @@
+// 外部HTTP呼び出しのモジュールの宣言
+pub mod circuit_breaker;
+pub mod outbound_policy;
+pub mod outbound_request;
+
+#[cfg(test)]
+mod tests;
+
+// 外部HTTP呼び出しのエントリーポイント
@@ -0,0 +1,72 @@
+// 必要なクレートのインポート
+use std::time::Duration;
+use rand::Rng;
+use reqwest::{Method, Request, StatusCode};
+// 設定のインポート
+use crate::config::outbound_config::OutboundConfig;
+// サーキットブレーカーのインポート
+use crate::outbound::circuit_breaker::CircuitBreaker;
+
+// 外部HTTP呼び出しのエラー
+#[derive(Debug, thiserror::Error)]
+pub enum OutboundError {
+    // 通信エラー(タイムアウトを含む)
+    #[error("{0}")]
+    Transport(#[from] reqwest::Error),
+    // サーキットブレーカーによる遮断
+    #[error("Supabase is temporarily unavailable")]
+    CircuitOpen,
+}
+
+// タイムアウト・再試行・サーキットブレーカーをまとめた呼び出しポリシー
+pub struct OutboundPolicy {
+    // 設定
+    pub config: OutboundConfig,
+    // サーキットブレーカー
+    pub breaker: CircuitBreaker,
+}
+
+// OutboundPolicyの実装
+impl OutboundPolicy {
+    // コンストラクタ
+    pub fn new(config: OutboundConfig) -> Self {
+        let breaker = CircuitBreaker::new(config.failure_threshold, config.open_duration);
+        Self { config, breaker }
+    }
+
+    // リクエストが再試行できるか判定する関数
+    //
+    // GET/HEADなどの冪等なメソッドのみ再試行する。
+    // 書き込み(POST/PATCH/DELETEなど)は二重に書き込まれるのを防ぐため、常に再試行しない
+    pub fn is_idempotent(&self, request: &Request) -> bool {
+        matches!(*request.method(), Method::GET | Method::HEAD | Method::OPTIONS)
+    }
+
+    // 再試行すべきステータスコードか判定する関数
+    pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    // 再試行までの待機時間を計算する関数(上限付きの指数バックオフにジッターを加える)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .config
+            .base_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        let cap = exponential.min(self.config.max_backoff).as_millis() as u64;
+        Duration::from_millis(rand::thread_rng().gen_range(cap / 2..=cap))
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for OutboundPolicy {
+    fn default() -> Self {
+        Self::new(OutboundConfig::default())
+    }
+}
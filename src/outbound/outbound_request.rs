@@ -0,0 +1,157 @@
+// 必要なクレートのインポート
+use std::future::Future;
+use std::time::Instant;
+use reqwest::{header::HeaderValue, Client, Request, RequestBuilder, Response};
+// メトリクスのインポート
+use crate::metrics::app_metrics::metrics;
+// リクエストIDのインポート
+use crate::middleware::request_id::request_id_middleware::{current_request_id, REQUEST_ID_HEADER};
+// 呼び出しポリシーのインポート
+use crate::outbound::circuit_breaker::CircuitState;
+use crate::outbound::outbound_policy::{OutboundError, OutboundPolicy};
+
+// Supabase(PostgREST)へのリクエスト送信を拡張するトレイト
+pub trait OutboundRequestExt {
+    // リクエストIDを付与し、ポリシーに従って再試行・遮断しながらリクエストを送信する
+    fn send_tracked(
+        self,
+        operation: &'static str,
+        policy: &OutboundPolicy,
+    ) -> impl Future<Output = Result<Response, OutboundError>> + Send;
+}
+
+// RequestBuilderへの実装
+impl OutboundRequestExt for RequestBuilder {
+    async fn send_tracked(
+        self,
+        operation: &'static str,
+        policy: &OutboundPolicy,
+    ) -> Result<Response, OutboundError> {
+        // サーキットブレーカーが開いている場合は呼び出さずに失敗
+        // (許可は結果を記録せずに破棄されると試行を解放するため、途中でのエラー・キャンセルでも遮断が続かない)
+        let Some(mut permit) = policy.breaker.try_acquire() else {
+            return Err(short_circuit(operation));
+        };
+
+        // リクエストを組み立て、タイムアウトとリクエストIDを設定
+        let (client, request) = self.build_split();
+        let mut request = request?;
+        *request.timeout_mut() = Some(policy.config.timeout);
+        if let Some(value) = current_request_id().and_then(|id| HeaderValue::from_str(&id).ok()) {
+            request.headers_mut().insert(REQUEST_ID_HEADER, value);
+        }
+
+        // 冪等なリクエストのみ再試行する
+        let idempotent = policy.is_idempotent(&request);
+        let mut attempt = 0;
+        loop {
+            // 再試行に備えてリクエストを複製
+            let retry_request = if idempotent && attempt < policy.config.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            // リクエストを送信し、結果をサーキットブレーカーに記録
+            let result = execute(&client, request, operation).await;
+            let retryable = match &result {
+                Ok(response) => policy.is_retryable_status(response.status()),
+                Err(_) => true,
+            };
+            let failed = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(_) => true,
+            };
+            if failed {
+                permit.record_failure();
+            } else {
+                permit.record_success();
+            }
+            record_breaker_state(policy);
+
+            // 再試行できない場合は結果を返す
+            let next_request = match retry_request {
+                Some(next_request) if retryable => next_request,
+                _ => return result.map_err(OutboundError::from),
+            };
+
+            // 待機してから再試行
+            attempt += 1;
+            metrics()
+                .supabase_request_retries_total
+                .with_label_values(&[operation])
+                .inc();
+            tokio::time::sleep(policy.backoff(attempt)).await;
+            permit = match policy.breaker.try_acquire() {
+                Some(permit) => permit,
+                None => return Err(short_circuit(operation)),
+            };
+            request = next_request;
+        }
+    }
+}
+
+// リクエストを1回送信し、操作名ごとにメトリクスを記録する関数
+async fn execute(client: &Client, request: Request, operation: &'static str) -> Result<Response, reqwest::Error> {
+    // 計測開始
+    let started = Instant::now();
+    // リクエストを送信
+    let result = client.execute(request).await;
+
+    // レイテンシを記録
+    metrics()
+        .supabase_request_duration_seconds
+        .with_label_values(&[operation])
+        .observe(started.elapsed().as_secs_f64());
+
+    // 結果に応じて呼び出し数とエラー数を記録
+    match &result {
+        Ok(response) => {
+            let status = response.status();
+            metrics()
+                .supabase_requests_total
+                .with_label_values(&[operation, status.as_str()])
+                .inc();
+            if !status.is_success() {
+                tracing::warn!(operation, status = status.as_u16(), "supabase request returned an error status");
+                metrics()
+                    .supabase_request_errors_total
+                    .with_label_values(&[operation, "status"])
+                    .inc();
+            }
+        }
+        Err(error) => {
+            tracing::error!(operation, error = %error, "supabase request failed");
+            metrics()
+                .supabase_requests_total
+                .with_label_values(&[operation, "error"])
+                .inc();
+            metrics()
+                .supabase_request_errors_total
+                .with_label_values(&[operation, "transport"])
+                .inc();
+        }
+    }
+
+    result
+}
+
+// サーキットブレーカーの状態をメトリクスに記録する関数
+fn record_breaker_state(policy: &OutboundPolicy) {
+    let state = match policy.breaker.state() {
+        CircuitState::Closed => 0,
+        CircuitState::Open => 1,
+        CircuitState::HalfOpen => 2,
+    };
+    metrics().supabase_circuit_breaker_state.set(state);
+}
+
+// サーキットブレーカーによる遮断を記録してエラーを返す関数
+fn short_circuit(operation: &'static str) -> OutboundError {
+    tracing::warn!(operation, "supabase request short-circuited by circuit breaker");
+    metrics()
+        .supabase_request_errors_total
+        .with_label_values(&[operation, "circuit_open"])
+        .inc();
+    OutboundError::CircuitOpen
+}
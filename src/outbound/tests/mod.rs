@@ -0,0 +1 @@
+pub mod outbound_request_01_test;
@@ -0,0 +1,233 @@
+use std::time::Duration;
+use reqwest::Client;
+// 設定のインポート
+use crate::config::outbound_config::OutboundConfig;
+// 呼び出しポリシーのインポート
+use crate::outbound::circuit_breaker::CircuitState;
+use crate::outbound::outbound_policy::{OutboundError, OutboundPolicy};
+use crate::outbound::outbound_request::OutboundRequestExt;
+
+// テスト用の呼び出しポリシーを作成
+fn create_test_policy(max_retries: u32, failure_threshold: u32) -> OutboundPolicy {
+    OutboundPolicy::new(OutboundConfig {
+        timeout: Duration::from_secs(1),
+        max_retries,
+        base_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+        failure_threshold,
+        open_duration: Duration::from_secs(60),
+    })
+}
+
+// GETは一時的なエラーの後に再試行されることのテスト
+#[tokio::test]
+async fn test_get_is_retried_on_transient_error() {
+    // モックサーバーの設定(1回目は503、2回目は200)
+    let mut mock_server = mockito::Server::new_async().await;
+    let unavailable_mock = mock_server
+        .mock("GET", "/rest/v1/trans_users")
+        .with_status(503)
+        .expect(1)
+        .create_async()
+        .await;
+    let ok_mock = mock_server
+        .mock("GET", "/rest/v1/trans_users")
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+
+    // リクエストを送信
+    let policy = create_test_policy(2, 5);
+    let response = Client::new()
+        .get(format!("{}/rest/v1/trans_users", mock_server.url()))
+        .send_tracked("find_all", &policy)
+        .await
+        .unwrap();
+
+    // 再試行後に成功することを確認
+    assert_eq!(response.status(), 200);
+    unavailable_mock.assert_async().await;
+    ok_mock.assert_async().await;
+}
+
+// 冪等キーのないPOSTは再試行されないことのテスト
+#[tokio::test]
+async fn test_post_without_idempotency_key_is_not_retried() {
+    // モックサーバーの設定(常に503)
+    let mut mock_server = mockito::Server::new_async().await;
+    let mock = mock_server
+        .mock("POST", "/rest/v1/trans_users")
+        .with_status(503)
+        .expect(1)
+        .create_async()
+        .await;
+
+    // リクエストを送信
+    let policy = create_test_policy(2, 5);
+    let response = Client::new()
+        .post(format!("{}/rest/v1/trans_users", mock_server.url()))
+        .json(&serde_json::json!({}))
+        .send_tracked("create", &policy)
+        .await
+        .unwrap();
+
+    // 1回だけ呼び出されることを確認
+    assert_eq!(response.status(), 503);
+    mock.assert_async().await;
+}
+
+// 冪等キー付きのPOSTも再試行されないことのテスト
+#[tokio::test]
+async fn test_post_with_idempotency_key_is_not_retried() {
+    // モックサーバーの設定(常に503)
+    let mut mock_server = mockito::Server::new_async().await;
+    let mock = mock_server
+        .mock("POST", "/rest/v1/trans_users")
+        .with_status(503)
+        .expect(1)
+        .create_async()
+        .await;
+
+    // リクエストを送信
+    let policy = create_test_policy(2, 5);
+    let response = Client::new()
+        .post(format!("{}/rest/v1/trans_users", mock_server.url()))
+        .header("Idempotency-Key", "test-key")
+        .json(&serde_json::json!({}))
+        .send_tracked("create", &policy)
+        .await
+        .unwrap();
+
+    // 1回だけ呼び出されることを確認
+    assert_eq!(response.status(), 503);
+    mock.assert_async().await;
+}
+
+// 連続して失敗するとサーキットブレーカーが開くことのテスト
+#[tokio::test]
+async fn test_circuit_breaker_short_circuits() {
+    // モックサーバーの設定(常に500)
+    let mut mock_server = mockito::Server::new_async().await;
+    let mock = mock_server
+        .mock("GET", "/rest/v1/trans_users")
+        .with_status(500)
+        .expect(2)
+        .create_async()
+        .await;
+
+    // 再試行なし、2回の失敗で遮断するポリシー
+    let policy = create_test_policy(0, 2);
+    let client = Client::new();
+    let url = format!("{}/rest/v1/trans_users", mock_server.url());
+
+    // しきい値まではPostgRESTが呼び出される
+    for _ in 0..2 {
+        let response = client.get(&url).send_tracked("find_all", &policy).await.unwrap();
+        assert_eq!(response.status(), 500);
+    }
+    assert_eq!(policy.breaker.state(), CircuitState::Open);
+
+    // 遮断中は呼び出さずに失敗する
+    let result = client.get(&url).send_tracked("find_all", &policy).await;
+    assert!(matches!(result, Err(OutboundError::CircuitOpen)));
+
+    // モックの確認
+    mock.assert_async().await;
+}
+
+// 遮断時間の経過後に試行が成功すると回復することのテスト
+#[tokio::test]
+async fn test_circuit_breaker_recovers_after_open_duration() {
+    // モックサーバーの設定
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = mock_server
+        .mock("GET", "/rest/v1/trans_users")
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    // 遮断時間が短いポリシーを作成し、失敗を記録して遮断
+    let policy = OutboundPolicy::new(OutboundConfig {
+        failure_threshold: 1,
+        open_duration: Duration::from_millis(50),
+        ..OutboundConfig::default()
+    });
+    policy.breaker.record_failure();
+    assert_eq!(policy.breaker.state(), CircuitState::Open);
+
+    // 遮断時間の経過後は試行が許可され、成功すると回復する
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    let response = Client::new()
+        .get(format!("{}/rest/v1/trans_users", mock_server.url()))
+        .send_tracked("find_all", &policy)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(policy.breaker.state(), CircuitState::Closed);
+}
+
+// 遮断時間が短いポリシーを作成し、失敗を記録して遮断する
+async fn open_breaker_policy() -> OutboundPolicy {
+    let policy = OutboundPolicy::new(OutboundConfig {
+        failure_threshold: 1,
+        open_duration: Duration::from_millis(50),
+        ..OutboundConfig::default()
+    });
+    policy.breaker.record_failure();
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    policy
+}
+
+// 試行が送信前のエラーで終わっても、次の呼び出しで再度試行できることのテスト
+#[tokio::test]
+async fn test_trial_is_released_on_request_error() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = mock_server
+        .mock("GET", "/rest/v1/trans_users")
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let policy = open_breaker_policy().await;
+
+    // 不正なURLはリクエストの組み立てで失敗する
+    let result = Client::new().get("not a url").send_tracked("find_all", &policy).await;
+    assert!(matches!(result, Err(OutboundError::Transport(_))));
+    assert_eq!(policy.breaker.state(), CircuitState::HalfOpen);
+
+    // 次の呼び出しは遮断されずに試行され、成功すると回復する
+    let response = Client::new()
+        .get(format!("{}/rest/v1/trans_users", mock_server.url()))
+        .send_tracked("find_all", &policy)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(policy.breaker.state(), CircuitState::Closed);
+}
+
+// 試行の呼び出しがキャンセルされても、次の呼び出しで再度試行できることのテスト
+#[tokio::test]
+async fn test_trial_is_released_when_cancelled() {
+    // 接続を受け付けるが応答しないサーバー
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/rest/v1/trans_users", listener.local_addr().unwrap());
+    let _server = tokio::spawn(async move {
+        let mut connections = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            connections.push(stream);
+        }
+    });
+    let policy = open_breaker_policy().await;
+
+    // 応答を待つ途中でキャンセルする
+    let client = Client::new();
+    let cancelled = tokio::time::timeout(Duration::from_millis(50), client.get(&url).send_tracked("find_all", &policy)).await;
+    assert!(cancelled.is_err());
+
+    // 試行は解放され、次の呼び出しは遮断されない
+    let cancelled = tokio::time::timeout(Duration::from_millis(50), client.get(&url).send_tracked("find_all", &policy)).await;
+    assert!(cancelled.is_err(), "the second trial should reach the server instead of short-circuiting");
+}
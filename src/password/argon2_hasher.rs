@@ -0,0 +1,73 @@
+// 必要なクレートのインポート
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString},
+    Algorithm, Argon2, Params, Version,
+};
+use rand::rngs::OsRng;
+// エラーのインポート
+use crate::errors::password::password_error::PasswordHashError;
+// パスワードのハッシュ化のトレイトのインポート
+use crate::password::password_hasher::PasswordHasher;
+
+// Argon2idのハッシュの接頭辞
+const ARGON2ID_PREFIX: &str = "$argon2id$";
+
+// Argon2idによるハッシュ化(PHC文字列形式で保存する)
+pub struct Argon2idHasher {
+    // メモリ使用量・反復回数・並列度
+    params: Params,
+}
+
+// Argon2idHasherの実装
+impl Argon2idHasher {
+    // メモリ使用量(KiB)・反復回数・並列度を指定して作成する関数
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self, PasswordHashError> {
+        let params = Params::new(memory_kib, iterations, parallelism, None)
+            .map_err(|e| PasswordHashError::InvalidParams(e.to_string()))?;
+        Ok(Self { params })
+    }
+
+    // 現在のパラメータのArgon2idのコンテキスト
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+}
+
+// PasswordHasherトレイトの実装
+impl PasswordHasher for Argon2idHasher {
+    fn hash(&self, password: &str) -> Result<String, PasswordHashError> {
+        let salt = SaltString::generate(&mut OsRng);
+        self.argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| PasswordHashError::Hash(e.to_string()))
+    }
+
+    fn supports(&self, password_hash: &str) -> bool {
+        password_hash.starts_with(ARGON2ID_PREFIX)
+    }
+
+    fn verify(&self, password: &str, password_hash: &str) -> Result<bool, PasswordHashError> {
+        let parsed = PasswordHash::new(password_hash).map_err(|e| PasswordHashError::Verify(e.to_string()))?;
+        // パラメータはハッシュに記録されたものを使う
+        match Argon2::default().verify_password(password.as_bytes(), &parsed) {
+            Ok(()) => Ok(true),
+            Err(argon2::password_hash::Error::Password) => Ok(false),
+            Err(e) => Err(PasswordHashError::Verify(e.to_string())),
+        }
+    }
+
+    fn needs_rehash(&self, password_hash: &str) -> bool {
+        let Ok(parsed) = PasswordHash::new(password_hash) else {
+            return true;
+        };
+        let Ok(params) = Params::try_from(&parsed) else {
+            return true;
+        };
+
+        parsed.version != Some(Version::V0x13.into())
+            || params.m_cost() != self.params.m_cost()
+            || params.t_cost() != self.params.t_cost()
+            || params.p_cost() != self.params.p_cost()
+    }
+}
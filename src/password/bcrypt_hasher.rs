@@ -0,0 +1,56 @@
+// エラーのインポート
+use crate::errors::password::password_error::PasswordHashError;
+// パスワードのハッシュ化のトレイトのインポート
+use crate::password::password_hasher::PasswordHasher;
+
+// bcryptのハッシュの接頭辞
+const BCRYPT_PREFIXES: [&str; 4] = ["$2a$", "$2b$", "$2x$", "$2y$"];
+// コストの最小値
+const MIN_COST: u32 = 4;
+// コストの最大値
+const MAX_COST: u32 = 31;
+
+// bcryptによるハッシュ化
+pub struct BcryptHasher {
+    // コスト(4〜31)
+    cost: u32,
+}
+
+// BcryptHasherの実装
+impl BcryptHasher {
+    // コストを指定して作成する関数
+    pub fn new(cost: u32) -> Result<Self, PasswordHashError> {
+        if !(MIN_COST..=MAX_COST).contains(&cost) {
+            return Err(PasswordHashError::InvalidParams(format!(
+                "bcrypt cost must be between {} and {}",
+                MIN_COST,
+                MAX_COST
+            )));
+        }
+        Ok(Self { cost })
+    }
+
+    // ハッシュからコストを取得する関数($2b$12$... の12)
+    fn cost_of(password_hash: &str) -> Option<u32> {
+        password_hash.get(4..6)?.parse().ok()
+    }
+}
+
+// PasswordHasherトレイトの実装
+impl PasswordHasher for BcryptHasher {
+    fn hash(&self, password: &str) -> Result<String, PasswordHashError> {
+        bcrypt::hash(password, self.cost).map_err(|e| PasswordHashError::Hash(e.to_string()))
+    }
+
+    fn supports(&self, password_hash: &str) -> bool {
+        BCRYPT_PREFIXES.iter().any(|prefix| password_hash.starts_with(prefix))
+    }
+
+    fn verify(&self, password: &str, password_hash: &str) -> Result<bool, PasswordHashError> {
+        bcrypt::verify(password, password_hash).map_err(|e| PasswordHashError::Verify(e.to_string()))
+    }
+
+    fn needs_rehash(&self, password_hash: &str) -> bool {
+        Self::cost_of(password_hash) != Some(self.cost)
+    }
+}
@@ -0,0 +1,9 @@
+// パスワードのハッシュ化のモジュールの宣言
+pub mod argon2_hasher;
+pub mod bcrypt_hasher;
+pub mod password_hasher;
+
+#[cfg(test)]
+mod tests;
+
+// パスワードのハッシュ化のエントリーポイント
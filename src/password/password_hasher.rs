@@ -0,0 +1,106 @@
+// 必要なクレートのインポート
+use std::sync::Arc;
+// 設定のインポート
+use crate::config::password_hash_config::{PasswordHashAlgorithm, PasswordHashConfig};
+// エラーのインポート
+use crate::errors::password::password_error::PasswordHashError;
+// ハッシュ化のアルゴリズムの実装のインポート
+use crate::password::argon2_hasher::Argon2idHasher;
+use crate::password::bcrypt_hasher::BcryptHasher;
+
+// パスワードのハッシュ化のトレイト
+//
+// ハッシュは$2b$12$...や$argon2id$...のような自己記述的な形式で保存するため、
+// 形式からアルゴリズムとパラメータを判別できる
+pub trait PasswordHasher: Send + Sync {
+    // パスワードをハッシュ化する
+    fn hash(&self, password: &str) -> Result<String, PasswordHashError>;
+    // このアルゴリズムで作成されたハッシュかどうか
+    fn supports(&self, password_hash: &str) -> bool;
+    // パスワードとハッシュを照合する
+    fn verify(&self, password: &str, password_hash: &str) -> Result<bool, PasswordHashError>;
+    // 現在のパラメータ(コストなど)と異なり、作り直すべきハッシュかどうか
+    fn needs_rehash(&self, password_hash: &str) -> bool;
+}
+
+// パスワードの照合の結果
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PasswordVerification {
+    // パスワードが一致したか
+    pub matches: bool,
+    // 現在のアルゴリズム・パラメータで作り直すべきか(一致した場合のみtrueになる)
+    pub needs_rehash: bool,
+}
+
+// 複数のアルゴリズムのハッシュを扱うハッシュ化の実装
+//
+// 新しいハッシュは現在のアルゴリズムで作成し、照合はハッシュの形式に合うアルゴリズムで行う
+pub struct PasswordHashers {
+    // 新しいハッシュに使うアルゴリズム
+    current: Arc<dyn PasswordHasher>,
+    // 照合のみ行う以前のアルゴリズム
+    legacy: Vec<Arc<dyn PasswordHasher>>,
+}
+
+// PasswordHashersの実装
+impl PasswordHashers {
+    // 現在のアルゴリズムと以前のアルゴリズムを指定して作成する関数
+    pub fn new(current: Arc<dyn PasswordHasher>, legacy: Vec<Arc<dyn PasswordHasher>>) -> Self {
+        Self { current, legacy }
+    }
+
+    // 設定から作成する関数(選択しなかったアルゴリズムは照合のみ行う)
+    pub fn from_config(config: &PasswordHashConfig) -> Result<Self, PasswordHashError> {
+        let bcrypt: Arc<dyn PasswordHasher> = Arc::new(BcryptHasher::new(config.bcrypt_cost)?);
+        let argon2: Arc<dyn PasswordHasher> = Arc::new(Argon2idHasher::new(
+            config.argon2_memory_kib,
+            config.argon2_iterations,
+            config.argon2_parallelism,
+        )?);
+
+        Ok(match config.algorithm {
+            PasswordHashAlgorithm::Bcrypt => Self::new(bcrypt, vec![argon2]),
+            PasswordHashAlgorithm::Argon2id => Self::new(argon2, vec![bcrypt]),
+        })
+    }
+
+    // パスワードを現在のアルゴリズムでハッシュ化する関数
+    pub fn hash(&self, password: &str) -> Result<String, PasswordHashError> {
+        self.current.hash(password)
+    }
+
+    // パスワードとハッシュを照合する関数
+    //
+    // 以前のアルゴリズムのハッシュ、または現在のアルゴリズムでもパラメータが異なるハッシュは作り直しが必要
+    pub fn verify(&self, password: &str, password_hash: &str) -> Result<PasswordVerification, PasswordHashError> {
+        // 現在のアルゴリズムのハッシュ
+        if self.current.supports(password_hash) {
+            let matches = self.current.verify(password, password_hash)?;
+            return Ok(PasswordVerification {
+                matches,
+                needs_rehash: matches && self.current.needs_rehash(password_hash),
+            });
+        }
+
+        // 以前のアルゴリズムのハッシュ
+        let hasher = self
+            .legacy
+            .iter()
+            .find(|hasher| hasher.supports(password_hash))
+            .ok_or(PasswordHashError::UnsupportedFormat)?;
+        let matches = hasher.verify(password, password_hash)?;
+        Ok(PasswordVerification { matches, needs_rehash: matches })
+    }
+
+    // パスワードとハッシュが一致するかを返す関数(作り直しが必要かは問わない)
+    pub fn matches(&self, password: &str, password_hash: &str) -> Result<bool, PasswordHashError> {
+        Ok(self.verify(password, password_hash)?.matches)
+    }
+}
+
+// Defaultトレイトの実装(既定の設定を使う)
+impl Default for PasswordHashers {
+    fn default() -> Self {
+        Self::from_config(&PasswordHashConfig::default()).expect("既定のパスワードのハッシュ化の設定が不正です")
+    }
+}
@@ -0,0 +1 @@
+pub mod password_hasher_01_test;
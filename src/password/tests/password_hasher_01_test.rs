@@ -0,0 +1,110 @@
+use std::sync::Arc;
+// 設定のインポート
+use crate::config::password_hash_config::{PasswordHashAlgorithm, PasswordHashConfig};
+// ハッシュ化のインポート
+use crate::password::argon2_hasher::Argon2idHasher;
+use crate::password::bcrypt_hasher::BcryptHasher;
+use crate::password::password_hasher::{PasswordHasher, PasswordHashers};
+// エラーのインポート
+use crate::errors::password::password_error::PasswordHashError;
+
+// テスト用のパスワード
+const PASSWORD: &str = "password123";
+
+// テスト用の設定(テストを速くするため、コストは最小にする)
+fn test_config(algorithm: PasswordHashAlgorithm) -> PasswordHashConfig {
+    PasswordHashConfig {
+        algorithm,
+        bcrypt_cost: 4,
+        argon2_memory_kib: 1024,
+        argon2_iterations: 1,
+        argon2_parallelism: 1,
+    }
+}
+
+// 各アルゴリズムでハッシュ化したパスワードを照合できることのテスト
+#[test]
+fn test_hash_and_verify() {
+    let hashers: [Box<dyn PasswordHasher>; 2] = [
+        Box::new(BcryptHasher::new(4).unwrap()),
+        Box::new(Argon2idHasher::new(1024, 1, 1).unwrap()),
+    ];
+
+    for hasher in hashers {
+        let password_hash = hasher.hash(PASSWORD).unwrap();
+        assert!(hasher.supports(&password_hash));
+        assert!(hasher.verify(PASSWORD, &password_hash).unwrap());
+        assert!(!hasher.verify("wrong_password", &password_hash).unwrap());
+        assert!(!hasher.needs_rehash(&password_hash));
+    }
+}
+
+// ハッシュの形式でアルゴリズムを判別することのテスト
+#[test]
+fn test_supports_only_own_format() {
+    let bcrypt = BcryptHasher::new(4).unwrap();
+    let argon2 = Argon2idHasher::new(1024, 1, 1).unwrap();
+    let bcrypt_hash = bcrypt.hash(PASSWORD).unwrap();
+    let argon2_hash = argon2.hash(PASSWORD).unwrap();
+
+    assert!(argon2_hash.starts_with("$argon2id$"));
+    assert!(!bcrypt.supports(&argon2_hash));
+    assert!(!argon2.supports(&bcrypt_hash));
+    assert!(!bcrypt.supports("plain"));
+    assert!(!argon2.supports("plain"));
+}
+
+// コスト・パラメータが変わったハッシュは作り直しが必要になることのテスト
+#[test]
+fn test_needs_rehash_when_cost_changes() {
+    let bcrypt_hash = BcryptHasher::new(4).unwrap().hash(PASSWORD).unwrap();
+    assert!(BcryptHasher::new(5).unwrap().needs_rehash(&bcrypt_hash));
+
+    let argon2_hash = Argon2idHasher::new(1024, 1, 1).unwrap().hash(PASSWORD).unwrap();
+    assert!(Argon2idHasher::new(2048, 1, 1).unwrap().needs_rehash(&argon2_hash));
+    assert!(Argon2idHasher::new(1024, 2, 1).unwrap().needs_rehash(&argon2_hash));
+    assert!(Argon2idHasher::new(1024, 1, 2).unwrap().needs_rehash(&argon2_hash));
+}
+
+// 以前のアルゴリズムのハッシュを照合し、一致した場合のみ作り直しが必要になることのテスト
+#[test]
+fn test_legacy_hash_is_verified_and_flagged_for_rehash() {
+    let hashers = PasswordHashers::from_config(&test_config(PasswordHashAlgorithm::Argon2id)).unwrap();
+    let bcrypt_hash = BcryptHasher::new(4).unwrap().hash(PASSWORD).unwrap();
+
+    let verification = hashers.verify(PASSWORD, &bcrypt_hash).unwrap();
+    assert!(verification.matches);
+    assert!(verification.needs_rehash);
+
+    // 一致しない場合は作り直さない
+    let verification = hashers.verify("wrong_password", &bcrypt_hash).unwrap();
+    assert!(!verification.matches);
+    assert!(!verification.needs_rehash);
+
+    // 新しいハッシュは現在のアルゴリズムで作成し、作り直しは不要
+    let argon2_hash = hashers.hash(PASSWORD).unwrap();
+    assert!(argon2_hash.starts_with("$argon2id$"));
+    assert!(!hashers.verify(PASSWORD, &argon2_hash).unwrap().needs_rehash);
+}
+
+// 現在のアルゴリズムでもコストが異なるハッシュは作り直しが必要になることのテスト
+#[test]
+fn test_current_algorithm_with_old_cost_is_flagged_for_rehash() {
+    let hashers = PasswordHashers::from_config(&test_config(PasswordHashAlgorithm::Bcrypt)).unwrap();
+    let old_hash = BcryptHasher::new(5).unwrap().hash(PASSWORD).unwrap();
+
+    let verification = hashers.verify(PASSWORD, &old_hash).unwrap();
+    assert!(verification.matches);
+    assert!(verification.needs_rehash);
+}
+
+// 扱えない形式のハッシュ・不正なパラメータはエラーになることのテスト
+#[test]
+fn test_invalid_inputs_are_rejected() {
+    let hashers = PasswordHashers::new(Arc::new(BcryptHasher::new(4).unwrap()), Vec::new());
+    assert!(matches!(hashers.verify(PASSWORD, "plain"), Err(PasswordHashError::UnsupportedFormat)));
+
+    assert!(BcryptHasher::new(3).is_err());
+    assert!(BcryptHasher::new(32).is_err());
+    assert!(Argon2idHasher::new(1024, 0, 1).is_err());
+}
@@ -0,0 +1,38 @@
+// 必要なクレートのインポート
+use axum::http::Method;
+use std::sync::Arc;
+// ルートの一覧のインポート
+use crate::routes::route_table::RouteTable;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// 認証サービス(v2)のインポート
+use crate::services::auth::auth_v2_services::{sign_in, sign_out, check_auth};
+// 二段階認証のサービスのインポート
+use crate::services::auth::two_factor_v2_services;
+// APIキーのサービスのインポート
+use crate::services::auth::api_key_v2_services;
+// セッションのサービスのインポート
+use crate::services::auth::session_v2_services;
+
+// 認証ルーティング(v2)を作成する関数
+//
+// /v2の下にネストして使用する
+pub fn auth_v2_routes(app_state: Arc<AppState>) -> RouteTable {
+    RouteTable::new()
+        .route(Method::POST, "/auth/signin", sign_in)
+        .route(Method::POST, "/auth/signout", sign_out)
+        .route(Method::GET, "/auth/check", check_auth)
+        // 二段階認証(設定・有効化・サインインの二段階目・管理者によるリセット)
+        .route(Method::POST, "/me/2fa/setup", two_factor_v2_services::setup_two_factor)
+        .route(Method::POST, "/me/2fa/confirm", two_factor_v2_services::confirm_two_factor)
+        .route(Method::POST, "/auth/2fa/verify", two_factor_v2_services::verify_two_factor)
+        .route(Method::DELETE, "/users/:id/2fa", two_factor_v2_services::reset_two_factor)
+        // APIキー(一覧・発行・削除)
+        .route(Method::GET, "/me/api-keys", api_key_v2_services::list_api_keys)
+        .route(Method::POST, "/me/api-keys", api_key_v2_services::create_api_key)
+        .route(Method::DELETE, "/me/api-keys/:id", api_key_v2_services::delete_api_key)
+        // サインインのセッション(一覧・削除)
+        .route(Method::GET, "/me/sessions", session_v2_services::list_sessions)
+        .route(Method::DELETE, "/me/sessions/:id", session_v2_services::delete_session)
+        .with_state(app_state)
+}
@@ -0,0 +1,18 @@
+// 必要なクレートのインポート
+use axum::http::Method;
+use std::sync::Arc;
+// ルートの一覧のインポート
+use crate::routes::route_table::RouteTable;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// JWKSのサービスのインポート
+use crate::services::auth::jwks_services::get_jwks;
+
+// JWKSのルーティングを作成する関数
+//
+// 検証する側が既定の場所から取得できるよう、バージョンの接頭辞を付けない
+pub fn jwks_routes(app_state: Arc<AppState>) -> RouteTable {
+    RouteTable::new()
+        .route(Method::GET, "/.well-known/jwks.json", get_jwks)
+        .with_state(app_state)
+}
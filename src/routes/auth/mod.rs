@@ -1,4 +1,7 @@
 // 認証ルーティングのモジュールの宣言
 pub mod auth_routes;
+pub mod auth_v2_routes;
+pub mod jwks_routes;
+pub mod oidc_routes;
 
 // 認証ルーティングのエントリーポイント 
\ No newline at end of file
@@ -0,0 +1,19 @@
+// 必要なクレートのインポート
+use axum::http::Method;
+use std::sync::Arc;
+// ルートの一覧のインポート
+use crate::routes::route_table::RouteTable;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// OIDCのサービスのインポート
+use crate::services::auth::oidc_services::{oidc_callback, start_oidc_login};
+
+// OIDCのサインインのルーティングを作成する関数
+//
+// IdPに登録するコールバックのURLが変わらないよう、バージョンの接頭辞を付けない
+pub fn oidc_routes(app_state: Arc<AppState>) -> RouteTable {
+    RouteTable::new()
+        .route(Method::GET, "/auth/oidc/:provider/start", start_oidc_login)
+        .route(Method::GET, "/auth/oidc/:provider/callback", oidc_callback)
+        .with_state(app_state)
+}
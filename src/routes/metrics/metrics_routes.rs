@@ -0,0 +1,13 @@
+// 必要なクレートのインポート
+use axum::http::Method;
+// ルートの一覧のインポート
+use crate::routes::route_table::RouteTable;
+// メトリクスサービスのインポート
+use crate::services::metrics::metrics_services;
+
+// メトリクスルーティングを作成する関数
+pub fn metrics_routes() -> RouteTable {
+    RouteTable::new()
+        // Prometheus形式のメトリクスを取得するルートを設定
+        .route(Method::GET, "/metrics", metrics_services::get_metrics)
+}
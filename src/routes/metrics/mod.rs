@@ -0,0 +1,4 @@
+// メトリクスルーティングのモジュールの宣言
+pub mod metrics_routes;
+
+// メトリクスルーティングのエントリーポイント
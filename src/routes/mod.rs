@@ -1,13 +1,26 @@
 // ルーティングのモジュールの宣言
 pub mod users;
 pub mod auth;
+pub mod metrics;
+pub mod route_table;
 // 必要なクレートのインポート
-use axum::Router;
+use axum::{middleware, Router};
 use std::sync::Arc;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 // アプリケーションの状態のインポート
 use crate::state::app_state::AppState;
+// 設定のインポート
+use crate::config::versioning_config::VersioningConfig;
+// ミドルウェアスタックのインポート
+use crate::middleware::stack::middleware_stack::apply_middleware_stack;
+// バージョンのインポート
+use crate::middleware::versioning::api_version::ApiVersion;
+use crate::middleware::versioning::versioning_middleware::{
+    deprecate_version, is_unversioned_api_path, negotiate_version,
+};
+// ルートの一覧のインポート
+use route_table::RouteTable;
 
 // APIドキュメントの定義
 #[derive(OpenApi)]
@@ -20,10 +33,39 @@ use crate::state::app_state::AppState;
         crate::services::users::user_services::create_user,
         crate::services::users::user_services::update_user,
         crate::services::users::user_services::delete_user,
+        // 自分のプロフィールのエンドポイント
+        crate::services::users::me_services::get_me,
+        crate::services::users::me_services::update_me,
+        crate::services::users::me_services::delete_me,
+        crate::services::users::me_services::change_password,
         // 認証関連のエンドポイント
         crate::services::auth::auth_services::sign_in,
         crate::services::auth::auth_services::check_auth,
         crate::services::auth::auth_services::sign_out,
+        // 二段階認証のエンドポイント
+        crate::services::auth::two_factor_services::setup_two_factor,
+        crate::services::auth::two_factor_services::confirm_two_factor,
+        crate::services::auth::two_factor_services::verify_two_factor,
+        crate::services::auth::two_factor_services::reset_two_factor,
+        // APIキーのエンドポイント
+        crate::services::auth::api_key_services::list_api_keys,
+        crate::services::auth::api_key_services::create_api_key,
+        crate::services::auth::api_key_services::delete_api_key,
+        crate::services::auth::session_services::list_sessions,
+        crate::services::auth::session_services::delete_session,
+        // OIDCのサインインのエンドポイント(バージョンの対象外)
+        crate::services::auth::oidc_services::start_oidc_login,
+        crate::services::auth::oidc_services::oidc_callback,
+        // トークンの検証の公開鍵のエンドポイント(バージョンの対象外)
+        crate::services::auth::jwks_services::get_jwks,
+        // ユーザー関連のエンドポイント(DI)
+        crate::di::routers::user_router::get_users_handler,
+        crate::di::routers::user_router::get_user_handler,
+        crate::di::routers::user_router::create_user_handler,
+        crate::di::routers::user_router::update_user_handler,
+        crate::di::routers::user_router::delete_user_handler,
+        // メトリクス関連のエンドポイント
+        crate::services::metrics::metrics_services::get_metrics,
     ),
     // モデルのスキーマの定義
     components(
@@ -31,25 +73,47 @@ use crate::state::app_state::AppState;
             // ユーザーモデル
             crate::models::users::users::User,
             crate::models::users::users::NewUser,
+            crate::models::users::users::UserUpdate,
+            crate::models::users::users::UserResponse,
+            crate::models::users::users::UserProfileUpdate,
+            crate::models::users::users::DeleteAccountRequest,
+            crate::models::users::users::ChangePasswordRequest,
             // 認証モデル
             crate::models::auth::auth::SignInCredentials,
             crate::models::auth::auth::Claims,
             crate::models::auth::auth::AuthResponse,
+            crate::models::auth::auth::AuthResponseV2,
+            crate::models::auth::jwks::JwksResponse,
+            // 二段階認証モデル
+            crate::models::auth::two_factor::TwoFactorSetupResponse,
+            crate::models::auth::two_factor::TwoFactorConfirmRequest,
+            crate::models::auth::two_factor::RecoveryCodesResponse,
+            crate::models::auth::two_factor::TwoFactorChallenge,
+            crate::models::auth::two_factor::TwoFactorVerifyRequest,
+            // APIキーモデル
+            crate::models::auth::api_key::ApiKeyScope,
+            crate::models::auth::api_key::CreateApiKeyRequest,
+            crate::models::auth::api_key::ApiKeyResponse,
+            crate::models::auth::api_key::CreatedApiKeyResponse,
+            crate::models::auth::session::SessionResponse,
             // 基本型のスキーマラッパー
             crate::models::NaiveDateTimeWrapper,
-            crate::models::UuidWrapper
+            crate::models::UuidWrapper,
+            // エラーレスポンス
+            crate::models::ErrorResponse
         ),
     ),
     // セキュリティスキーマの追加
-    modifiers(&SecurityAddon),
+    modifiers(&SecurityAddon, &ErrorResponsesAddon),
     // タグの定義
     tags(
         (name = "users", description = "ユーザー管理API"),
-        (name = "auth", description = "認証API")
+        (name = "me", description = "自分のプロフィールAPI"),
+        (name = "di-users", description = "ユーザー管理API(DI)"),
+        (name = "auth", description = "認証API"),
+        (name = "metrics", description = "メトリクスAPI")
     )
 )]
-
-// APIドキュメントの定義
 struct ApiDoc;
 // セキュリティスキーマの定義
 struct SecurityAddon;
@@ -76,27 +140,214 @@ impl utoipa::Modify for SecurityAddon {
     }
 }
 
-// ルーティングを作成する関数
-pub fn create_routes(state: Arc<AppState>) -> Router {
-    // コンテナの初期化
-    let container = {
-        use crate::di::container::Container;
-        Container::new(
-            state.client.clone(),
-            state.supabase_url.clone(),
-            state.supabase_anon_key.clone(),
-        )
-    };
-
-    // 新しいルーターを作成し、ユーザールーティングをマージ
-    Router::new()
+// ミドルウェアが返すエラーレスポンスの定義
+struct ErrorResponsesAddon;
+
+// Modifyトレイトの実装
+impl utoipa::Modify for ErrorResponsesAddon {
+    // 全ての操作にミドルウェアが返すエラー(レート制限・タイムアウト・ボディサイズ超過)を追加
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        // エラーレスポンスの作成
+        let error = |description: &str| {
+            utoipa::openapi::ResponseBuilder::new()
+                .description(description)
+                .content(
+                    "application/json",
+                    utoipa::openapi::ContentBuilder::new()
+                        .schema(utoipa::openapi::Ref::from_schema_name("ErrorResponse"))
+                        .build(),
+                )
+                .build()
+        };
+
+        for item in openapi.paths.paths.values_mut() {
+            for operation in item.operations.values_mut() {
+                // 操作に記載したエラーを優先
+                let mut errors = vec![
+                    ("429", "リクエストが多すぎます"),
+                    ("504", "処理がタイムアウトしました"),
+                ];
+                if operation.request_body.is_some() {
+                    errors.push(("413", "リクエストボディが大きすぎます"));
+                }
+
+                for (status, description) in errors {
+                    operation
+                        .responses
+                        .responses
+                        .entry(status.to_string())
+                        .or_insert_with(|| error(description).into());
+                }
+            }
+        }
+    }
+}
+
+// APIドキュメント(v2)の定義
+#[derive(OpenApi)]
+#[openapi(
+    // パスの定義
+    paths(
+        // ユーザー関連のエンドポイント
+        crate::services::users::user_v2_services::get_users,
+        crate::services::users::user_v2_services::get_user_by_id,
+        crate::services::users::user_v2_services::create_user,
+        crate::services::users::user_v2_services::update_user,
+        crate::services::users::user_v2_services::delete_user,
+        // 自分のプロフィールのエンドポイント
+        crate::services::users::me_v2_services::get_me,
+        crate::services::users::me_v2_services::update_me,
+        crate::services::users::me_v2_services::delete_me,
+        crate::services::users::me_v2_services::change_password,
+        // 認証関連のエンドポイント
+        crate::services::auth::auth_v2_services::sign_in,
+        crate::services::auth::auth_v2_services::check_auth,
+        crate::services::auth::auth_v2_services::sign_out,
+        // 二段階認証のエンドポイント
+        crate::services::auth::two_factor_v2_services::setup_two_factor,
+        crate::services::auth::two_factor_v2_services::confirm_two_factor,
+        crate::services::auth::two_factor_v2_services::verify_two_factor,
+        crate::services::auth::two_factor_v2_services::reset_two_factor,
+        // APIキーのエンドポイント
+        crate::services::auth::api_key_v2_services::list_api_keys,
+        crate::services::auth::api_key_v2_services::create_api_key,
+        crate::services::auth::api_key_v2_services::delete_api_key,
+        crate::services::auth::session_v2_services::list_sessions,
+        crate::services::auth::session_v2_services::delete_session,
+    ),
+    // モデルのスキーマの定義
+    components(
+        schemas(
+            // ユーザーモデル
+            crate::models::users::users::UserResponse,
+            crate::models::users::users::NewUser,
+            crate::models::users::users::UserUpdate,
+            crate::models::users::users::UserProfileUpdate,
+            crate::models::users::users::DeleteAccountRequest,
+            crate::models::users::users::ChangePasswordRequest,
+            // 認証モデル
+            crate::models::auth::auth::SignInCredentials,
+            crate::models::auth::auth::AuthResponseV2,
+            // 二段階認証モデル
+            crate::models::auth::two_factor::TwoFactorSetupResponse,
+            crate::models::auth::two_factor::TwoFactorConfirmRequest,
+            crate::models::auth::two_factor::RecoveryCodesResponse,
+            crate::models::auth::two_factor::TwoFactorChallenge,
+            crate::models::auth::two_factor::TwoFactorVerifyRequest,
+            // APIキーモデル
+            crate::models::auth::api_key::ApiKeyScope,
+            crate::models::auth::api_key::CreateApiKeyRequest,
+            crate::models::auth::api_key::ApiKeyResponse,
+            crate::models::auth::api_key::CreatedApiKeyResponse,
+            crate::models::auth::session::SessionResponse,
+            // 基本型のスキーマラッパー
+            crate::models::NaiveDateTimeWrapper,
+            crate::models::UuidWrapper,
+            // エラーレスポンス
+            crate::models::ErrorResponse
+        ),
+    ),
+    // セキュリティスキーマの追加
+    modifiers(&SecurityAddon, &ErrorResponsesAddon),
+    // タグの定義
+    tags(
+        (name = "users", description = "ユーザー管理API"),
+        (name = "me", description = "自分のプロフィールAPI"),
+        (name = "auth", description = "認証API")
+    )
+)]
+struct ApiDocV2;
+
+// 接頭辞のないパスのOpenAPIドキュメントを作成する関数
+//
+// 汎用CRUDリソース(manuals/crud.md)のドキュメントはここで結合する
+pub fn unversioned_openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+// バージョンごとのOpenAPIドキュメントを作成する関数
+pub fn openapi_for(version: ApiVersion) -> utoipa::openapi::OpenApi {
+    match version {
+        // v1は接頭辞のないドキュメントのAPIのパスに/v1を付け、全ての操作を非推奨にする
+        ApiVersion::V1 => {
+            let mut openapi = unversioned_openapi();
+            openapi.paths.paths = std::mem::take(&mut openapi.paths.paths)
+                .into_iter()
+                // メトリクスなどバージョンの対象外のパスを除く
+                .filter(|(path, _)| is_unversioned_api_path(path))
+                .map(|(path, mut item)| {
+                    for operation in item.operations.values_mut() {
+                        operation.deprecated = Some(utoipa::openapi::Deprecated::True);
+                    }
+                    (format!("{}{}", version.prefix(), path), item)
+                })
+                .collect();
+            openapi
+        }
+        ApiVersion::V2 => ApiDocV2::openapi(),
+    }
+}
+
+// v1のルーティングを作成する関数(レスポンスに非推奨のヘッダーを付ける)
+fn v1_routes(state: Arc<AppState>, versioning_config: Arc<VersioningConfig>) -> RouteTable {
+    RouteTable::new()
         // ユーザールーティングをマージ
         .merge(users::user_routes::user_routes(state.clone()))
         // 認証ルーティングをマージ
-        .merge(auth::auth_routes::auth_routes(state))
-        // Swagger UIをマージ
-        .merge(SwaggerUi::new("/swagger-ui")
-            .url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .merge(auth::auth_routes::auth_routes(state.clone()))
+        // ユーザールーティング(DI)をマージ
+        .merge(state.container.user_router().route_table())
+        .map_router(|router| {
+            router.layer(middleware::from_fn_with_state(versioning_config, deprecate_version))
+        })
+}
+
+// v2のルーティングを作成する関数
+fn v2_routes(state: Arc<AppState>) -> RouteTable {
+    RouteTable::new()
         // ユーザールーティングをマージ
-        .merge(container.user_router().routes())
-}
\ No newline at end of file
+        .merge(users::user_v2_routes::user_v2_routes(state.clone()))
+        // 認証ルーティングをマージ
+        .merge(auth::auth_v2_routes::auth_v2_routes(state))
+}
+
+// APIのルーティングを作成する関数(ドキュメント用のルートを除く全てのルート)
+//
+// /v1・/v2の下にバージョンごとのルートをネストし、接頭辞のないパスはv1として扱う
+pub fn api_routes(state: Arc<AppState>) -> RouteTable {
+    // バージョンの設定を取得
+    let versioning_config = Arc::new(state.versioning_config.clone());
+
+    RouteTable::new()
+        // 接頭辞のないルーティング(v1と同じ)をマージ
+        .merge(v1_routes(state.clone(), versioning_config.clone()))
+        // バージョンごとのルーティングをネスト
+        .nest(ApiVersion::V1.prefix(), v1_routes(state.clone(), versioning_config))
+        .nest(ApiVersion::V2.prefix(), v2_routes(state.clone()))
+        // OIDCのサインインのルーティングをマージ
+        .merge(auth::oidc_routes::oidc_routes(state.clone()))
+        // JWKSのルーティングをマージ
+        .merge(auth::jwks_routes::jwks_routes(state))
+        // メトリクスルーティングをマージ
+        .merge(metrics::metrics_routes::metrics_routes())
+}
+
+// ルーティングを作成する関数
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    // APIのルーティングにSwagger UIをマージ
+    let router = api_routes(state.clone())
+        .into_router()
+        .merge(SwaggerUi::new("/swagger-ui")
+            .url("/api-docs/openapi.json", unversioned_openapi())
+            .url(ApiVersion::V1.openapi_path(), openapi_for(ApiVersion::V1))
+            .url(ApiVersion::V2.openapi_path(), openapi_for(ApiVersion::V2)));
+
+    // マージした全ルートに共通のミドルウェアを適用
+    let router = apply_middleware_stack(router, state.clone());
+
+    // Acceptヘッダーによるバージョンの選択(ルーティングの前にパスを書き換えるため、全体を包む)
+    let versioning_config = Arc::new(state.versioning_config.clone());
+    Router::new()
+        .fallback_service(router)
+        .layer(middleware::from_fn_with_state(versioning_config, negotiate_version))
+}
@@ -0,0 +1,118 @@
+// 必要なクレートのインポート
+use axum::{
+    handler::Handler,
+    http::Method,
+    routing::{on, MethodFilter},
+    Router,
+};
+
+// 登録したルート(メソッドとパス)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RouteEntry {
+    // HTTPメソッド
+    pub method: Method,
+    // axumのパス(例: /users/:id)
+    pub path: String,
+}
+
+// RouteEntryの実装
+impl RouteEntry {
+    // OpenAPIのパス(例: /users/{id})に変換する関数
+    pub fn openapi_path(&self) -> String {
+        self.path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => format!("{{{}}}", name),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+// 登録したルートを記録しながらルーターを作成する構造体
+//
+// 記録したルートはOpenAPIドキュメントとの突き合わせ(全てのルートが文書化されているかのテスト)に使う
+pub struct RouteTable<S = ()> {
+    // ルーター
+    router: Router<S>,
+    // 登録したルート
+    entries: Vec<RouteEntry>,
+}
+
+// RouteTableの実装
+impl<S: Clone + Send + Sync + 'static> RouteTable<S> {
+    // コンストラクタ
+    pub fn new() -> Self {
+        Self {
+            router: Router::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    // ルートを登録する関数
+    pub fn route<H, T>(mut self, method: Method, path: &str, handler: H) -> Self
+    where
+        H: Handler<T, S>,
+        T: 'static,
+    {
+        let filter = MethodFilter::try_from(method.clone()).expect("HTTP method is supported by axum");
+        self.router = self.router.route(path, on(filter, handler));
+        self.entries.push(RouteEntry {
+            method,
+            path: path.to_string(),
+        });
+        self
+    }
+
+    // 別のテーブルのルートをマージする関数
+    pub fn merge(mut self, other: RouteTable<S>) -> Self {
+        self.router = self.router.merge(other.router);
+        self.entries.extend(other.entries);
+        self
+    }
+
+    // ルーターに状態を渡す関数
+    pub fn with_state<S2>(self, state: S) -> RouteTable<S2> {
+        RouteTable {
+            router: self.router.with_state(state),
+            entries: self.entries,
+        }
+    }
+
+    // ルーターを変換する関数(レイヤーの適用など、ルートを増やさない変換に使う)
+    pub fn map_router(mut self, f: impl FnOnce(Router<S>) -> Router<S>) -> Self {
+        self.router = f(self.router);
+        self
+    }
+
+    // 登録したルートを取得する関数
+    pub fn entries(&self) -> &[RouteEntry] {
+        &self.entries
+    }
+
+    // ルーターを取得する関数
+    pub fn into_router(self) -> Router<S> {
+        self.router
+    }
+}
+
+// 状態を渡し終えたテーブルの実装
+impl RouteTable {
+    // 接頭辞の下に別のテーブルのルートをネストする関数
+    pub fn nest(mut self, prefix: &str, other: RouteTable) -> Self {
+        self.router = self.router.nest(prefix, other.router);
+        self.entries.extend(other.entries.into_iter().map(|entry| RouteEntry {
+            path: format!("{}{}", prefix, entry.path),
+            ..entry
+        }));
+        self
+    }
+}
+
+// Defaultトレイトの実装
+impl<S: Clone + Send + Sync + 'static> Default for RouteTable<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
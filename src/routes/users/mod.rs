@@ -1,4 +1,5 @@
 // ユーザールーティングのモジュールの宣言
 pub mod user_routes;
+pub mod user_v2_routes;
 
 // ユーザールーティングのエントリーポイント
\ No newline at end of file
@@ -1,28 +1,33 @@
 // 必要なクレートのインポート
-use axum::{
-    routing::{get, post, put, delete},
-    Router,
-};
+use axum::http::Method;
 use std::sync::Arc;
+// ルートの一覧のインポート
+use crate::routes::route_table::RouteTable;
 // アプリケーションの状態のインポート
 use crate::state::app_state::AppState;
 // ユーザーサービスのインポート
-use crate::services::users::user_services;
+use crate::services::users::{user_services, me_services};
 
 // ユーザールーティングを作成する関数
-pub fn user_routes(state: Arc<AppState>) -> Router {
+pub fn user_routes(state: Arc<AppState>) -> RouteTable {
     // 新しいルーターを作成し、ユーザールーティングを設定
-    Router::new()
+    RouteTable::new()
         // ユーザーの一覧を取得するルートを設定 
-        .route("/users", get(user_services::get_users))
+        .route(Method::GET, "/users", user_services::get_users)
         // ユーザーを作成するルートを設定
-        .route("/users", post(user_services::create_user))
+        .route(Method::POST, "/users", user_services::create_user)
         // 特定のユーザーを取得するルートを設定
-        .route("/users/:id", get(user_services::get_user_by_id))
+        .route(Method::GET, "/users/:id", user_services::get_user_by_id)
         // ユーザーを更新するルートを設定
-        .route("/users/:id", put(user_services::update_user))
+        .route(Method::PUT, "/users/:id", user_services::update_user)
         // ユーザーを削除するルートを設定
-        .route("/users/:id", delete(user_services::delete_user))
+        .route(Method::DELETE, "/users/:id", user_services::delete_user)
+        // 自分のプロフィールを取得・更新・削除するルートを設定
+        .route(Method::GET, "/me", me_services::get_me)
+        .route(Method::PATCH, "/me", me_services::update_me)
+        .route(Method::DELETE, "/me", me_services::delete_me)
+        // 自分のパスワードを変更するルートを設定
+        .route(Method::POST, "/me/password", me_services::change_password)
         // アプリケーションの状態をルーターに渡す
         .with_state(state)
 }
\ No newline at end of file
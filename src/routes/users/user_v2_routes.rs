@@ -0,0 +1,34 @@
+// 必要なクレートのインポート
+use axum::http::Method;
+use std::sync::Arc;
+// ルートの一覧のインポート
+use crate::routes::route_table::RouteTable;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// ユーザーサービス(v2)のインポート
+use crate::services::users::{user_v2_services, me_v2_services};
+
+// ユーザールーティング(v2)を作成する関数
+//
+// /v2の下にネストして使用する
+pub fn user_v2_routes(state: Arc<AppState>) -> RouteTable {
+    RouteTable::new()
+        // ユーザーの一覧を取得するルートを設定
+        .route(Method::GET, "/users", user_v2_services::get_users)
+        // ユーザーを作成するルートを設定
+        .route(Method::POST, "/users", user_v2_services::create_user)
+        // 特定のユーザーを取得するルートを設定
+        .route(Method::GET, "/users/:id", user_v2_services::get_user_by_id)
+        // ユーザーを更新するルートを設定
+        .route(Method::PUT, "/users/:id", user_v2_services::update_user)
+        // ユーザーを削除するルートを設定
+        .route(Method::DELETE, "/users/:id", user_v2_services::delete_user)
+        // 自分のプロフィールを取得・更新・削除するルートを設定
+        .route(Method::GET, "/me", me_v2_services::get_me)
+        .route(Method::PATCH, "/me", me_v2_services::update_me)
+        .route(Method::DELETE, "/me", me_v2_services::delete_me)
+        // 自分のパスワードを変更するルートを設定
+        .route(Method::POST, "/me/password", me_v2_services::change_password)
+        // アプリケーションの状態をルーターに渡す
+        .with_state(state)
+}
@@ -0,0 +1,125 @@
+// 必要なクレートのインポート
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// APIキーのモデルのインポート
+use crate::models::auth::api_key::{ApiKeyResponse, CreateApiKeyRequest, CreatedApiKeyResponse};
+// 認証済みユーザーのエクストラクターのインポート
+use crate::extractors::auth::authenticated_user::AuthenticatedUser;
+
+// /me/api-keysの各関数はサインインしたユーザーのトークンでのみ呼び出せる
+// (漏えいしたAPIキーで新しいキーを発行できないよう、APIキーでの呼び出しは403を返す)
+
+// 自分のAPIキーの一覧を取得する関数(キーそのものは含まない)
+#[utoipa::path(
+    get,
+    path = "/me/api-keys",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "一覧取得成功(新しい順)", body = Vec<ApiKeyResponse>),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "APIキーでは呼び出せません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn list_api_keys(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+) -> Result<Json<Vec<ApiKeyResponse>>, (StatusCode, String)> {
+    list(&state, &caller).await.map(Json)
+}
+
+// APIキーの一覧を取得する関数(バージョン間で共通)
+pub(crate) async fn list(state: &AppState, caller: &AuthenticatedUser) -> Result<Vec<ApiKeyResponse>, (StatusCode, String)> {
+    caller.reject_api_key()?;
+    Ok(state.container.api_key_service().list(caller.id).await?)
+}
+
+// APIキーを発行する関数(キーはこのレスポンスでのみ返す)
+#[utoipa::path(
+    post,
+    path = "/me/api-keys",
+    request_body = CreateApiKeyRequest,
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 201, description = "発行成功", body = CreatedApiKeyResponse),
+        (status = 400, description = "名前・権限・有効日数が不正", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "APIキーでは呼び出せません", body = ErrorResponse),
+        (status = 409, description = "発行できるAPIキーの数の上限に達しています", body = ErrorResponse),
+        (status = 422, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn create_api_key(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> Result<(StatusCode, Json<CreatedApiKeyResponse>), (StatusCode, String)> {
+    issue(&state, &caller, request).await
+}
+
+// APIキーを発行する関数(バージョン間で共通)
+pub(crate) async fn issue(
+    state: &AppState,
+    caller: &AuthenticatedUser,
+    request: CreateApiKeyRequest,
+) -> Result<(StatusCode, Json<CreatedApiKeyResponse>), (StatusCode, String)> {
+    caller.reject_api_key()?;
+    let created = state
+        .container
+        .api_key_service()
+        .create(caller.id, request, &state.api_key_config)
+        .await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+// 自分のAPIキーを削除する関数(以降そのキーは使えない)
+#[utoipa::path(
+    delete,
+    path = "/me/api-keys/{id}",
+    params(
+        ("id" = crate::models::UuidWrapper, Path, description = "APIキーのID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 204, description = "削除成功"),
+        (status = 400, description = "無効なID", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "APIキーでは呼び出せません", body = ErrorResponse),
+        (status = 404, description = "APIキーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn delete_api_key(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    revoke(&state, &caller, id).await
+}
+
+// APIキーを削除する関数(バージョン間で共通、他のユーザーのキーは404)
+pub(crate) async fn revoke(state: &AppState, caller: &AuthenticatedUser, id: Uuid) -> Result<StatusCode, (StatusCode, String)> {
+    caller.reject_api_key()?;
+    state.container.api_key_service().delete(caller.id, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -2,71 +2,215 @@
 use axum::{
     extract::{State, Json},
     http::{StatusCode, HeaderMap},
+    response::{IntoResponse, Response},
 };
+use serde::Serialize;
 use std::sync::Arc;
-use bcrypt::verify;
-use jsonwebtoken::{encode, decode, Header, EncodingKey, DecodingKey, Validation};
 
 // アプリケーションの状態のインポート
 use crate::state::app_state::AppState;
 // 認証モデルのインポート
 use crate::models::auth::auth::{SignInCredentials, Claims, AuthResponse};
+use crate::models::auth::two_factor::TwoFactorChallenge;
+use crate::models::auth::api_key::ApiKeyScope;
+use crate::models::auth::session::DeviceInfo;
 // ユーザーモデル
 use crate::models::users::users::User;
 // 認証エラーのインポート
 use crate::errors::auth::auth_error::AuthError;
+// 認証済みユーザーのエクストラクターのインポート
+use crate::extractors::auth::authenticated_user::AuthenticatedUser;
+// メトリクスのインポート
+use crate::metrics::app_metrics::metrics;
+// PostgRESTのクエリビルダーのインポート
+use crate::supabase::postgrest_query::PostgrestQuery;
+// 二段階認証のチャレンジのインポート
+use crate::services::auth::two_factor_challenge_store::generate_challenge_token;
+// セッションのCookieのインポート
+use crate::services::auth::session_cookie::{with_session_cookies, without_session_cookies};
 
 // サインイン処理
 #[utoipa::path(
     post,
-    path = "/auth/sign-in",
+    path = "/auth/signin",
     request_body = SignInCredentials,
+    security(()),
     responses(
         (status = 200, description = "サインイン成功", body = AuthResponse),
-        (status = 401, description = "認証失敗"),
-        (status = 500, description = "サーバーエラー")
+        (status = 202, description = "二段階認証のコードが必要(/auth/2fa/verifyで検証する)", body = TwoFactorChallenge),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 429, description = "二段階認証のコードの失敗が多すぎる(しばらくしてから再度試す)", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
     ),
     tag = "auth"
 )]
 pub async fn sign_in(
     State(state): State<Arc<AppState>>,
+    device: DeviceInfo,
     Json(credentials): Json<SignInCredentials>,
-) -> Result<Json<AuthResponse>, (StatusCode, String)> {
-    // メールアドレスでユーザーを検索
-    let response = state
-        .client
-        .get(format!("{}/rest/v1/trans_users?email=eq.{}", state.supabase_url, credentials.email))
-        .header("apikey", &state.supabase_anon_key)
-        .send()
-        .await
-        .map_err(|e| AuthError::DatabaseError(e.to_string()))?;
+) -> Result<Response, (StatusCode, String)> {
+    Ok(sign_in_and_record(&state, credentials, &device).await?.into_response_as::<AuthResponse>(&state))
+}
+
+// サインインの結果
+pub(crate) enum SignInOutcome {
+    // トークンを発行した
+    Authenticated(AuthResponse),
+    // 二段階認証のコードが必要
+    TwoFactorRequired(TwoFactorChallenge),
+}
+
+// SignInOutcomeの実装
+impl SignInOutcome {
+    // バージョンごとの認証レスポンスに変換する関数(チャレンジは202で返す)
+    //
+    // Cookieのセッションが有効な場合は、トークンを発行したレスポンスにセッションのCookieを設定する
+    pub(crate) fn into_response_as<T: Serialize + From<AuthResponse>>(self, state: &AppState) -> Response {
+        match self {
+            SignInOutcome::Authenticated(response) => {
+                let token = response.token.clone();
+                with_session_cookies(&state.session_cookie_config, &token, Json(T::from(response)))
+            }
+            SignInOutcome::TwoFactorRequired(challenge) => (StatusCode::ACCEPTED, Json(challenge)).into_response(),
+        }
+    }
+}
+
+// サインインを実行し、結果をメトリクスに記録する関数(バージョン間で共通)
+//
+// 二段階認証のチャレンジを返した場合は、コードの検証の結果を記録する
+pub(crate) async fn sign_in_and_record(
+    state: &AppState,
+    credentials: SignInCredentials,
+    device: &DeviceInfo,
+) -> Result<SignInOutcome, (StatusCode, String)> {
+    let result = authenticate(state, credentials, device).await;
+    record_sign_in(&result);
 
-    let users: Vec<User> = response
-        .json()
+    result
+}
+
+// サインインの結果をメトリクスに記録する関数(二段階認証のチャレンジを返した場合は記録しない)
+pub(crate) fn record_sign_in<T>(result: &Result<SignInOutcome, T>) {
+    if !matches!(result, Ok(SignInOutcome::TwoFactorRequired(_))) {
+        metrics().record_sign_in(result.is_ok());
+    }
+}
+
+// 資格情報を検証し、トークン(二段階認証が有効な場合はチャレンジ)を発行する関数
+async fn authenticate(
+    state: &AppState,
+    credentials: SignInCredentials,
+    device: &DeviceInfo,
+) -> Result<SignInOutcome, (StatusCode, String)> {
+    // メールアドレスでユーザーを検索
+    let users: Vec<User> = state
+        .supabase
+        .select("find_by_email", &PostgrestQuery::new("trans_users").eq("email", &credentials.email))
         .await
-        .map_err(|e| AuthError::DatabaseError(e.to_string()))?;
+        .map_err(AuthError::from)?;
 
-    let user = users
+    let mut user = users
         .into_iter()
         .next()
         .ok_or(AuthError::InvalidCredentials)?;
 
-    // パスワードの検証
-    if !verify(&credentials.password, &user.password)
-        .map_err(|e| AuthError::DatabaseError(e.to_string()))? {
+    // パスワードの検証(保存されたハッシュのアルゴリズムで照合する)
+    let verification = state
+        .container
+        .password_hashers()
+        .verify(&credentials.password, &user.password)
+        .map_err(AuthError::from)?;
+    if !verification.matches {
         return Err(AuthError::InvalidCredentials.into());
     }
 
-    // JWTトークンの生成
-    let claims = Claims::new(&user.id);
-    let token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
-    )
-    .map_err(|e| AuthError::TokenCreationError(e.to_string()))?;
+    // 以前のアルゴリズム・コストのハッシュは現在の設定で作り直す
+    if verification.needs_rehash {
+        if let Some(password_hash) = rehash_password(state, &user, &credentials.password).await {
+            user.password = password_hash;
+        }
+    }
 
-    Ok(Json(AuthResponse { token, user }))
+    Ok(complete_sign_in(state, user, device).await?)
+}
+
+// 本人確認が済んだユーザーのトークン(二段階認証が有効な場合はチャレンジ)を発行する関数
+//
+// パスワード・外部のIdP(OIDC)のどちらで確認した場合も、二段階認証が有効なユーザーにはチャレンジを返す。
+// 有効にした直後のサインインでチャレンジを省かないよう、キャッシュを経由せず最新の行で判定する
+pub(crate) async fn complete_sign_in(state: &AppState, user: User, device: &DeviceInfo) -> Result<SignInOutcome, AuthError> {
+    let user = state.container.user_repository().find_by_id_uncached(user.id).await?;
+    if user.two_factor_enabled {
+        return Ok(SignInOutcome::TwoFactorRequired(issue_challenge(state, &user).await?));
+    }
+
+    Ok(SignInOutcome::Authenticated(issue_token(state, user, device).await?))
+}
+
+// 二段階認証のチャレンジを発行する関数
+//
+// コードの失敗が多すぎるユーザーには、チャレンジを発行し直して試行回数を増やせないよう発行しない
+async fn issue_challenge(state: &AppState, user: &User) -> Result<TwoFactorChallenge, AuthError> {
+    let config = &state.two_factor_config;
+    if state.two_factor_challenge_store.is_locked(user.id, config.max_user_failures).await {
+        return Err(AuthError::TwoFactorLocked);
+    }
+    let challenge_token = generate_challenge_token();
+    state
+        .two_factor_challenge_store
+        .insert(&challenge_token, user.id, config.challenge_ttl, config.max_challenge_attempts)
+        .await;
+
+    Ok(TwoFactorChallenge {
+        challenge_token,
+        expires_in: config.challenge_ttl.as_secs(),
+    })
+}
+
+// ユーザーのJWTトークンを発行する関数
+//
+// トークンごとにセッション(端末の情報)を記録し、トークンにセッションのIDを含める
+pub(crate) async fn issue_token(state: &AppState, user: User, device: &DeviceInfo) -> Result<AuthResponse, AuthError> {
+    let mut claims = Claims::new(&user.id);
+    let expires_at = chrono::DateTime::from_timestamp(claims.exp, 0)
+        .ok_or(AuthError::TokenCreationError("invalid expiration".to_string()))?
+        .naive_utc();
+    let session = state.container.session_service().create(user.id, device, expires_at).await?;
+    claims.sid = Some(session.id);
+
+    let token = state.jwt_keys.sign(&state.jwt_secret, claims)?;
+
+    Ok(AuthResponse { token, user })
+}
+
+// パスワードのハッシュを現在のアルゴリズム・コストで作り直す関数
+//
+// 失敗してもサインインは成功させ、次回のサインインで再度作り直す。書き込んだ場合は新しいハッシュを返す
+async fn rehash_password(state: &AppState, user: &User, password: &str) -> Option<String> {
+    let password_hash = match state.container.password_hashers().hash(password) {
+        Ok(password_hash) => password_hash,
+        Err(e) => {
+            tracing::warn!(user_id = %user.id, error = %e, "password rehash failed");
+            return None;
+        }
+    };
+
+    // 照合したハッシュのままの場合のみ書き込む(同時にパスワードが変更された場合は書き込まない)
+    match state
+        .container
+        .user_repository()
+        .rehash_password(user.id, user.password.clone(), password_hash.clone())
+        .await
+    {
+        Ok(true) => Some(password_hash),
+        Ok(false) => None,
+        Err(e) => {
+            tracing::warn!(user_id = %user.id, error = %e, "storing rehashed password failed");
+            None
+        }
+    }
 }
 
 // 認証状態チェック
@@ -78,8 +222,10 @@ pub async fn sign_in(
     ),
     responses(
         (status = 200, description = "認証成功", body = User),
-        (status = 401, description = "認証失敗"),
-        (status = 500, description = "サーバーエラー")
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
     ),
     tag = "auth"
 )]
@@ -87,53 +233,61 @@ pub async fn check_auth(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
 ) -> Result<Json<User>, (StatusCode, String)> {
-    // Authorizationヘッダーからトークンを取得
-    let token = headers
-        .get("Authorization")
-        .and_then(|value| value.to_str().ok())
-        .and_then(|value| value.strip_prefix("Bearer "))
-        .ok_or(AuthError::InvalidToken)?;
-
-    // トークンの検証
-    let claims = decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(state.jwt_secret.as_bytes()),
-        &Validation::default(),
-    )
-    .map_err(|_| AuthError::InvalidToken)?;
-
-    // ユーザー情報の取得
-    let response = state
-        .client
-        .get(format!("{}/rest/v1/trans_users?id=eq.{}", state.supabase_url, claims.claims.sub))
-        .header("apikey", &state.supabase_anon_key)
-        .send()
-        .await
-        .map_err(|e| AuthError::DatabaseError(e.to_string()))?;
+    authenticated_user(&state, &headers).await.map(Json)
+}
 
-    let users: Vec<User> = response
-        .json()
-        .await
-        .map_err(|e| AuthError::DatabaseError(e.to_string()))?;
+// Authorizationヘッダーのトークンを検証し、ユーザーを取得する関数(バージョン間で共通)
+pub(crate) async fn authenticated_user(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<User, (StatusCode, String)> {
+    // トークンを検証してユーザーIDを取得(APIキーの場合は参照の権限が必要)
+    let caller = AuthenticatedUser::from_headers(state, headers).await?;
+    caller.require_scope(ApiKeyScope::Read)?;
 
-    let user = users
-        .into_iter()
-        .next()
-        .ok_or(AuthError::UserNotFound)?;
+    // ユーザー情報の取得(Supabase Authのトークンの場合はトークンを転送し、それ以外はキャッシュつきのリポジトリから取得)
+    let user = caller
+        .container(state)
+        .user_repository()
+        .find_by_id(caller.id)
+        .await
+        .map_err(AuthError::from)?;
 
-    Ok(Json(user))
+    Ok(user)
 }
 
 // サインアウト
 #[utoipa::path(
     post,
-    path = "/auth/sign-out",
+    path = "/auth/signout",
+    security(()),
     responses(
-        (status = 200, description = "サインアウト成功", body = String),
-        (status = 500, description = "サーバーエラー")
+        (status = 200, description = "サインアウト成功(トークンのセッションを削除し、Cookieのセッションが有効な場合はセッションのCookieを削除)", body = String),
+        (status = 403, description = "セッションのCookieがあり、CSRFトークンがないまたは一致しない", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
     ),
     tag = "auth"
 )]
-pub async fn sign_out() -> Result<Json<String>, (StatusCode, String)> {
-    Ok(Json("Successfully signed out".to_string()))
+pub async fn sign_out(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, String)> {
+    Ok(sign_out_response(&state, &headers).await)
+}
+
+// サインアウトのレスポンスを作成する関数(バージョン間で共通)
+//
+// 有効なトークン(またはセッションのCookie)があれば、そのトークンのセッションを削除する。
+// 削除に失敗してもサインアウトは成功させる
+pub(crate) async fn sign_out_response(state: &AppState, headers: &HeaderMap) -> Response {
+    if let Ok(caller) = AuthenticatedUser::from_headers(state, headers).await {
+        if let Some(session_id) = caller.claims.sid {
+            match state.container.session_service().delete(caller.id, session_id).await {
+                Ok(()) | Err(AuthError::SessionNotFound) => {}
+                Err(e) => tracing::warn!(session_id = %session_id, error = ?e, "revoking session on sign out failed"),
+            }
+        }
+    }
+
+    without_session_cookies(&state.session_cookie_config, Json("Successfully signed out".to_string()))
 }
\ No newline at end of file
@@ -0,0 +1,84 @@
+// 必要なクレートのインポート
+use axum::{
+    extract::{State, Json},
+    http::{StatusCode, HeaderMap},
+    response::Response,
+};
+use std::sync::Arc;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// 認証モデルのインポート
+use crate::models::auth::auth::{SignInCredentials, AuthResponseV2};
+use crate::models::auth::session::DeviceInfo;
+// ユーザーモデル
+use crate::models::users::users::UserResponse;
+// 認証サービスのインポート(バージョン間で共通の処理)
+use crate::services::auth::auth_services::{authenticated_user, sign_in_and_record, sign_out_response};
+
+// サインイン処理
+#[utoipa::path(
+    post,
+    path = "/v2/auth/signin",
+    request_body = SignInCredentials,
+    security(()),
+    responses(
+        (status = 200, description = "サインイン成功", body = AuthResponseV2),
+        (status = 202, description = "二段階認証のコードが必要(/v2/auth/2fa/verifyで検証する)", body = TwoFactorChallenge),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 429, description = "二段階認証のコードの失敗が多すぎる(しばらくしてから再度試す)", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn sign_in(
+    State(state): State<Arc<AppState>>,
+    device: DeviceInfo,
+    Json(credentials): Json<SignInCredentials>,
+) -> Result<Response, (StatusCode, String)> {
+    Ok(sign_in_and_record(&state, credentials, &device).await?.into_response_as::<AuthResponseV2>(&state))
+}
+
+// 認証状態チェック
+#[utoipa::path(
+    get,
+    path = "/v2/auth/check",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "認証成功", body = UserResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn check_auth(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<UserResponse>, (StatusCode, String)> {
+    authenticated_user(&state, &headers)
+        .await
+        .map(|user| Json(user.into()))
+}
+
+// サインアウト
+#[utoipa::path(
+    post,
+    path = "/v2/auth/signout",
+    security(()),
+    responses(
+        (status = 200, description = "サインアウト成功(トークンのセッションを削除し、Cookieのセッションが有効な場合はセッションのCookieを削除)", body = String),
+        (status = 403, description = "セッションのCookieがあり、CSRFトークンがないまたは一致しない", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn sign_out(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, String)> {
+    Ok(sign_out_response(&state, &headers).await)
+}
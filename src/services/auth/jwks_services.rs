@@ -0,0 +1,35 @@
+// 必要なクレートのインポート
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// JWKSのモデルのインポート
+use crate::models::auth::jwks::JwksResponse;
+
+// JWKSをキャッシュしてよい秒数(ローテーションで追加した鍵が使われるまでの時間より短くする)
+const JWKS_MAX_AGE_SECS: u64 = 5 * 60;
+
+// トークンの検証に使う公開鍵を取得する関数
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    security(()),
+    responses(
+        (status = 200, description = "公開鍵の取得成功", body = JwksResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn get_jwks(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let cache_control = format!("public, max-age={}", JWKS_MAX_AGE_SECS);
+    Ok((
+        [(header::CACHE_CONTROL, cache_control)],
+        Json(JwksResponse { keys: state.jwt_keys.jwks() }),
+    ))
+}
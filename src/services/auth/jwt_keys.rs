@@ -0,0 +1,217 @@
+// 必要なクレートのインポート
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::jwk::{Jwk, PublicKeyUse};
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::signature::{Ed25519KeyPair, KeyPair, RsaKeyPair};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+// 設定のインポート
+use crate::config::jwt_config::JwtConfig;
+// 認証モデルのインポート
+use crate::models::auth::auth::Claims;
+// 認証エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+// 鍵の種類とアルゴリズムの判定(OIDCのIDトークンと共通)
+use crate::services::auth::oidc_client::key_matches_algorithm;
+
+// 署名の鍵
+struct SigningKey {
+    // 鍵ID(トークンのヘッダーのkid)
+    kid: String,
+    // 秘密鍵
+    key: EncodingKey,
+}
+
+// 検証の鍵(公開鍵)
+struct VerificationKey {
+    // 公開鍵のJWK(JWKSで公開する)
+    jwk: Jwk,
+    // 検証に使う鍵
+    key: DecodingKey,
+}
+
+// このサーバーが発行するトークンの署名・検証の鍵
+//
+// HS256の場合はJWTシークレット(AppState::jwt_secret)で署名・検証する。
+// RS256・EdDSAの場合は秘密鍵で署名してkidを付け、署名の鍵と検証のみの鍵(ローテーション前の鍵)のkidで検証する
+pub struct JwtKeys {
+    // 署名のアルゴリズム
+    algorithm: Algorithm,
+    // 署名の鍵(HS256の場合はNone)
+    signing: Option<SigningKey>,
+    // 検証の鍵(公開鍵、先頭が署名の鍵)
+    verification: Vec<VerificationKey>,
+    // 発行者
+    issuer: Option<String>,
+    // 対象者
+    audience: Option<String>,
+}
+
+// JwtKeysの実装
+impl JwtKeys {
+    // 設定から鍵を読み込む関数(HS256でJWTシークレットが空の場合、秘密鍵・公開鍵が不正な場合はエラー)
+    pub fn from_config(config: &JwtConfig) -> Result<Self, String> {
+        let mut verification = Vec::new();
+
+        let signing = match config.algorithm {
+            // 空のシークレットでは誰でもトークンを作れるので受け付けない
+            Algorithm::HS256 if config.secret.as_deref().unwrap_or_default().is_empty() => {
+                return Err("JWT_SECRET is required for HS256".to_string());
+            }
+            Algorithm::HS256 => None,
+            Algorithm::RS256 | Algorithm::EdDSA => {
+                let pem = config
+                    .signing_key
+                    .as_deref()
+                    .ok_or_else(|| format!("JWT_SIGNING_KEY is required for {:?}", config.algorithm))?;
+                let (key, public_jwk) = load_signing_key(config.algorithm, pem)?;
+                let kid = config.signing_key_id.clone().unwrap_or_else(|| thumbprint(&public_jwk));
+                let mut public_jwk = public_jwk;
+                public_jwk["kid"] = json!(kid);
+                verification.push(verification_key(public_jwk)?);
+                Some(SigningKey { kid, key })
+            }
+            algorithm => return Err(format!("unsupported JWT algorithm {:?}", algorithm)),
+        };
+
+        // 検証のみに使う鍵(kidのない鍵は使わない)
+        if let Some(jwks) = &config.verification_keys {
+            let document: serde_json::Value =
+                serde_json::from_str(jwks).map_err(|e| format!("JWT_VERIFICATION_KEYS is not valid JSON: {}", e))?;
+            let keys = document["keys"]
+                .as_array()
+                .ok_or("JWT_VERIFICATION_KEYS has no keys")?;
+            for key in keys {
+                if key["kid"].is_string() {
+                    verification.push(verification_key(key.clone())?);
+                }
+            }
+        }
+
+        Ok(Self {
+            algorithm: config.algorithm,
+            signing,
+            verification,
+            issuer: config.issuer.clone(),
+            audience: config.audience.clone(),
+        })
+    }
+
+    // トークンに署名する関数(設定した発行者・対象者を含める)
+    pub fn sign(&self, jwt_secret: &str, mut claims: Claims) -> Result<String, AuthError> {
+        claims.iss = self.issuer.clone();
+        claims.aud = self.audience.clone();
+
+        let (header, key) = match &self.signing {
+            Some(signing) => {
+                let mut header = Header::new(self.algorithm);
+                header.kid = Some(signing.kid.clone());
+                (header, signing.key.clone())
+            }
+            None => (Header::new(Algorithm::HS256), EncodingKey::from_secret(jwt_secret.as_bytes())),
+        };
+
+        encode(&header, &claims, &key).map_err(|e| AuthError::TokenCreationError(e.to_string()))
+    }
+
+    // トークンを検証し、クレームを返す関数(署名・有効期限・発行者・対象者)
+    pub fn verify(&self, jwt_secret: &str, token: &str) -> Result<Claims, AuthError> {
+        let header = decode_header(token).map_err(|_| AuthError::InvalidToken)?;
+        let key = match (header.alg, header.kid.as_deref()) {
+            // HS256のトークンはHS256で署名している場合のみ受け付ける
+            (Algorithm::HS256, _) if self.signing.is_none() => DecodingKey::from_secret(jwt_secret.as_bytes()),
+            (Algorithm::HS256, _) => return Err(AuthError::InvalidToken),
+            (algorithm, Some(kid)) => self
+                .verification
+                .iter()
+                .find(|key| {
+                    key.jwk.common.key_id.as_deref() == Some(kid)
+                        && key.jwk.common.algorithm.is_none_or(|alg| alg == algorithm)
+                        && key_matches_algorithm(&key.jwk, algorithm)
+                })
+                .map(|key| key.key.clone())
+                .ok_or(AuthError::InvalidToken)?,
+            (_, None) => return Err(AuthError::InvalidToken),
+        };
+
+        let mut validation = Validation::new(header.alg);
+        let mut required = vec!["exp", "sub"];
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+            required.push("iss");
+        }
+        if let Some(audience) = &self.audience {
+            validation.set_audience(&[audience]);
+            required.push("aud");
+        }
+        validation.set_required_spec_claims(&required);
+
+        decode::<Claims>(token, &key, &validation)
+            .map(|data| data.claims)
+            .map_err(|_| AuthError::InvalidToken)
+    }
+
+    // 公開鍵のJWKSを取得する関数(HS256の場合は空)
+    pub fn jwks(&self) -> Vec<Jwk> {
+        self.verification.iter().map(|key| key.jwk.clone()).collect()
+    }
+}
+
+// 秘密鍵(PEM)を読み込み、署名の鍵と公開鍵のJWKを返す関数
+fn load_signing_key(algorithm: Algorithm, pem: &str) -> Result<(EncodingKey, serde_json::Value), String> {
+    let parsed = pem::parse(pem).map_err(|e| format!("JWT_SIGNING_KEY is not valid PEM: {}", e))?;
+    let der = parsed.contents.as_slice();
+
+    match algorithm {
+        Algorithm::RS256 => {
+            let key_pair = match parsed.tag.as_str() {
+                "RSA PRIVATE KEY" => RsaKeyPair::from_der(der),
+                _ => RsaKeyPair::from_pkcs8(der),
+            }
+            .map_err(|e| format!("JWT_SIGNING_KEY is not a valid RSA private key: {}", e))?;
+            let public_key = key_pair.public_key();
+            let jwk = json!({
+                "kty": "RSA",
+                "n": URL_SAFE_NO_PAD.encode(public_key.modulus().big_endian_without_leading_zero()),
+                "e": URL_SAFE_NO_PAD.encode(public_key.exponent().big_endian_without_leading_zero()),
+                "use": "sig",
+                "alg": "RS256",
+            });
+            let key = EncodingKey::from_rsa_pem(pem.as_bytes()).map_err(|e| e.to_string())?;
+            Ok((key, jwk))
+        }
+        Algorithm::EdDSA => {
+            let key_pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(der)
+                .map_err(|e| format!("JWT_SIGNING_KEY is not a valid Ed25519 private key: {}", e))?;
+            let jwk = json!({
+                "kty": "OKP",
+                "crv": "Ed25519",
+                "x": URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+                "use": "sig",
+                "alg": "EdDSA",
+            });
+            Ok((EncodingKey::from_ed_der(der), jwk))
+        }
+        algorithm => Err(format!("unsupported JWT algorithm {:?}", algorithm)),
+    }
+}
+
+// JWKから検証の鍵を作成する関数
+fn verification_key(jwk: serde_json::Value) -> Result<VerificationKey, String> {
+    let jwk: Jwk = serde_json::from_value(jwk).map_err(|e| format!("invalid JWK: {}", e))?;
+    if matches!(jwk.common.public_key_use, Some(PublicKeyUse::Encryption)) {
+        return Err("JWK for encryption cannot verify tokens".to_string());
+    }
+    let key = DecodingKey::from_jwk(&jwk).map_err(|e| format!("invalid JWK: {}", e))?;
+    Ok(VerificationKey { jwk, key })
+}
+
+// 公開鍵のサムプリントを計算する関数(RFC 7638、鍵IDの既定値)
+pub(crate) fn thumbprint(jwk: &serde_json::Value) -> String {
+    // 必須のメンバーのみを辞書順に並べる
+    let canonical = match jwk["kty"].as_str() {
+        Some("RSA") => format!(r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#, jwk["e"].as_str().unwrap_or_default(), jwk["n"].as_str().unwrap_or_default()),
+        _ => format!(r#"{{"crv":"{}","kty":"OKP","x":"{}"}}"#, jwk["crv"].as_str().unwrap_or_default(), jwk["x"].as_str().unwrap_or_default()),
+    };
+    URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+}
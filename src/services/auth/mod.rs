@@ -1,4 +1,24 @@
 // 認証サービスのモジュールの宣言
+pub mod api_key_services;
+pub mod api_key_v2_services;
 pub mod auth_services;
+pub mod auth_v2_services;
+pub mod jwks_services;
+pub mod jwt_keys;
+pub mod oidc_client;
+pub mod oidc_login_store;
+pub mod oidc_services;
+pub mod session_cookie;
+pub mod session_services;
+pub mod session_v2_services;
+pub mod supabase_jwt_verifier;
+pub mod token_revocation_store;
+pub mod totp;
+pub mod two_factor_challenge_store;
+pub mod two_factor_services;
+pub mod two_factor_v2_services;
+
+#[cfg(test)]
+mod tests;
 
 // 認証サービスのエントリーポイント
\ No newline at end of file
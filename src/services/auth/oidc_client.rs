@@ -0,0 +1,399 @@
+// 必要なクレートのインポート
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::jwk::{AlgorithmParameters, Jwk, PublicKeyUse};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use rand::RngCore;
+use reqwest::{header, Client, Url};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tokio::time::Instant;
+// 設定のインポート
+use crate::config::oidc_config::{OidcConfig, OidcProviderConfig};
+// エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+
+// state・nonce・PKCEのコード検証子のバイト数
+const RANDOM_TOKEN_BYTES: usize = 32;
+// ディスカバリーのパス(発行者のURLに付ける)
+const DISCOVERY_PATH: &str = "/.well-known/openid-configuration";
+// IDトークンの署名に受け付けるアルゴリズム(公開鍵の署名のみ、HS256などの共通鍵は受け付けない)
+pub(crate) const ALLOWED_ALGORITHMS: [Algorithm; 9] = [
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+// state・nonce・PKCEのコード検証子に使うランダムな文字列を生成する関数(Base64URL)
+pub fn generate_random_token() -> String {
+    let mut bytes = [0u8; RANDOM_TOKEN_BYTES];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+// PKCEのコードチャレンジを計算する関数(S256、RFC 7636)
+pub fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+// IdPのメタデータ(ディスカバリーのレスポンスのうち使う項目)
+#[derive(Deserialize, Clone, Debug)]
+pub struct ProviderMetadata {
+    // 発行者
+    pub issuer: String,
+    // 認可エンドポイント
+    pub authorization_endpoint: String,
+    // トークンエンドポイント
+    pub token_endpoint: String,
+    // JWKSのURL
+    pub jwks_uri: String,
+    // トークンエンドポイントのクライアント認証の方式(省略時はclient_secret_basic)
+    #[serde(default)]
+    pub token_endpoint_auth_methods_supported: Option<Vec<String>>,
+}
+
+// ProviderMetadataの実装
+impl ProviderMetadata {
+    // クライアントシークレットをリクエストボディで送るか(client_secret_basicに対応していない場合のみ)
+    fn uses_client_secret_post(&self) -> bool {
+        self.token_endpoint_auth_methods_supported.as_ref().is_some_and(|methods| {
+            methods.iter().any(|method| method == "client_secret_post")
+                && !methods.iter().any(|method| method == "client_secret_basic")
+        })
+    }
+}
+
+// 検証したIDトークンのユーザー
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedIdentity {
+    // IdPのユーザーID(sub)
+    pub subject: String,
+    // メールアドレス
+    pub email: Option<String>,
+    // IdPがメールアドレスを確認しているか
+    pub email_verified: bool,
+}
+
+// トークンエンドポイントのレスポンス
+#[derive(Deserialize)]
+struct TokenResponse {
+    // IDトークン
+    id_token: Option<String>,
+}
+
+// JWKS(形式の分からない鍵を読み飛ばすため、鍵ごとに変換する)
+#[derive(Deserialize)]
+struct JwksDocument {
+    // 鍵
+    keys: Vec<serde_json::Value>,
+}
+
+// IDトークンのクレーム(exp・iss・audはjsonwebtokenで検証する)
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    // IdPのユーザーID
+    sub: String,
+    // メールアドレス
+    #[serde(default)]
+    email: Option<String>,
+    // メールアドレスの確認(文字列で返すIdPもあるため、どちらも受け付ける)
+    #[serde(default)]
+    email_verified: Option<serde_json::Value>,
+    // サインインの開始時に送ったnonce
+    #[serde(default)]
+    nonce: Option<String>,
+}
+
+// キャッシュした値
+struct Cached<T> {
+    // 値
+    value: Arc<T>,
+    // 取得した時刻
+    fetched_at: Instant,
+}
+
+// OpenID Connectのクライアント(認可コードフロー + PKCE)
+//
+// ディスカバリーとJWKSはプロバイダーごとにキャッシュし、未知の鍵(kid)の場合はJWKSを取得し直す
+pub struct OidcClient {
+    // HTTPクライアント
+    http: Client,
+    // 1回の呼び出しのタイムアウト
+    timeout: Duration,
+    // ディスカバリー・JWKSをキャッシュする期間
+    metadata_ttl: Duration,
+    // プロバイダー名ごとのメタデータ
+    metadata: Mutex<HashMap<String, Cached<ProviderMetadata>>>,
+    // プロバイダー名ごとの署名の鍵
+    jwks: Mutex<HashMap<String, Cached<Vec<Jwk>>>>,
+}
+
+// OidcClientの実装
+impl OidcClient {
+    // コンストラクタ
+    pub fn new(http: Client, config: &OidcConfig) -> Self {
+        Self {
+            http,
+            timeout: config.http_timeout,
+            metadata_ttl: config.metadata_ttl,
+            metadata: Mutex::new(HashMap::new()),
+            jwks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // 認可エンドポイントのURLを作成する関数
+    pub async fn authorization_url(
+        &self,
+        provider: &OidcProviderConfig,
+        state: &str,
+        nonce: &str,
+        code_challenge: &str,
+    ) -> Result<String, AuthError> {
+        let metadata = self.metadata(provider).await?;
+        let url = Url::parse_with_params(&metadata.authorization_endpoint, [
+            ("response_type", "code"),
+            ("client_id", provider.client_id.as_str()),
+            ("redirect_uri", provider.redirect_uri.as_str()),
+            ("scope", provider.scopes.as_str()),
+            ("state", state),
+            ("nonce", nonce),
+            ("code_challenge", code_challenge),
+            ("code_challenge_method", "S256"),
+        ])
+        .map_err(|e| AuthError::IdentityProviderError(format!("invalid authorization endpoint: {}", e)))?;
+
+        Ok(url.into())
+    }
+
+    // 認可コードをトークンに交換し、IDトークンを返す関数
+    pub async fn exchange_code(
+        &self,
+        provider: &OidcProviderConfig,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<String, AuthError> {
+        let metadata = self.metadata(provider).await?;
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", provider.redirect_uri.as_str()),
+            ("code_verifier", code_verifier),
+        ];
+        let mut request = self
+            .http
+            .post(&metadata.token_endpoint)
+            .timeout(self.timeout)
+            .header(header::ACCEPT, "application/json");
+
+        // クライアント認証(シークレットがない場合は公開クライアントとしてclient_idのみ送る)
+        match &provider.client_secret {
+            Some(secret) if metadata.uses_client_secret_post() => {
+                form.push(("client_id", provider.client_id.as_str()));
+                form.push(("client_secret", secret.as_str()));
+            }
+            Some(secret) => request = request.basic_auth(&provider.client_id, Some(secret)),
+            None => form.push(("client_id", provider.client_id.as_str())),
+        }
+
+        let response = request.form(&form).send().await.map_err(|e| transport_error(provider, e))?;
+        let status = response.status();
+        if !status.is_success() {
+            // レスポンスの内容はクライアントに返さず、ログにのみ残す
+            let body = response.text().await.unwrap_or_default();
+            tracing::warn!(provider = %provider.name, status = status.as_u16(), body = %body, "OIDC token exchange failed");
+            return Err(AuthError::IdentityProviderError(format!("token endpoint returned {}", status)));
+        }
+
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| AuthError::IdentityProviderError(format!("invalid token response: {}", e)))?;
+        token
+            .id_token
+            .ok_or_else(|| AuthError::IdentityProviderError("token response has no id_token".to_string()))
+    }
+
+    // IDトークンを検証する関数(署名・iss・aud・exp・nonce)
+    pub async fn verify_id_token(
+        &self,
+        provider: &OidcProviderConfig,
+        id_token: &str,
+        nonce: &str,
+    ) -> Result<VerifiedIdentity, AuthError> {
+        let header = decode_header(id_token).map_err(|e| AuthError::InvalidIdToken(e.to_string()))?;
+        if !ALLOWED_ALGORITHMS.contains(&header.alg) {
+            return Err(AuthError::InvalidIdToken(format!("unsupported algorithm {:?}", header.alg)));
+        }
+
+        // 鍵のローテーションに備えて、見つからない場合はJWKSを取得し直す
+        let key = match self.find_key(provider, header.kid.as_deref(), header.alg, false).await? {
+            Some(key) => key,
+            None => self
+                .find_key(provider, header.kid.as_deref(), header.alg, true)
+                .await?
+                .ok_or_else(|| AuthError::InvalidIdToken("no matching signing key".to_string()))?,
+        };
+
+        // 発行者はディスカバリーの値と完全に一致させる
+        let metadata = self.metadata(provider).await?;
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&metadata.issuer]);
+        validation.set_audience(&[&provider.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|e| AuthError::InvalidIdToken(e.to_string()))?
+            .claims;
+
+        // 別のサインインで発行されたIDトークンの再利用を防ぐ
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(AuthError::InvalidIdToken("nonce mismatch".to_string()));
+        }
+
+        let email_verified = match &claims.email_verified {
+            Some(serde_json::Value::Bool(verified)) => *verified,
+            Some(serde_json::Value::String(verified)) => verified == "true",
+            _ => false,
+        };
+
+        Ok(VerifiedIdentity {
+            subject: claims.sub,
+            email: claims.email,
+            email_verified,
+        })
+    }
+
+    // IDトークンのヘッダーに合う署名の鍵を探す関数
+    //
+    // kidがない場合は、アルゴリズムに合う鍵が1つだけのときのみ使う
+    async fn find_key(
+        &self,
+        provider: &OidcProviderConfig,
+        kid: Option<&str>,
+        algorithm: Algorithm,
+        refresh: bool,
+    ) -> Result<Option<DecodingKey>, AuthError> {
+        let keys = self.jwks(provider, refresh).await?;
+        let mut candidates = keys.iter().filter(|jwk| {
+            kid.is_none_or(|kid| jwk.common.key_id.as_deref() == Some(kid))
+                && jwk.common.algorithm.is_none_or(|alg| alg == algorithm)
+                && !matches!(jwk.common.public_key_use, Some(PublicKeyUse::Encryption))
+                && key_matches_algorithm(jwk, algorithm)
+        });
+
+        let jwk = match (candidates.next(), candidates.next()) {
+            (Some(jwk), None) => jwk,
+            (Some(jwk), Some(_)) if kid.is_some() => jwk,
+            _ => return Ok(None),
+        };
+        DecodingKey::from_jwk(jwk)
+            .map(Some)
+            .map_err(|e| AuthError::InvalidIdToken(format!("invalid signing key: {}", e)))
+    }
+
+    // ディスカバリーのメタデータを取得する関数(キャッシュの期間内はキャッシュを返す)
+    async fn metadata(&self, provider: &OidcProviderConfig) -> Result<Arc<ProviderMetadata>, AuthError> {
+        if let Some(metadata) = self.cached(&self.metadata, &provider.name) {
+            return Ok(metadata);
+        }
+
+        let url = format!("{}{}", provider.issuer.trim_end_matches('/'), DISCOVERY_PATH);
+        let metadata: ProviderMetadata = self.get_json(provider, &url).await?;
+        // 別の発行者のメタデータを使わないよう、設定の発行者と一致することを確認する
+        if metadata.issuer.trim_end_matches('/') != provider.issuer.trim_end_matches('/') {
+            return Err(AuthError::IdentityProviderError(format!(
+                "discovery issuer {} does not match {}",
+                metadata.issuer, provider.issuer
+            )));
+        }
+
+        Ok(self.store(&self.metadata, &provider.name, metadata))
+    }
+
+    // 署名の鍵を取得する関数(refreshがtrueの場合はキャッシュを使わない)
+    async fn jwks(&self, provider: &OidcProviderConfig, refresh: bool) -> Result<Arc<Vec<Jwk>>, AuthError> {
+        if !refresh {
+            if let Some(keys) = self.cached(&self.jwks, &provider.name) {
+                return Ok(keys);
+            }
+        }
+
+        let metadata = self.metadata(provider).await?;
+        let document: JwksDocument = self.get_json(provider, &metadata.jwks_uri).await?;
+        // 対応していない形式の鍵(暗号化用の鍵など)は読み飛ばす
+        let keys = document
+            .keys
+            .into_iter()
+            .filter_map(|key| serde_json::from_value::<Jwk>(key).ok())
+            .collect();
+
+        Ok(self.store(&self.jwks, &provider.name, keys))
+    }
+
+    // GETでJSONを取得する関数
+    async fn get_json<T: DeserializeOwned>(&self, provider: &OidcProviderConfig, url: &str) -> Result<T, AuthError> {
+        let response = self
+            .http
+            .get(url)
+            .timeout(self.timeout)
+            .header(header::ACCEPT, "application/json")
+            .send()
+            .await
+            .map_err(|e| transport_error(provider, e))?;
+        let status = response.status();
+        if !status.is_success() {
+            tracing::warn!(provider = %provider.name, url, status = status.as_u16(), "OIDC provider request failed");
+            return Err(AuthError::IdentityProviderError(format!("{} returned {}", url, status)));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AuthError::IdentityProviderError(format!("invalid response from {}: {}", url, e)))
+    }
+
+    // キャッシュの期間内の値を返す関数
+    fn cached<T>(&self, cache: &Mutex<HashMap<String, Cached<T>>>, name: &str) -> Option<Arc<T>> {
+        let cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache
+            .get(name)
+            .filter(|cached| cached.fetched_at.elapsed() < self.metadata_ttl)
+            .map(|cached| cached.value.clone())
+    }
+
+    // 値をキャッシュに保存する関数
+    fn store<T>(&self, cache: &Mutex<HashMap<String, Cached<T>>>, name: &str, value: T) -> Arc<T> {
+        let value = Arc::new(value);
+        let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.insert(name.to_string(), Cached { value: value.clone(), fetched_at: Instant::now() });
+        value
+    }
+}
+
+// 鍵の種類がアルゴリズムに合うか判定する関数
+pub(crate) fn key_matches_algorithm(jwk: &Jwk, algorithm: Algorithm) -> bool {
+    match &jwk.algorithm {
+        AlgorithmParameters::RSA(_) => matches!(
+            algorithm,
+            Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 | Algorithm::PS256 | Algorithm::PS384 | Algorithm::PS512
+        ),
+        AlgorithmParameters::EllipticCurve(_) => matches!(algorithm, Algorithm::ES256 | Algorithm::ES384),
+        AlgorithmParameters::OctetKeyPair(_) => algorithm == Algorithm::EdDSA,
+        // 共通鍵は受け付けない
+        AlgorithmParameters::OctetKey(_) => false,
+    }
+}
+
+// 通信エラーを記録してエラーを返す関数
+fn transport_error(provider: &OidcProviderConfig, error: reqwest::Error) -> AuthError {
+    tracing::warn!(provider = %provider.name, error = %error, "OIDC provider is unreachable");
+    AuthError::IdentityProviderError(error.to_string())
+}
@@ -0,0 +1,102 @@
+// 必要なクレートのインポート
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use async_trait::async_trait;
+use tokio::time::Instant;
+
+// 保存するサインインの数の上限(超えた場合は期限の近いものから削除する)
+const DEFAULT_CAPACITY: usize = 10_000;
+
+// 開始したOIDCのサインイン(コールバックで照合する値)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingLogin {
+    // プロバイダー名
+    pub provider: String,
+    // IDトークンのnonceと照合する値
+    pub nonce: String,
+    // PKCEのコード検証子(トークンの交換で送る)
+    pub code_verifier: String,
+}
+
+// 開始したOIDCのサインインをstateごとに保存するストアのトレイト
+#[async_trait]
+pub trait OidcLoginStore: Send + Sync {
+    // サインインを保存する
+    async fn insert(&self, state: &str, login: PendingLogin, ttl: Duration);
+    // サインインを取り出して削除する(期限切れ・使用済みはNone)
+    async fn take(&self, state: &str) -> Option<PendingLogin>;
+}
+
+// エントリ
+struct Entry {
+    // 開始したサインイン
+    login: PendingLogin,
+    // 有効期限
+    expires_at: Instant,
+}
+
+// メモリ上にサインインを保持するストア(複数のインスタンスで共有する場合は別の実装に差し替える)
+//
+// 開始は認証なしで呼び出せるため、保存する数に上限を設けてメモリを使い切らないようにする
+pub struct InMemoryOidcLoginStore {
+    // stateごとのエントリ
+    entries: Mutex<HashMap<String, Entry>>,
+    // 保存するサインインの数の上限
+    capacity: usize,
+}
+
+// InMemoryOidcLoginStoreの実装
+impl InMemoryOidcLoginStore {
+    // コンストラクタ
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    // 保存するサインインの数の上限を指定するコンストラクタ
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            capacity: capacity.max(1),
+        }
+    }
+}
+
+// Defaultトレイトの実装
+impl Default for InMemoryOidcLoginStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// トレイト実装
+#[async_trait]
+impl OidcLoginStore for InMemoryOidcLoginStore {
+    async fn insert(&self, state: &str, login: PendingLogin, ttl: Duration) {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        // 上限に達した場合は期限切れのものを削除し、それでも空かない場合は期限の最も近いものを削除
+        if entries.len() >= self.capacity {
+            entries.retain(|_, entry| entry.expires_at > now);
+        }
+        while entries.len() >= self.capacity {
+            let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires_at)
+                .map(|(state, _)| state.clone())
+            else {
+                break;
+            };
+            entries.remove(&oldest);
+        }
+
+        entries.insert(state.to_string(), Entry { login, expires_at: now + ttl });
+    }
+
+    async fn take(&self, state: &str) -> Option<PendingLogin> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = entries.remove(state)?;
+        (entry.expires_at > Instant::now()).then_some(entry.login)
+    }
+}
@@ -0,0 +1,183 @@
+// 必要なクレートのインポート
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ring::constant_time::verify_slices_are_equal;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// 認証モデルのインポート
+use crate::models::auth::auth::AuthResponseV2;
+use crate::models::auth::oidc::OidcCallbackParams;
+use crate::models::auth::session::DeviceInfo;
+// 認証エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+// 認証サービスのインポート(パスワードのサインインと共通の処理)
+use crate::services::auth::auth_services::{complete_sign_in, record_sign_in, SignInOutcome};
+// OIDCのクライアントのインポート
+use crate::services::auth::oidc_client::{generate_random_token, pkce_challenge};
+// 開始したサインインのストアのインポート
+use crate::services::auth::oidc_login_store::PendingLogin;
+// Cookieの読み取りのインポート
+use crate::services::auth::session_cookie::cookie_value;
+
+// サインインを開始したブラウザとstateを結び付けるCookieの名前(stateのハッシュを保存する)
+const LOGIN_STATE_COOKIE: &str = "oidc_login_state";
+// Cookieを送るパス(開始とコールバックのみ)
+const LOGIN_STATE_COOKIE_PATH: &str = "/auth/oidc";
+
+// OIDCのサインインの開始(IdPの認可エンドポイントへリダイレクト)
+#[utoipa::path(
+    get,
+    path = "/auth/oidc/{provider}/start",
+    params(
+        ("provider" = String, Path, description = "プロバイダー名(OIDC_PROVIDERS)")
+    ),
+    security(()),
+    responses(
+        (status = 302, description = "IdPの認可エンドポイントへリダイレクト", headers(
+            ("location" = String, description = "認可エンドポイントのURL(state・nonce・PKCEのコードチャレンジを含む)"),
+            ("set-cookie" = String, description = "stateをこのブラウザに結び付けるCookie(HttpOnly、コールバックで照合する)")
+        )),
+        (status = 404, description = "設定されていないプロバイダー", body = ErrorResponse),
+        (status = 429, description = "開始の回数が多すぎる", body = ErrorResponse),
+        (status = 502, description = "IdPのディスカバリーの取得に失敗", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn start_oidc_login(
+    State(state): State<Arc<AppState>>,
+    Path(provider): Path<String>,
+) -> Result<Response, (StatusCode, String)> {
+    let provider = state.oidc_config.provider(&provider).ok_or(AuthError::UnknownProvider)?;
+
+    // コールバックで照合する値を発行
+    let login_state = generate_random_token();
+    let login = PendingLogin {
+        provider: provider.name.clone(),
+        nonce: generate_random_token(),
+        code_verifier: generate_random_token(),
+    };
+
+    let url = state
+        .oidc_client
+        .authorization_url(provider, &login_state, &login.nonce, &pkce_challenge(&login.code_verifier))
+        .await?;
+    state.oidc_login_store.insert(&login_state, login, state.oidc_config.login_ttl).await;
+
+    // 他のブラウザで開始したstateでコールバックさせられないよう、stateのハッシュをCookieに保存する
+    let cookie = login_state_cookie(&state, &hash_login_state(&login_state), state.oidc_config.login_ttl.as_secs());
+    Ok((StatusCode::FOUND, [(header::LOCATION, url), (header::SET_COOKIE, cookie)]).into_response())
+}
+
+// OIDCのサインインのコールバック(認可コードをトークンに交換し、ユーザーのトークンを発行)
+#[utoipa::path(
+    get,
+    path = "/auth/oidc/{provider}/callback",
+    params(
+        ("provider" = String, Path, description = "プロバイダー名(OIDC_PROVIDERS)"),
+        OidcCallbackParams
+    ),
+    security(()),
+    responses(
+        (status = 200, description = "サインイン成功", body = AuthResponseV2),
+        (status = 202, description = "二段階認証のコードが必要(/auth/2fa/verifyで検証する)", body = TwoFactorChallenge),
+        (status = 400, description = "stateが無効、期限切れ、またはサインインを開始したブラウザではない", body = ErrorResponse),
+        (status = 401, description = "IdPがサインインを拒否した、またはIDトークンが無効", body = ErrorResponse),
+        (status = 403, description = "メールアドレスが未確認、または対応するユーザーがいない", body = ErrorResponse),
+        (status = 404, description = "設定されていないプロバイダー", body = ErrorResponse),
+        (status = 429, description = "二段階認証のコードの失敗が多すぎる(しばらくしてから再度試す)", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 502, description = "IdPの呼び出しに失敗", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn oidc_callback(
+    State(state): State<Arc<AppState>>,
+    Path(provider): Path<String>,
+    device: DeviceInfo,
+    headers: HeaderMap,
+    Query(params): Query<OidcCallbackParams>,
+) -> Result<Response, (StatusCode, String)> {
+    let result = complete_oidc_login(&state, &provider, params, &headers, &device).await;
+    record_sign_in(&result);
+
+    // stateは使用済みのため、成否によらず結び付けのCookieを削除する
+    let mut response = match result {
+        Ok(outcome) => outcome.into_response_as::<AuthResponseV2>(&state),
+        Err(e) => <(StatusCode, String)>::from(e).into_response(),
+    };
+    if let Ok(cookie) = HeaderValue::from_str(&login_state_cookie(&state, "", 0)) {
+        response.headers_mut().append(header::SET_COOKIE, cookie);
+    }
+    Ok(response)
+}
+
+// コールバックを検証し、IdPのアカウントに紐付いたユーザーでサインインする関数
+async fn complete_oidc_login(
+    state: &AppState,
+    provider: &str,
+    params: OidcCallbackParams,
+    headers: &HeaderMap,
+    device: &DeviceInfo,
+) -> Result<SignInOutcome, AuthError> {
+    let provider = state.oidc_config.provider(provider).ok_or(AuthError::UnknownProvider)?;
+
+    // stateはサインインを開始したブラウザ(Cookie)でのみ使える
+    let login_state = params
+        .state
+        .as_deref()
+        .filter(|login_state| is_bound_to_browser(headers, login_state))
+        .ok_or(AuthError::InvalidLoginState)?;
+
+    // stateは1回だけ使える(IdPがエラーを返した場合も破棄する)
+    let login = state
+        .oidc_login_store
+        .take(login_state)
+        .await
+        .filter(|login| login.provider == provider.name)
+        .ok_or(AuthError::InvalidLoginState)?;
+
+    if let Some(error) = params.error {
+        return Err(AuthError::LoginRejected(params.error_description.unwrap_or(error)));
+    }
+    let code = params.code.ok_or(AuthError::InvalidLoginState)?;
+
+    let id_token = state.oidc_client.exchange_code(provider, &code, &login.code_verifier).await?;
+    let identity = state.oidc_client.verify_id_token(provider, &id_token, &login.nonce).await?;
+    let user = state.container.user_identity_service().resolve(&provider.name, &identity).await?;
+
+    complete_sign_in(state, user, device).await
+}
+
+// stateのハッシュ(SHA-256、URLセーフなBase64)を計算する関数
+fn hash_login_state(login_state: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(login_state.as_bytes()))
+}
+
+// stateがサインインを開始したブラウザのCookieと一致するかを判定する関数
+fn is_bound_to_browser(headers: &HeaderMap, login_state: &str) -> bool {
+    cookie_value(headers, LOGIN_STATE_COOKIE).is_some_and(|expected| {
+        verify_slices_are_equal(hash_login_state(login_state).as_bytes(), expected.as_bytes()).is_ok()
+    })
+}
+
+// stateを結び付けるCookieのSet-Cookieヘッダーの値を作成する関数(max_ageが0の場合は削除)
+//
+// IdPからのリダイレクト(別サイトからのトップレベルのGET)でも送られるよう、SameSite=Laxにする
+fn login_state_cookie(state: &AppState, value: &str, max_age: u64) -> String {
+    let mut cookie = format!(
+        "{}={}; Path={}; Max-Age={}; HttpOnly",
+        LOGIN_STATE_COOKIE, value, LOGIN_STATE_COOKIE_PATH, max_age
+    );
+    if state.session_cookie_config.secure {
+        cookie.push_str("; Secure");
+    }
+    cookie.push_str("; SameSite=Lax");
+    cookie
+}
@@ -0,0 +1,110 @@
+// 必要なクレートのインポート
+use axum::{
+    http::{header, HeaderMap, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use rand::distributions::{Alphanumeric, DistString};
+use ring::constant_time::verify_slices_are_equal;
+// 設定のインポート
+use crate::config::session_cookie_config::SessionCookieConfig;
+// トークンの有効期間のインポート
+use crate::models::auth::auth::TOKEN_TTL_SECS;
+
+// CSRFトークンのヘッダー名
+pub const CSRF_HEADER: &str = "x-csrf-token";
+// CSRFトークンの文字数(英数字、約190ビット)
+const CSRF_TOKEN_LEN: usize = 32;
+
+// AuthorizationヘッダーのBearerトークンを取得する関数
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+// セッションのCookieのトークンを取得する関数(Cookieのセッションが無効な場合はNone)
+pub fn session_token<'a>(config: &SessionCookieConfig, headers: &'a HeaderMap) -> Option<&'a str> {
+    if !config.enabled {
+        return None;
+    }
+    cookie_value(headers, &config.cookie_name)
+}
+
+// リクエストの認証に使うトークンを取得する関数(Authorizationヘッダーを優先し、ない場合はセッションのCookie)
+pub fn request_token<'a>(config: &SessionCookieConfig, headers: &'a HeaderMap) -> Option<&'a str> {
+    bearer_token(headers).or_else(|| session_token(config, headers))
+}
+
+// Cookieヘッダーから名前が一致するCookieの値を取得する関数(空の値はNone)
+pub fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+// CSRFトークンを検証する関数(X-CSRF-TokenヘッダーとCSRFトークンのCookieが一致する場合のみtrue)
+pub fn verify_csrf(config: &SessionCookieConfig, headers: &HeaderMap) -> bool {
+    let submitted = headers.get(CSRF_HEADER).and_then(|value| value.to_str().ok());
+    match (submitted, cookie_value(headers, &config.csrf_cookie_name)) {
+        (Some(submitted), Some(expected)) => verify_slices_are_equal(submitted.as_bytes(), expected.as_bytes()).is_ok(),
+        _ => false,
+    }
+}
+
+// レスポンスにセッションとCSRFトークンのCookieを設定する関数(Cookieのセッションが無効な場合はそのまま返す)
+//
+// CSRFトークンはサインインごとに作り直す
+pub fn with_session_cookies(config: &SessionCookieConfig, token: &str, body: impl IntoResponse) -> Response {
+    let mut response = body.into_response();
+    if config.enabled {
+        let csrf_token = Alphanumeric.sample_string(&mut rand::rngs::OsRng, CSRF_TOKEN_LEN);
+        append_cookie(response.headers_mut(), set_cookie(config, &config.cookie_name, token, TOKEN_TTL_SECS, true));
+        append_cookie(response.headers_mut(), set_cookie(config, &config.csrf_cookie_name, &csrf_token, TOKEN_TTL_SECS, false));
+    }
+    response
+}
+
+// レスポンスでセッションとCSRFトークンのCookieを削除する関数(Cookieのセッションが無効な場合はそのまま返す)
+pub fn without_session_cookies(config: &SessionCookieConfig, body: impl IntoResponse) -> Response {
+    let mut response = body.into_response();
+    if config.enabled {
+        append_cookie(response.headers_mut(), set_cookie(config, &config.cookie_name, "", 0, true));
+        append_cookie(response.headers_mut(), set_cookie(config, &config.csrf_cookie_name, "", 0, false));
+    }
+    response
+}
+
+// Set-Cookieヘッダーの値を作成する関数
+//
+// セッションのCookieはJavaScriptから読めないようHttpOnlyにし、CSRFトークンのCookieは読めるようにする
+pub fn set_cookie(config: &SessionCookieConfig, name: &str, value: &str, max_age: i64, http_only: bool) -> String {
+    let mut cookie = format!("{}={}; Path=/; Max-Age={}", name, value, max_age);
+    if let Some(domain) = &config.domain {
+        cookie.push_str(&format!("; Domain={}", domain));
+    }
+    if http_only {
+        cookie.push_str("; HttpOnly");
+    }
+    if config.secure {
+        cookie.push_str("; Secure");
+    }
+    cookie.push_str(&format!("; SameSite={}", config.same_site.as_str()));
+    cookie
+}
+
+// Set-Cookieヘッダーを追加する関数(ヘッダーに使えない文字を含む設定の場合は追加しない)
+fn append_cookie(headers: &mut HeaderMap, cookie: String) {
+    match HeaderValue::from_str(&cookie) {
+        Ok(value) => {
+            headers.append(header::SET_COOKIE, value);
+        }
+        Err(_) => tracing::warn!("session cookie contains invalid characters"),
+    }
+}
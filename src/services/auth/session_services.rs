@@ -0,0 +1,81 @@
+// 必要なクレートのインポート
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// セッションのモデルのインポート
+use crate::models::auth::session::SessionResponse;
+// 認証済みユーザーのエクストラクターのインポート
+use crate::extractors::auth::authenticated_user::AuthenticatedUser;
+
+// /me/sessionsの各関数はサインインしたユーザーのトークンでのみ呼び出せる(APIキーでの呼び出しは403を返す)
+
+// 自分のサインインのセッションの一覧を取得する関数
+#[utoipa::path(
+    get,
+    path = "/me/sessions",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "一覧取得成功(新しい順、期限切れのセッションは含まない)", body = Vec<SessionResponse>),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "APIキーでは呼び出せません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn list_sessions(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+) -> Result<Json<Vec<SessionResponse>>, (StatusCode, String)> {
+    list(&state, &caller).await.map(Json)
+}
+
+// セッションの一覧を取得する関数(バージョン間で共通)
+pub(crate) async fn list(state: &AppState, caller: &AuthenticatedUser) -> Result<Vec<SessionResponse>, (StatusCode, String)> {
+    caller.reject_api_key()?;
+    Ok(state.container.session_service().list(caller.id, caller.claims.sid).await?)
+}
+
+// 自分のサインインのセッションを削除する関数(以降そのセッションのトークンは使えない)
+#[utoipa::path(
+    delete,
+    path = "/me/sessions/{id}",
+    params(
+        ("id" = crate::models::UuidWrapper, Path, description = "セッションのID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 204, description = "削除成功"),
+        (status = 400, description = "無効なID", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "APIキーでは呼び出せません", body = ErrorResponse),
+        (status = 404, description = "セッションが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn delete_session(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    revoke(&state, &caller, id).await
+}
+
+// セッションを削除する関数(バージョン間で共通、他のユーザーのセッションは404)
+pub(crate) async fn revoke(state: &AppState, caller: &AuthenticatedUser, id: Uuid) -> Result<StatusCode, (StatusCode, String)> {
+    caller.reject_api_key()?;
+    state.container.session_service().delete(caller.id, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
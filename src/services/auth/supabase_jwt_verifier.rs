@@ -0,0 +1,227 @@
+// 必要なクレートのインポート
+use std::sync::{Arc, Mutex};
+use jsonwebtoken::jwk::{Jwk, PublicKeyUse};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use reqwest::{header, Client};
+use serde::Deserialize;
+use tokio::time::Instant;
+// 設定のインポート
+use crate::config::supabase_auth_config::SupabaseAuthConfig;
+// 認証モデルのインポート
+use crate::models::auth::auth::Claims;
+// エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+// 署名のアルゴリズムと鍵の判定(OIDCのIDトークンと共通)
+use crate::services::auth::oidc_client::{key_matches_algorithm, ALLOWED_ALGORITHMS};
+
+// サインインしたユーザーのトークンのロール
+const AUTHENTICATED_ROLE: &str = "authenticated";
+
+// JWKS(形式の分からない鍵を読み飛ばすため、鍵ごとに変換する)
+#[derive(Deserialize)]
+struct JwksDocument {
+    // 鍵
+    keys: Vec<serde_json::Value>,
+}
+
+// GoTrueのトークンのクレーム(exp・iss・audはjsonwebtokenで検証する)
+#[derive(Deserialize)]
+struct GoTrueClaims {
+    // ユーザーID(auth.users.id)
+    sub: String,
+    // 有効期限
+    exp: i64,
+    // 発行時間
+    #[serde(default)]
+    iat: i64,
+    // ロール(authenticatedのみ受け付ける)
+    #[serde(default)]
+    role: Option<String>,
+}
+
+// キャッシュしたJWKS
+struct CachedKeys {
+    // 署名の鍵
+    keys: Arc<Vec<Jwk>>,
+    // 取得した時刻
+    fetched_at: Instant,
+}
+
+// Supabase Auth(GoTrue)が発行したトークンの検証
+//
+// HS256はプロジェクトのJWTシークレット、それ以外はプロジェクトのJWKSで検証する。
+// JWKSはキャッシュし、未知の鍵(kid)の場合は取得し直す(前回の取得から一定の間隔をあける)。
+// 取得に失敗した場合は、しばらく取得せずにすぐ失敗を返す(停止中のSupabaseをリクエストごとに待たない)
+pub struct SupabaseJwtVerifier {
+    // HTTPクライアント
+    http: Client,
+    // SupabaseのURL(発行者・JWKSのURLの既定値に使う)
+    supabase_url: String,
+    // 署名の鍵
+    jwks: Mutex<Option<CachedKeys>>,
+    // 最後にJWKSの取得を始めた時刻
+    last_fetch: Mutex<Option<Instant>>,
+    // 最後にJWKSの取得に失敗した時刻
+    last_failure: Mutex<Option<Instant>>,
+}
+
+// SupabaseJwtVerifierの実装
+impl SupabaseJwtVerifier {
+    // コンストラクタ
+    pub fn new(http: Client, supabase_url: impl Into<String>) -> Self {
+        Self {
+            http,
+            supabase_url: supabase_url.into(),
+            jwks: Mutex::new(None),
+            last_fetch: Mutex::new(None),
+            last_failure: Mutex::new(None),
+        }
+    }
+
+    // トークンを検証し、クレームを返す関数(検証できない場合はInvalidToken)
+    pub async fn verify(&self, config: &SupabaseAuthConfig, token: &str) -> Result<Claims, AuthError> {
+        let header = decode_header(token).map_err(|_| AuthError::InvalidToken)?;
+        let key = match header.alg {
+            Algorithm::HS256 => {
+                let secret = config.jwt_secret.as_ref().ok_or(AuthError::InvalidToken)?;
+                DecodingKey::from_secret(secret.as_bytes())
+            }
+            alg if ALLOWED_ALGORITHMS.contains(&alg) => {
+                // 鍵のローテーションに備えて、見つからない場合はJWKSを取得し直す
+                // 未知のkidのトークンで毎回取得させないよう、直前に取得した場合は取得し直さない
+                match self.find_key(config, header.kid.as_deref(), alg, false).await? {
+                    Some(key) => key,
+                    None if !self.begin_refresh(config) => return Err(AuthError::InvalidToken),
+                    None => self
+                        .find_key(config, header.kid.as_deref(), alg, true)
+                        .await?
+                        .ok_or(AuthError::InvalidToken)?,
+                }
+            }
+            _ => return Err(AuthError::InvalidToken),
+        };
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[config.issuer(&self.supabase_url)]);
+        validation.set_audience(&[&config.audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        let claims = decode::<GoTrueClaims>(token, &key, &validation)
+            .map_err(|_| AuthError::InvalidToken)?
+            .claims;
+
+        // 匿名キー・サービスロールのキーなど、ユーザーのトークン以外は受け付けない
+        if claims.role.as_deref() != Some(AUTHENTICATED_ROLE) {
+            return Err(AuthError::InvalidToken);
+        }
+
+        Ok(Claims {
+            sub: claims.sub,
+            exp: claims.exp,
+            iat: claims.iat,
+            iss: None,
+            aud: None,
+            sid: None,
+        })
+    }
+
+    // トークンのヘッダーに合う署名の鍵を探す関数
+    //
+    // kidがない場合は、アルゴリズムに合う鍵が1つだけのときのみ使う
+    async fn find_key(
+        &self,
+        config: &SupabaseAuthConfig,
+        kid: Option<&str>,
+        algorithm: Algorithm,
+        refresh: bool,
+    ) -> Result<Option<DecodingKey>, AuthError> {
+        let keys = self.keys(config, refresh).await?;
+        let mut candidates = keys.iter().filter(|jwk| {
+            kid.is_none_or(|kid| jwk.common.key_id.as_deref() == Some(kid))
+                && jwk.common.algorithm.is_none_or(|alg| alg == algorithm)
+                && !matches!(jwk.common.public_key_use, Some(PublicKeyUse::Encryption))
+                && key_matches_algorithm(jwk, algorithm)
+        });
+
+        let jwk = match (candidates.next(), candidates.next()) {
+            (Some(jwk), None) => jwk,
+            (Some(jwk), Some(_)) if kid.is_some() => jwk,
+            _ => return Ok(None),
+        };
+        Ok(DecodingKey::from_jwk(jwk).ok())
+    }
+
+    // JWKSを取得し直してよいか確認し、取得を始めた時刻を記録する関数
+    fn begin_refresh(&self, config: &SupabaseAuthConfig) -> bool {
+        let mut last_fetch = self.last_fetch.lock().unwrap_or_else(|e| e.into_inner());
+        if last_fetch.is_some_and(|fetched| fetched.elapsed() < config.jwks_min_refresh_interval) {
+            return false;
+        }
+        *last_fetch = Some(Instant::now());
+        true
+    }
+
+    // 署名の鍵を取得する関数(refreshがtrueの場合はキャッシュを使わない)
+    async fn keys(&self, config: &SupabaseAuthConfig, refresh: bool) -> Result<Arc<Vec<Jwk>>, AuthError> {
+        if !refresh {
+            let cached = self.jwks.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(cached) = cached.as_ref().filter(|cached| cached.fetched_at.elapsed() < config.jwks_ttl) {
+                return Ok(cached.keys.clone());
+            }
+        }
+
+        // 直前に取得に失敗した場合は、取得せずにすぐ失敗を返す
+        let failed_recently = self
+            .last_failure
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some_and(|failed| failed.elapsed() < config.jwks_failure_backoff);
+        if failed_recently {
+            return Err(AuthError::InvalidToken);
+        }
+
+        // 取得できない場合はトークンを検証できないため、401として扱いログに残す
+        *self.last_fetch.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+        let url = config.jwks_url(&self.supabase_url);
+        let document = match self.fetch_jwks(config, &url).await {
+            Ok(document) => document,
+            Err(e) => {
+                *self.last_failure.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+                return Err(e);
+            }
+        };
+        *self.last_failure.lock().unwrap_or_else(|e| e.into_inner()) = None;
+
+        // 対応していない形式の鍵は読み飛ばす
+        let keys: Arc<Vec<Jwk>> = Arc::new(
+            document
+                .keys
+                .into_iter()
+                .filter_map(|key| serde_json::from_value::<Jwk>(key).ok())
+                .collect(),
+        );
+
+        let mut cached = self.jwks.lock().unwrap_or_else(|e| e.into_inner());
+        *cached = Some(CachedKeys { keys: keys.clone(), fetched_at: Instant::now() });
+        Ok(keys)
+    }
+
+    // JWKSを取得する関数
+    async fn fetch_jwks(&self, config: &SupabaseAuthConfig, url: &str) -> Result<JwksDocument, AuthError> {
+        let response = self
+            .http
+            .get(url)
+            .timeout(config.http_timeout)
+            .header(header::ACCEPT, "application/json")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| {
+                tracing::warn!(url = %url, error = %e, "fetching Supabase JWKS failed");
+                AuthError::InvalidToken
+            })?;
+        response.json().await.map_err(|e| {
+            tracing::warn!(url = %url, error = %e, "Supabase JWKS is not valid JSON");
+            AuthError::InvalidToken
+        })
+    }
+}
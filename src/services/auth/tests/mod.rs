@@ -0,0 +1,7 @@
+pub mod jwt_keys_01_test;
+pub mod oidc_client_01_test;
+pub mod oidc_login_store_01_test;
+pub mod session_cookie_01_test;
+pub mod token_revocation_store_01_test;
+pub mod totp_01_test;
+pub mod two_factor_challenge_store_01_test;
@@ -0,0 +1,21 @@
+// OIDCのクライアントのインポート
+use crate::services::auth::oidc_client::{generate_random_token, pkce_challenge};
+
+// PKCEのコードチャレンジがRFC 7636の例と一致することのテスト
+#[test]
+fn test_pkce_challenge_matches_rfc7636() {
+    assert_eq!(
+        pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+        "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+    );
+}
+
+// ランダムな文字列がURLにそのまま使え、PKCEのコード検証子の長さ(43〜128文字)を満たすことのテスト
+#[test]
+fn test_generate_random_token() {
+    let token = generate_random_token();
+
+    assert_eq!(token.len(), 43);
+    assert!(token.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+    assert_ne!(token, generate_random_token());
+}
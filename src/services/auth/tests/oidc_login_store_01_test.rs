@@ -0,0 +1,52 @@
+use std::time::Duration;
+// ストアのインポート
+use crate::services::auth::oidc_login_store::{InMemoryOidcLoginStore, OidcLoginStore, PendingLogin};
+
+// テスト用のサインイン
+fn pending_login() -> PendingLogin {
+    PendingLogin {
+        provider: "corp".to_string(),
+        nonce: "nonce".to_string(),
+        code_verifier: "verifier".to_string(),
+    }
+}
+
+// サインインは1回だけ取り出せることのテスト
+#[tokio::test]
+async fn test_login_can_be_taken_once() {
+    let store = InMemoryOidcLoginStore::new();
+    store.insert("state", pending_login(), Duration::from_secs(600)).await;
+
+    assert_eq!(store.take("state").await, Some(pending_login()));
+    assert_eq!(store.take("state").await, None);
+    // 未知のstate
+    assert_eq!(store.take("unknown").await, None);
+}
+
+// 期限切れのサインインは取り出せないことのテスト
+#[tokio::test(start_paused = true)]
+async fn test_login_expires() {
+    let store = InMemoryOidcLoginStore::new();
+    store.insert("fresh", pending_login(), Duration::from_secs(600)).await;
+    store.insert("expired", pending_login(), Duration::from_secs(600)).await;
+
+    tokio::time::advance(Duration::from_secs(599)).await;
+    assert_eq!(store.take("fresh").await, Some(pending_login()));
+
+    tokio::time::advance(Duration::from_secs(2)).await;
+    assert_eq!(store.take("expired").await, None);
+}
+
+// 上限を超えた場合は期限の最も近いサインインから削除することのテスト
+#[tokio::test(start_paused = true)]
+async fn test_store_is_bounded() {
+    let store = InMemoryOidcLoginStore::with_capacity(2);
+    store.insert("first", pending_login(), Duration::from_secs(600)).await;
+    tokio::time::advance(Duration::from_secs(1)).await;
+    store.insert("second", pending_login(), Duration::from_secs(600)).await;
+    store.insert("third", pending_login(), Duration::from_secs(600)).await;
+
+    assert_eq!(store.take("first").await, None);
+    assert_eq!(store.take("second").await, Some(pending_login()));
+    assert_eq!(store.take("third").await, Some(pending_login()));
+}
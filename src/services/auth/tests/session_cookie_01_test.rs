@@ -0,0 +1,92 @@
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue};
+// セッションのCookieのインポート
+use crate::services::auth::session_cookie::{
+    cookie_value, request_token, set_cookie, verify_csrf, with_session_cookies, without_session_cookies, CSRF_HEADER,
+};
+// 設定のインポート
+use crate::config::session_cookie_config::{SameSite, SessionCookieConfig};
+
+// Cookieのセッションを有効にした設定
+fn enabled() -> SessionCookieConfig {
+    SessionCookieConfig { enabled: true, ..SessionCookieConfig::default() }
+}
+
+// ヘッダーを作成
+fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in pairs {
+        headers.append(HeaderName::from_static(name), HeaderValue::from_str(value).unwrap());
+    }
+    headers
+}
+
+// Cookieヘッダーから値を取り出すことのテスト
+#[test]
+fn test_cookie_value() {
+    let headers = headers(&[("cookie", "theme=dark; session=abc.def"), ("cookie", "csrf_token=xyz; empty=")]);
+    assert_eq!(cookie_value(&headers, "session"), Some("abc.def"));
+    assert_eq!(cookie_value(&headers, "csrf_token"), Some("xyz"));
+    assert_eq!(cookie_value(&headers, "empty"), None);
+    assert_eq!(cookie_value(&headers, "sess"), None);
+}
+
+// Authorizationヘッダーを優先し、Cookieは有効な場合のみ使うことのテスト
+#[test]
+fn test_request_token() {
+    let cookie_only = headers(&[("cookie", "session=from-cookie")]);
+    assert_eq!(request_token(&enabled(), &cookie_only), Some("from-cookie"));
+    assert_eq!(request_token(&SessionCookieConfig::default(), &cookie_only), None);
+
+    let both = headers(&[("authorization", "Bearer from-header"), ("cookie", "session=from-cookie")]);
+    assert_eq!(request_token(&enabled(), &both), Some("from-header"));
+}
+
+// CSRFトークンはヘッダーとCookieが一致する場合のみ有効であることのテスト
+#[test]
+fn test_verify_csrf() {
+    let config = enabled();
+    assert!(verify_csrf(&config, &headers(&[("cookie", "csrf_token=t0ken"), (CSRF_HEADER, "t0ken")])));
+    assert!(!verify_csrf(&config, &headers(&[("cookie", "csrf_token=t0ken"), (CSRF_HEADER, "other")])));
+    assert!(!verify_csrf(&config, &headers(&[("cookie", "csrf_token=t0ken")])));
+    assert!(!verify_csrf(&config, &headers(&[(CSRF_HEADER, "t0ken")])));
+}
+
+// Set-Cookieヘッダーの属性のテスト
+#[test]
+fn test_set_cookie_attributes() {
+    let config = SessionCookieConfig {
+        domain: Some("example.com".to_string()),
+        same_site: SameSite::Strict,
+        ..enabled()
+    };
+    assert_eq!(
+        set_cookie(&config, "session", "jwt", 86400, true),
+        "session=jwt; Path=/; Max-Age=86400; Domain=example.com; HttpOnly; Secure; SameSite=Strict"
+    );
+
+    let config = SessionCookieConfig { secure: false, ..enabled() };
+    assert_eq!(set_cookie(&config, "csrf_token", "", 0, false), "csrf_token=; Path=/; Max-Age=0; SameSite=Lax");
+}
+
+// サインインでCookieを設定し、サインアウトで削除することのテスト
+#[test]
+fn test_session_cookies_on_response() {
+    let response = with_session_cookies(&enabled(), "jwt", "body");
+    let cookies: Vec<&str> = response
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .map(|value| value.to_str().unwrap())
+        .collect();
+    assert_eq!(cookies.len(), 2);
+    assert!(cookies[0].starts_with("session=jwt;") && cookies[0].contains("HttpOnly"));
+    assert!(cookies[1].starts_with("csrf_token=") && !cookies[1].contains("HttpOnly"));
+    assert!(cookies[1].contains("Max-Age=86400"));
+
+    let response = without_session_cookies(&enabled(), "body");
+    assert!(response.headers().get_all(header::SET_COOKIE).iter().all(|value| value.to_str().unwrap().contains("Max-Age=0")));
+
+    // 無効な場合は設定しない
+    let response = with_session_cookies(&SessionCookieConfig::default(), "jwt", "body");
+    assert!(response.headers().get(header::SET_COOKIE).is_none());
+}
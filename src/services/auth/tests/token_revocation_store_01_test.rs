@@ -0,0 +1,34 @@
+use uuid::Uuid;
+// ストアのインポート
+use crate::services::auth::token_revocation_store::{InMemoryTokenRevocationStore, TokenRevocationStore};
+
+// 無効にした時刻より前に発行されたトークンのみ無効になることのテスト
+#[tokio::test]
+async fn test_tokens_issued_before_revocation_are_revoked() {
+    let store = InMemoryTokenRevocationStore::new();
+    let user_id = Uuid::new_v4();
+
+    // 無効にする前は全て有効
+    assert!(!store.is_revoked(user_id, 100).await);
+
+    store.revoke_before(user_id, 200).await;
+
+    assert!(store.is_revoked(user_id, 199).await);
+    // 同じ秒・後に発行されたトークンは有効
+    assert!(!store.is_revoked(user_id, 200).await);
+    assert!(!store.is_revoked(user_id, 201).await);
+    // 他のユーザーには影響しない
+    assert!(!store.is_revoked(Uuid::new_v4(), 199).await);
+}
+
+// 前の時刻で無効にしても時刻が戻らないことのテスト
+#[tokio::test]
+async fn test_revocation_time_never_moves_backwards() {
+    let store = InMemoryTokenRevocationStore::new();
+    let user_id = Uuid::new_v4();
+
+    store.revoke_before(user_id, 200).await;
+    store.revoke_before(user_id, 150).await;
+
+    assert_eq!(store.revoked_before(user_id).await, Some(200));
+}
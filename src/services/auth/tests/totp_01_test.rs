@@ -0,0 +1,71 @@
+// TOTPのインポート
+use crate::services::auth::totp::{base32_decode, base32_encode, Totp};
+
+// RFC 6238の付録Bの秘密鍵(SHA-1)
+const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+// RFC 6238のテストベクタ(8桁の値の下6桁)と一致することのテスト
+#[test]
+fn test_codes_match_rfc6238_vectors() {
+    let totp = Totp::from_base32(&base32_encode(RFC_SECRET)).unwrap();
+
+    let vectors = [
+        (59, "287082"),
+        (1_111_111_109, "081804"),
+        (1_111_111_111, "050471"),
+        (1_234_567_890, "005924"),
+        (2_000_000_000, "279037"),
+    ];
+    for (timestamp, expected) in vectors {
+        assert_eq!(totp.code_at(timestamp), expected, "timestamp: {}", timestamp);
+    }
+}
+
+// Base32のエンコード・デコードのテスト
+#[test]
+fn test_base32_round_trip() {
+    assert_eq!(base32_encode(b"Hello!\xde\xad\xbe\xef"), "JBSWY3DPEHPK3PXP");
+    assert_eq!(base32_decode("JBSWY3DPEHPK3PXP").unwrap(), b"Hello!\xde\xad\xbe\xef");
+    // 小文字・空白・パディングを許容
+    assert_eq!(base32_decode("jbsw y3dp ehpk 3pxp====").unwrap(), b"Hello!\xde\xad\xbe\xef");
+    // 不正な文字
+    assert!(base32_decode("JBSWY3DP1").is_none());
+
+    let totp = Totp::generate();
+    assert_eq!(Totp::from_base32(&totp.to_base32()).unwrap().to_base32(), totp.to_base32());
+}
+
+// 前後のステップのずれを許容し、一致したステップを返すことのテスト
+#[test]
+fn test_verify_allows_skew() {
+    let totp = Totp::generate();
+    let now = 1_700_000_000;
+    let step = Totp::step_at(now);
+
+    assert_eq!(totp.verify(&totp.code_at(now), now, 1), Some(step));
+    assert_eq!(totp.verify(&totp.code_at(now - 30), now, 1), Some(step - 1));
+    assert_eq!(totp.verify(&totp.code_at(now + 30), now, 1), Some(step + 1));
+    // 許容範囲外
+    assert_eq!(totp.verify(&totp.code_at(now - 60), now, 1), None);
+    assert_eq!(totp.verify(&totp.code_at(now - 30), now, 0), None);
+}
+
+// 不正な形式のコードを拒否することのテスト
+#[test]
+fn test_verify_rejects_malformed_codes() {
+    let totp = Totp::generate();
+    for code in ["", "12345", "1234567", "12a456", "１２３４５６"] {
+        assert_eq!(totp.verify(code, 1_700_000_000, 1), None, "code: {}", code);
+    }
+}
+
+// 認証アプリに登録するURIのテスト
+#[test]
+fn test_otpauth_uri() {
+    let totp = Totp::from_base32("JBSWY3DPEHPK3PXP").unwrap();
+
+    assert_eq!(
+        totp.otpauth_uri("My App", "user@example.com"),
+        "otpauth://totp/My%20App:user%40example%2Ecom?secret=JBSWY3DPEHPK3PXP&issuer=My%20App&algorithm=SHA1&digits=6&period=30"
+    );
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+use uuid::Uuid;
+// ストアのインポート
+use crate::services::auth::two_factor_challenge_store::{
+    generate_challenge_token, InMemoryTwoFactorChallengeStore, TwoFactorChallengeStore,
+};
+
+// 試行回数を使い切るとチャレンジが無効になることのテスト
+#[tokio::test]
+async fn test_challenge_allows_limited_attempts() {
+    let store = InMemoryTwoFactorChallengeStore::new();
+    let user_id = Uuid::new_v4();
+    store.insert("token", user_id, Duration::from_secs(300), 2).await;
+
+    assert_eq!(store.attempt("token").await, Some(user_id));
+    assert_eq!(store.attempt("token").await, Some(user_id));
+    assert_eq!(store.attempt("token").await, None);
+    // 未知のトークン
+    assert_eq!(store.attempt("unknown").await, None);
+}
+
+// 期限切れ・削除したチャレンジが無効になることのテスト
+#[tokio::test(start_paused = true)]
+async fn test_challenge_expires_and_can_be_removed() {
+    let store = InMemoryTwoFactorChallengeStore::new();
+    let user_id = Uuid::new_v4();
+    store.insert("expiring", user_id, Duration::from_secs(300), 5).await;
+    store.insert("removed", user_id, Duration::from_secs(300), 5).await;
+
+    store.remove("removed").await;
+    assert_eq!(store.attempt("removed").await, None);
+
+    tokio::time::advance(Duration::from_secs(299)).await;
+    assert_eq!(store.attempt("expiring").await, Some(user_id));
+
+    tokio::time::advance(Duration::from_secs(2)).await;
+    assert_eq!(store.attempt("expiring").await, None);
+}
+
+// チャレンジトークンが推測できない値になることのテスト
+#[test]
+fn test_generate_challenge_token() {
+    let token = generate_challenge_token();
+
+    assert_eq!(token.len(), 64);
+    assert!(token.bytes().all(|b| b.is_ascii_hexdigit()));
+    assert_ne!(token, generate_challenge_token());
+}
+
+// コードの失敗をチャレンジをまたいで数え、期間を過ぎると数え直すことのテスト
+#[tokio::test(start_paused = true)]
+async fn test_failures_lock_user_until_window_ends() {
+    let store = InMemoryTwoFactorChallengeStore::new();
+    let user_id = Uuid::new_v4();
+    let other_user_id = Uuid::new_v4();
+    let window = Duration::from_secs(900);
+
+    store.record_failure(user_id, window).await;
+    assert!(!store.is_locked(user_id, 2).await);
+    store.record_failure(user_id, window).await;
+    assert!(store.is_locked(user_id, 2).await);
+    // 他のユーザーには影響しない
+    assert!(!store.is_locked(other_user_id, 2).await);
+
+    // 最初の失敗から期間を過ぎると数え直す
+    tokio::time::advance(Duration::from_secs(901)).await;
+    assert!(!store.is_locked(user_id, 2).await);
+    store.record_failure(user_id, window).await;
+    assert!(!store.is_locked(user_id, 2).await);
+
+    // 成功すると消える
+    store.record_failure(user_id, window).await;
+    assert!(store.is_locked(user_id, 2).await);
+    store.clear_failures(user_id).await;
+    assert!(!store.is_locked(user_id, 2).await);
+}
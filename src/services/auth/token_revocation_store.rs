@@ -0,0 +1,56 @@
+// 必要なクレートのインポート
+use std::collections::HashMap;
+use std::sync::Mutex;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+// ユーザーごとにトークンを無効にした時刻を保存するストアのトレイト
+//
+// JWTは発行後に取り消せないため、無効にした時刻より前に発行(iat)されたトークンを拒否する。
+// iatは秒単位のため、無効にした時刻と同じ秒に発行されたトークンは有効のまま扱う
+#[async_trait]
+pub trait TokenRevocationStore: Send + Sync {
+    // 指定した時刻(UNIX時間・秒)より前に発行されたユーザーのトークンを無効にする
+    async fn revoke_before(&self, user_id: Uuid, issued_before: i64);
+    // ユーザーのトークンを無効にした時刻を取得する
+    async fn revoked_before(&self, user_id: Uuid) -> Option<i64>;
+
+    // トークンが無効にされているかを判定する
+    async fn is_revoked(&self, user_id: Uuid, issued_at: i64) -> bool {
+        self.revoked_before(user_id)
+            .await
+            .is_some_and(|revoked_before| issued_at < revoked_before)
+    }
+}
+
+// メモリ上に時刻を保持するストア(複数のインスタンスで共有する場合は別の実装に差し替える)
+#[derive(Default)]
+pub struct InMemoryTokenRevocationStore {
+    // ユーザーごとの無効にした時刻
+    entries: Mutex<HashMap<Uuid, i64>>,
+}
+
+// InMemoryTokenRevocationStoreの実装
+impl InMemoryTokenRevocationStore {
+    // コンストラクタ
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// トレイト実装
+#[async_trait]
+impl TokenRevocationStore for InMemoryTokenRevocationStore {
+    async fn revoke_before(&self, user_id: Uuid, issued_before: i64) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        // 既に後の時刻で無効にしている場合は戻さない
+        let entry = entries.entry(user_id).or_insert(issued_before);
+        *entry = (*entry).max(issued_before);
+    }
+
+    async fn revoked_before(&self, user_id: Uuid) -> Option<i64> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(&user_id).copied()
+    }
+}
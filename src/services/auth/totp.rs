@@ -0,0 +1,127 @@
+// 必要なクレートのインポート
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use rand::RngCore;
+use ring::{constant_time, hmac};
+
+// コードの桁数
+pub const TOTP_DIGITS: u32 = 6;
+// コードが切り替わる間隔(秒)
+pub const TOTP_PERIOD_SECS: i64 = 30;
+// 秘密鍵のバイト数(RFC 4226の推奨値)
+const SECRET_BYTES: usize = 20;
+// Base32(RFC 4648)の文字
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+// 時刻ベースのワンタイムパスワード(RFC 6238、HMAC-SHA1・6桁・30秒)
+//
+// 認証アプリ(Google Authenticatorなど)の既定の設定に合わせる
+pub struct Totp {
+    // 秘密鍵
+    secret: Vec<u8>,
+}
+
+// Totpの実装
+impl Totp {
+    // ランダムな秘密鍵で作成する関数
+    pub fn generate() -> Self {
+        let mut secret = vec![0u8; SECRET_BYTES];
+        rand::rngs::OsRng.fill_bytes(&mut secret);
+        Self { secret }
+    }
+
+    // Base32の秘密鍵から作成する関数(空白・小文字・パディングを許容し、不正な場合はNone)
+    pub fn from_base32(secret: &str) -> Option<Self> {
+        let secret = base32_decode(secret)?;
+        (!secret.is_empty()).then_some(Self { secret })
+    }
+
+    // 秘密鍵をBase32で返す関数(パディングなし)
+    pub fn to_base32(&self) -> String {
+        base32_encode(&self.secret)
+    }
+
+    // 時刻(UNIX時間・秒)のステップを返す関数
+    pub fn step_at(timestamp: i64) -> i64 {
+        timestamp.div_euclid(TOTP_PERIOD_SECS)
+    }
+
+    // ステップのコードを計算する関数(RFC 4226の動的切り捨て)
+    pub fn code_at_step(&self, step: i64) -> String {
+        let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, &self.secret);
+        let tag = hmac::sign(&key, &step.to_be_bytes());
+        let digest = tag.as_ref();
+
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([digest[offset], digest[offset + 1], digest[offset + 2], digest[offset + 3]])
+            & 0x7fff_ffff;
+        format!("{:0width$}", binary % 10u32.pow(TOTP_DIGITS), width = TOTP_DIGITS as usize)
+    }
+
+    // 時刻のコードを計算する関数
+    pub fn code_at(&self, timestamp: i64) -> String {
+        self.code_at_step(Self::step_at(timestamp))
+    }
+
+    // コードを検証し、一致したステップを返す関数
+    //
+    // 端末の時刻のずれを考慮して前後skew_stepsステップまで許容する。
+    // 同じコードの再利用を防ぐため、呼び出し元は返したステップ以前のコードを拒否する
+    pub fn verify(&self, code: &str, timestamp: i64, skew_steps: i64) -> Option<i64> {
+        let code = code.trim();
+        if code.len() != TOTP_DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let current = Self::step_at(timestamp);
+        (current - skew_steps..=current + skew_steps).find(|step| {
+            constant_time::verify_slices_are_equal(self.code_at_step(*step).as_bytes(), code.as_bytes()).is_ok()
+        })
+    }
+
+    // 認証アプリに登録するためのURIを返す関数(QRコードにして表示する)
+    pub fn otpauth_uri(&self, issuer: &str, account: &str) -> String {
+        let issuer = utf8_percent_encode(issuer, NON_ALPHANUMERIC).to_string();
+        let account = utf8_percent_encode(account, NON_ALPHANUMERIC).to_string();
+        format!(
+            "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={digits}&period={period}",
+            secret = self.to_base32(),
+            digits = TOTP_DIGITS,
+            period = TOTP_PERIOD_SECS,
+        )
+    }
+}
+
+// Base32でエンコードする関数(パディングなし)
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    for chunk in bytes.chunks(5) {
+        // 5バイトを40ビットの整数にまとめ、5ビットずつ取り出す
+        let mut buffer = [0u8; 5];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let bits = buffer.iter().fold(0u64, |bits, byte| (bits << 8) | *byte as u64);
+        let chars = (chunk.len() * 8).div_ceil(5);
+        for i in 0..chars {
+            let index = (bits >> (35 - i * 5)) & 0x1f;
+            encoded.push(BASE32_ALPHABET[index as usize] as char);
+        }
+    }
+    encoded
+}
+
+// Base32をデコードする関数(空白・小文字・パディングを許容し、不正な文字がある場合はNone)
+pub fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut bits = 0u64;
+    let mut bit_count = 0;
+    for c in encoded.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = BASE32_ALPHABET.iter().position(|a| *a as char == c.to_ascii_uppercase())?;
+        bits = (bits << 5) | value as u64;
+        bit_count += 5;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            bytes.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+    }
+    Some(bytes)
+}
@@ -0,0 +1,141 @@
+// 必要なクレートのインポート
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use async_trait::async_trait;
+use rand::RngCore;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+// 期限切れのチャレンジを削除するエントリ数の目安
+const MAX_ENTRIES_BEFORE_PRUNE: usize = 10_000;
+// チャレンジトークンのバイト数
+const CHALLENGE_TOKEN_BYTES: usize = 32;
+
+// 二段階認証のチャレンジ(パスワードの確認が済んだサインイン)を保存するストアのトレイト
+//
+// チャレンジトークンはアクセストークンとして使えないよう、JWTではなくランダムな文字列にする
+#[async_trait]
+pub trait TwoFactorChallengeStore: Send + Sync {
+    // チャレンジを保存する
+    async fn insert(&self, token: &str, user_id: Uuid, ttl: Duration, max_attempts: u32);
+    // 検証の試行を記録し、有効なチャレンジのユーザーIDを返す(期限切れ・試行回数の超過はNone)
+    async fn attempt(&self, token: &str) -> Option<Uuid>;
+    // チャレンジを削除する(検証に成功した場合)
+    async fn remove(&self, token: &str);
+    // ユーザーのコードの失敗を記録する(最初の失敗からwindowの間数える)
+    async fn record_failure(&self, user_id: Uuid, window: Duration);
+    // ユーザーのコードの失敗がmax_failuresに達しているか(チャレンジの発行・検証を拒否する)
+    async fn is_locked(&self, user_id: Uuid, max_failures: u32) -> bool;
+    // ユーザーのコードの失敗を消す(検証に成功した場合)
+    async fn clear_failures(&self, user_id: Uuid);
+}
+
+// チャレンジトークンを生成する関数(URLに使える16進数の文字列)
+pub fn generate_challenge_token() -> String {
+    let mut bytes = [0u8; CHALLENGE_TOKEN_BYTES];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// エントリ
+struct Entry {
+    // パスワードを確認したユーザーのID
+    user_id: Uuid,
+    // 残りの試行回数
+    attempts_left: u32,
+    // 有効期限
+    expires_at: Instant,
+}
+
+// ユーザーのコードの失敗
+struct Failures {
+    // 失敗の数
+    count: u32,
+    // 数え直す時刻
+    reset_at: Instant,
+}
+
+// メモリ上にチャレンジを保持するストア(複数のインスタンスで共有する場合は別の実装に差し替える)
+#[derive(Default)]
+pub struct InMemoryTwoFactorChallengeStore {
+    // トークンごとのエントリ
+    entries: Mutex<HashMap<String, Entry>>,
+    // ユーザーごとのコードの失敗
+    failures: Mutex<HashMap<Uuid, Failures>>,
+}
+
+// InMemoryTwoFactorChallengeStoreの実装
+impl InMemoryTwoFactorChallengeStore {
+    // コンストラクタ
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// トレイト実装
+#[async_trait]
+impl TwoFactorChallengeStore for InMemoryTwoFactorChallengeStore {
+    async fn insert(&self, token: &str, user_id: Uuid, ttl: Duration, max_attempts: u32) {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        // エントリが多くなりすぎた場合は期限切れのものを削除
+        if entries.len() >= MAX_ENTRIES_BEFORE_PRUNE {
+            entries.retain(|_, entry| entry.expires_at > now);
+        }
+
+        entries.insert(token.to_string(), Entry {
+            user_id,
+            attempts_left: max_attempts,
+            expires_at: now + ttl,
+        });
+    }
+
+    async fn attempt(&self, token: &str) -> Option<Uuid> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = entries.get_mut(token)?;
+
+        // 期限切れ・試行回数を使い切ったチャレンジは削除
+        if entry.expires_at <= Instant::now() || entry.attempts_left == 0 {
+            entries.remove(token);
+            return None;
+        }
+
+        entry.attempts_left -= 1;
+        Some(entry.user_id)
+    }
+
+    async fn remove(&self, token: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.remove(token);
+    }
+
+    async fn record_failure(&self, user_id: Uuid, window: Duration) {
+        let now = Instant::now();
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+
+        // エントリが多くなりすぎた場合は数え直す時刻を過ぎたものを削除
+        if failures.len() >= MAX_ENTRIES_BEFORE_PRUNE {
+            failures.retain(|_, entry| entry.reset_at > now);
+        }
+
+        let entry = failures.entry(user_id).or_insert(Failures { count: 0, reset_at: now + window });
+        if entry.reset_at <= now {
+            *entry = Failures { count: 0, reset_at: now + window };
+        }
+        entry.count = entry.count.saturating_add(1);
+    }
+
+    async fn is_locked(&self, user_id: Uuid, max_failures: u32) -> bool {
+        let failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        failures
+            .get(&user_id)
+            .is_some_and(|entry| entry.reset_at > Instant::now() && entry.count >= max_failures)
+    }
+
+    async fn clear_failures(&self, user_id: Uuid) {
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        failures.remove(&user_id);
+    }
+}
@@ -0,0 +1,208 @@
+// 必要なクレートのインポート
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// 認証モデルのインポート
+use crate::models::auth::auth::AuthResponse;
+use crate::models::auth::session::DeviceInfo;
+use crate::models::auth::two_factor::{
+    RecoveryCodesResponse, TwoFactorConfirmRequest, TwoFactorSetupResponse, TwoFactorVerifyRequest,
+};
+// 認証エラーのインポート
+use crate::errors::auth::auth_error::AuthError;
+// 認証済みユーザー・管理者のエクストラクターのインポート
+use crate::extractors::auth::admin_user::AdminUser;
+use crate::extractors::auth::authenticated_user::AuthenticatedUser;
+// メトリクスのインポート
+use crate::metrics::app_metrics::metrics;
+// 認証サービスのインポート(トークンの発行)
+use crate::services::auth::auth_services::issue_token;
+// セッションのCookieのインポート
+use crate::services::auth::session_cookie::with_session_cookies;
+
+// 二段階認証の設定を開始する関数(認証アプリに登録する秘密鍵を返す)
+#[utoipa::path(
+    post,
+    path = "/me/2fa/setup",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "設定開始(/me/2fa/confirmで有効にする)", body = TwoFactorSetupResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "APIキーでは呼び出せません", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 409, description = "二段階認証が有効になっています", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn setup_two_factor(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+) -> Result<Json<TwoFactorSetupResponse>, (StatusCode, String)> {
+    // APIキーでは二段階認証を設定できない
+    caller.reject_api_key()?;
+    begin_setup(&state, caller.id).await.map(Json)
+}
+
+// 二段階認証の設定を開始する関数(バージョン間で共通)
+//
+// 認証アプリにはメールアドレスをアカウント名として表示する
+pub(crate) async fn begin_setup(state: &AppState, user_id: Uuid) -> Result<TwoFactorSetupResponse, (StatusCode, String)> {
+    let user = state
+        .container
+        .user_repository()
+        .find_by_id(user_id)
+        .await
+        .map_err(AuthError::from)?;
+
+    Ok(state
+        .container
+        .two_factor_service()
+        .begin_setup(user_id, &user.email, &state.two_factor_config)
+        .await?)
+}
+
+// 二段階認証を有効にする関数(認証アプリのコードで確認し、リカバリーコードを返す)
+#[utoipa::path(
+    post,
+    path = "/me/2fa/confirm",
+    request_body = TwoFactorConfirmRequest,
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "有効化成功(リカバリーコードはこのレスポンスでのみ返す)", body = RecoveryCodesResponse),
+        (status = 400, description = "設定が開始されていません", body = ErrorResponse),
+        (status = 401, description = "認証失敗、またはコードが一致しません", body = ErrorResponse),
+        (status = 403, description = "APIキーでは呼び出せません", body = ErrorResponse),
+        (status = 409, description = "二段階認証が有効になっています", body = ErrorResponse),
+        (status = 422, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn confirm_two_factor(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+    Json(request): Json<TwoFactorConfirmRequest>,
+) -> Result<Json<RecoveryCodesResponse>, (StatusCode, String)> {
+    caller.reject_api_key()?;
+    let response = state
+        .container
+        .two_factor_service()
+        .confirm_setup(caller.id, &request.code, &state.two_factor_config)
+        .await?;
+    Ok(Json(response))
+}
+
+// 二段階認証のコードを検証してトークンを発行する関数(サインインの二段階目)
+#[utoipa::path(
+    post,
+    path = "/auth/2fa/verify",
+    request_body = TwoFactorVerifyRequest,
+    security(()),
+    responses(
+        (status = 200, description = "サインイン成功", body = AuthResponse),
+        (status = 401, description = "コードが一致しない、またはチャレンジが無効", body = ErrorResponse),
+        (status = 422, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 429, description = "二段階認証のコードの失敗が多すぎる(しばらくしてから再度試す)", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn verify_two_factor(
+    State(state): State<Arc<AppState>>,
+    device: DeviceInfo,
+    Json(request): Json<TwoFactorVerifyRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    let response = verify_and_record(&state, request, &device).await?;
+    let token = response.token.clone();
+    Ok(with_session_cookies(&state.session_cookie_config, &token, Json(response)))
+}
+
+// チャレンジのコードを検証し、結果をメトリクスに記録する関数(バージョン間で共通)
+pub(crate) async fn verify_and_record(
+    state: &AppState,
+    request: TwoFactorVerifyRequest,
+    device: &DeviceInfo,
+) -> Result<AuthResponse, (StatusCode, String)> {
+    let result = verify_challenge(state, request, device).await;
+    metrics().record_sign_in(result.is_ok());
+
+    result.map_err(Into::into)
+}
+
+// チャレンジのコードを検証してトークンを発行する関数
+//
+// 試行ごとにチャレンジの残り回数を減らし、成功したチャレンジは再利用できないよう削除する。
+// コードの失敗はユーザーごとにも数え、多すぎる場合は発行済みのチャレンジも受け付けない
+async fn verify_challenge(
+    state: &AppState,
+    request: TwoFactorVerifyRequest,
+    device: &DeviceInfo,
+) -> Result<AuthResponse, AuthError> {
+    let config = &state.two_factor_config;
+    let store = &state.two_factor_challenge_store;
+    let user_id = store
+        .attempt(&request.challenge_token)
+        .await
+        .ok_or(AuthError::InvalidChallenge)?;
+    if store.is_locked(user_id, config.max_user_failures).await {
+        return Err(AuthError::TwoFactorLocked);
+    }
+
+    match state.container.two_factor_service().verify(user_id, &request.code, config).await {
+        Ok(()) => store.clear_failures(user_id).await,
+        Err(AuthError::InvalidTwoFactorCode) => {
+            store.record_failure(user_id, config.failure_window).await;
+            return Err(AuthError::InvalidTwoFactorCode);
+        }
+        Err(e) => return Err(e),
+    }
+    store.remove(&request.challenge_token).await;
+
+    let user = state.container.user_repository().find_by_id(user_id).await?;
+    issue_token(state, user, device).await
+}
+
+// ユーザーの二段階認証をリセットする関数(管理者のみ、認証アプリを紛失した場合など)
+#[utoipa::path(
+    delete,
+    path = "/users/{id}/2fa",
+    params(
+        ("id" = crate::models::UuidWrapper, Path, description = "対象のユーザーID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 204, description = "リセット成功"),
+        (status = 400, description = "無効なユーザーID", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "管理者ではありません", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "users"
+)]
+pub async fn reset_two_factor(
+    State(state): State<Arc<AppState>>,
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state.container.two_factor_service().reset(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
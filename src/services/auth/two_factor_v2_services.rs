@@ -0,0 +1,141 @@
+// 必要なクレートのインポート
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// 認証モデルのインポート
+use crate::models::auth::auth::AuthResponseV2;
+use crate::models::auth::session::DeviceInfo;
+use crate::models::auth::two_factor::{
+    RecoveryCodesResponse, TwoFactorConfirmRequest, TwoFactorSetupResponse, TwoFactorVerifyRequest,
+};
+// 認証済みユーザー・管理者のエクストラクターのインポート
+use crate::extractors::auth::admin_user::AdminUser;
+use crate::extractors::auth::authenticated_user::AuthenticatedUser;
+// 二段階認証のサービスのインポート(バージョン間で共通の処理)
+use crate::services::auth::two_factor_services::{begin_setup, verify_and_record};
+// セッションのCookieのインポート
+use crate::services::auth::session_cookie::with_session_cookies;
+
+// 二段階認証の設定を開始する関数(認証アプリに登録する秘密鍵を返す)
+#[utoipa::path(
+    post,
+    path = "/v2/me/2fa/setup",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "設定開始(/v2/me/2fa/confirmで有効にする)", body = TwoFactorSetupResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "APIキーでは呼び出せません", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 409, description = "二段階認証が有効になっています", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn setup_two_factor(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+) -> Result<Json<TwoFactorSetupResponse>, (StatusCode, String)> {
+    // APIキーでは二段階認証を設定できない
+    caller.reject_api_key()?;
+    begin_setup(&state, caller.id).await.map(Json)
+}
+
+// 二段階認証を有効にする関数(認証アプリのコードで確認し、リカバリーコードを返す)
+#[utoipa::path(
+    post,
+    path = "/v2/me/2fa/confirm",
+    request_body = TwoFactorConfirmRequest,
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "有効化成功(リカバリーコードはこのレスポンスでのみ返す)", body = RecoveryCodesResponse),
+        (status = 400, description = "設定が開始されていません", body = ErrorResponse),
+        (status = 401, description = "認証失敗、またはコードが一致しません", body = ErrorResponse),
+        (status = 403, description = "APIキーでは呼び出せません", body = ErrorResponse),
+        (status = 409, description = "二段階認証が有効になっています", body = ErrorResponse),
+        (status = 422, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn confirm_two_factor(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+    Json(request): Json<TwoFactorConfirmRequest>,
+) -> Result<Json<RecoveryCodesResponse>, (StatusCode, String)> {
+    caller.reject_api_key()?;
+    let response = state
+        .container
+        .two_factor_service()
+        .confirm_setup(caller.id, &request.code, &state.two_factor_config)
+        .await?;
+    Ok(Json(response))
+}
+
+// 二段階認証のコードを検証してトークンを発行する関数(サインインの二段階目)
+#[utoipa::path(
+    post,
+    path = "/v2/auth/2fa/verify",
+    request_body = TwoFactorVerifyRequest,
+    security(()),
+    responses(
+        (status = 200, description = "サインイン成功", body = AuthResponseV2),
+        (status = 401, description = "コードが一致しない、またはチャレンジが無効", body = ErrorResponse),
+        (status = 422, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 429, description = "二段階認証のコードの失敗が多すぎる(しばらくしてから再度試す)", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn verify_two_factor(
+    State(state): State<Arc<AppState>>,
+    device: DeviceInfo,
+    Json(request): Json<TwoFactorVerifyRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    let response = verify_and_record(&state, request, &device).await?;
+    let token = response.token.clone();
+    Ok(with_session_cookies(&state.session_cookie_config, &token, Json(AuthResponseV2::from(response))))
+}
+
+// ユーザーの二段階認証をリセットする関数(管理者のみ、認証アプリを紛失した場合など)
+#[utoipa::path(
+    delete,
+    path = "/v2/users/{id}/2fa",
+    params(
+        ("id" = crate::models::UuidWrapper, Path, description = "対象のユーザーID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 204, description = "リセット成功"),
+        (status = 400, description = "無効なユーザーID", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "管理者ではありません", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "users"
+)]
+pub async fn reset_two_factor(
+    State(state): State<Arc<AppState>>,
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state.container.two_factor_service().reset(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,25 @@
+// 必要なクレートのインポート
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+// メトリクスのインポート
+use crate::metrics::app_metrics::metrics;
+
+// Prometheus形式のメトリクスを取得する関数
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    security(()),
+    responses(
+        (status = 200, description = "メトリクス取得成功", body = String, content_type = "text/plain"),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse)
+    ),
+    tag = "metrics"
+)]
+pub async fn get_metrics() -> Result<impl IntoResponse, (StatusCode, String)> {
+    // メトリクスをテキスト形式で出力
+    let body = metrics()
+        .render()
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body))
+}
@@ -0,0 +1,4 @@
+// メトリクスサービスのモジュールの宣言
+pub mod metrics_services;
+
+// メトリクスサービスのエントリーポイント
@@ -1,5 +1,6 @@
 // サービスのモジュールの宣言
 pub mod auth;
+pub mod metrics;
 pub mod users;
 
 // サービスのエントリーポイント
\ No newline at end of file
@@ -0,0 +1,151 @@
+// 必要なクレートのインポート
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Response,
+    Json,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// ユーザーモデルのインポート
+use crate::models::users::users::{UserResponse, UserProfileUpdate, DeleteAccountRequest, ChangePasswordRequest};
+// 認証済みユーザーのエクストラクターのインポート
+use crate::extractors::auth::authenticated_user::AuthenticatedUser;
+
+// /meの各関数はトークンのユーザーID(Claims::sub)を対象にDIのスタック(標準のハンドラー)に委譲する
+// (Supabase Authのトークンの場合は、トークンを転送するコンテナを使う)
+//
+// 新しいエンドポイントのため、v1でもパスワードを含まないレスポンスを返す
+
+// 自分のプロフィールを取得する関数
+#[utoipa::path(
+    get,
+    path = "/me",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "プロフィール取得成功", body = UserResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn get_me(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+) -> Result<Json<UserResponse>, (StatusCode, String)> {
+    let Json(user) = caller.container(&state).user_handler().get_user(caller.id).await?;
+    Ok(Json(user.into()))
+}
+
+// 自分のユーザー名・メールアドレスを更新する関数
+#[utoipa::path(
+    patch,
+    path = "/me",
+    request_body = UserProfileUpdate,
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "プロフィール更新成功", body = UserResponse),
+        (status = 400, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 409, description = "メールアドレスが使用済み", body = ErrorResponse),
+        (status = 422, description = "変更できない項目(パスワードなど)を含む", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn update_me(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+    Json(profile): Json<UserProfileUpdate>,
+) -> Result<Json<UserResponse>, (StatusCode, String)> {
+    let Json(user) = caller.container(&state).user_handler().update_profile(caller.id, Json(profile)).await?;
+    Ok(Json(user.into()))
+}
+
+// 自分のアカウントを削除する関数(現在のパスワードで確認する)
+#[utoipa::path(
+    delete,
+    path = "/me",
+    request_body = DeleteAccountRequest,
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 204, description = "アカウント削除成功"),
+        (status = 400, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "パスワードが一致しません", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 422, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn delete_me(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+    Json(request): Json<DeleteAccountRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    caller.container(&state).user_handler().delete_account(caller.id, Json(request)).await
+}
+
+// 自分のパスワードを変更する関数(発行済みのトークン・APIキーは全て無効になる)
+#[utoipa::path(
+    post,
+    path = "/me/password",
+    request_body = ChangePasswordRequest,
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 204, description = "パスワード変更成功(再度サインインが必要、発行済みのAPIキーは全て削除される)"),
+        (status = 400, description = "ポリシーを満たさない、または最近使用したパスワード", body = ErrorResponse),
+        (status = 401, description = "認証失敗", body = ErrorResponse),
+        (status = 403, description = "現在のパスワードが一致しません", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 422, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "me"
+)]
+pub async fn change_password(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedUser,
+    Json(request): Json<ChangePasswordRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let status = caller
+        .container(&state)
+        .user_handler()
+        .change_password(caller.id, Json(request), state.password_policy_config.history_size)
+        .await?;
+
+    // 変更前に発行されたトークンを無効にし、セッションを削除する(APIキーは変更のRPCで削除済み)
+    revoke_tokens(&state, caller.id).await;
+
+    Ok(status)
+}
+
+// パスワードの変更前に発行されたトークンを無効にし、セッションを削除する関数(バージョン間で共通)
+//
+// APIキーはパスワードの変更と同じRPC(トランザクション)で削除するため、ここでは扱わない。
+// トークンは失効の記録で無効になるため、セッションの削除に失敗しても変更は成功させる
+pub(crate) async fn revoke_tokens(state: &AppState, user_id: Uuid) {
+    state.token_revocation_store.revoke_before(user_id, Utc::now().timestamp()).await;
+
+    if let Err(e) = state.container.session_service().delete_all(user_id).await {
+        tracing::warn!(user_id = %user_id, error = ?e, "revoking sessions after password change failed");
+    }
+}
@@ -1,4 +1,7 @@
 // ユーザーサービスのモジュールの宣言
+pub mod me_services;
+pub mod me_v2_services;
 pub mod user_services;
+pub mod user_v2_services;
 
 // ユーザーサービスのエントリーポイント
\ No newline at end of file
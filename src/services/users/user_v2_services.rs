@@ -0,0 +1,139 @@
+// 必要なクレートのインポート
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+// アプリケーションの状態のインポート
+use crate::state::app_state::AppState;
+// ユーザーモデルのインポート
+use crate::models::users::users::{NewUser, UserResponse, UserUpdate};
+
+// /v2/usersの各関数はDIのスタック(標準のハンドラー)に委譲し、パスワードを含まないレスポンスに変換する
+
+// ユーザーの一覧を取得する関数
+#[utoipa::path(
+    get,
+    path = "/v2/users",
+    security(()),
+    responses(
+        (status = 200, description = "ユーザー一覧を取得成功", body = Vec<UserResponse>),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "users"
+)]
+pub async fn get_users(
+    State(state): State<Arc<AppState>>
+) -> Result<Json<Vec<UserResponse>>, (StatusCode, String)> {
+    let Json(users) = state.container.user_handler().get_users().await?;
+    Ok(Json(users.into_iter().map(UserResponse::from).collect()))
+}
+
+// 特定のユーザーを取得する関数
+#[utoipa::path(
+    get,
+    path = "/v2/users/{id}",
+    params(
+        ("id" = crate::models::UuidWrapper, Path, description = "ユーザーID")
+    ),
+    security(()),
+    responses(
+        (status = 200, description = "ユーザー取得成功", body = UserResponse),
+        (status = 400, description = "無効なユーザーID", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "users"
+)]
+pub async fn get_user_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>
+) -> Result<Json<UserResponse>, (StatusCode, String)> {
+    let Json(user) = state.container.user_handler().get_user(id).await?;
+    Ok(Json(user.into()))
+}
+
+// 新しいユーザーを作成する関数
+#[utoipa::path(
+    post,
+    path = "/v2/users",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "再試行時に同じレスポンスを返すための冪等キー")
+    ),
+    request_body = NewUser,
+    security(()),
+    responses(
+        (status = 201, description = "ユーザー作成成功", body = UserResponse),
+        (status = 400, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 409, description = "メールアドレスが使用済み、または同じ冪等キーのリクエストが処理中", body = ErrorResponse),
+        (status = 422, description = "冪等キーが別のリクエストで使用済み", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "users"
+)]
+pub async fn create_user(
+    State(state): State<Arc<AppState>>,
+    Json(new_user): Json<NewUser>
+) -> Result<(StatusCode, Json<UserResponse>), (StatusCode, String)> {
+    let Json(user) = state.container.user_handler().create_user(Json(new_user)).await?;
+    Ok((StatusCode::CREATED, Json(user.into())))
+}
+
+// ユーザーを更新する関数
+#[utoipa::path(
+    put,
+    path = "/v2/users/{id}",
+    params(
+        ("id" = crate::models::UuidWrapper, Path, description = "更新対象のユーザーID")
+    ),
+    request_body = UserUpdate,
+    security(()),
+    responses(
+        (status = 200, description = "ユーザー更新成功", body = UserResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 400, description = "無効なリクエストデータ", body = ErrorResponse),
+        (status = 409, description = "メールアドレスが使用済み", body = ErrorResponse),
+        (status = 422, description = "変更できない項目(パスワードなど)を含む、パスワードは/me/passwordで変更する", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "users"
+)]
+pub async fn update_user(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(updated_user): Json<UserUpdate>
+) -> Result<Json<UserResponse>, (StatusCode, String)> {
+    let Json(user) = state.container.user_handler().update_user(id, Json(updated_user)).await?;
+    Ok(Json(user.into()))
+}
+
+// ユーザーを削除する関数
+#[utoipa::path(
+    delete,
+    path = "/v2/users/{id}",
+    params(
+        ("id" = crate::models::UuidWrapper, Path, description = "削除対象のユーザーID")
+    ),
+    security(()),
+    responses(
+        (status = 204, description = "ユーザー削除成功"),
+        (status = 400, description = "無効なユーザーID", body = ErrorResponse),
+        (status = 404, description = "ユーザーが見つかりません", body = ErrorResponse),
+        (status = 500, description = "サーバーエラー", body = ErrorResponse),
+        (status = 503, description = "データベースが一時的に利用できません", body = ErrorResponse)
+    ),
+    tag = "users"
+)]
+pub async fn delete_user(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>
+) -> Result<Response, (StatusCode, String)> {
+    state.container.user_handler().delete_user(id).await
+}
@@ -1,5 +1,46 @@
 // 必要なクレートのインポート
+use std::sync::Arc;
 use reqwest::Client;
+// 設定のインポート
+use crate::config::admin_config::AdminConfig;
+use crate::config::api_key_config::ApiKeyConfig;
+use crate::config::cache_config::CacheConfig;
+use crate::config::idempotency_config::IdempotencyConfig;
+use crate::config::jwt_config::JwtConfig;
+use crate::config::middleware_config::MiddlewareConfig;
+use crate::config::oidc_config::OidcConfig;
+use crate::config::outbound_config::OutboundConfig;
+use crate::config::password_hash_config::PasswordHashConfig;
+use crate::config::password_policy_config::PasswordPolicyConfig;
+use crate::config::rate_limit_config::RateLimitConfig;
+use crate::config::session_config::SessionConfig;
+use crate::config::session_cookie_config::SessionCookieConfig;
+use crate::config::supabase_auth_config::SupabaseAuthConfig;
+use crate::config::two_factor_config::TwoFactorConfig;
+use crate::config::versioning_config::VersioningConfig;
+// 呼び出しポリシーのインポート
+use crate::outbound::outbound_policy::OutboundPolicy;
+// レート制限ストアのインポート
+use crate::middleware::rate_limit::rate_limit_store::{InMemoryRateLimitStore, RateLimitStore};
+// 冪等キーストアのインポート
+use crate::middleware::idempotency::idempotency_store::{IdempotencyStore, InMemoryIdempotencyStore};
+// トークンの無効化ストアのインポート
+use crate::services::auth::token_revocation_store::{InMemoryTokenRevocationStore, TokenRevocationStore};
+// 二段階認証のチャレンジのストアのインポート
+use crate::services::auth::two_factor_challenge_store::{InMemoryTwoFactorChallengeStore, TwoFactorChallengeStore};
+// OIDCのクライアントと開始したサインインのストアのインポート
+use crate::services::auth::oidc_client::OidcClient;
+use crate::services::auth::oidc_login_store::{InMemoryOidcLoginStore, OidcLoginStore};
+// トークンの署名・検証の鍵のインポート
+use crate::services::auth::jwt_keys::JwtKeys;
+// Supabase Authのトークンの検証のインポート
+use crate::services::auth::supabase_jwt_verifier::SupabaseJwtVerifier;
+// Supabaseのクライアントのインポート
+use crate::supabase::supabase_client::SupabaseClient;
+// コンテナのインポート
+use crate::di::container::Container;
+// パスワードのハッシュ化のインポート
+use crate::password::password_hasher::PasswordHashers;
 
 // アプリケーションの状態を管理する構造体
 #[derive(Clone)]
@@ -12,22 +53,173 @@ pub struct AppState {
     pub client: Client,
     // JWTシークレット
     pub jwt_secret: String,
+    // トークンの署名の設定
+    pub jwt_config: JwtConfig,
+    // トークンの署名・検証の鍵(署名の設定から作成)
+    pub jwt_keys: Arc<JwtKeys>,
+    // Supabase(PostgREST)のクライアント(呼び出しポリシーを含む)
+    pub supabase: SupabaseClient,
+    // ミドルウェアの設定
+    pub middleware_config: MiddlewareConfig,
+    // レート制限の設定
+    pub rate_limit_config: RateLimitConfig,
+    // レート制限の状態を保存するストア
+    pub rate_limit_store: Arc<dyn RateLimitStore>,
+    // 冪等キーの設定
+    pub idempotency_config: IdempotencyConfig,
+    // 冪等キーの状態と保存したレスポンスのストア
+    pub idempotency_store: Arc<dyn IdempotencyStore>,
+    // ユーザーのキャッシュの設定
+    pub cache_config: CacheConfig,
+    // APIのバージョンの設定
+    pub versioning_config: VersioningConfig,
+    // パスワードのポリシーの設定
+    pub password_policy_config: PasswordPolicyConfig,
+    // パスワードのハッシュ化の設定
+    pub password_hash_config: PasswordHashConfig,
+    // ユーザーごとにトークンを無効にした時刻のストア
+    pub token_revocation_store: Arc<dyn TokenRevocationStore>,
+    // 二段階認証の設定
+    pub two_factor_config: TwoFactorConfig,
+    // 二段階認証のチャレンジのストア
+    pub two_factor_challenge_store: Arc<dyn TwoFactorChallengeStore>,
+    // 管理者の設定
+    pub admin_config: AdminConfig,
+    // OpenID Connectの設定(プロバイダー)
+    pub oidc_config: OidcConfig,
+    // OpenID Connectのクライアント(ディスカバリー・JWKSのキャッシュを含む)
+    pub oidc_client: Arc<OidcClient>,
+    // 開始したOIDCのサインインのストア
+    pub oidc_login_store: Arc<dyn OidcLoginStore>,
+    // Supabase Auth(GoTrue)のトークンを受け付ける設定
+    pub supabase_auth_config: SupabaseAuthConfig,
+    // Supabase Authのトークンの検証(JWKSのキャッシュを含む)
+    pub supabase_jwt_verifier: Arc<SupabaseJwtVerifier>,
+    // APIキーの設定
+    pub api_key_config: ApiKeyConfig,
+    // サインインのセッションの設定
+    pub session_config: SessionConfig,
+    // ブラウザー向けのCookieのセッションの設定
+    pub session_cookie_config: SessionCookieConfig,
+    // リポジトリ・サービス・ハンドラーのコンテナ(Supabaseのクライアントから作成)
+    pub container: Arc<Container>,
 }
 
 // AppStateの実装
 impl AppState {
     // AppStateの新しいインスタンスを作成する関数
     pub fn new(supabase_url: String, supabase_anon_key: String) -> Self {
+        // HTTPクライアントを作成
+        let client = Client::new();
+        // 呼び出しポリシーの設定を環境変数から読み込む
+        let outbound_policy = Arc::new(OutboundPolicy::new(OutboundConfig::from_env()));
+        // Supabaseのクライアントを作成
+        let supabase = SupabaseClient::new(
+            client.clone(),
+            supabase_url.clone(),
+            supabase_anon_key.clone(),
+            outbound_policy,
+        );
+        // ユーザーのキャッシュの設定を環境変数から読み込む
+        let cache_config = CacheConfig::from_env();
+        // パスワードのハッシュ化の設定を環境変数から読み込む
+        let password_hash_config = PasswordHashConfig::from_env();
+        // トークンの署名の設定を環境変数から読み込む
+        let jwt_config = JwtConfig::from_env();
+        let jwt_keys = jwt_keys(&jwt_config);
+        // OpenID Connectの設定を環境変数から読み込む
+        let oidc_config = OidcConfig::from_env();
+        let oidc_client = Arc::new(OidcClient::new(client.clone(), &oidc_config));
+        // Supabase Authのトークンの検証を作成
+        let supabase_jwt_verifier = Arc::new(SupabaseJwtVerifier::new(client.clone(), supabase_url.clone()));
+        // コンテナを作成
+        let container = Arc::new(Container::new(
+            supabase.clone(),
+            &cache_config,
+            password_hashers(&password_hash_config),
+        ));
+
         // 新しいAppStateインスタンスを作成
         Self {
             // SupabaseのURLを設定
             supabase_url,
             // Supabaseの匿名キーを設定
             supabase_anon_key,
-            // HTTPクライアントを作成
-            client: Client::new(),
-            // JWTシークレットを設定
-            jwt_secret: String::new(),
+            // HTTPクライアントを設定
+            client,
+            // JWTシークレットを設定(署名の設定から取得)
+            jwt_secret: jwt_config.secret.clone().unwrap_or_default(),
+            // トークンの署名の設定
+            jwt_config,
+            // トークンの署名・検証の鍵
+            jwt_keys,
+            // Supabaseのクライアントを設定
+            supabase,
+            // ミドルウェアの設定を環境変数から読み込む
+            middleware_config: MiddlewareConfig::from_env(),
+            // レート制限の設定を環境変数から読み込む
+            rate_limit_config: RateLimitConfig::from_env(),
+            // レート制限の状態はメモリ上に保存
+            rate_limit_store: Arc::new(InMemoryRateLimitStore::new()),
+            // 冪等キーの設定を環境変数から読み込む
+            idempotency_config: IdempotencyConfig::from_env(),
+            // 冪等キーの状態はメモリ上に保存
+            idempotency_store: Arc::new(InMemoryIdempotencyStore::new()),
+            // ユーザーのキャッシュの設定
+            cache_config,
+            // APIのバージョンの設定を環境変数から読み込む
+            versioning_config: VersioningConfig::from_env(),
+            // パスワードのポリシーの設定を環境変数から読み込む
+            password_policy_config: PasswordPolicyConfig::from_env(),
+            // パスワードのハッシュ化の設定
+            password_hash_config,
+            // トークンを無効にした時刻はメモリ上に保存
+            token_revocation_store: Arc::new(InMemoryTokenRevocationStore::new()),
+            // 二段階認証の設定を環境変数から読み込む
+            two_factor_config: TwoFactorConfig::from_env(),
+            // 二段階認証のチャレンジはメモリ上に保存
+            two_factor_challenge_store: Arc::new(InMemoryTwoFactorChallengeStore::new()),
+            // 管理者の設定を環境変数から読み込む
+            admin_config: AdminConfig::from_env(),
+            // OpenID Connectの設定
+            oidc_config,
+            // OpenID Connectのクライアント
+            oidc_client,
+            // 開始したOIDCのサインインはメモリ上に保存
+            oidc_login_store: Arc::new(InMemoryOidcLoginStore::new()),
+            // Supabase Authの設定を環境変数から読み込む
+            supabase_auth_config: SupabaseAuthConfig::from_env(),
+            // Supabase Authのトークンの検証
+            supabase_jwt_verifier,
+            // APIキーの設定を環境変数から読み込む
+            api_key_config: ApiKeyConfig::from_env(),
+            // サインインのセッションの設定を環境変数から読み込む
+            session_config: SessionConfig::from_env(),
+            // Cookieのセッションの設定を環境変数から読み込む
+            session_cookie_config: SessionCookieConfig::from_env(),
+            // コンテナを設定
+            container,
         }
     }
+
+    // Supabaseのクライアントを差し替える関数(クライアントを使うコンテナも作り直す)
+    pub fn with_supabase(mut self, supabase: SupabaseClient) -> Self {
+        self.container = Arc::new(Container::new(
+            supabase.clone(),
+            &self.cache_config,
+            password_hashers(&self.password_hash_config),
+        ));
+        self.supabase = supabase;
+        self
+    }
+}
+
+// 設定からパスワードのハッシュ化を作成する関数(不正なパラメータの場合は起動しない)
+fn password_hashers(config: &PasswordHashConfig) -> Arc<PasswordHashers> {
+    Arc::new(PasswordHashers::from_config(config).expect("PASSWORD_BCRYPT_COST または PASSWORD_ARGON2_* の設定が不正です"))
+}
+
+// 設定からトークンの署名・検証の鍵を作成する関数(鍵が不正な場合は起動しない)
+pub fn jwt_keys(config: &JwtConfig) -> Arc<JwtKeys> {
+    Arc::new(JwtKeys::from_config(config).unwrap_or_else(|e| panic!("JWT_* の設定が不正です: {}", e)))
 }
@@ -0,0 +1,8 @@
+// Supabaseのモジュールの宣言
+pub mod postgrest_query;
+pub mod supabase_client;
+
+#[cfg(test)]
+mod tests;
+
+// Supabaseのエントリーポイント
@@ -0,0 +1,196 @@
+// 必要なクレートのインポート
+use std::fmt::Display;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::RequestBuilder;
+
+// クエリ文字列でエンコードしない文字(RFC 3986の非予約文字)
+const QUERY_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+// 並び順
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Order {
+    // 昇順
+    Asc,
+    // 降順
+    Desc,
+}
+
+// PostgRESTのクエリを組み立てるビルダー
+//
+// 値は全てURLエンコードされるため、ユーザー入力をそのまま渡してもクエリに注入されない
+#[derive(Clone, Debug, Default)]
+pub struct PostgrestQuery {
+    // 対象のパス(エンコード済みのテーブル名またはRPCの関数名)
+    path: String,
+    // クエリパラメータ(エンコード済み)
+    params: Vec<(String, String)>,
+    // Preferヘッダーの値
+    prefer: Vec<String>,
+    // 取得範囲(Rangeヘッダー)
+    range: Option<(u64, u64)>,
+}
+
+// PostgrestQueryの実装
+impl PostgrestQuery {
+    // テーブルを対象とするクエリを作成する関数
+    pub fn new(table: &str) -> Self {
+        Self {
+            path: encode(table),
+            ..Self::default()
+        }
+    }
+
+    // RPC(ストアドファンクション)を対象とするクエリを作成する関数
+    pub fn rpc(function: &str) -> Self {
+        Self {
+            path: format!("rpc/{}", encode(function)),
+            ..Self::default()
+        }
+    }
+
+    // 取得する列を指定する関数
+    pub fn select(mut self, columns: &str) -> Self {
+        self.params.push(("select".to_string(), encode(columns)));
+        self
+    }
+
+    // 等しい(eq)
+    pub fn eq(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "eq", value)
+    }
+
+    // 等しくない(neq)
+    pub fn neq(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "neq", value)
+    }
+
+    // より大きい(gt)
+    pub fn gt(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "gt", value)
+    }
+
+    // 以上(gte)
+    pub fn gte(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "gte", value)
+    }
+
+    // より小さい(lt)
+    pub fn lt(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "lt", value)
+    }
+
+    // 以下(lte)
+    pub fn lte(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "lte", value)
+    }
+
+    // 大文字小文字を区別しないパターン一致(ilike)
+    //
+    // パターン中の`*`はワイルドカードとして扱われる。利用者の入力を部分一致させる場合は
+    // `escape_like`でワイルドカードを無効化してから渡すこと
+    pub fn ilike(self, column: &str, pattern: &str) -> Self {
+        self.filter(column, "ilike", pattern)
+    }
+
+    // いずれかに一致(in)
+    pub fn in_list<T: Display>(mut self, column: &str, values: impl IntoIterator<Item = T>) -> Self {
+        // 各値をダブルクォートで囲み、区切り文字や括弧を値として扱わせる
+        let list = values
+            .into_iter()
+            .map(|value| quote(&value.to_string()))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.params.push((encode(column), encode(&format!("in.({})", list))));
+        self
+    }
+
+    // 並び順を指定する関数
+    pub fn order(mut self, column: &str, order: Order) -> Self {
+        let direction = match order {
+            Order::Asc => "asc",
+            Order::Desc => "desc",
+        };
+        self.params.push(("order".to_string(), encode(&format!("{}.{}", column, direction))));
+        self
+    }
+
+    // 取得件数の上限を指定する関数
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.params.push(("limit".to_string(), limit.to_string()));
+        self
+    }
+
+    // 取得開始位置を指定する関数
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.params.push(("offset".to_string(), offset.to_string()));
+        self
+    }
+
+    // 取得範囲を指定する関数(両端を含む)
+    pub fn range(mut self, from: u64, to: u64) -> Self {
+        self.range = Some((from, to));
+        self
+    }
+
+    // Preferヘッダーを追加する関数(例: "return=representation", "count=exact")
+    pub fn prefer(mut self, preference: &str) -> Self {
+        self.prefer.push(preference.to_string());
+        self
+    }
+
+    // SupabaseのURLからリクエストURLを組み立てる関数
+    pub fn to_url(&self, supabase_url: &str) -> String {
+        // エンコード済みのパスを組み立てる
+        let mut url = format!("{}/rest/v1/{}", supabase_url.trim_end_matches('/'), self.path);
+        if !self.params.is_empty() {
+            let query = self
+                .params
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join("&");
+            url.push('?');
+            url.push_str(&query);
+        }
+        url
+    }
+
+    // Prefer・Rangeヘッダーをリクエストに設定する関数
+    pub fn apply_headers(&self, mut request: RequestBuilder) -> RequestBuilder {
+        // Preferヘッダーはカンマ区切りで1つにまとめる
+        if !self.prefer.is_empty() {
+            request = request.header("Prefer", self.prefer.join(","));
+        }
+        if let Some((from, to)) = self.range {
+            request = request
+                .header("Range-Unit", "items")
+                .header("Range", format!("{}-{}", from, to));
+        }
+        request
+    }
+
+    // フィルタを追加する関数
+    fn filter(mut self, column: &str, operator: &str, value: impl Display) -> Self {
+        self.params.push((encode(column), encode(&format!("{}.{}", operator, value))));
+        self
+    }
+}
+
+// ilikeのパターンとして使う文字列のワイルドカードを無効化する関数
+pub fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('*', "\\*").replace('%', "\\%").replace('_', "\\_")
+}
+
+// クエリ文字列の要素をURLエンコードする関数
+fn encode(value: &str) -> String {
+    utf8_percent_encode(value, QUERY_COMPONENT).to_string()
+}
+
+// PostgRESTのリスト内の値をダブルクォートで囲む関数
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
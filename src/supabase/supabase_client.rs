@@ -0,0 +1,167 @@
+// 必要なクレートのインポート
+use std::sync::Arc;
+use reqwest::{header, Client, Method, RequestBuilder, Response};
+use serde::{de::DeserializeOwned, Serialize};
+// Supabaseエラーのインポート
+use crate::errors::supabase::supabase_error::SupabaseError;
+// 外部HTTP呼び出しのインポート
+use crate::outbound::outbound_policy::OutboundPolicy;
+use crate::outbound::outbound_request::OutboundRequestExt;
+// PostgRESTのクエリビルダーのインポート
+use crate::supabase::postgrest_query::PostgrestQuery;
+
+// Supabase(PostgREST)のクライアント
+//
+// 共通ヘッダーの付与・呼び出しポリシーの適用・ステータスの確認・JSONのデコードをまとめて行う
+#[derive(Clone)]
+pub struct SupabaseClient {
+    // HTTPクライアント
+    client: Client,
+    // SupabaseのURL
+    supabase_url: String,
+    // Supabaseのキー(apikeyヘッダー)
+    api_key: String,
+    // Authorizationヘッダーに設定するトークン
+    auth_token: Option<String>,
+    // 呼び出しポリシー(タイムアウト・再試行・サーキットブレーカー)
+    outbound_policy: Arc<OutboundPolicy>,
+}
+
+// SupabaseClientの実装
+impl SupabaseClient {
+    // 新しいクライアントを作成する関数
+    pub fn new(
+        client: Client,
+        supabase_url: String,
+        api_key: String,
+        outbound_policy: Arc<OutboundPolicy>,
+    ) -> Self {
+        Self {
+            client,
+            supabase_url,
+            api_key,
+            auth_token: None,
+            outbound_policy,
+        }
+    }
+
+    // Authorizationヘッダーにトークンを設定したクライアントを作成する関数
+    pub fn with_auth_token(&self, token: impl Into<String>) -> Self {
+        Self {
+            auth_token: Some(token.into()),
+            ..self.clone()
+        }
+    }
+
+    // SupabaseのURLを取得する関数
+    pub fn supabase_url(&self) -> &str {
+        &self.supabase_url
+    }
+
+    // 呼び出しポリシーを取得する関数
+    pub fn outbound_policy(&self) -> &Arc<OutboundPolicy> {
+        &self.outbound_policy
+    }
+
+    // 共通ヘッダーを設定したリクエストを作成する関数
+    pub fn request(&self, method: Method, query: &PostgrestQuery) -> RequestBuilder {
+        let mut request = self.client
+            .request(method, query.to_url(&self.supabase_url))
+            .header("apikey", &self.api_key)
+            .header(header::CONTENT_TYPE, "application/json");
+
+        // トークンがあればAuthorizationヘッダーを設定
+        if let Some(token) = &self.auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        query.apply_headers(request)
+    }
+
+    // リクエストを送信し、エラーレスポンスをSupabaseエラーに変換する関数
+    pub async fn send(
+        &self,
+        operation: &'static str,
+        request: RequestBuilder,
+    ) -> Result<Response, SupabaseError> {
+        let response = request
+            .send_tracked(operation, &self.outbound_policy)
+            .await?;
+
+        // ステータスコードの確認
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let error = SupabaseError::from_response(status, &body);
+            tracing::warn!(operation, error = %error, "supabase request failed");
+            return Err(error);
+        }
+
+        Ok(response)
+    }
+
+    // リクエストを送信し、レスポンスをJSONとしてデコードする関数
+    pub async fn execute<T: DeserializeOwned>(
+        &self,
+        operation: &'static str,
+        request: RequestBuilder,
+    ) -> Result<T, SupabaseError> {
+        self.send(operation, request)
+            .await?
+            .json()
+            .await
+            .map_err(|e| SupabaseError::Decode(e.to_string()))
+    }
+
+    // 行を取得する関数
+    pub async fn select<T: DeserializeOwned>(
+        &self,
+        operation: &'static str,
+        query: &PostgrestQuery,
+    ) -> Result<Vec<T>, SupabaseError> {
+        self.execute(operation, self.request(Method::GET, query)).await
+    }
+
+    // 行を挿入し、挿入された行を返す関数
+    pub async fn insert<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        operation: &'static str,
+        query: &PostgrestQuery,
+        body: &B,
+    ) -> Result<Vec<T>, SupabaseError> {
+        let query = query.clone().prefer("return=representation");
+        self.execute(operation, self.request(Method::POST, &query).json(body)).await
+    }
+
+    // 行を更新し、更新された行を返す関数
+    pub async fn update<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        operation: &'static str,
+        query: &PostgrestQuery,
+        body: &B,
+    ) -> Result<Vec<T>, SupabaseError> {
+        let query = query.clone().prefer("return=representation");
+        self.execute(operation, self.request(Method::PATCH, &query).json(body)).await
+    }
+
+    // 行を削除し、削除された行を返す関数
+    pub async fn delete<T: DeserializeOwned>(
+        &self,
+        operation: &'static str,
+        query: &PostgrestQuery,
+    ) -> Result<Vec<T>, SupabaseError> {
+        let query = query.clone().prefer("return=representation");
+        self.execute(operation, self.request(Method::DELETE, &query)).await
+    }
+
+    // RPC(ストアドファンクション)を呼び出す関数
+    pub async fn rpc<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        operation: &'static str,
+        function: &str,
+        args: &B,
+    ) -> Result<T, SupabaseError> {
+        let query = PostgrestQuery::rpc(function);
+        self.execute(operation, self.request(Method::POST, &query).json(args)).await
+    }
+}
@@ -0,0 +1,2 @@
+pub mod postgrest_query_01_test;
+pub mod supabase_client_01_test;
@@ -0,0 +1,132 @@
+use reqwest::Client;
+use uuid::Uuid;
+// クエリビルダーのインポート
+use crate::supabase::postgrest_query::{escape_like, Order, PostgrestQuery};
+
+// 基本的なクエリの組み立てのテスト
+#[test]
+fn test_build_basic_query() {
+    // テーブルのみ
+    assert_eq!(
+        PostgrestQuery::new("trans_users").to_url("http://localhost"),
+        "http://localhost/rest/v1/trans_users"
+    );
+
+    // 列の指定・フィルタ・並び順・件数
+    let user_id = Uuid::nil();
+    let url = PostgrestQuery::new("trans_users")
+        .select("id,email")
+        .eq("id", user_id)
+        .order("created_at", Order::Desc)
+        .limit(10)
+        .offset(20)
+        .to_url("http://localhost/");
+    assert_eq!(
+        url,
+        "http://localhost/rest/v1/trans_users?select=id%2Cemail&id=eq.00000000-0000-0000-0000-000000000000&order=created_at.desc&limit=10&offset=20"
+    );
+
+    // RPC
+    assert_eq!(
+        PostgrestQuery::rpc("create_user_with_audit").to_url("http://localhost"),
+        "http://localhost/rest/v1/rpc/create_user_with_audit"
+    );
+}
+
+// 比較演算子のテスト
+#[test]
+fn test_comparison_filters() {
+    let url = PostgrestQuery::new("trans_users")
+        .neq("username", "admin")
+        .gt("created_at", "2024-01-01")
+        .gte("age", 18)
+        .lt("age", 65)
+        .lte("score", 100)
+        .to_url("http://localhost");
+    assert_eq!(
+        url,
+        "http://localhost/rest/v1/trans_users?username=neq.admin&created_at=gt.2024-01-01&age=gte.18&age=lt.65&score=lte.100"
+    );
+}
+
+// クエリの注入を試みる入力のテスト
+#[test]
+fn test_hostile_filter_values_are_encoded() {
+    // 別のフィルタを追加しようとするメールアドレス
+    let url = PostgrestQuery::new("trans_users")
+        .eq("email", "x@y.com&or=(id.neq.0)")
+        .to_url("http://localhost");
+    assert_eq!(
+        url,
+        "http://localhost/rest/v1/trans_users?email=eq.x%40y.com%26or%3D%28id.neq.0%29"
+    );
+
+    // クエリパラメータの区切り文字・フラグメント・改行を含む値
+    let url = PostgrestQuery::new("trans_users")
+        .eq("username", "a#b?c=d\r\n+e f")
+        .to_url("http://localhost");
+    assert_eq!(
+        url,
+        "http://localhost/rest/v1/trans_users?username=eq.a%23b%3Fc%3Dd%0D%0A%2Be%20f"
+    );
+
+    // 列名に注入を試みる場合もエンコードされる
+    let url = PostgrestQuery::new("trans_users")
+        .eq("id&select=password", "1")
+        .to_url("http://localhost");
+    assert_eq!(
+        url,
+        "http://localhost/rest/v1/trans_users?id%26select%3Dpassword=eq.1"
+    );
+
+    // テーブル名にパスを含めようとする場合もエンコードされる
+    assert_eq!(
+        PostgrestQuery::new("../auth/users").to_url("http://localhost"),
+        "http://localhost/rest/v1/..%2Fauth%2Fusers"
+    );
+}
+
+// inフィルタのテスト
+#[test]
+fn test_in_list_quotes_values() {
+    // 区切り文字や引用符を含む値は1つの値として扱われる
+    let url = PostgrestQuery::new("trans_users")
+        .in_list("username", ["alice", "bob,carol", "dave\")"])
+        .to_url("http://localhost");
+    assert_eq!(
+        url,
+        "http://localhost/rest/v1/trans_users?username=in.%28%22alice%22%2C%22bob%2Ccarol%22%2C%22dave%5C%22%29%22%29"
+    );
+}
+
+// ilikeのテスト
+#[test]
+fn test_ilike_with_escaped_pattern() {
+    // ワイルドカードを無効化した部分一致
+    let pattern = format!("*{}*", escape_like("50%_off*"));
+    let url = PostgrestQuery::new("trans_users")
+        .ilike("username", &pattern)
+        .to_url("http://localhost");
+    assert_eq!(
+        url,
+        "http://localhost/rest/v1/trans_users?username=ilike.%2A50%5C%25%5C_off%5C%2A%2A"
+    );
+}
+
+// Prefer・Rangeヘッダーのテスト
+#[test]
+fn test_apply_headers() {
+    let query = PostgrestQuery::new("trans_users")
+        .prefer("return=representation")
+        .prefer("count=exact")
+        .range(0, 9);
+    let request = query
+        .apply_headers(Client::new().get(query.to_url("http://localhost")))
+        .build()
+        .unwrap();
+
+    // ヘッダーが設定されていることを確認
+    assert_eq!(request.headers()["Prefer"], "return=representation,count=exact");
+    assert_eq!(request.headers()["Range-Unit"], "items");
+    assert_eq!(request.headers()["Range"], "0-9");
+}
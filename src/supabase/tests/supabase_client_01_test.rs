@@ -0,0 +1,164 @@
+use std::sync::Arc;
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+// Supabaseのクライアントのインポート
+use crate::supabase::supabase_client::SupabaseClient;
+use crate::supabase::postgrest_query::PostgrestQuery;
+// 呼び出しポリシーのインポート
+use crate::outbound::outbound_policy::OutboundPolicy;
+// エラーのインポート
+use crate::errors::supabase::supabase_error::SupabaseError;
+use crate::errors::users::user_error::UserError;
+use crate::errors::auth::auth_error::AuthError;
+
+// テスト用のクライアントを作成
+fn create_test_client(supabase_url: String) -> SupabaseClient {
+    SupabaseClient::new(
+        Client::new(),
+        supabase_url,
+        "test_key".to_string(),
+        Arc::new(OutboundPolicy::default()),
+    )
+}
+
+// 共通ヘッダーを付けて行を取得することのテスト
+#[tokio::test]
+async fn test_select_sends_common_headers() {
+    // モックサーバーの設定
+    let mut mock_server = mockito::Server::new_async().await;
+    let mock = mock_server
+        .mock("GET", "/rest/v1/trans_users?id=eq.1")
+        .match_header("apikey", "test_key")
+        .match_header("Content-Type", "application/json")
+        .match_header("Authorization", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_body(json!([{ "id": 1 }]).to_string())
+        .create_async()
+        .await;
+
+    // 行を取得
+    let client = create_test_client(mock_server.url());
+    let rows: Vec<serde_json::Value> = client
+        .select("find_by_id", &PostgrestQuery::new("trans_users").eq("id", 1))
+        .await
+        .unwrap();
+
+    // 結果の確認
+    assert_eq!(rows, vec![json!({ "id": 1 })]);
+    mock.assert_async().await;
+}
+
+// トークンを設定した場合はAuthorizationヘッダーを付けることのテスト
+#[tokio::test]
+async fn test_auth_token_is_sent_as_bearer() {
+    // モックサーバーの設定
+    let mut mock_server = mockito::Server::new_async().await;
+    let mock = mock_server
+        .mock("POST", "/rest/v1/trans_users")
+        .match_header("Authorization", "Bearer user-token")
+        .match_header("Prefer", "return=representation")
+        .match_body(mockito::Matcher::Json(json!({ "username": "test_user" })))
+        .with_status(201)
+        .with_body(json!([{ "username": "test_user" }]).to_string())
+        .create_async()
+        .await;
+
+    // トークン付きで行を挿入
+    let client = create_test_client(mock_server.url()).with_auth_token("user-token");
+    let rows: Vec<serde_json::Value> = client
+        .insert("create", &PostgrestQuery::new("trans_users"), &json!({ "username": "test_user" }))
+        .await
+        .unwrap();
+
+    // 結果の確認
+    assert_eq!(rows.len(), 1);
+    mock.assert_async().await;
+}
+
+// PostgRESTのエラーボディを解析することのテスト
+#[tokio::test]
+async fn test_error_body_is_parsed() {
+    // モックサーバーの設定(一意制約違反)
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = mock_server
+        .mock("PATCH", "/rest/v1/trans_users?id=eq.1")
+        .with_status(409)
+        .with_body(json!({
+            "code": "23505",
+            "message": "duplicate key value violates unique constraint \"trans_users_email_key\"",
+            "details": "Key (email)=(test@example.com) already exists.",
+            "hint": null
+        }).to_string())
+        .create_async()
+        .await;
+
+    // 行を更新
+    let client = create_test_client(mock_server.url());
+    let error = client
+        .update::<_, serde_json::Value>(
+            "update",
+            &PostgrestQuery::new("trans_users").eq("id", 1),
+            &json!({ "email": "test@example.com" }),
+        )
+        .await
+        .unwrap_err();
+
+    // エラーの確認
+    assert_eq!(error.code(), Some("23505"));
+    match &error {
+        SupabaseError::Api { status, body } => {
+            assert_eq!(*status, StatusCode::CONFLICT);
+            assert_eq!(body.details.as_deref(), Some("Key (email)=(test@example.com) already exists."));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    // ユーザーエラーへの変換
+    assert!(matches!(UserError::from(error), UserError::Conflict(_)));
+}
+
+// JSONでないエラーボディも扱えることのテスト
+#[test]
+fn test_non_json_error_body() {
+    // 本文がそのままメッセージになる
+    let error = SupabaseError::from_response(StatusCode::BAD_GATEWAY, "upstream error");
+    assert_eq!(error.code(), None);
+    assert!(error.to_string().contains("upstream error"));
+
+    // 空の本文はステータスの説明文になる
+    let error = SupabaseError::from_response(StatusCode::BAD_GATEWAY, "");
+    assert!(error.to_string().contains("Bad Gateway"));
+}
+
+// ユーザーエラー・認証エラーへの変換のテスト
+#[test]
+fn test_conversion_into_domain_errors() {
+    // ヒントを含むメッセージ
+    let error = SupabaseError::from_response(
+        StatusCode::BAD_REQUEST,
+        &json!({ "code": "22P02", "message": "invalid input syntax for type uuid", "hint": "check the id" }).to_string(),
+    );
+    match UserError::from(error) {
+        UserError::InvalidData(msg) => assert_eq!(msg, "invalid input syntax for type uuid (hint: check the id)"),
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    // 単一行が見つからない
+    let error = SupabaseError::from_response(
+        StatusCode::NOT_ACCEPTABLE,
+        &json!({ "code": "PGRST116", "message": "JSON object requested, multiple (or no) rows returned" }).to_string(),
+    );
+    assert!(matches!(UserError::from(error), UserError::UserNotFound));
+
+    // 遮断中はサービス利用不可
+    let error = SupabaseError::Unavailable("circuit open".to_string());
+    assert!(matches!(UserError::from(error), UserError::ServiceUnavailable(_)));
+    let error = SupabaseError::Unavailable("circuit open".to_string());
+    assert!(matches!(AuthError::from(error), AuthError::ServiceUnavailable(_)));
+
+    // デコードエラー
+    let error = SupabaseError::Decode("expected value".to_string());
+    assert!(matches!(UserError::from(error), UserError::JsonError(_)));
+    let error = SupabaseError::Decode("expected value".to_string());
+    assert!(matches!(AuthError::from(error), AuthError::DatabaseError(_)));
+}
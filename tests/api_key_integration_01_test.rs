@@ -0,0 +1,372 @@
+// 必要なクレートのインポート
+use backend::{
+    config::rate_limit_config::RateLimitPolicy,
+    di::services::api_key_service::{generate_api_key, hash_api_key},
+    models::auth::auth::Claims,
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// テスト用のユーザーID(管理者)
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// テスト用のAPIキーのID
+const KEY_ID: &str = "00000000-0000-0000-0000-0000000000a1";
+
+// テスト用のJWTシークレット
+const TEST_JWT_SECRET: &str = "test_secret";
+
+// テスト用のアプリケーションを作成(USER_IDを管理者にする)
+fn create_test_app(supabase_url: String) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.jwt_secret = TEST_JWT_SECRET.to_string();
+    state.admin_config.user_ids.insert(Uuid::parse_str(USER_ID).unwrap());
+    create_routes(Arc::new(state))
+}
+
+// ユーザーのトークンを作成(テスト用のJWTシークレットで署名)
+fn user_token() -> String {
+    let claims = Claims::new(&Uuid::parse_str(USER_ID).unwrap());
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes())).unwrap()
+}
+
+// ユーザーを返すモックを設定
+async fn setup_user_mock(mock_server: &mut mockito::Server) -> mockito::Mock {
+    mock_server
+        .mock("GET", format!("/rest/v1/trans_users?id=eq.{}", USER_ID).as_str())
+        .with_status(200)
+        .with_body(json!([{
+            "id": USER_ID,
+            "username": "test_user",
+            "email": "test@example.com",
+            "password": "$2b$04$unused",
+            "created_at": "2024-01-01T00:00:00",
+            "updated_at": "2024-01-01T00:00:00",
+        }]).to_string())
+        .create_async()
+        .await
+}
+
+// 保存されたAPIキーの行
+fn api_key_row(prefix: &str, key: &str, scopes: Value, expires_at: &str) -> Value {
+    json!({
+        "id": KEY_ID,
+        "user_id": USER_ID,
+        "name": "nightly-batch",
+        "prefix": prefix,
+        "key_hash": hash_api_key(key),
+        "scopes": scopes,
+        "expires_at": expires_at,
+        "last_used_at": null,
+        "created_at": "2024-01-01T00:00:00",
+    })
+}
+
+// APIキーを生成し、先頭での取得と最終使用日時の更新のモックを設定
+async fn setup_api_key_mocks(mock_server: &mut mockito::Server, scopes: Value, expires_at: &str) -> (String, Vec<mockito::Mock>) {
+    let (prefix, key) = generate_api_key();
+    let find_mock = mock_server
+        .mock("GET", format!("/rest/v1/trans_user_api_keys?prefix=eq.{}", prefix).as_str())
+        .with_status(200)
+        .with_body(json!([api_key_row(&prefix, &key, scopes, expires_at)]).to_string())
+        .create_async()
+        .await;
+    let touch_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/touch_api_key")
+        .match_body(mockito::Matcher::Json(json!({ "p_id": KEY_ID })))
+        .expect_at_least(1)
+        .with_status(200)
+        .with_body(json!([{ "id": KEY_ID }]).to_string())
+        .create_async()
+        .await;
+    (key, vec![find_mock, touch_mock])
+}
+
+// リクエストを送信し、ステータスとボディを返す
+async fn send(app: Router, method: Method, uri: &str, token: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token));
+    let body = match body {
+        Some(body) => {
+            request = request.header(header::CONTENT_TYPE, "application/json");
+            Body::from(body.to_string())
+        }
+        None => Body::empty(),
+    };
+    let response = app.oneshot(request.body(body).unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+// APIキーを発行すると、キーを1回だけ返しハッシュのみ保存することのテスト
+#[tokio::test]
+async fn test_create_api_key() {
+    let mut mock_server = mockito::Server::new_async().await;
+    // RPCの引数を保存し、保存した行として返す
+    let args: Arc<Mutex<Value>> = Arc::new(Mutex::new(Value::Null));
+    let captured = args.clone();
+    let create_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/create_api_key_with_audit")
+        .with_status(200)
+        .with_body_from_request(move |request| {
+            let body: Value = serde_json::from_slice(request.body().unwrap()).unwrap();
+            *captured.lock().unwrap() = body.clone();
+            json!([{
+                "id": KEY_ID,
+                "user_id": body["p_user_id"],
+                "name": body["p_name"],
+                "prefix": body["p_prefix"],
+                "key_hash": body["p_key_hash"],
+                "scopes": body["p_scopes"],
+                "expires_at": body["p_expires_at"],
+                "last_used_at": null,
+                "created_at": "2024-01-01T00:00:00",
+            }])
+            .to_string()
+            .into_bytes()
+        })
+        .create_async()
+        .await;
+    let app = create_test_app(mock_server.url());
+
+    let request = json!({ "name": "nightly-batch", "scopes": ["read", "write"], "expires_in_days": 30 });
+    let (status, body) = send(app.clone(), Method::POST, "/v2/me/api-keys", &user_token(), Some(request)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let key = body["key"].as_str().unwrap();
+    assert!(key.starts_with(body["api_key"]["prefix"].as_str().unwrap()));
+    assert_eq!(body["api_key"]["scopes"], json!(["read", "write"]));
+    assert!(body["api_key"].get("key_hash").is_none());
+
+    // 保存するのはキーのハッシュのみ
+    let args = args.lock().unwrap().clone();
+    assert_eq!(args["p_user_id"], USER_ID);
+    assert_eq!(args["p_key_hash"], hash_api_key(key));
+    assert_eq!(args["p_max_keys"], 10);
+
+    // 不正な権限・有効日数
+    for request in [
+        json!({ "name": "batch", "scopes": [] }),
+        json!({ "name": "batch", "scopes": ["read"], "expires_in_days": 10000 }),
+    ] {
+        let (status, _) = send(app.clone(), Method::POST, "/v2/me/api-keys", &user_token(), Some(request)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    create_mock.assert_async().await;
+}
+
+// APIキーでトークンと同じように認証でき、権限に応じて拒否することのテスト
+#[tokio::test]
+async fn test_api_key_authenticates_within_scopes() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mock = setup_user_mock(&mut mock_server).await;
+    let (key, mocks) = setup_api_key_mocks(&mut mock_server, json!(["read"]), "2999-01-01T00:00:00").await;
+    let app = create_test_app(mock_server.url());
+
+    for uri in ["/auth/check", "/v2/auth/check", "/v2/me"] {
+        let (status, body) = send(app.clone(), Method::GET, uri, &key, None).await;
+        assert_eq!(status, StatusCode::OK, "{}", uri);
+        assert_eq!(body["id"], USER_ID);
+    }
+
+    // 更新はwrite、管理者向けのAPIはadminの権限が必要
+    let update = Some(json!({ "username": "renamed" }));
+    let (status, _) = send(app.clone(), Method::PATCH, "/v2/me", &key, update).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let uri = format!("/v2/users/{}/2fa", USER_ID);
+    let (status, _) = send(app.clone(), Method::DELETE, &uri, &key, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // APIキーではAPIキー・二段階認証を管理できない
+    let (status, _) = send(app.clone(), Method::GET, "/v2/me/api-keys", &key, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(app.clone(), Method::POST, "/v2/me/2fa/setup", &key, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 最終使用日時を更新する
+    mocks[1].assert_async().await;
+}
+
+// adminの権限を持つ管理者のAPIキーは、管理者向けのAPIを呼び出せることのテスト
+#[tokio::test]
+async fn test_admin_scope() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let (key, _mocks) = setup_api_key_mocks(&mut mock_server, json!(["admin"]), "2999-01-01T00:00:00").await;
+    let reset_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/reset_two_factor_with_audit")
+        .with_status(200)
+        .with_body(json!([{ "id": USER_ID }]).to_string())
+        .create_async()
+        .await;
+    let app = create_test_app(mock_server.url());
+
+    let uri = format!("/v2/users/{}/2fa", USER_ID);
+    let (status, _) = send(app, Method::DELETE, &uri, &key, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    reset_mock.assert_async().await;
+}
+
+// 期限切れ・秘密の部分が異なる・存在しないキーを拒否することのテスト
+#[tokio::test]
+async fn test_invalid_api_keys_are_rejected() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let (expired, _expired_mocks) = setup_api_key_mocks(&mut mock_server, json!(["read"]), "2020-01-01T00:00:00").await;
+    let (valid, _valid_mocks) = setup_api_key_mocks(&mut mock_server, json!(["read"]), "2999-01-01T00:00:00").await;
+    let (unknown, _) = generate_api_key();
+    let _unknown_mock = mock_server
+        .mock("GET", format!("/rest/v1/trans_user_api_keys?prefix=eq.{}", unknown).as_str())
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let app = create_test_app(mock_server.url());
+
+    // 先頭が同じで秘密の部分が異なるキー
+    let forged = format!("{}{}", &valid[..valid.len() - 1], if valid.ends_with('A') { 'B' } else { 'A' });
+    let unknown_key = format!("{}_{}", unknown, "A".repeat(40));
+    for (case, key) in [("expired", expired), ("forged", forged), ("unknown", unknown_key), ("malformed", "bk_abc".to_string())] {
+        let (status, _) = send(app.clone(), Method::GET, "/v2/auth/check", &key, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", case);
+    }
+}
+
+// 自分のAPIキーの一覧・削除のテスト
+#[tokio::test]
+async fn test_list_and_delete_api_keys() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let (prefix, key) = generate_api_key();
+    let _list_mock = mock_server
+        .mock("GET", format!("/rest/v1/trans_user_api_keys?user_id=eq.{}&order=created_at.desc", USER_ID).as_str())
+        .with_status(200)
+        .with_body(json!([api_key_row(&prefix, &key, json!(["read"]), "2999-01-01T00:00:00")]).to_string())
+        .create_async()
+        .await;
+    let delete_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/delete_api_key_with_audit")
+        .match_body(mockito::Matcher::Json(json!({ "p_user_id": USER_ID, "p_id": KEY_ID })))
+        .with_status(200)
+        .with_body(json!([{ "id": KEY_ID }]).to_string())
+        .create_async()
+        .await;
+    let _missing_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/delete_api_key_with_audit")
+        .match_body(mockito::Matcher::PartialJson(json!({ "p_id": Uuid::nil() })))
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let app = create_test_app(mock_server.url());
+
+    let (status, body) = send(app.clone(), Method::GET, "/v2/me/api-keys", &user_token(), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["prefix"], prefix);
+    assert!(body[0].get("key_hash").is_none());
+
+    let uri = format!("/v2/me/api-keys/{}", KEY_ID);
+    let (status, _) = send(app.clone(), Method::DELETE, &uri, &user_token(), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let uri = format!("/v2/me/api-keys/{}", Uuid::nil());
+    let (status, _) = send(app, Method::DELETE, &uri, &user_token(), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    delete_mock.assert_async().await;
+}
+
+// 存在しないAPIキーを毎回変えても、IPアドレスのレート制限を回避できないことのテスト
+#[tokio::test]
+async fn test_unknown_api_keys_do_not_bypass_rate_limit() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _find_mock = mock_server
+        .mock("GET", mockito::Matcher::Regex("^/rest/v1/trans_user_api_keys\\?prefix=eq\\.".to_string()))
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    // 認証の確認を60秒で2回までに制限した状態を作成
+    let mut state = AppState::new(mock_server.url(), "test_key".to_string());
+    state.rate_limit_config.route_policies.insert(
+        "GET /auth/check".to_string(),
+        RateLimitPolicy::new(2, Duration::from_secs(60)),
+    );
+    let app = create_routes(Arc::new(state));
+
+    // リクエストごとに別の(形式は正しいが存在しない)APIキーを送る
+    let mut statuses = Vec::new();
+    for _ in 0..3 {
+        let (_, key) = generate_api_key();
+        let (status, _) = send(app.clone(), Method::GET, "/auth/check", &key, None).await;
+        statuses.push(status);
+    }
+
+    // 同じIPアドレスのバケットで数えられ、3回目は拒否される
+    assert_eq!(statuses, [StatusCode::UNAUTHORIZED, StatusCode::UNAUTHORIZED, StatusCode::TOO_MANY_REQUESTS]);
+}
+
+// 検証に成功したキーはキーごとのバケットで数え、制限を超えたリクエストではDBを呼び出さないことのテスト
+#[tokio::test]
+async fn test_verified_api_key_has_own_bucket_without_db_lookup() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mock = setup_user_mock(&mut mock_server).await;
+    let (prefix, key) = generate_api_key();
+    // 認証での取得のみ(制限を超えた4回目では呼び出さない)
+    let find_mock = mock_server
+        .mock("GET", format!("/rest/v1/trans_user_api_keys?prefix=eq.{}", prefix).as_str())
+        .with_status(200)
+        .with_body(json!([api_key_row(&prefix, &key, json!(["read"]), "2999-01-01T00:00:00")]).to_string())
+        .expect(3)
+        .create_async()
+        .await;
+    let _touch_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/touch_api_key")
+        .with_status(200)
+        .with_body(json!([{ "id": KEY_ID }]).to_string())
+        .create_async()
+        .await;
+    let _unknown_mock = mock_server
+        .mock("GET", mockito::Matcher::Regex("^/rest/v1/trans_user_api_keys\\?prefix=eq\\.".to_string()))
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    // 認証の確認を60秒で2回までに制限した状態を作成
+    let mut state = AppState::new(mock_server.url(), "test_key".to_string());
+    state.rate_limit_config.route_policies.insert(
+        "GET /auth/check".to_string(),
+        RateLimitPolicy::new(2, Duration::from_secs(60)),
+    );
+    let app = create_routes(Arc::new(state));
+
+    // 1回目は検証前のためIPアドレスで数え、2回目以降はキーのIDで数える
+    let mut statuses = Vec::new();
+    for _ in 0..4 {
+        let (status, _) = send(app.clone(), Method::GET, "/auth/check", &key, None).await;
+        statuses.push(status);
+    }
+    assert_eq!(statuses, [StatusCode::OK, StatusCode::OK, StatusCode::OK, StatusCode::TOO_MANY_REQUESTS]);
+
+    // IPアドレスのバケットは残り1回
+    let (unknown, _) = generate_api_key();
+    let unknown_key = format!("{}_{}", unknown, "A".repeat(40));
+    let (status, _) = send(app.clone(), Method::GET, "/auth/check", &unknown_key, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(app, Method::GET, "/auth/check", &unknown_key, None).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    find_mock.assert_async().await;
+}
@@ -0,0 +1,52 @@
+// 必要なクレートのインポート
+use backend::{
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+
+// サインインのメールアドレスがクエリに注入されないことのテスト
+#[tokio::test]
+async fn test_sign_in_email_is_not_injected() {
+    // モックサーバーの設定(エンコードされたメールアドレスでのみ検索される)
+    let mut mock_server = mockito::Server::new_async().await;
+    let mock = mock_server
+        .mock("GET", "/rest/v1/trans_users?email=eq.x%40y.com%26or%3D%28id.neq.0%29")
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    // テスト用のアプリケーションを作成
+    let state = Arc::new(AppState::new(mock_server.url(), "test_key".to_string()));
+    let app = create_routes(state);
+
+    // 別のフィルタを追加しようとするメールアドレスでサインイン
+    let body = serde_json::json!({
+        "email": "x@y.com&or=(id.neq.0)",
+        "password": "password123",
+    });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/auth/signin")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // ステータスコードがUNAUTHORIZEDであることを確認
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // モックの確認
+    mock.assert_async().await;
+}
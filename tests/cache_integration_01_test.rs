@@ -0,0 +1,158 @@
+// 必要なクレートのインポート
+use backend::{
+    models::auth::auth::Claims,
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::Utc;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::json;
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// テスト用のJWTシークレット
+const TEST_JWT_SECRET: &str = "test_secret";
+
+// テスト用のユーザーのJSONを作成
+fn test_user_json(id: Uuid, username: &str) -> serde_json::Value {
+    let now = Utc::now().naive_utc();
+    json!({
+        "id": id,
+        "username": username,
+        "email": "test@example.com",
+        "password": "hashed_password",
+        "created_at": now,
+        "updated_at": now,
+    })
+}
+
+// テスト用のトークンを作成
+fn create_token(user_id: Uuid) -> String {
+    let now = Utc::now().timestamp();
+    let claims = Claims {
+        sub: user_id.to_string(),
+        exp: now + 3600,
+        iat: now,
+        iss: None,
+        aud: None,
+        sid: None,
+    };
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes())).unwrap()
+}
+
+// テスト用のアプリケーションを作成
+fn create_test_app(supabase_url: String) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.jwt_secret = TEST_JWT_SECRET.to_string();
+    create_routes(Arc::new(state))
+}
+
+// 認証の確認をリクエスト
+async fn check_auth(app: &Router, token: &str) -> StatusCode {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .uri("/auth/check")
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+// 認証の確認とユーザーの取得がキャッシュを共有し、更新で無効化されることのテスト
+#[tokio::test]
+async fn test_user_lookups_are_cached_until_update() {
+    let user_id = Uuid::new_v4();
+
+    // モックサーバーの設定(更新の前後で1回ずつ取得される)
+    let mut mock_server = mockito::Server::new_async().await;
+    let find_mock = mock_server
+        .mock("GET", format!("/rest/v1/trans_users?id=eq.{}", user_id).as_str())
+        .with_status(200)
+        .with_body(json!([test_user_json(user_id, "test_user")]).to_string())
+        .expect(2)
+        .create_async()
+        .await;
+    let update_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/update_user_with_audit")
+        .with_status(200)
+        .with_body(json!([test_user_json(user_id, "updated_user")]).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let app = create_test_app(mock_server.url());
+    let token = create_token(user_id);
+
+    // 認証の確認とユーザーの取得を繰り返してもSupabaseへの取得は1回
+    assert_eq!(check_auth(&app, &token).await, StatusCode::OK);
+    assert_eq!(check_auth(&app, &token).await, StatusCode::OK);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/users/{}", user_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 更新するとキャッシュが無効化される
+    let body = json!({
+        "username": "updated_user",
+        "email": "test@example.com",
+    });
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/users/{}", user_id))
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 更新後は取得し直す
+    assert_eq!(check_auth(&app, &token).await, StatusCode::OK);
+    assert_eq!(check_auth(&app, &token).await, StatusCode::OK);
+
+    // モックの確認
+    find_mock.assert_async().await;
+    update_mock.assert_async().await;
+}
+
+// ユーザーIDでないサブジェクトのトークンは無効として扱うことのテスト
+#[tokio::test]
+async fn test_check_auth_rejects_non_uuid_subject() {
+    let mock_server = mockito::Server::new_async().await;
+    let app = create_test_app(mock_server.url());
+
+    let now = Utc::now().timestamp();
+    let claims = Claims {
+        sub: "not-a-uuid".to_string(),
+        exp: now + 3600,
+        iat: now,
+        iss: None,
+        aud: None,
+        sid: None,
+    };
+    let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes())).unwrap();
+
+    assert_eq!(check_auth(&app, &token).await, StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,231 @@
+// 必要なクレートのインポート
+use backend::{
+    config::cache_config::CacheConfig,
+    di::{container::Container, repositories::repository::Resource, routers::crud_router::CrudRouter},
+    outbound::outbound_policy::OutboundPolicy,
+    password::password_hasher::PasswordHashers,
+    supabase::supabase_client::SupabaseClient,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// テスト用のリソース(モデルとテーブル名を宣言するだけ)
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+struct Note {
+    // ID
+    #[schema(value_type = String)]
+    id: Uuid,
+    // 本文
+    body: String,
+}
+
+// テスト用のリソースの入力
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+struct NewNote {
+    // 本文
+    body: String,
+}
+
+// Resourceトレイトの実装
+impl Resource for Note {
+    type Id = Uuid;
+    type Input = NewNote;
+    const TABLE: &'static str = "trans_notes";
+    const RESOURCE: &'static str = "notes";
+}
+
+// テスト用のアプリケーションを作成
+fn create_test_app(supabase_url: String) -> Router {
+    let supabase = SupabaseClient::new(
+        reqwest::Client::new(),
+        supabase_url,
+        "test_key".to_string(),
+        Arc::new(OutboundPolicy::default()),
+    );
+    Container::new(supabase, &CacheConfig::default(), Arc::new(PasswordHashers::default())).resource::<Note>().routes()
+}
+
+// レスポンスボディをバイト列に変換
+async fn body_to_bytes(body: Body) -> Vec<u8> {
+    axum::body::to_bytes(body, usize::MAX).await.unwrap().to_vec()
+}
+
+// 宣言したリソースのCRUDのテスト
+#[tokio::test]
+async fn test_declared_resource_crud() {
+    // モックサーバーの設定
+    let mut mock_server = mockito::Server::new_async().await;
+    let note_id = Uuid::new_v4();
+    let note = serde_json::json!({ "id": note_id, "body": "hello" });
+    let item_path = format!("/rest/v1/trans_notes?id=eq.{}", note_id);
+
+    let list_mock = mock_server
+        .mock("GET", "/rest/v1/trans_notes")
+        .match_header("apikey", "test_key")
+        .with_status(200)
+        .with_body(serde_json::json!([note]).to_string())
+        .create_async()
+        .await;
+    let create_mock = mock_server
+        .mock("POST", "/rest/v1/trans_notes")
+        .match_header("Prefer", "return=representation")
+        .match_body(mockito::Matcher::Json(serde_json::json!({ "body": "hello" })))
+        .with_status(201)
+        .with_body(serde_json::json!([note]).to_string())
+        .create_async()
+        .await;
+    let update_mock = mock_server
+        .mock("PATCH", item_path.as_str())
+        .match_body(mockito::Matcher::Json(serde_json::json!({ "body": "updated" })))
+        .with_status(200)
+        .with_body(serde_json::json!([{ "id": note_id, "body": "updated" }]).to_string())
+        .create_async()
+        .await;
+    let delete_mock = mock_server
+        .mock("DELETE", item_path.as_str())
+        .with_status(200)
+        .with_body(serde_json::json!([note]).to_string())
+        .create_async()
+        .await;
+
+    let app = create_test_app(mock_server.url());
+
+    // ------------------------------------------------------------------------
+    // 1. 一覧の取得
+    // ------------------------------------------------------------------------
+    let response = app.clone()
+        .oneshot(Request::builder().uri("/di/notes").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let notes: Vec<serde_json::Value> = serde_json::from_slice(&body_to_bytes(response.into_body()).await).unwrap();
+    assert_eq!(notes, vec![note.clone()]);
+
+    // ------------------------------------------------------------------------
+    // 2. 作成
+    // ------------------------------------------------------------------------
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/di/notes")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::json!({ "body": "hello" }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // ------------------------------------------------------------------------
+    // 3. 更新
+    // ------------------------------------------------------------------------
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/di/notes/{}", note_id))
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::json!({ "body": "updated" }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let updated: serde_json::Value = serde_json::from_slice(&body_to_bytes(response.into_body()).await).unwrap();
+    assert_eq!(updated["body"], "updated");
+
+    // ------------------------------------------------------------------------
+    // 4. 削除
+    // ------------------------------------------------------------------------
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/di/notes/{}", note_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // モックの確認
+    list_mock.assert_async().await;
+    create_mock.assert_async().await;
+    update_mock.assert_async().await;
+    delete_mock.assert_async().await;
+}
+
+// 存在しないリソースの取得・削除は404を返すことのテスト
+#[tokio::test]
+async fn test_missing_resource_returns_not_found() {
+    // モックサーバーの設定(該当する行なし)
+    let mut mock_server = mockito::Server::new_async().await;
+    let note_id = Uuid::new_v4();
+    let item_path = format!("/rest/v1/trans_notes?id=eq.{}", note_id);
+    let _get_mock = mock_server
+        .mock("GET", item_path.as_str())
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let _delete_mock = mock_server
+        .mock("DELETE", item_path.as_str())
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    let app = create_test_app(mock_server.url());
+
+    for method in ["GET", "DELETE"] {
+        let response = app.clone()
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(format!("/di/notes/{}", note_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", method);
+    }
+}
+
+// 宣言したリソースのOpenAPIドキュメントのテスト
+#[test]
+fn test_declared_resource_openapi() {
+    let doc = serde_json::to_value(CrudRouter::<Note>::openapi()).unwrap();
+
+    // パスと操作
+    let collection = &doc["paths"]["/di/notes"];
+    assert!(collection["get"].is_object());
+    assert!(collection["post"].is_object());
+    let item = &doc["paths"]["/di/notes/{id}"];
+    for method in ["get", "put", "delete"] {
+        assert!(item[method].is_object(), "{}", method);
+        assert_eq!(item[method]["tags"][0], "notes");
+    }
+    assert_eq!(item["get"]["parameters"][0]["name"], "id");
+
+    // スキーマ
+    let schemas = &doc["components"]["schemas"];
+    assert!(schemas["Note"].is_object());
+    assert!(schemas["NewNote"].is_object());
+    assert!(schemas["ErrorResponse"].is_object());
+    assert_eq!(
+        collection["post"]["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/NewNote"
+    );
+}
@@ -0,0 +1,349 @@
+// 必要なクレートのインポート
+use backend::{
+    models::auth::auth::Claims,
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// レスポンスボディをバイト列に変換
+async fn body_to_bytes(body: Body) -> Vec<u8> {
+    axum::body::to_bytes(body, usize::MAX).await.unwrap().to_vec()
+}
+
+// 冪等キー付きのユーザー作成のリクエストを作成
+fn create_user_request(uri: &str, key: &str, username: &str) -> Request<Body> {
+    let body = serde_json::json!({
+        "username": username,
+        "email": "test@example.com",
+        "password": "password123",
+    });
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .header("Idempotency-Key", key)
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+// テスト用のJWTシークレット
+const TEST_JWT_SECRET: &str = "test_secret";
+
+// テスト用のJWTシークレットで署名したAuthorizationヘッダーの値を作成
+fn bearer(user_id: &Uuid) -> String {
+    let claims = Claims::new(user_id);
+    let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes())).unwrap();
+    format!("Bearer {}", token)
+}
+
+// ユーザー作成のRPCが1回だけ呼ばれるアプリケーションを作成
+async fn create_test_app(mock_server: &mut mockito::Server) -> (Router, mockito::Mock) {
+    let created_user = serde_json::json!({
+        "id": "00000000-0000-0000-0000-000000000001",
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": "hashed",
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    });
+    let mock = mock_server
+        .mock("POST", "/rest/v1/rpc/create_user_with_audit")
+        .with_status(200)
+        .with_body(serde_json::json!([created_user]).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mut state = AppState::new(mock_server.url(), "test_key".to_string());
+    state.jwt_secret = TEST_JWT_SECRET.to_string();
+    (create_routes(Arc::new(state)), mock)
+}
+
+// 同じキーの再試行は最初のレスポンスを返し、ユーザーを重複して作成しないことのテスト
+#[tokio::test]
+async fn test_retry_replays_first_response() {
+    for uri in ["/users", "/di/users"] {
+        let mut mock_server = mockito::Server::new_async().await;
+        let (app, mock) = create_test_app(&mut mock_server).await;
+
+        // 1回目は作成される
+        let first = app.clone()
+            .oneshot(create_user_request(uri, "key-1", "test_user"))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK, "{}", uri);
+        assert!(first.headers().get("idempotent-replayed").is_none());
+        let first_body = body_to_bytes(first.into_body()).await;
+
+        // 2回目は保存したレスポンスが返る
+        let second = app.clone()
+            .oneshot(create_user_request(uri, "key-1", "test_user"))
+            .await
+            .unwrap();
+        assert_eq!(second.status(), StatusCode::OK, "{}", uri);
+        assert_eq!(second.headers()["idempotent-replayed"], "true");
+        assert_eq!(body_to_bytes(second.into_body()).await, first_body);
+
+        // RPCは1回しか呼ばれない
+        mock.assert_async().await;
+    }
+}
+
+// 同じキーを別のリクエストボディで使うと422を返すことのテスト
+#[tokio::test]
+async fn test_key_reuse_with_different_body_is_rejected() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let (app, mock) = create_test_app(&mut mock_server).await;
+
+    let first = app.clone()
+        .oneshot(create_user_request("/di/users", "key-1", "test_user"))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+
+    // 別のリクエストボディ
+    let second = app.clone()
+        .oneshot(create_user_request("/di/users", "key-1", "other_user"))
+        .await
+        .unwrap();
+    assert_eq!(second.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // 別のルート
+    let third = app
+        .oneshot(create_user_request("/users", "key-1", "test_user"))
+        .await
+        .unwrap();
+    assert_eq!(third.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    mock.assert_async().await;
+}
+
+// キーは呼び出し元ごとに分かれることのテスト
+#[tokio::test]
+async fn test_keys_are_scoped_per_caller() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let mock = mock_server
+        .mock("POST", "/rest/v1/rpc/create_user_with_audit")
+        .with_status(200)
+        .with_body("[]")
+        .expect(2)
+        .create_async()
+        .await;
+    let mut state = AppState::new(mock_server.url(), "test_key".to_string());
+    state.jwt_secret = TEST_JWT_SECRET.to_string();
+    let app = create_routes(Arc::new(state));
+
+    // 別のユーザーのトークンで同じ冪等キーを使う(テスト用のJWTシークレットで署名)
+    for _ in 0..2 {
+        let mut request = create_user_request("/di/users", "shared-key", "test_user");
+        request.headers_mut().insert("authorization", bearer(&Uuid::new_v4()).parse().unwrap());
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert!(response.headers().get("idempotent-replayed").is_none());
+    }
+
+    // どちらも処理される
+    mock.assert_async().await;
+}
+
+// 同じユーザーの再試行は、トークンを発行し直しても保存したレスポンスを返すことのテスト
+#[tokio::test]
+async fn test_same_user_retry_is_replayed() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let (app, mock) = create_test_app(&mut mock_server).await;
+
+    let user_id = Uuid::new_v4();
+    let mut replayed = Vec::new();
+    for _ in 0..2 {
+        let mut request = create_user_request("/di/users", "shared-key", "test_user");
+        request.headers_mut().insert("authorization", bearer(&user_id).parse().unwrap());
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        replayed.push(response.headers().get("idempotent-replayed").is_some());
+    }
+    assert_eq!(replayed, vec![false, true]);
+
+    mock.assert_async().await;
+}
+
+// 検証できないトークンのリクエストは保存も再送もしないことのテスト
+#[tokio::test]
+async fn test_invalid_token_is_not_stored() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let mock = mock_server
+        .mock("POST", "/rest/v1/rpc/create_user_with_audit")
+        .with_status(200)
+        .with_body("[]")
+        .expect(2)
+        .create_async()
+        .await;
+    let app = create_routes(Arc::new(AppState::new(mock_server.url(), "test_key".to_string())));
+
+    for _ in 0..2 {
+        let mut request = create_user_request("/di/users", "key-1", "test_user");
+        request.headers_mut().insert("authorization", "Bearer invalid".parse().unwrap());
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert!(response.headers().get("idempotent-replayed").is_none());
+    }
+
+    mock.assert_async().await;
+}
+
+// サーバーエラーは保存されず、同じキーで再試行できることのテスト
+#[tokio::test]
+async fn test_server_error_is_not_stored() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let mock = mock_server
+        .mock("POST", "/rest/v1/rpc/create_user_with_audit")
+        .with_status(500)
+        .with_body(serde_json::json!({ "message": "internal error" }).to_string())
+        .expect(2)
+        .create_async()
+        .await;
+    let app = create_routes(Arc::new(AppState::new(mock_server.url(), "test_key".to_string())));
+
+    for _ in 0..2 {
+        let response = app.clone()
+            .oneshot(create_user_request("/di/users", "key-1", "test_user"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(response.headers().get("idempotent-replayed").is_none());
+    }
+
+    mock.assert_async().await;
+}
+
+// Cookieを設定するレスポンスは保存せず、再送でセッションのCookieを再発行しないことのテスト
+#[tokio::test]
+async fn test_responses_setting_cookies_are_not_stored() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let user_row = serde_json::json!([{
+        "id": "00000000-0000-0000-0000-000000000001",
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": bcrypt::hash("password123", 4).unwrap(),
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    }])
+    .to_string();
+    let user_mock = mock_server
+        .mock("GET", "/rest/v1/trans_users?email=eq.test%40example.com")
+        .with_status(200)
+        .with_body(user_row.clone())
+        .expect(2)
+        .create_async()
+        .await;
+    let _user_by_id_mock = mock_server
+        .mock("GET", "/rest/v1/trans_users?id=eq.00000000-0000-0000-0000-000000000001")
+        .with_status(200)
+        .with_body(user_row)
+        .create_async()
+        .await;
+    let _session_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/create_user_session")
+        .with_status(200)
+        .with_body(serde_json::json!([{
+            "id": "00000000-0000-0000-0000-0000000000e1",
+            "user_id": "00000000-0000-0000-0000-000000000001",
+            "user_agent": null,
+            "ip_address": null,
+            "created_at": "2024-01-01T00:00:00",
+            "last_seen_at": "2024-01-01T00:00:00",
+            "expires_at": "2099-01-01T00:00:00",
+        }]).to_string())
+        .create_async()
+        .await;
+    let mut state = AppState::new(mock_server.url(), "test_key".to_string());
+    state.session_cookie_config.enabled = true;
+    let app = create_routes(Arc::new(state));
+
+    // 同じ冪等キーでサインインを2回送る
+    for _ in 0..2 {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/auth/signin")
+            .header("Content-Type", "application/json")
+            .header("Idempotency-Key", "sign-in-key")
+            .body(Body::from(r#"{"email":"test@example.com","password":"password123"}"#))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().contains_key("set-cookie"));
+        assert!(response.headers().get("idempotent-replayed").is_none());
+    }
+
+    // どちらも処理される
+    user_mock.assert_async().await;
+}
+
+// 認証のルートは、Cookieを使わない場合もトークンを含むレスポンスを保存・再送しないことのテスト
+#[tokio::test]
+async fn test_auth_routes_are_not_stored() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let user_row = serde_json::json!([{
+        "id": "00000000-0000-0000-0000-000000000001",
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": bcrypt::hash("password123", 4).unwrap(),
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    }])
+    .to_string();
+    let user_mock = mock_server
+        .mock("GET", "/rest/v1/trans_users?email=eq.test%40example.com")
+        .with_status(200)
+        .with_body(user_row.clone())
+        .expect(4)
+        .create_async()
+        .await;
+    let _user_by_id_mock = mock_server
+        .mock("GET", "/rest/v1/trans_users?id=eq.00000000-0000-0000-0000-000000000001")
+        .with_status(200)
+        .with_body(user_row)
+        .create_async()
+        .await;
+    let _session_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/create_user_session")
+        .with_status(200)
+        .with_body(serde_json::json!([{
+            "id": "00000000-0000-0000-0000-0000000000e1",
+            "user_id": "00000000-0000-0000-0000-000000000001",
+            "user_agent": null,
+            "ip_address": null,
+            "created_at": "2024-01-01T00:00:00",
+            "last_seen_at": "2024-01-01T00:00:00",
+            "expires_at": "2099-01-01T00:00:00",
+        }]).to_string())
+        .create_async()
+        .await;
+    let app = create_routes(Arc::new(AppState::new(mock_server.url(), "test_key".to_string())));
+
+    // 同じ冪等キーでサインインを2回ずつ送る(バージョンの接頭辞の有無の両方)
+    for uri in ["/auth/signin", "/v2/auth/signin"] {
+        for _ in 0..2 {
+            let request = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("Content-Type", "application/json")
+                .header("Idempotency-Key", "sign-in-key")
+                .body(Body::from(r#"{"email":"test@example.com","password":"password123"}"#))
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+            assert!(response.headers().get("idempotent-replayed").is_none(), "{}", uri);
+        }
+    }
+
+    // 全て処理される
+    user_mock.assert_async().await;
+}
@@ -0,0 +1,216 @@
+// 必要なクレートのインポート
+use backend::{
+    config::jwt_config::JwtConfig,
+    models::auth::auth::Claims,
+    routes::create_routes,
+    state::{app_state::jwt_keys, AppState},
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use jsonwebtoken::{decode, decode_header, encode, jwk::Jwk, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::signature::Ed25519KeyPair;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// テスト用のユーザーID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// テスト用のセッションのID
+const SESSION_ID: &str = "00000000-0000-0000-0000-0000000000e1";
+// テスト用のユーザーのパスワード
+const PASSWORD: &str = "password123";
+// 発行者
+const ISSUER: &str = "https://api.example.com";
+// 対象者
+const AUDIENCE: &str = "backend";
+
+// Ed25519の秘密鍵(PKCS#8のPEM)を生成する
+fn generate_ed25519_pem() -> String {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+    pem::encode(&pem::Pem { tag: "PRIVATE KEY".to_string(), contents: pkcs8.as_ref().to_vec() })
+}
+
+// EdDSAで署名し、発行者・対象者を検証する設定
+fn eddsa_config() -> JwtConfig {
+    JwtConfig {
+        algorithm: Algorithm::EdDSA,
+        signing_key: Some(generate_ed25519_pem()),
+        signing_key_id: Some("2024-01".to_string()),
+        issuer: Some(ISSUER.to_string()),
+        audience: Some(AUDIENCE.to_string()),
+        ..JwtConfig::default()
+    }
+}
+
+// JWTシークレットを使うHS256で署名する設定
+fn hs256_config() -> JwtConfig {
+    JwtConfig { secret: Some("test_secret".to_string()), ..JwtConfig::default() }
+}
+
+// サインインで作成するセッションのモックを設定(作成・トークンの検証での取得・最終アクセス日時の更新)
+async fn setup_session_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let body = json!([{
+        "id": SESSION_ID,
+        "user_id": USER_ID,
+        "user_agent": null,
+        "ip_address": null,
+        "created_at": "2024-01-01T00:00:00",
+        "last_seen_at": "2024-01-01T00:00:00",
+        "expires_at": "2099-01-01T00:00:00",
+    }])
+    .to_string();
+    vec![
+        mock_server
+            .mock("POST", "/rest/v1/rpc/create_user_session")
+            .with_status(200)
+            .with_body(body.clone())
+            .create_async()
+            .await,
+        mock_server
+            .mock("GET", format!("/rest/v1/trans_user_sessions?id=eq.{}", SESSION_ID).as_str())
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await,
+        mock_server
+            .mock("POST", "/rest/v1/rpc/touch_user_session")
+            .with_status(200)
+            .with_body("[]")
+            .create_async()
+            .await,
+    ]
+}
+
+// 署名の設定を指定したテスト用のアプリケーションを作成
+fn create_test_app(supabase_url: String, config: JwtConfig) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.jwt_keys = jwt_keys(&config);
+    state.jwt_config = config;
+    create_routes(Arc::new(state))
+}
+
+// サインインと認証の確認で使うユーザーのモックを設定
+async fn setup_user_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let body = json!([{
+        "id": USER_ID,
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": bcrypt::hash(PASSWORD, 4).unwrap(),
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    }])
+    .to_string();
+    let mut mocks = Vec::new();
+    for path in [
+        format!("/rest/v1/trans_users?id=eq.{}", USER_ID),
+        "/rest/v1/trans_users?email=eq.test%40example.com".to_string(),
+    ] {
+        mocks.push(mock_server.mock("GET", path.as_str()).with_status(200).with_body(body.clone()).create_async().await);
+    }
+    mocks
+}
+
+// リクエストを送信し、ステータス・ヘッダー・ボディを返す
+async fn send(app: Router, request: Request<Body>) -> (StatusCode, axum::http::HeaderMap, Value) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, headers, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+// JWKSを取得する
+async fn get_jwks(app: Router) -> (StatusCode, axum::http::HeaderMap, Value) {
+    send(app, Request::builder().uri("/.well-known/jwks.json").body(Body::empty()).unwrap()).await
+}
+
+// 認証の確認をリクエストする
+async fn check_auth(app: Router, token: &str) -> StatusCode {
+    let request = Request::builder()
+        .uri("/v2/auth/check")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    send(app, request).await.0
+}
+
+// サインインで発行したトークンを、公開したJWKSで検証できることのテスト
+#[tokio::test]
+async fn test_signed_token_verifies_with_published_jwks() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut mock_server).await;
+    let _mocks = setup_user_mocks(&mut mock_server).await;
+    let app = create_test_app(mock_server.url(), eddsa_config());
+
+    let sign_in = Request::builder()
+        .method("POST")
+        .uri("/v2/auth/signin")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(json!({ "email": "test@example.com", "password": PASSWORD }).to_string()))
+        .unwrap();
+    let (status, _, body) = send(app.clone(), sign_in).await;
+    assert_eq!(status, StatusCode::OK);
+    let token = body["token"].as_str().unwrap().to_string();
+    let token_header = decode_header(&token).unwrap();
+    assert_eq!(token_header.alg, Algorithm::EdDSA);
+    assert_eq!(token_header.kid.as_deref(), Some("2024-01"));
+
+    // 検証する側はJWKSの公開鍵のみで検証できる
+    let (status, headers, jwks) = get_jwks(app.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers[header::CACHE_CONTROL].to_str().unwrap().starts_with("public"));
+    let jwk: Jwk = serde_json::from_value(jwks["keys"][0].clone()).unwrap();
+    assert_eq!(jwk.common.key_id.as_deref(), Some("2024-01"));
+    let mut validation = Validation::new(Algorithm::EdDSA);
+    validation.set_issuer(&[ISSUER]);
+    validation.set_audience(&[AUDIENCE]);
+    let claims = decode::<Claims>(&token, &DecodingKey::from_jwk(&jwk).unwrap(), &validation).unwrap().claims;
+    assert_eq!(claims.sub, USER_ID);
+
+    assert_eq!(check_auth(app, &token).await, StatusCode::OK);
+}
+
+// 発行者・対象者が異なるトークン、共通鍵のトークンを拒否することのテスト
+#[tokio::test]
+async fn test_check_auth_validates_issuer_audience_and_algorithm() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mocks = setup_user_mocks(&mut mock_server).await;
+    let config = eddsa_config();
+    let pem = pem::parse(config.signing_key.clone().unwrap()).unwrap();
+    let signing_key = EncodingKey::from_ed_der(&pem.contents);
+    let app = create_test_app(mock_server.url(), config);
+
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some("2024-01".to_string());
+    let claims = |iss: &str, aud: &str| Claims {
+        iss: Some(iss.to_string()),
+        aud: Some(aud.to_string()),
+        ..Claims::new(&Uuid::parse_str(USER_ID).unwrap())
+    };
+
+    let valid = encode(&header, &claims(ISSUER, AUDIENCE), &signing_key).unwrap();
+    assert_eq!(check_auth(app.clone(), &valid).await, StatusCode::OK);
+
+    for (case, token) in [
+        ("issuer", encode(&header, &claims("https://other.example.com", AUDIENCE), &signing_key).unwrap()),
+        ("audience", encode(&header, &claims(ISSUER, "other"), &signing_key).unwrap()),
+        ("hs256", encode(&Header::default(), &claims(ISSUER, AUDIENCE), &EncodingKey::from_secret(b"")).unwrap()),
+    ] {
+        assert_eq!(check_auth(app.clone(), &token).await, StatusCode::UNAUTHORIZED, "{}", case);
+    }
+}
+
+// HS256で署名している場合、JWKSは空であることのテスト
+#[tokio::test]
+async fn test_jwks_is_empty_for_hs256() {
+    let app = create_test_app("http://localhost".to_string(), hs256_config());
+
+    let (status, _, jwks) = get_jwks(app).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(jwks, json!({ "keys": [] }));
+}
@@ -0,0 +1,190 @@
+// 必要なクレートのインポート
+use backend::{
+    models::auth::auth::Claims,
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// トークンのユーザーID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// テスト用のユーザーのパスワード
+const PASSWORD: &str = "password123";
+
+// テスト用のユーザーのJSON(Supabaseが返す行)
+fn test_user_row() -> Value {
+    json!({
+        "id": USER_ID,
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": bcrypt::hash(PASSWORD, 4).unwrap(),
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    })
+}
+
+// ユーザーを返すモックを設定
+async fn setup_user_mock(mock_server: &mut mockito::Server) -> mockito::Mock {
+    mock_server
+        .mock("GET", format!("/rest/v1/trans_users?id=eq.{}", USER_ID).as_str())
+        .with_status(200)
+        .with_body(json!([test_user_row()]).to_string())
+        .create_async()
+        .await
+}
+
+// テスト用のJWTシークレット
+const TEST_JWT_SECRET: &str = "test_secret";
+
+// テスト用のアプリケーションを作成
+fn create_test_app(supabase_url: String) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.jwt_secret = TEST_JWT_SECRET.to_string();
+    create_routes(Arc::new(state))
+}
+
+// テスト用のユーザーのトークンを作成(テスト用のJWTシークレットで署名)
+fn token() -> String {
+    let claims = Claims::new(&Uuid::parse_str(USER_ID).unwrap());
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes())).unwrap()
+}
+
+// リクエストを送信し、ステータスとボディを返す
+async fn send(app: Router, method: &str, uri: &str, token: Option<&str>, body: Option<Value>) -> (StatusCode, Value) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let request = request
+        .body(body.map(|body| Body::from(body.to_string())).unwrap_or_else(Body::empty))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+// トークンのユーザーのプロフィールを返すことのテスト
+#[tokio::test]
+async fn test_get_me_returns_callers_profile() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = setup_user_mock(&mut mock_server).await;
+
+    for uri in ["/me", "/v1/me", "/v2/me"] {
+        let (status, body) = send(create_test_app(mock_server.url()), "GET", uri, Some(&token()), None).await;
+        assert_eq!(status, StatusCode::OK, "{}", uri);
+        assert_eq!(body["id"], USER_ID);
+        // パスワードは含まない
+        assert!(body.get("password").is_none());
+    }
+}
+
+// トークンがない・無効な場合は401を返すことのテスト
+#[tokio::test]
+async fn test_me_requires_valid_token() {
+    let mock_server = mockito::Server::new_async().await;
+
+    for token in [None, Some("invalid")] {
+        for method in ["GET", "PATCH", "DELETE"] {
+            let body = Some(json!({ "username": "renamed", "password": PASSWORD }));
+            let (status, _) = send(create_test_app(mock_server.url()), method, "/v2/me", token, body).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED, "{} {:?}", method, token);
+        }
+    }
+}
+
+// 指定した項目のみをプロフィール更新のRPCに渡すことのテスト
+#[tokio::test]
+async fn test_update_me_changes_only_given_fields() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let rpc_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/update_user_profile_with_audit")
+        .match_body(mockito::Matcher::Json(json!({
+            "p_id": USER_ID,
+            "p_username": "renamed",
+            "p_email": null,
+        })))
+        .with_status(200)
+        .with_body(json!([test_user_row()]).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let app = create_test_app(mock_server.url());
+    let (status, body) = send(app, "PATCH", "/v2/me", Some(&token()), Some(json!({ "username": "renamed" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], USER_ID);
+
+    rpc_mock.assert_async().await;
+}
+
+// 不正な更新内容は書き込まずに拒否することのテスト
+#[tokio::test]
+async fn test_update_me_rejects_invalid_input() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let rpc_mock = mock_server
+        .mock("POST", mockito::Matcher::Regex(r"^/rest/v1/rpc/".to_string()))
+        .expect(0)
+        .create_async()
+        .await;
+
+    let cases = [
+        // 項目なし
+        (json!({}), StatusCode::BAD_REQUEST),
+        // 空のユーザー名
+        (json!({ "username": "" }), StatusCode::BAD_REQUEST),
+        // 不正なメールアドレス
+        (json!({ "email": "invalid" }), StatusCode::BAD_REQUEST),
+        // パスワードは変更できない
+        (json!({ "password": "new_password" }), StatusCode::UNPROCESSABLE_ENTITY),
+    ];
+    for (body, expected) in cases {
+        let app = create_test_app(mock_server.url());
+        let (status, _) = send(app, "PATCH", "/v2/me", Some(&token()), Some(body.clone())).await;
+        assert_eq!(status, expected, "{}", body);
+    }
+
+    rpc_mock.assert_async().await;
+}
+
+// パスワードが一致する場合のみアカウントを削除することのテスト
+#[tokio::test]
+async fn test_delete_me_requires_password_confirmation() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mock = setup_user_mock(&mut mock_server).await;
+    let delete_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/delete_user_with_audit")
+        .match_body(mockito::Matcher::Json(json!({ "p_id": USER_ID })))
+        .with_status(200)
+        .with_body(json!([test_user_row()]).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    // 誤ったパスワード
+    let app = create_test_app(mock_server.url());
+    let (status, body) = send(app, "DELETE", "/v2/me", Some(&token()), Some(json!({ "password": "wrong" }))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(body["message"].is_string());
+
+    // 正しいパスワード
+    let app = create_test_app(mock_server.url());
+    let (status, _) = send(app, "DELETE", "/v2/me", Some(&token()), Some(json!({ "password": PASSWORD }))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    delete_mock.assert_async().await;
+}
@@ -0,0 +1,99 @@
+// 必要なクレートのインポート
+use backend::{
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use axum::body::Bytes;
+use futures_util::StreamExt;
+
+// メトリクスエンドポイントのテスト
+#[tokio::test]
+async fn test_metrics_endpoint() {
+    // モックサーバーの設定
+    let mut mock_server = mockito::Server::new_async().await;
+    // ユーザー一覧のモック
+    let mock = mock_server
+        .mock("GET", "/rest/v1/trans_users")
+        .match_header("apikey", "test_key")
+        .with_status(200)
+        .with_body("[]")
+        .expect(2)
+        .create_async()
+        .await;
+
+    // テスト用のアプリケーションを作成
+    let state = Arc::new(AppState::new(mock_server.url(), "test_key".to_string()));
+    let app = create_routes(state);
+
+    // ------------------------------------------------------------------------
+    // 1. 旧ルーターとDIルーターの両方にリクエストを送信
+    // ------------------------------------------------------------------------
+    for uri in ["/users", "/di/users"] {
+        let response = app.clone()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // ステータスコードがOKであることを確認
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // ------------------------------------------------------------------------
+    // 2. メトリクスを取得
+    // ------------------------------------------------------------------------
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // ステータスコードがOKであることを確認
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body_to_bytes(response.into_body()).await;
+    let body = String::from_utf8(bytes.to_vec()).unwrap();
+
+    // ルートごとのリクエスト数が記録されていることを確認
+    assert!(body.contains(r#"http_requests_total{method="GET",route="/users",status="200"}"#));
+    assert!(body.contains(r#"http_requests_total{method="GET",route="/di/users",status="200"}"#));
+    // レイテンシと処理中のリクエスト数が記録されていることを確認
+    assert!(body.contains(r#"http_request_duration_seconds_count{method="GET",route="/di/users"}"#));
+    assert!(body.contains(r#"http_requests_in_flight{method="GET",route="/metrics"} 1"#));
+    // Supabaseへの呼び出しが操作名ごとに記録されていることを確認
+    assert!(body.contains(r#"supabase_requests_total{operation="find_all",status="200"} 2"#));
+    assert!(body.contains(r#"supabase_request_duration_seconds_count{operation="find_all"} 2"#));
+
+    // モックの確認
+    mock.assert_async().await;
+}
+
+// ボディをバイト列に変換するヘルパー関数
+async fn body_to_bytes(body: Body) -> Bytes {
+    let mut bytes = Vec::new();
+    let mut stream = body.into_data_stream();
+
+    while let Some(chunk) = stream.next().await {
+        if let Ok(data) = chunk {
+            bytes.extend_from_slice(&data);
+        }
+    }
+
+    Bytes::from(bytes)
+}
@@ -0,0 +1,197 @@
+// 必要なクレートのインポート
+use backend::{
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use flate2::read::GzDecoder;
+use std::io::Read;
+use std::sync::Arc;
+use std::time::Duration;
+use tower::util::ServiceExt;
+
+// テスト用のアプリケーションを作成するヘルパー関数
+fn create_test_app(supabase_url: String) -> Router {
+    // ミドルウェアの設定を上書きした状態を作成
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.middleware_config.cors_allowed_origins = vec!["https://app.example.com".to_string()];
+    state.middleware_config.max_body_bytes = 128;
+    state.middleware_config.route_timeouts.insert(
+        "GET /di/users".to_string(),
+        Duration::from_millis(100),
+    );
+
+    create_routes(Arc::new(state))
+}
+
+// CORSのテスト
+#[tokio::test]
+async fn test_cors_allow_list() {
+    // テスト用のアプリケーションを作成
+    let app = create_test_app("http://127.0.0.1:9".to_string());
+
+    // ------------------------------------------------------------------------
+    // 1. 許可されたオリジンからのプリフライト
+    // ------------------------------------------------------------------------
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("OPTIONS")
+                .uri("/users")
+                .header("Origin", "https://app.example.com")
+                .header("Access-Control-Request-Method", "POST")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // 許可されたオリジンが返されることを確認
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+
+    // ------------------------------------------------------------------------
+    // 2. 許可されていないオリジンからのプリフライト
+    // ------------------------------------------------------------------------
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("OPTIONS")
+                .uri("/users")
+                .header("Origin", "https://evil.example.com")
+                .header("Access-Control-Request-Method", "POST")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // オリジンが許可されないことを確認
+    assert!(response.headers().get("access-control-allow-origin").is_none());
+}
+
+// ボディサイズ制限のテスト
+#[tokio::test]
+async fn test_body_size_limit() {
+    // テスト用のアプリケーションを作成
+    let app = create_test_app("http://127.0.0.1:9".to_string());
+
+    // 制限を超えるボディでユーザーを作成
+    let body = serde_json::json!({
+        "username": "a".repeat(256),
+        "email": "test@example.com",
+        "password": "password123",
+    });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/di/users")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // ステータスコードがPAYLOAD_TOO_LARGEであることを確認
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+// タイムアウトのテスト
+#[tokio::test]
+async fn test_route_timeout() {
+    // 応答が遅いモックサーバーの設定
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = mock_server
+        .mock("GET", "/rest/v1/trans_users")
+        .with_status(200)
+        .with_chunked_body(|writer| {
+            std::thread::sleep(Duration::from_millis(500));
+            writer.write_all(b"[]")
+        })
+        .create_async()
+        .await;
+
+    // テスト用のアプリケーションを作成
+    let app = create_test_app(mock_server.url());
+
+    // タイムアウトが設定されたルートにリクエストを送信(バージョンの接頭辞付きのパスにも適用される)
+    for uri in ["/di/users", "/v1/di/users"] {
+        let response = app.clone()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // ステータスコードがGATEWAY_TIMEOUTであることを確認
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT, "{}", uri);
+    }
+}
+
+// 圧縮のテスト
+#[tokio::test]
+async fn test_response_compression() {
+    // テスト用のアプリケーションを作成
+    let app = create_test_app("http://127.0.0.1:9".to_string());
+
+    // gzipを受け付けるリクエストを送信
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/api-docs/openapi.json")
+                .header("Accept-Encoding", "gzip")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // レスポンスが圧縮されていることを確認
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+}
+
+// 圧縮したエラーレスポンスを展開すると、リクエストID付きのJSONになることのテスト
+#[tokio::test]
+async fn test_compressed_error_body_decodes() {
+    // テスト用のアプリケーションを作成
+    let app = create_test_app("http://127.0.0.1:9".to_string());
+
+    // gzipを受け付ける不正なリクエスト(400)を送信
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/di/users/not-a-uuid")
+                .header("Accept-Encoding", "gzip")
+                .header("x-request-id", "gzip-error-1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+
+    // 展開したボディがJSONとして読めることを確認
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let mut decoded = String::new();
+    GzDecoder::new(&bytes[..]).read_to_string(&mut decoded).unwrap();
+    let body: serde_json::Value = serde_json::from_str(&decoded).unwrap();
+    assert_eq!(body["request_id"], "gzip-error-1");
+    assert!(body["message"].is_string());
+}
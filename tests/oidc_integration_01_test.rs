@@ -0,0 +1,594 @@
+// 必要なクレートのインポート
+use backend::{
+    config::oidc_config::OidcProviderConfig,
+    routes::create_routes,
+    services::auth::oidc_client::pkce_challenge,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tower::util::ServiceExt;
+use url::Url;
+
+// テスト用のユーザーID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// テスト用のセッションのID
+const SESSION_ID: &str = "00000000-0000-0000-0000-0000000000e1";
+// プロバイダー名
+const PROVIDER: &str = "corp";
+// クライアントID
+const CLIENT_ID: &str = "backend-client";
+// クライアントシークレット
+const CLIENT_SECRET: &str = "client-secret";
+// コールバックのURL
+const REDIRECT_URI: &str = "http://localhost:3000/auth/oidc/corp/callback";
+// IdPのユーザーID
+const SUBJECT: &str = "idp-user-1";
+
+// 署名の鍵(Ed25519)
+struct SigningKey {
+    // 鍵ID
+    kid: String,
+    // PKCS#8の秘密鍵
+    pkcs8: Vec<u8>,
+    // JWK
+    jwk: Value,
+}
+
+// SigningKeyの実装
+impl SigningKey {
+    // 鍵を生成する関数
+    fn generate(kid: &str) -> Self {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let jwk = json!({
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "use": "sig",
+            "alg": "EdDSA",
+            "kid": kid,
+            "x": URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+        });
+        Self { kid: kid.to_string(), pkcs8: pkcs8.as_ref().to_vec(), jwk }
+    }
+
+    // クレームに署名する関数
+    fn sign(&self, claims: &Value) -> String {
+        let mut header = Header::new(Algorithm::EdDSA);
+        header.kid = Some(self.kid.clone());
+        encode(&header, claims, &EncodingKey::from_ed_der(&self.pkcs8)).unwrap()
+    }
+}
+
+// テスト用のIdP(ディスカバリー・JWKS・トークンエンドポイント)
+struct TestIdp {
+    // IdPのモックサーバー
+    server: mockito::ServerGuard,
+    // 署名の鍵
+    key: SigningKey,
+    // JWKSで返す鍵(テスト中に差し替える)
+    jwks: Arc<Mutex<Vec<Value>>>,
+    // モックのハンドル
+    _mocks: Vec<mockito::Mock>,
+}
+
+// TestIdpの実装
+impl TestIdp {
+    // IdPを起動する関数
+    async fn start() -> Self {
+        let mut server = mockito::Server::new_async().await;
+        let key = SigningKey::generate("key-1");
+        let jwks = Arc::new(Mutex::new(vec![key.jwk.clone()]));
+        let url = server.url();
+
+        let discovery = server
+            .mock("GET", "/.well-known/openid-configuration")
+            .with_status(200)
+            .with_body(json!({
+                "issuer": url,
+                "authorization_endpoint": format!("{}/authorize", url),
+                "token_endpoint": format!("{}/token", url),
+                "jwks_uri": format!("{}/jwks", url),
+            }).to_string())
+            .create_async()
+            .await;
+        let keys = jwks.clone();
+        let jwks_mock = server
+            .mock("GET", "/jwks")
+            .with_status(200)
+            .with_body_from_request(move |_| json!({ "keys": *keys.lock().unwrap() }).to_string().into())
+            .create_async()
+            .await;
+
+        Self { server, key, jwks, _mocks: vec![discovery, jwks_mock] }
+    }
+
+    // プロバイダーの設定
+    fn provider(&self) -> OidcProviderConfig {
+        OidcProviderConfig {
+            name: PROVIDER.to_string(),
+            issuer: self.server.url(),
+            client_id: CLIENT_ID.to_string(),
+            client_secret: Some(CLIENT_SECRET.to_string()),
+            redirect_uri: REDIRECT_URI.to_string(),
+            scopes: "openid email profile".to_string(),
+        }
+    }
+
+    // 有効なIDトークンのクレーム
+    fn claims(&self, nonce: &str) -> Value {
+        let now = chrono::Utc::now().timestamp();
+        json!({
+            "iss": self.server.url(),
+            "aud": CLIENT_ID,
+            "sub": SUBJECT,
+            "email": "Test@Example.com",
+            "email_verified": true,
+            "nonce": nonce,
+            "iat": now,
+            "exp": now + 300,
+        })
+    }
+
+    // 認可コードをIDトークンに交換するモックを設定(PKCEのコード検証子とクライアント認証を検証する)
+    async fn token_mock(&mut self, code: &str, code_challenge: &str, id_token: String) -> mockito::Mock {
+        let code = code.to_string();
+        let code_challenge = code_challenge.to_string();
+        self.server
+            .mock("POST", "/token")
+            .match_header(
+                "authorization",
+                format!("Basic {}", base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", CLIENT_ID, CLIENT_SECRET))).as_str(),
+            )
+            .match_request(move |request| {
+                let body = request.utf8_lossy_body().unwrap().to_string();
+                let form: HashMap<String, String> = url::form_urlencoded::parse(body.as_bytes()).into_owned().collect();
+                form.get("grant_type").map(String::as_str) == Some("authorization_code")
+                    && form.get("code") == Some(&code)
+                    && form.get("redirect_uri").map(String::as_str) == Some(REDIRECT_URI)
+                    && form.get("code_verifier").map(|verifier| pkce_challenge(verifier)) == Some(code_challenge.clone())
+            })
+            .with_status(200)
+            .with_body(json!({ "access_token": "idp-access-token", "token_type": "Bearer", "id_token": id_token }).to_string())
+            .expect(1)
+            .create_async()
+            .await
+    }
+}
+
+// テスト用のユーザーのJSON(Supabaseが返す行)
+fn test_user_row(two_factor_enabled: bool) -> Value {
+    json!({
+        "id": USER_ID,
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": "$2b$04$unused",
+        "two_factor_enabled": two_factor_enabled,
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    })
+}
+
+// IdPのアカウントの紐付けを返すモックを設定(紐付いていない場合は空)
+async fn setup_identity_mock(supabase: &mut mockito::Server, linked: bool) -> mockito::Mock {
+    let body = if linked {
+        json!([{ "provider": PROVIDER, "subject": SUBJECT, "user_id": USER_ID }])
+    } else {
+        json!([])
+    };
+    supabase
+        .mock("GET", format!("/rest/v1/trans_user_identities?provider=eq.{}&subject=eq.{}", PROVIDER, SUBJECT).as_str())
+        .with_status(200)
+        .with_body(body.to_string())
+        .create_async()
+        .await
+}
+
+// メールアドレスでの紐付けのRPCのモックを設定(usersはRPCが返すユーザー)
+async fn setup_link_mock(supabase: &mut mockito::Server, users: Value, expect: usize) -> mockito::Mock {
+    supabase
+        .mock("POST", "/rest/v1/rpc/link_user_identity_with_audit")
+        .match_body(mockito::Matcher::Json(json!({
+            "p_provider": PROVIDER,
+            "p_subject": SUBJECT,
+            "p_email": "Test@Example.com",
+        })))
+        .with_status(200)
+        .with_body(users.to_string())
+        .expect(expect)
+        .create_async()
+        .await
+}
+
+// IDでユーザーを返すモックを設定(サインインの完了時に最新の行を取得する)
+async fn setup_user_mock(supabase: &mut mockito::Server, two_factor_enabled: bool) -> mockito::Mock {
+    supabase
+        .mock("GET", format!("/rest/v1/trans_users?id=eq.{}", USER_ID).as_str())
+        .with_status(200)
+        .with_body(json!([test_user_row(two_factor_enabled)]).to_string())
+        .create_async()
+        .await
+}
+
+// サインインで作成するセッションのモックを設定(作成・トークンの検証での取得・最終アクセス日時の更新)
+async fn setup_session_mocks(supabase: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let body = json!([{
+        "id": SESSION_ID,
+        "user_id": USER_ID,
+        "user_agent": null,
+        "ip_address": null,
+        "created_at": "2024-01-01T00:00:00",
+        "last_seen_at": "2024-01-01T00:00:00",
+        "expires_at": "2099-01-01T00:00:00",
+    }])
+    .to_string();
+    vec![
+        supabase
+            .mock("POST", "/rest/v1/rpc/create_user_session")
+            .with_status(200)
+            .with_body(body.clone())
+            .create_async()
+            .await,
+        supabase
+            .mock("GET", format!("/rest/v1/trans_user_sessions?id=eq.{}", SESSION_ID).as_str())
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await,
+        supabase
+            .mock("POST", "/rest/v1/rpc/touch_user_session")
+            .with_status(200)
+            .with_body("[]")
+            .create_async()
+            .await,
+    ]
+}
+
+// テスト用のアプリケーションを作成(IdPをプロバイダーとして登録する)
+fn create_test_app(supabase_url: String, idp: &TestIdp) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.oidc_config.providers.insert(PROVIDER.to_string(), idp.provider());
+    create_routes(Arc::new(state))
+}
+
+// GETリクエストを送信し、ステータス・Locationヘッダー・ボディを返す
+async fn get(app: &Router, uri: &str) -> (StatusCode, Option<String>, Value) {
+    get_with_cookie(app, uri, None).await
+}
+
+// Cookieを付けてGETリクエストを送信し、ステータス・Locationヘッダー・ボディを返す
+async fn get_with_cookie(app: &Router, uri: &str, cookie: Option<&str>) -> (StatusCode, Option<String>, Value) {
+    let mut request = Request::builder().uri(uri);
+    if let Some(cookie) = cookie {
+        request = request.header(header::COOKIE, cookie);
+    }
+    let response = app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let location = response
+        .headers()
+        .get(header::LOCATION)
+        .map(|value| value.to_str().unwrap().to_string());
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, location, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+// サインインを開始し、認可エンドポイントのクエリとstateを結び付けるCookie(name=value)を返す
+async fn start(app: &Router) -> (HashMap<String, String>, String) {
+    let request = Request::builder()
+        .uri(format!("/auth/oidc/{}/start", PROVIDER))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FOUND);
+    let headers = response.headers();
+    let cookie = headers[header::SET_COOKIE].to_str().unwrap().split(';').next().unwrap().to_string();
+    let location = headers[header::LOCATION].to_str().unwrap();
+    (Url::parse(location).unwrap().query_pairs().into_owned().collect(), cookie)
+}
+
+// コールバックのURI
+fn callback_uri(code: &str, state: &str) -> String {
+    format!("/auth/oidc/{}/callback?code={}&state={}", PROVIDER, code, state)
+}
+
+// 開始すると、PKCE・state・nonceを付けて認可エンドポイントへリダイレクトすることのテスト
+#[tokio::test]
+async fn test_start_redirects_to_authorization_endpoint() {
+    let idp = TestIdp::start().await;
+    let app = create_test_app("http://localhost".to_string(), &idp);
+
+    let request = Request::builder()
+        .uri(format!("/auth/oidc/{}/start", PROVIDER))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FOUND);
+    let headers = response.headers();
+    let location = Url::parse(headers[header::LOCATION].to_str().unwrap()).unwrap();
+    assert_eq!(location.as_str().split('?').next().unwrap(), format!("{}/authorize", idp.server.url()));
+
+    let query: HashMap<String, String> = location.query_pairs().into_owned().collect();
+    assert_eq!(query["response_type"], "code");
+    assert_eq!(query["client_id"], CLIENT_ID);
+    assert_eq!(query["redirect_uri"], REDIRECT_URI);
+    assert_eq!(query["scope"], "openid email profile");
+    assert_eq!(query["code_challenge_method"], "S256");
+    assert_eq!(query["code_challenge"].len(), 43);
+    // stateのハッシュをHttpOnlyのCookieに保存する
+    let cookie = headers[header::SET_COOKIE].to_str().unwrap();
+    assert!(cookie.starts_with("oidc_login_state="));
+    assert!(!cookie.contains(query["state"].as_str()));
+    for attribute in ["Path=/auth/oidc", "Max-Age=600", "HttpOnly", "Secure", "SameSite=Lax"] {
+        assert!(cookie.contains(attribute), "{}", attribute);
+    }
+    // 開始ごとに別の値を発行する
+    let (second, _) = start(&app).await;
+    assert_ne!(query["state"], second["state"]);
+    assert_ne!(query["nonce"], second["nonce"]);
+}
+
+// サインインを開始したブラウザのCookieがないコールバックは、stateを消費せずに拒否することのテスト
+#[tokio::test]
+async fn test_callback_requires_state_cookie() {
+    let mut idp = TestIdp::start().await;
+    let mut supabase = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut supabase).await;
+    let _identity_mock = setup_identity_mock(&mut supabase, false).await;
+    let _link_mock = setup_link_mock(&mut supabase, json!([test_user_row(false)]), 1).await;
+    let _user_mock = setup_user_mock(&mut supabase, false).await;
+    let app = create_test_app(supabase.url(), &idp);
+
+    let (query, cookie) = start(&app).await;
+    let (_, other_cookie) = start(&app).await;
+    let id_token = idp.key.sign(&idp.claims(&query["nonce"]));
+    let token_mock = idp.token_mock("auth-code", &query["code_challenge"], id_token).await;
+
+    // Cookieがない、または別のサインインのCookie(別のブラウザで開始したstate)
+    let uri = callback_uri("auth-code", &query["state"]);
+    for cookie in [None, Some(other_cookie.as_str())] {
+        let (status, _, body) = get_with_cookie(&app, &uri, cookie).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{:?}", cookie);
+        assert_eq!(body["message"], "Invalid or expired login state");
+    }
+
+    // 開始したブラウザからは使える
+    let (status, _, _) = get_with_cookie(&app, &uri, Some(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    token_mock.assert_async().await;
+}
+
+// サインインの開始は既定でクライアントごとに回数を制限することのテスト
+#[tokio::test]
+async fn test_start_is_rate_limited() {
+    let idp = TestIdp::start().await;
+    let app = create_test_app("http://localhost".to_string(), &idp);
+
+    for _ in 0..20 {
+        start(&app).await;
+    }
+    let (status, _, _) = get(&app, &format!("/auth/oidc/{}/start", PROVIDER)).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+}
+
+// 設定されていないプロバイダーは404を返すことのテスト
+#[tokio::test]
+async fn test_unknown_provider_returns_not_found() {
+    let idp = TestIdp::start().await;
+    let app = create_test_app("http://localhost".to_string(), &idp);
+
+    for uri in ["/auth/oidc/unknown/start", "/auth/oidc/unknown/callback?code=code&state=state"] {
+        let (status, _, _) = get(&app, uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{}", uri);
+    }
+}
+
+// 確認済みのメールアドレスで既存のユーザーに紐付け、トークンを発行することのテスト
+#[tokio::test]
+async fn test_callback_links_existing_user_by_verified_email() {
+    let mut idp = TestIdp::start().await;
+    let mut supabase = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut supabase).await;
+    let _identity_mock = setup_identity_mock(&mut supabase, false).await;
+    let link_mock = setup_link_mock(&mut supabase, json!([test_user_row(false)]), 1).await;
+    let _user_mock = setup_user_mock(&mut supabase, false).await;
+    let app = create_test_app(supabase.url(), &idp);
+
+    let (query, cookie) = start(&app).await;
+    let id_token = idp.key.sign(&idp.claims(&query["nonce"]));
+    let token_mock = idp.token_mock("auth-code", &query["code_challenge"], id_token).await;
+
+    let (status, _, body) = get_with_cookie(&app, &callback_uri("auth-code", &query["state"]), Some(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["token"].is_string());
+    assert_eq!(body["user"]["id"], USER_ID);
+    // パスワードは含まない
+    assert!(body["user"].get("password").is_none());
+
+    // stateは1回だけ使える
+    let (status, _, _) = get_with_cookie(&app, &callback_uri("auth-code", &query["state"]), Some(&cookie)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    token_mock.assert_async().await;
+    link_mock.assert_async().await;
+}
+
+// 紐付け済みのアカウントは紐付けのユーザーでサインインすることのテスト
+#[tokio::test]
+async fn test_callback_uses_linked_identity() {
+    let mut idp = TestIdp::start().await;
+    let mut supabase = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut supabase).await;
+    let _identity_mock = setup_identity_mock(&mut supabase, true).await;
+    let _user_mock = setup_user_mock(&mut supabase, false).await;
+    let link_mock = setup_link_mock(&mut supabase, json!([]), 0).await;
+    let app = create_test_app(supabase.url(), &idp);
+
+    // 紐付け済みの場合はメールアドレスの確認を求めない
+    let (query, cookie) = start(&app).await;
+    let mut claims = idp.claims(&query["nonce"]);
+    claims["email_verified"] = json!(false);
+    let id_token = idp.key.sign(&claims);
+    let _token_mock = idp.token_mock("auth-code", &query["code_challenge"], id_token).await;
+
+    let (status, _, body) = get_with_cookie(&app, &callback_uri("auth-code", &query["state"]), Some(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["user"]["id"], USER_ID);
+
+    link_mock.assert_async().await;
+}
+
+// 二段階認証が有効なユーザーはチャレンジを返すことのテスト
+//
+// 紐付けで返された行ではなく、最新の行(two_factor_enabled)で判定する
+#[tokio::test]
+async fn test_callback_requires_second_factor() {
+    let mut idp = TestIdp::start().await;
+    let mut supabase = mockito::Server::new_async().await;
+    let _identity_mock = setup_identity_mock(&mut supabase, false).await;
+    let _link_mock = setup_link_mock(&mut supabase, json!([test_user_row(false)]), 1).await;
+    let _user_mock = setup_user_mock(&mut supabase, true).await;
+    let app = create_test_app(supabase.url(), &idp);
+
+    let (query, cookie) = start(&app).await;
+    let id_token = idp.key.sign(&idp.claims(&query["nonce"]));
+    let _token_mock = idp.token_mock("auth-code", &query["code_challenge"], id_token).await;
+
+    let (status, _, body) = get_with_cookie(&app, &callback_uri("auth-code", &query["state"]), Some(&cookie)).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert!(body["challenge_token"].is_string());
+    assert!(body.get("token").is_none());
+}
+
+// 不正なIDトークンを拒否することのテスト
+#[tokio::test]
+async fn test_callback_rejects_invalid_id_tokens() {
+    let mut idp = TestIdp::start().await;
+    let mut supabase = mockito::Server::new_async().await;
+    let link_mock = setup_link_mock(&mut supabase, json!([test_user_row(false)]), 0).await;
+    let app = create_test_app(supabase.url(), &idp);
+    let unknown_key = SigningKey::generate("unknown");
+
+    // (説明, クレームを書き換える関数, 署名の鍵)
+    type Tamper = fn(&mut Value);
+    let cases: [(&str, Tamper, Option<&SigningKey>); 6] = [
+        ("nonce", |claims| claims["nonce"] = json!("other-nonce"), None),
+        ("audience", |claims| claims["aud"] = json!("other-client"), None),
+        ("issuer", |claims| claims["iss"] = json!("https://evil.example.com"), None),
+        ("expired", |claims| claims["exp"] = json!(chrono::Utc::now().timestamp() - 3600), None),
+        ("missing subject", |claims| { claims.as_object_mut().unwrap().remove("sub"); }, None),
+        ("unknown key", |_| {}, Some(&unknown_key)),
+    ];
+    for (index, (case, tamper, key)) in cases.into_iter().enumerate() {
+        let (query, cookie) = start(&app).await;
+        let mut claims = idp.claims(&query["nonce"]);
+        tamper(&mut claims);
+        let id_token = key.unwrap_or(&idp.key).sign(&claims);
+        let code = format!("code-{}", index);
+        let _token_mock = idp.token_mock(&code, &query["code_challenge"], id_token).await;
+
+        let (status, _, _) = get_with_cookie(&app, &callback_uri(&code, &query["state"]), Some(&cookie)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", case);
+    }
+
+    // 共通鍵(HS256)の署名は受け付けない
+    let (query, cookie) = start(&app).await;
+    let id_token = encode(
+        &Header::new(Algorithm::HS256),
+        &idp.claims(&query["nonce"]),
+        &EncodingKey::from_secret(CLIENT_SECRET.as_bytes()),
+    )
+    .unwrap();
+    let _token_mock = idp.token_mock("code-hs256", &query["code_challenge"], id_token).await;
+    let (status, _, _) = get_with_cookie(&app, &callback_uri("code-hs256", &query["state"]), Some(&cookie)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    link_mock.assert_async().await;
+}
+
+// 鍵がローテーションされた場合はJWKSを取得し直すことのテスト
+#[tokio::test]
+async fn test_callback_refreshes_rotated_keys() {
+    let mut idp = TestIdp::start().await;
+    let mut supabase = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut supabase).await;
+    let _identity_mock = setup_identity_mock(&mut supabase, false).await;
+    let _link_mock = setup_link_mock(&mut supabase, json!([test_user_row(false)]), 2).await;
+    let _user_mock = setup_user_mock(&mut supabase, false).await;
+    let app = create_test_app(supabase.url(), &idp);
+
+    // 最初の鍵でサインインし、JWKSをキャッシュさせる
+    let (query, cookie) = start(&app).await;
+    let id_token = idp.key.sign(&idp.claims(&query["nonce"]));
+    let _first = idp.token_mock("code-1", &query["code_challenge"], id_token).await;
+    let (status, _, _) = get_with_cookie(&app, &callback_uri("code-1", &query["state"]), Some(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 新しい鍵に差し替える
+    let rotated = SigningKey::generate("key-2");
+    *idp.jwks.lock().unwrap() = vec![rotated.jwk.clone()];
+
+    let (query, cookie) = start(&app).await;
+    let id_token = rotated.sign(&idp.claims(&query["nonce"]));
+    let _second = idp.token_mock("code-2", &query["code_challenge"], id_token).await;
+    let (status, _, _) = get_with_cookie(&app, &callback_uri("code-2", &query["state"]), Some(&cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+// 紐付けられない場合は403を返すことのテスト
+#[tokio::test]
+async fn test_callback_requires_verified_email_of_existing_user() {
+    let mut idp = TestIdp::start().await;
+    let mut supabase = mockito::Server::new_async().await;
+    let _identity_mock = setup_identity_mock(&mut supabase, false).await;
+    // メールアドレスが一致するユーザーがいない
+    let link_mock = setup_link_mock(&mut supabase, json!([]), 1).await;
+    let app = create_test_app(supabase.url(), &idp);
+
+    // メールアドレスが未確認
+    let (query, cookie) = start(&app).await;
+    let mut claims = idp.claims(&query["nonce"]);
+    claims["email_verified"] = json!(false);
+    let _token_mock = idp.token_mock("code-unverified", &query["code_challenge"], idp.key.sign(&claims)).await;
+    let (status, _, body) = get_with_cookie(&app, &callback_uri("code-unverified", &query["state"]), Some(&cookie)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["message"], "Email address is not verified by the identity provider");
+
+    // 対応するユーザーがいない
+    let (query, cookie) = start(&app).await;
+    let id_token = idp.key.sign(&idp.claims(&query["nonce"]));
+    let _token_mock = idp.token_mock("code-unknown", &query["code_challenge"], id_token).await;
+    let (status, _, body) = get_with_cookie(&app, &callback_uri("code-unknown", &query["state"]), Some(&cookie)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["message"], "No account matches this identity");
+
+    link_mock.assert_async().await;
+}
+
+// IdPがサインインを拒否した場合は401を返し、stateを破棄することのテスト
+#[tokio::test]
+async fn test_callback_handles_provider_error() {
+    let idp = TestIdp::start().await;
+    let app = create_test_app("http://localhost".to_string(), &idp);
+
+    let (query, cookie) = start(&app).await;
+    let uri = format!("/auth/oidc/{}/callback?error=access_denied&state={}", PROVIDER, query["state"]);
+    let (status, _, body) = get_with_cookie(&app, &uri, Some(&cookie)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["message"], "Sign-in rejected by identity provider: access_denied");
+
+    // 破棄したstateと未知のstateは400
+    for state in [query["state"].as_str(), "unknown"] {
+        let (status, _, _) = get_with_cookie(&app, &callback_uri("code", state), Some(&cookie)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", state);
+    }
+}
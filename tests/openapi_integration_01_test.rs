@@ -0,0 +1,144 @@
+// 必要なクレートのインポート
+use backend::{
+    routes::{api_routes, create_routes},
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use std::{collections::BTreeSet, sync::Arc};
+use tower::util::ServiceExt;
+
+// 公開しているOpenAPIドキュメントのパス
+const OPENAPI_PATHS: [&str; 3] = [
+    "/api-docs/openapi.json",
+    "/api-docs/v1/openapi.json",
+    "/api-docs/v2/openapi.json",
+];
+
+// OpenAPIのHTTPメソッド
+const OPENAPI_METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+// テスト用の状態を作成
+fn create_test_state() -> Arc<AppState> {
+    Arc::new(AppState::new("http://localhost".to_string(), "test_key".to_string()))
+}
+
+// 公開しているOpenAPIドキュメントを取得
+async fn fetch_openapi_documents() -> Vec<Value> {
+    let app = create_routes(create_test_state());
+    let mut documents = Vec::new();
+    for path in OPENAPI_PATHS {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{} が取得できません", path);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        documents.push(serde_json::from_slice(&body).unwrap());
+    }
+    documents
+}
+
+// ドキュメントの全ての操作を(メソッド, パス, 操作)で列挙
+fn operations(document: &Value) -> Vec<(String, String, Value)> {
+    let mut operations = Vec::new();
+    for (path, item) in document["paths"].as_object().unwrap() {
+        for method in OPENAPI_METHODS {
+            if let Some(operation) = item.get(method) {
+                operations.push((method.to_string(), path.clone(), operation.clone()));
+            }
+        }
+    }
+    operations
+}
+
+// 全てのルートがOpenAPIに記載され、記載された操作が全てルートとして存在することを確認
+#[tokio::test]
+async fn test_every_route_is_documented() {
+    // 登録したルートを取得
+    let routes: BTreeSet<(String, String)> = api_routes(create_test_state())
+        .entries()
+        .iter()
+        .map(|entry| (entry.method.as_str().to_lowercase(), entry.openapi_path()))
+        .collect();
+    assert!(!routes.is_empty());
+
+    // ドキュメントに記載された操作を取得
+    let documented: BTreeSet<(String, String)> = fetch_openapi_documents()
+        .await
+        .iter()
+        .flat_map(operations)
+        .map(|(method, path, _)| (method, path))
+        .collect();
+
+    // ドキュメントのないルート
+    let undocumented: Vec<_> = routes.difference(&documented).collect();
+    assert!(undocumented.is_empty(), "OpenAPIに記載のないルート: {:?}", undocumented);
+
+    // ルートのない操作
+    let unrouted: Vec<_> = documented.difference(&routes).collect();
+    assert!(unrouted.is_empty(), "ルートのない操作: {:?}", unrouted);
+}
+
+// 全てのエラーレスポンスがErrorResponseのスキーマを持つことを確認
+#[tokio::test]
+async fn test_error_responses_use_error_schema() {
+    for document in fetch_openapi_documents().await {
+        for (method, path, operation) in operations(&document) {
+            for (status, response) in operation["responses"].as_object().unwrap() {
+                // 4xxと5xxのみを確認
+                if !(status.starts_with('4') || status.starts_with('5')) {
+                    continue;
+                }
+                let schema = &response["content"]["application/json"]["schema"]["$ref"];
+                assert_eq!(
+                    schema, "#/components/schemas/ErrorResponse",
+                    "{} {} の {} にErrorResponseのスキーマがありません",
+                    method, path, status
+                );
+            }
+        }
+        // スキーマが定義されていることを確認
+        assert!(document["components"]["schemas"]["ErrorResponse"].is_object());
+    }
+}
+
+// ミドルウェアによるエラーが全ての操作に記載されていることを確認
+#[tokio::test]
+async fn test_middleware_errors_are_documented() {
+    for document in fetch_openapi_documents().await {
+        for (method, path, operation) in operations(&document) {
+            let responses = operation["responses"].as_object().unwrap();
+            for status in ["429", "504"] {
+                assert!(
+                    responses.contains_key(status),
+                    "{} {} に {} がありません",
+                    method, path, status
+                );
+            }
+            // リクエストボディがある場合は413も記載する
+            if operation.get("requestBody").is_some() {
+                assert!(responses.contains_key("413"), "{} {} に 413 がありません", method, path);
+            }
+        }
+    }
+}
+
+// 全ての操作にセキュリティ要件が記載されていることを確認
+#[tokio::test]
+async fn test_every_operation_declares_security() {
+    for document in fetch_openapi_documents().await {
+        for (method, path, operation) in operations(&document) {
+            assert!(
+                operation["security"].is_array(),
+                "{} {} にセキュリティ要件がありません",
+                method, path
+            );
+        }
+    }
+}
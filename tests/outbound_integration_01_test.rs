@@ -0,0 +1,84 @@
+// 必要なクレートのインポート
+use backend::{
+    config::outbound_config::OutboundConfig,
+    outbound::outbound_policy::OutboundPolicy,
+    routes::create_routes,
+    state::AppState,
+    supabase::supabase_client::SupabaseClient,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::util::ServiceExt;
+
+// PostgRESTが不安定な場合に503を返すことのテスト
+#[tokio::test]
+async fn test_unhealthy_postgrest_returns_service_unavailable() {
+    // モックサーバーの設定(常に503)
+    let mut mock_server = mockito::Server::new_async().await;
+    let mock = mock_server
+        .mock("GET", "/rest/v1/trans_users")
+        .with_status(503)
+        .expect(2)
+        .create_async()
+        .await;
+
+    // 再試行1回、2回の失敗で遮断する状態を作成
+    let state = AppState::new(mock_server.url(), "test_key".to_string());
+    let supabase = SupabaseClient::new(
+        state.client.clone(),
+        mock_server.url(),
+        "test_key".to_string(),
+        Arc::new(OutboundPolicy::new(OutboundConfig {
+            max_retries: 1,
+            base_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            failure_threshold: 2,
+            ..OutboundConfig::default()
+        })),
+    );
+    let app = create_routes(Arc::new(state.with_supabase(supabase)));
+
+    // ------------------------------------------------------------------------
+    // 1. 再試行しても失敗した場合はエラーを返し、サーキットブレーカーが開く
+    // ------------------------------------------------------------------------
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/di/users")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // ステータスコードがINTERNAL_SERVER_ERRORであることを確認
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    // ------------------------------------------------------------------------
+    // 2. 遮断中はPostgRESTを呼び出さずに503を返す
+    // ------------------------------------------------------------------------
+    for uri in ["/di/users", "/users"] {
+        let response = app.clone()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // どちらのルーターも503を返すことを確認
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE, "{}", uri);
+    }
+
+    // 遮断後はPostgRESTが呼び出されないことを確認
+    mock.assert_async().await;
+}
@@ -0,0 +1,167 @@
+// 必要なクレートのインポート
+use backend::{
+    password::password_hasher::PasswordHashers,
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+
+// テスト用のユーザーID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// テスト用のセッションのID
+const SESSION_ID: &str = "00000000-0000-0000-0000-0000000000e1";
+// テスト用のユーザーのメールアドレス
+const EMAIL: &str = "test@example.com";
+// テスト用のユーザーのパスワード
+const PASSWORD: &str = "password123";
+
+// 指定したハッシュを持つユーザーのJSON(Supabaseが返す行)
+fn test_user_row(password_hash: &str) -> Value {
+    json!({
+        "id": USER_ID,
+        "username": "test_user",
+        "email": EMAIL,
+        "password": password_hash,
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    })
+}
+
+// メールアドレス・IDでユーザーを返すモックを設定
+async fn setup_user_mock(mock_server: &mut mockito::Server, password_hash: &str) -> Vec<mockito::Mock> {
+    let body = json!([test_user_row(password_hash)]).to_string();
+    let mut mocks = Vec::new();
+    for path in [
+        format!("/rest/v1/trans_users?id=eq.{}", USER_ID),
+        "/rest/v1/trans_users?email=eq.test%40example.com".to_string(),
+    ] {
+        mocks.push(mock_server.mock("GET", path.as_str()).with_status(200).with_body(body.clone()).create_async().await);
+    }
+    mocks
+}
+
+// サインインで作成するセッションのモックを設定(作成・トークンの検証での取得・最終アクセス日時の更新)
+async fn setup_session_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let body = json!([{
+        "id": SESSION_ID,
+        "user_id": USER_ID,
+        "user_agent": null,
+        "ip_address": null,
+        "created_at": "2024-01-01T00:00:00",
+        "last_seen_at": "2024-01-01T00:00:00",
+        "expires_at": "2099-01-01T00:00:00",
+    }])
+    .to_string();
+    vec![
+        mock_server
+            .mock("POST", "/rest/v1/rpc/create_user_session")
+            .with_status(200)
+            .with_body(body.clone())
+            .create_async()
+            .await,
+        mock_server
+            .mock("GET", format!("/rest/v1/trans_user_sessions?id=eq.{}", SESSION_ID).as_str())
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await,
+        mock_server
+            .mock("POST", "/rest/v1/rpc/touch_user_session")
+            .with_status(200)
+            .with_body("[]")
+            .create_async()
+            .await,
+    ]
+}
+
+// テスト用のアプリケーションを作成
+fn create_test_app(supabase_url: String) -> Router {
+    create_routes(Arc::new(AppState::new(supabase_url, "test_key".to_string())))
+}
+
+// サインインのリクエストを送信し、ステータスを返す
+async fn sign_in(app: Router, password: &str) -> StatusCode {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v2/auth/signin")
+        .header("Content-Type", "application/json")
+        .body(Body::from(json!({ "email": EMAIL, "password": password }).to_string()))
+        .unwrap();
+    app.oneshot(request).await.unwrap().status()
+}
+
+// bcryptのハッシュのユーザーがサインインすると、Argon2idのハッシュに作り直すことのテスト
+#[tokio::test]
+async fn test_sign_in_upgrades_legacy_hash() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut mock_server).await;
+    let bcrypt_hash = bcrypt::hash(PASSWORD, 4).unwrap();
+    let _user_mocks = setup_user_mock(&mut mock_server, &bcrypt_hash).await;
+    let rehash_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/rehash_user_password")
+        .match_body(mockito::Matcher::AllOf(vec![
+            // 照合したハッシュのままの場合のみ書き込む
+            mockito::Matcher::PartialJson(json!({ "p_id": USER_ID, "p_current_password": bcrypt_hash })),
+            // 新しいハッシュは既定のアルゴリズム(Argon2id)
+            mockito::Matcher::Regex(r#""p_password":"\$argon2id\$"#.to_string()),
+        ]))
+        .with_status(200)
+        .with_body(json!([test_user_row("$argon2id$")]).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    assert_eq!(sign_in(create_test_app(mock_server.url()), PASSWORD).await, StatusCode::OK);
+
+    rehash_mock.assert_async().await;
+}
+
+// 現在の設定のハッシュ、または誤ったパスワードの場合は作り直さないことのテスト
+#[tokio::test]
+async fn test_sign_in_keeps_current_hash() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut mock_server).await;
+    let rehash_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/rehash_user_password")
+        .expect(0)
+        .create_async()
+        .await;
+
+    // 現在の設定のハッシュ
+    let current_hash = PasswordHashers::default().hash(PASSWORD).unwrap();
+    let user_mocks = setup_user_mock(&mut mock_server, &current_hash).await;
+    assert_eq!(sign_in(create_test_app(mock_server.url()), PASSWORD).await, StatusCode::OK);
+    for mock in user_mocks {
+        mock.remove_async().await;
+    }
+
+    // 誤ったパスワード
+    let _user_mocks = setup_user_mock(&mut mock_server, &bcrypt::hash(PASSWORD, 4).unwrap()).await;
+    assert_eq!(sign_in(create_test_app(mock_server.url()), "wrong_password").await, StatusCode::UNAUTHORIZED);
+
+    rehash_mock.assert_async().await;
+}
+
+// ハッシュの作り直しに失敗してもサインインは成功することのテスト
+#[tokio::test]
+async fn test_sign_in_succeeds_when_rehash_fails() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut mock_server).await;
+    let _user_mocks = setup_user_mock(&mut mock_server, &bcrypt::hash(PASSWORD, 4).unwrap()).await;
+    let _rehash_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/rehash_user_password")
+        .with_status(500)
+        .with_body(json!({ "message": "internal error" }).to_string())
+        .create_async()
+        .await;
+
+    assert_eq!(sign_in(create_test_app(mock_server.url()), PASSWORD).await, StatusCode::OK);
+}
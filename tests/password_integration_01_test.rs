@@ -0,0 +1,172 @@
+// 必要なクレートのインポート
+use backend::{
+    models::auth::auth::Claims,
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// トークンのユーザーID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// 現在のパスワード
+const CURRENT_PASSWORD: &str = "password123";
+// 以前に使用したパスワード
+const PREVIOUS_PASSWORD: &str = "previous_password1";
+// 新しいパスワード
+const NEW_PASSWORD: &str = "new_password456";
+
+// テスト用のユーザーのJSON(Supabaseが返す行)
+fn test_user_row() -> Value {
+    json!({
+        "id": USER_ID,
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": bcrypt::hash(CURRENT_PASSWORD, 4).unwrap(),
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    })
+}
+
+// ユーザーとパスワードの履歴のモックを設定
+async fn setup_read_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    vec![
+        mock_server
+            .mock("GET", format!("/rest/v1/trans_users?id=eq.{}", USER_ID).as_str())
+            .with_status(200)
+            .with_body(json!([test_user_row()]).to_string())
+            .create_async()
+            .await,
+        mock_server
+            .mock("GET", mockito::Matcher::Regex(r"^/rest/v1/trans_user_password_history\?".to_string()))
+            .with_status(200)
+            .with_body(json!([{ "password_hash": bcrypt::hash(PREVIOUS_PASSWORD, 4).unwrap() }]).to_string())
+            .create_async()
+            .await,
+    ]
+}
+
+// テスト用のJWTシークレット
+const TEST_JWT_SECRET: &str = "test_secret";
+
+// テスト用のアプリケーションを作成(同じ状態を共有するため、リクエストごとにクローンして使う)
+fn create_test_app(supabase_url: String) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.jwt_secret = TEST_JWT_SECRET.to_string();
+    create_routes(Arc::new(state))
+}
+
+// 指定した発行時刻のトークンを作成(テスト用のJWTシークレットで署名)
+fn token_issued_at(iat: i64) -> String {
+    let mut claims = Claims::new(&Uuid::parse_str(USER_ID).unwrap());
+    claims.iat = iat;
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes())).unwrap()
+}
+
+// 現在の時刻に発行したトークン
+fn token() -> String {
+    token_issued_at(chrono::Utc::now().timestamp())
+}
+
+// リクエストを送信し、ステータスを返す
+async fn send(app: &Router, method: &str, uri: &str, token: &str, body: Option<Value>) -> StatusCode {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", token))
+        .body(body.map(|body| Body::from(body.to_string())).unwrap_or_else(Body::empty))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+// パスワードの変更のリクエストボディ
+fn change_request(current_password: &str, new_password: &str) -> Value {
+    json!({ "current_password": current_password, "new_password": new_password })
+}
+
+// 現在のパスワードが一致しない、またはポリシーを満たさない場合は変更しないことのテスト
+#[tokio::test]
+async fn test_invalid_change_is_rejected_before_write() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _read_mocks = setup_read_mocks(&mut mock_server).await;
+    let rpc_mock = mock_server
+        .mock("POST", mockito::Matcher::Regex(r"^/rest/v1/rpc/".to_string()))
+        .expect(0)
+        .create_async()
+        .await;
+    let app = create_test_app(mock_server.url());
+
+    let cases = [
+        // 現在のパスワードが一致しない
+        (change_request("wrong_password", NEW_PASSWORD), StatusCode::FORBIDDEN),
+        // 短すぎる
+        (change_request(CURRENT_PASSWORD, "short"), StatusCode::BAD_REQUEST),
+        // bcryptで扱えない長さ
+        (change_request(CURRENT_PASSWORD, &"a".repeat(73)), StatusCode::BAD_REQUEST),
+        // 現在のパスワードの再利用
+        (change_request(CURRENT_PASSWORD, CURRENT_PASSWORD), StatusCode::BAD_REQUEST),
+        // 以前のパスワードの再利用
+        (change_request(CURRENT_PASSWORD, PREVIOUS_PASSWORD), StatusCode::BAD_REQUEST),
+        // 項目の不足
+        (json!({ "new_password": NEW_PASSWORD }), StatusCode::UNPROCESSABLE_ENTITY),
+    ];
+    for (body, expected) in cases {
+        let status = send(&app, "POST", "/v2/me/password", &token(), Some(body.clone())).await;
+        assert_eq!(status, expected, "{}", body);
+    }
+
+    rpc_mock.assert_async().await;
+}
+
+// パスワードを変更すると、変更前に発行されたトークン・セッション・APIキーが無効になることのテスト
+#[tokio::test]
+async fn test_change_password_revokes_existing_tokens() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _read_mocks = setup_read_mocks(&mut mock_server).await;
+    let rpc_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/change_user_password_with_audit")
+        .match_body(mockito::Matcher::PartialJson(json!({ "p_id": USER_ID, "p_history_size": 5 })))
+        .with_status(200)
+        .with_body(json!([test_user_row()]).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    // セッションはユーザーごとに全て削除する(APIキーは変更のRPCと同じトランザクションで削除する)
+    let revoke_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/revoke_user_sessions")
+        .match_body(mockito::Matcher::Json(json!({ "p_user_id": USER_ID })))
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+    let app = create_test_app(mock_server.url());
+
+    // 変更前に発行されたトークン
+    let old_token = token_issued_at(chrono::Utc::now().timestamp() - 60);
+    assert_eq!(send(&app, "GET", "/v2/me", &old_token, None).await, StatusCode::OK);
+
+    // パスワードの変更
+    let body = change_request(CURRENT_PASSWORD, NEW_PASSWORD);
+    assert_eq!(send(&app, "POST", "/me/password", &old_token, Some(body)).await, StatusCode::NO_CONTENT);
+    rpc_mock.assert_async().await;
+    revoke_mock.assert_async().await;
+
+    // 変更前のトークンは無効
+    assert_eq!(send(&app, "GET", "/v2/me", &old_token, None).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(send(&app, "GET", "/auth/check", &old_token, None).await, StatusCode::UNAUTHORIZED);
+
+    // 変更後に発行されたトークンは有効
+    let new_token = token_issued_at(chrono::Utc::now().timestamp() + 1);
+    assert_eq!(send(&app, "GET", "/v2/me", &new_token, None).await, StatusCode::OK);
+}
@@ -0,0 +1,166 @@
+// 必要なクレートのインポート
+use backend::{
+    config::rate_limit_config::RateLimitPolicy,
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::util::ServiceExt;
+
+// サインインのレート制限のテスト
+#[tokio::test]
+async fn test_sign_in_rate_limit() {
+    // モックサーバーの設定
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = mock_server
+        .mock("GET", mockito::Matcher::Regex("^/rest/v1/trans_users".to_string()))
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    // サインインを60秒で2回までに制限した状態を作成
+    let mut state = AppState::new(mock_server.url(), "test_key".to_string());
+    state.rate_limit_config.route_policies.insert(
+        "POST /auth/signin".to_string(),
+        RateLimitPolicy::new(2, Duration::from_secs(60)),
+    );
+    let app = create_routes(Arc::new(state));
+
+    // サインインのリクエストを作成するヘルパー
+    let sign_in_request = || {
+        Request::builder()
+            .method("POST")
+            .uri("/auth/signin")
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"email":"test@example.com","password":"password123"}"#))
+            .unwrap()
+    };
+
+    // ------------------------------------------------------------------------
+    // 1. 制限内のリクエストは処理される
+    // ------------------------------------------------------------------------
+    for remaining in ["1", "0"] {
+        let response = app.clone().oneshot(sign_in_request()).await.unwrap();
+
+        // ステータスコードがUNAUTHORIZEDであることを確認
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        // レート制限のヘッダーが設定されていることを確認
+        assert_eq!(response.headers()["ratelimit-limit"], "2");
+        assert_eq!(response.headers()["ratelimit-remaining"], remaining);
+    }
+
+    // ------------------------------------------------------------------------
+    // 2. 制限を超えたリクエストは拒否される
+    // ------------------------------------------------------------------------
+    let response = app.clone().oneshot(sign_in_request()).await.unwrap();
+
+    // ステータスコードがTOO_MANY_REQUESTSであることを確認
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["retry-after"], "30");
+
+    // ------------------------------------------------------------------------
+    // 3. ポリシーのないルートは制限されない
+    // ------------------------------------------------------------------------
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/users")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // ステータスコードがOKで、レート制限のヘッダーがないことを確認
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("ratelimit-limit").is_none());
+}
+
+// 検証していないAPIキーのヘッダーを毎回変えても、IPアドレスの制限を回避できないことのテスト
+#[tokio::test]
+async fn test_rotating_api_key_header_does_not_bypass_limit() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = mock_server
+        .mock("GET", mockito::Matcher::Regex("^/rest/v1/trans_users".to_string()))
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    // サインインを60秒で2回までに制限した状態を作成
+    let mut state = AppState::new(mock_server.url(), "test_key".to_string());
+    state.rate_limit_config.route_policies.insert(
+        "POST /auth/signin".to_string(),
+        RateLimitPolicy::new(2, Duration::from_secs(60)),
+    );
+    let app = create_routes(Arc::new(state));
+
+    // リクエストごとに別のx-api-keyを送る
+    let mut statuses = Vec::new();
+    for i in 0..3 {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/auth/signin")
+            .header("Content-Type", "application/json")
+            .header("x-api-key", format!("random-{}", i))
+            .body(Body::from(r#"{"email":"test@example.com","password":"password123"}"#))
+            .unwrap();
+        statuses.push(app.clone().oneshot(request).await.unwrap().status());
+    }
+
+    // 同じIPアドレスのバケットで数えられ、3回目は拒否される
+    assert_eq!(statuses, [StatusCode::UNAUTHORIZED, StatusCode::UNAUTHORIZED, StatusCode::TOO_MANY_REQUESTS]);
+}
+
+// バージョンの接頭辞付きのパスにも接頭辞のないルートのポリシーを適用することのテスト
+#[tokio::test]
+async fn test_versioned_sign_in_shares_policy() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = mock_server
+        .mock("GET", mockito::Matcher::Regex("^/rest/v1/trans_users".to_string()))
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    // サインインを60秒で3回までに制限した状態を作成
+    let mut state = AppState::new(mock_server.url(), "test_key".to_string());
+    state.rate_limit_config.route_policies.insert(
+        "POST /auth/signin".to_string(),
+        RateLimitPolicy::new(3, Duration::from_secs(60)),
+    );
+    let app = create_routes(Arc::new(state));
+
+    // 接頭辞なし・/v1・/v2で同じバケットを使う
+    let mut statuses = Vec::new();
+    for uri in ["/v1/auth/signin", "/v2/auth/signin", "/auth/signin", "/v1/auth/signin", "/v2/auth/signin"] {
+        let request = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"email":"test@example.com","password":"password123"}"#))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.headers()["ratelimit-limit"], "3", "{}", uri);
+        statuses.push(response.status());
+    }
+
+    assert_eq!(
+        statuses,
+        [
+            StatusCode::UNAUTHORIZED,
+            StatusCode::UNAUTHORIZED,
+            StatusCode::UNAUTHORIZED,
+            StatusCode::TOO_MANY_REQUESTS,
+            StatusCode::TOO_MANY_REQUESTS,
+        ]
+    );
+}
@@ -0,0 +1,129 @@
+// 必要なクレートのインポート
+use backend::{
+    middleware::request_id::request_id_middleware::{attach_request_id_to_errors, propagate_request_id},
+    models::ErrorResponse,
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use axum::body::Bytes;
+use futures_util::StreamExt;
+use uuid::Uuid;
+
+// リクエストIDの伝播のテスト
+#[tokio::test]
+async fn test_request_id_is_propagated() {
+    // モックサーバーの設定
+    let mut mock_server = mockito::Server::new_async().await;
+    // ユーザーID
+    let user_id = Uuid::new_v4();
+    // SupabaseへのリクエストにリクエストIDが付与されていることを確認するモック
+    let mock = mock_server
+        .mock("GET", format!("/rest/v1/trans_users?id=eq.{}", user_id).as_str())
+        .match_header("x-request-id", "test-request-id")
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+
+    // テスト用のアプリケーションを作成
+    let state = Arc::new(AppState::new(mock_server.url(), "test_key".to_string()));
+    let app = create_routes(state);
+
+    // ------------------------------------------------------------------------
+    // 1. 受け取ったリクエストIDがSupabase・レスポンス・エラーボディに含まれる
+    // ------------------------------------------------------------------------
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/di/users/{}", user_id))
+                .header("X-Request-Id", "test-request-id")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // ステータスコードがNOT_FOUNDであることを確認
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    // レスポンスヘッダーにリクエストIDが含まれることを確認
+    assert_eq!(response.headers()["x-request-id"], "test-request-id");
+    // エラーボディにリクエストIDが含まれることを確認
+    let bytes = body_to_bytes(response.into_body()).await;
+    let error: ErrorResponse = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(error.request_id, "test-request-id");
+    assert_eq!(error.message, "ユーザーが見つかりません");
+
+    // モックの確認
+    mock.assert_async().await;
+
+    // ------------------------------------------------------------------------
+    // 2. リクエストIDがない場合は生成される
+    // ------------------------------------------------------------------------
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/users/invalid_id")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // ステータスコードがBAD_REQUESTであることを確認
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    // 生成されたリクエストIDがUUIDであることを確認
+    let request_id = response.headers()["x-request-id"].to_str().unwrap().to_string();
+    assert!(Uuid::parse_str(&request_id).is_ok());
+    // エラーボディのリクエストIDがヘッダーと一致することを確認
+    let bytes = body_to_bytes(response.into_body()).await;
+    let error: ErrorResponse = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(error.request_id, request_id);
+}
+
+// 上限を超えるエラーボディは書き換えずにそのまま返すことのテスト
+#[tokio::test]
+async fn test_large_error_body_is_passed_through() {
+    // 64KBを超えるエラーボディを返すアプリケーション
+    let large_body = "x".repeat(100 * 1024);
+    let expected = large_body.clone();
+    let app = Router::new()
+        .route("/large", get(move || async move { (StatusCode::BAD_GATEWAY, large_body) }))
+        .layer(axum::middleware::from_fn(attach_request_id_to_errors))
+        .layer(axum::middleware::from_fn(propagate_request_id));
+
+    let response = app
+        .oneshot(Request::builder().uri("/large").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    // ステータス・リクエストIDのヘッダー・元のボディが返ることを確認
+    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    assert!(response.headers().contains_key("x-request-id"));
+    let bytes = body_to_bytes(response.into_body()).await;
+    assert_eq!(bytes, Bytes::from(expected));
+}
+
+// ボディをバイト列に変換するヘルパー関数
+async fn body_to_bytes(body: Body) -> Bytes {
+    let mut bytes = Vec::new();
+    let mut stream = body.into_data_stream();
+
+    while let Some(chunk) = stream.next().await {
+        if let Ok(data) = chunk {
+            bytes.extend_from_slice(&data);
+        }
+    }
+
+    Bytes::from(bytes)
+}
@@ -0,0 +1,232 @@
+// 必要なクレートのインポート
+use backend::{
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+    Router,
+};
+use serde_json::json;
+use std::sync::Arc;
+use tower::util::ServiceExt;
+
+// テスト用のユーザーID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// テスト用のセッションのID
+const SESSION_ID: &str = "00000000-0000-0000-0000-0000000000e1";
+// テスト用のユーザーのパスワード
+const PASSWORD: &str = "password123";
+
+// ユーザーを返すモックを設定(IDとメールアドレスの両方)
+async fn setup_user_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let body = json!([{
+        "id": USER_ID,
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": bcrypt::hash(PASSWORD, 4).unwrap(),
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    }]).to_string();
+    let mut mocks = Vec::new();
+    for path in [
+        format!("/rest/v1/trans_users?id=eq.{}", USER_ID),
+        "/rest/v1/trans_users?email=eq.test%40example.com".to_string(),
+    ] {
+        mocks.push(mock_server
+            .mock("GET", path.as_str())
+            .with_status(200)
+            .with_body(body.clone())
+            .create_async()
+            .await);
+    }
+    mocks
+}
+
+// サインインで作成するセッションのモックを設定(作成・トークンの検証での取得・最終アクセス日時の更新)
+async fn setup_session_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let body = json!([{
+        "id": SESSION_ID,
+        "user_id": USER_ID,
+        "user_agent": null,
+        "ip_address": null,
+        "created_at": "2024-01-01T00:00:00",
+        "last_seen_at": "2024-01-01T00:00:00",
+        "expires_at": "2099-01-01T00:00:00",
+    }])
+    .to_string();
+    vec![
+        mock_server
+            .mock("POST", "/rest/v1/rpc/create_user_session")
+            .with_status(200)
+            .with_body(body.clone())
+            .create_async()
+            .await,
+        mock_server
+            .mock("GET", format!("/rest/v1/trans_user_sessions?id=eq.{}", SESSION_ID).as_str())
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await,
+        mock_server
+            .mock("POST", "/rest/v1/rpc/touch_user_session")
+            .with_status(200)
+            .with_body("[]")
+            .create_async()
+            .await,
+    ]
+}
+
+// テスト用のアプリケーションを作成
+fn create_test_app(supabase_url: String, enabled: bool) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.session_cookie_config.enabled = enabled;
+    create_routes(Arc::new(state))
+}
+
+// サインインのリクエストを送信
+async fn sign_in(app: &Router) -> Response {
+    let body = json!({ "email": "test@example.com", "password": PASSWORD });
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/auth/signin")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+// レスポンスのSet-Cookieヘッダーから名前と値の組を取り出す
+fn set_cookies(response: &Response) -> Vec<(String, String, String)> {
+    response
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .map(|value| {
+            let value = value.to_str().unwrap().to_string();
+            let (pair, _) = value.split_once(';').unwrap();
+            let (name, cookie) = pair.split_once('=').unwrap();
+            (name.to_string(), cookie.to_string(), value.clone())
+        })
+        .collect()
+}
+
+// サインインで設定されたセッションのCookieとCSRFトークンを取り出す
+fn session_cookies(response: &Response) -> (String, String) {
+    let cookies = set_cookies(response);
+    let find = |name: &str| cookies.iter().find(|(cookie, _, _)| cookie == name).unwrap().1.clone();
+    (find("session"), find("csrf_token"))
+}
+
+// Cookieを付けたリクエストを送信
+async fn send_with_cookie(app: &Router, method: &str, uri: &str, cookie: &str, csrf: Option<&str>) -> Response {
+    let mut request = Request::builder().method(method).uri(uri).header(header::COOKIE, cookie);
+    if let Some(csrf) = csrf {
+        request = request.header("X-CSRF-Token", csrf);
+    }
+    app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap()
+}
+
+// サインインでセッションのCookieが設定され、Cookieで認証できることのテスト
+#[tokio::test]
+async fn test_sign_in_sets_session_cookie() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut mock_server).await;
+    let _user_mocks = setup_user_mocks(&mut mock_server).await;
+    let app = create_test_app(mock_server.url(), true);
+
+    let response = sign_in(&app).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // セッションのCookieはHttpOnly、CSRFトークンのCookieはJavaScriptから読める
+    let cookies = set_cookies(&response);
+    assert_eq!(cookies.len(), 2);
+    let session = cookies.iter().find(|(name, _, _)| name == "session").unwrap();
+    assert!(session.2.contains("HttpOnly") && session.2.contains("Secure") && session.2.contains("SameSite=Lax"));
+    let csrf = cookies.iter().find(|(name, _, _)| name == "csrf_token").unwrap();
+    assert!(!csrf.2.contains("HttpOnly"));
+
+    // Cookieのトークンで認証状態を確認できる
+    let (session, _) = session_cookies(&response);
+    let response = send_with_cookie(&app, "GET", "/v2/auth/check", &format!("session={}", session), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+// Cookieのセッションでの状態を変更するリクエストにはCSRFトークンが必要であることのテスト
+#[tokio::test]
+async fn test_state_changing_requests_require_csrf_token() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut mock_server).await;
+    let _user_mocks = setup_user_mocks(&mut mock_server).await;
+    let app = create_test_app(mock_server.url(), true);
+
+    let (session, csrf) = session_cookies(&sign_in(&app).await);
+    let cookie = format!("session={}; csrf_token={}", session, csrf);
+
+    // CSRFトークンがない・一致しない
+    let response = send_with_cookie(&app, "POST", "/auth/signout", &cookie, None).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = send_with_cookie(&app, "POST", "/auth/signout", &cookie, Some("forged")).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 一致するCSRFトークンがあればサインアウトでき、Cookieが削除される
+    let response = send_with_cookie(&app, "POST", "/auth/signout", &cookie, Some(&csrf)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let cookies = set_cookies(&response);
+    assert_eq!(cookies.len(), 2);
+    assert!(cookies.iter().all(|(_, value, header)| value.is_empty() && header.contains("Max-Age=0")));
+}
+
+// Authorizationヘッダーのリクエストと、Cookieのセッションが無効な場合はCSRFトークンを検証しないことのテスト
+#[tokio::test]
+async fn test_bearer_requests_skip_csrf_check() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut mock_server).await;
+    let _user_mocks = setup_user_mocks(&mut mock_server).await;
+    let app = create_test_app(mock_server.url(), true);
+
+    let (session, _) = session_cookies(&sign_in(&app).await);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/auth/signout")
+                .header(header::AUTHORIZATION, format!("Bearer {}", session))
+                .header(header::COOKIE, format!("session={}", session))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+// Cookieのセッションが無効な場合はCookieを設定せず、Cookieのトークンも受け付けないことのテスト
+#[tokio::test]
+async fn test_session_cookie_disabled() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut mock_server).await;
+    let _user_mocks = setup_user_mocks(&mut mock_server).await;
+    let app = create_test_app(mock_server.url(), false);
+
+    let response = sign_in(&app).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(set_cookies(&response).is_empty());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let token = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["token"].as_str().unwrap().to_string();
+
+    let response = send_with_cookie(&app, "GET", "/v2/auth/check", &format!("session={}", token), None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // CSRFトークンも検証しない
+    let response = send_with_cookie(&app, "POST", "/auth/signout", &format!("session={}", token), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,242 @@
+// 必要なクレートのインポート
+use backend::{
+    models::auth::auth::Claims,
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// テスト用のユーザーID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// テスト用のユーザーのパスワード
+const PASSWORD: &str = "password123";
+// テスト用のセッションのID(呼び出し元のトークンのセッション)
+const SESSION_ID: &str = "00000000-0000-0000-0000-0000000000e1";
+// 別の端末のセッションのID
+const OTHER_SESSION_ID: &str = "00000000-0000-0000-0000-0000000000e2";
+// サインインした端末のUser-Agent
+const USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64)";
+
+// テスト用のJWTシークレット
+const TEST_JWT_SECRET: &str = "test_secret";
+
+// テスト用のアプリケーションを作成
+fn create_test_app(supabase_url: String) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.jwt_secret = TEST_JWT_SECRET.to_string();
+    create_routes(Arc::new(state))
+}
+
+// セッションのトークンを作成(テスト用のJWTシークレットで署名)
+fn session_token() -> String {
+    let mut claims = Claims::new(&Uuid::parse_str(USER_ID).unwrap());
+    claims.sid = Some(Uuid::parse_str(SESSION_ID).unwrap());
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes())).unwrap()
+}
+
+// 保存されたセッションの行
+fn session_row(id: &str, expires_at: &str) -> Value {
+    json!({
+        "id": id,
+        "user_id": USER_ID,
+        "user_agent": USER_AGENT,
+        "ip_address": null,
+        "created_at": "2024-01-01T00:00:00",
+        "last_seen_at": "2024-01-01T00:00:00",
+        "expires_at": expires_at,
+    })
+}
+
+// ユーザーを返すモックを設定
+async fn setup_user_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let body = json!([{
+        "id": USER_ID,
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": bcrypt::hash(PASSWORD, 4).unwrap(),
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    }])
+    .to_string();
+    let mut mocks = Vec::new();
+    for path in [
+        format!("/rest/v1/trans_users?id=eq.{}", USER_ID),
+        "/rest/v1/trans_users?email=eq.test%40example.com".to_string(),
+    ] {
+        mocks.push(mock_server.mock("GET", path.as_str()).with_status(200).with_body(body.clone()).create_async().await);
+    }
+    mocks
+}
+
+// IDでセッションを返すモックを設定(rowsが空の場合は削除済み)
+async fn setup_find_mock(mock_server: &mut mockito::Server, rows: Value) -> mockito::Mock {
+    mock_server
+        .mock("GET", format!("/rest/v1/trans_user_sessions?id=eq.{}", SESSION_ID).as_str())
+        .with_status(200)
+        .with_body(rows.to_string())
+        .create_async()
+        .await
+}
+
+// リクエストを送信し、ステータスとボディを返す
+async fn send(app: Router, method: Method, uri: &str, token: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+// サインインで端末の情報とともにセッションを作成し、トークンにセッションのIDを含めることのテスト
+#[tokio::test]
+async fn test_sign_in_records_session() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mocks = setup_user_mocks(&mut mock_server).await;
+    let create_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/create_user_session")
+        .match_body(mockito::Matcher::PartialJson(json!({ "p_user_id": USER_ID, "p_user_agent": USER_AGENT })))
+        .with_status(200)
+        .with_body(json!([session_row(SESSION_ID, "2099-01-01T00:00:00")]).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let app = create_test_app(mock_server.url());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v2/auth/signin")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::USER_AGENT, USER_AGENT)
+        .body(Body::from(json!({ "email": "test@example.com", "password": PASSWORD }).to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+    let claims = decode::<Claims>(
+        body["token"].as_str().unwrap(),
+        &DecodingKey::from_secret(TEST_JWT_SECRET.as_bytes()),
+        &Validation::default(),
+    )
+    .unwrap()
+    .claims;
+    assert_eq!(claims.sid, Some(Uuid::parse_str(SESSION_ID).unwrap()));
+
+    create_mock.assert_async().await;
+}
+
+// セッションの一覧は期限切れのセッションを含まず、呼び出し元のセッションを示すことのテスト
+#[tokio::test]
+async fn test_list_sessions() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mocks = setup_user_mocks(&mut mock_server).await;
+    let _find_mock = setup_find_mock(&mut mock_server, json!([session_row(SESSION_ID, "2099-01-01T00:00:00")])).await;
+    let _touch_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/touch_user_session")
+        .match_body(mockito::Matcher::Json(json!({ "p_id": SESSION_ID })))
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let _list_mock = mock_server
+        .mock("GET", format!("/rest/v1/trans_user_sessions?user_id=eq.{}&order=created_at.desc", USER_ID).as_str())
+        .with_status(200)
+        .with_body(json!([
+            session_row(OTHER_SESSION_ID, "2099-01-01T00:00:00"),
+            session_row(SESSION_ID, "2099-01-01T00:00:00"),
+            session_row("00000000-0000-0000-0000-0000000000e3", "2000-01-01T00:00:00"),
+        ]).to_string())
+        .create_async()
+        .await;
+    let app = create_test_app(mock_server.url());
+
+    let (status, body) = send(app, Method::GET, "/v2/me/sessions", &session_token()).await;
+    assert_eq!(status, StatusCode::OK);
+    let sessions = body.as_array().unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions[0]["id"], OTHER_SESSION_ID);
+    assert_eq!(sessions[0]["current"], false);
+    assert_eq!(sessions[1]["id"], SESSION_ID);
+    assert_eq!(sessions[1]["current"], true);
+    assert_eq!(sessions[1]["user_agent"], USER_AGENT);
+}
+
+// セッションを削除すると、そのセッションのトークンが使えなくなることのテスト
+#[tokio::test]
+async fn test_deleted_session_rejects_token() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mocks = setup_user_mocks(&mut mock_server).await;
+    let _find_mock = setup_find_mock(&mut mock_server, json!([])).await;
+    let app = create_test_app(mock_server.url());
+
+    let (status, _) = send(app, Method::GET, "/v2/auth/check", &session_token()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+// 自分のセッションの削除とサインアウトでのセッションの削除のテスト
+#[tokio::test]
+async fn test_delete_session_and_sign_out() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mocks = setup_user_mocks(&mut mock_server).await;
+    let _find_mock = setup_find_mock(&mut mock_server, json!([session_row(SESSION_ID, "2099-01-01T00:00:00")])).await;
+    let _touch_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/touch_user_session")
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let delete_other_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/revoke_session_with_audit")
+        .match_body(mockito::Matcher::Json(json!({ "p_user_id": USER_ID, "p_id": OTHER_SESSION_ID })))
+        .with_status(200)
+        .with_body(json!([{ "id": OTHER_SESSION_ID }]).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let sign_out_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/revoke_session_with_audit")
+        .match_body(mockito::Matcher::Json(json!({ "p_user_id": USER_ID, "p_id": SESSION_ID })))
+        .with_status(200)
+        .with_body(json!([{ "id": SESSION_ID }]).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let _missing_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/revoke_session_with_audit")
+        .match_body(mockito::Matcher::PartialJson(json!({ "p_id": Uuid::nil() })))
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let app = create_test_app(mock_server.url());
+
+    let uri = format!("/v2/me/sessions/{}", OTHER_SESSION_ID);
+    let (status, _) = send(app.clone(), Method::DELETE, &uri, &session_token()).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let uri = format!("/v2/me/sessions/{}", Uuid::nil());
+    let (status, _) = send(app.clone(), Method::DELETE, &uri, &session_token()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // サインアウトは呼び出し元のセッションを削除する
+    let (status, _) = send(app, Method::POST, "/v2/auth/signout", &session_token()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    delete_other_mock.assert_async().await;
+    sign_out_mock.assert_async().await;
+}
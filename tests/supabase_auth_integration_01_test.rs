@@ -0,0 +1,315 @@
+// 必要なクレートのインポート
+use backend::{
+    config::supabase_auth_config::SupabaseAuthConfig,
+    models::auth::auth::Claims,
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use mockito::Matcher;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// トークンのユーザーID(auth.users.idとtrans_users.idは同じ)
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// プロジェクトのJWTシークレット
+const PROJECT_SECRET: &str = "super-secret-jwt-token-with-at-least-32-characters";
+
+// テスト用のユーザーのJSON(Supabaseが返す行)
+fn test_user_row() -> Value {
+    json!({
+        "id": USER_ID,
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": "$2b$04$unused",
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    })
+}
+
+// GoTrueが発行するトークンのクレーム
+fn gotrue_claims(supabase_url: &str) -> Value {
+    let now = chrono::Utc::now().timestamp();
+    json!({
+        "iss": format!("{}/auth/v1", supabase_url),
+        "aud": "authenticated",
+        "sub": USER_ID,
+        "role": "authenticated",
+        "email": "test@example.com",
+        "session_id": "00000000-0000-0000-0000-0000000000aa",
+        "iat": now,
+        "exp": now + 3600,
+    })
+}
+
+// プロジェクトのJWTシークレット(HS256)で署名する
+fn sign_with_secret(claims: &Value, secret: &str) -> String {
+    encode(&Header::new(Algorithm::HS256), claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap()
+}
+
+// テスト用のJWTシークレット
+const TEST_JWT_SECRET: &str = "test_secret";
+
+// Supabase Authを有効にしたテスト用のアプリケーションを作成
+fn create_test_app(supabase_url: String, config: SupabaseAuthConfig) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.jwt_secret = TEST_JWT_SECRET.to_string();
+    state.supabase_auth_config = config;
+    create_routes(Arc::new(state))
+}
+
+// JWTシークレットで検証する設定
+fn secret_config() -> SupabaseAuthConfig {
+    SupabaseAuthConfig {
+        enabled: true,
+        jwt_secret: Some(PROJECT_SECRET.to_string()),
+        ..SupabaseAuthConfig::default()
+    }
+}
+
+// ユーザーを返すモックを設定(Authorizationヘッダーを検証する)
+async fn setup_user_mock(mock_server: &mut mockito::Server, authorization: Matcher) -> mockito::Mock {
+    mock_server
+        .mock("GET", format!("/rest/v1/trans_users?id=eq.{}", USER_ID).as_str())
+        .match_header("authorization", authorization)
+        .expect_at_least(1)
+        .with_status(200)
+        .with_body(json!([test_user_row()]).to_string())
+        .create_async()
+        .await
+}
+
+// GETリクエストを送信し、ステータスとボディを返す
+async fn get(app: Router, uri: &str, token: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .uri(uri)
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+// GoTrueのトークンを受け付け、PostgRESTに転送することのテスト
+#[tokio::test]
+async fn test_gotrue_token_is_accepted_and_forwarded() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let token = sign_with_secret(&gotrue_claims(&mock_server.url()), PROJECT_SECRET);
+    let user_mock = setup_user_mock(&mut mock_server, Matcher::Exact(format!("Bearer {}", token))).await;
+
+    for uri in ["/auth/check", "/v2/auth/check", "/me", "/v2/me"] {
+        let (status, body) = get(create_test_app(mock_server.url(), secret_config()), uri, &token).await;
+        assert_eq!(status, StatusCode::OK, "{}", uri);
+        assert_eq!(body["id"], USER_ID);
+    }
+
+    user_mock.assert_async().await;
+}
+
+// このサーバーのトークンは転送せず、匿名キーで呼び出すことのテスト
+#[tokio::test]
+async fn test_backend_token_is_not_forwarded() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mock = setup_user_mock(&mut mock_server, Matcher::Missing).await;
+    // このサーバーのトークン(テスト用のJWTシークレットで署名)
+    let claims = Claims::new(&Uuid::parse_str(USER_ID).unwrap());
+    let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes())).unwrap();
+
+    let (status, body) = get(create_test_app(mock_server.url(), secret_config()), "/v2/me", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], USER_ID);
+}
+
+// 無効にしている場合はGoTrueのトークンを受け付けないことのテスト
+#[tokio::test]
+async fn test_gotrue_token_is_rejected_when_disabled() {
+    let mock_server = mockito::Server::new_async().await;
+    let token = sign_with_secret(&gotrue_claims(&mock_server.url()), PROJECT_SECRET);
+    let config = SupabaseAuthConfig { enabled: false, ..secret_config() };
+
+    let (status, _) = get(create_test_app(mock_server.url(), config), "/v2/me", &token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+// 不正なGoTrueのトークンを拒否することのテスト
+#[tokio::test]
+async fn test_invalid_gotrue_tokens_are_rejected() {
+    let mock_server = mockito::Server::new_async().await;
+    let url = mock_server.url();
+
+    // (説明, トークン)
+    let mut cases = Vec::new();
+    cases.push(("wrong secret", sign_with_secret(&gotrue_claims(&url), "another-project-secret")));
+    let mut claims = gotrue_claims(&url);
+    claims["iss"] = json!("https://other-project.supabase.co/auth/v1");
+    cases.push(("issuer", sign_with_secret(&claims, PROJECT_SECRET)));
+    let mut claims = gotrue_claims(&url);
+    claims["aud"] = json!("other");
+    cases.push(("audience", sign_with_secret(&claims, PROJECT_SECRET)));
+    let mut claims = gotrue_claims(&url);
+    claims["exp"] = json!(chrono::Utc::now().timestamp() - 3600);
+    cases.push(("expired", sign_with_secret(&claims, PROJECT_SECRET)));
+    // 匿名キー(ユーザーのトークンではない)
+    let mut claims = gotrue_claims(&url);
+    claims["role"] = json!("anon");
+    cases.push(("anon role", sign_with_secret(&claims, PROJECT_SECRET)));
+
+    for (case, token) in cases {
+        let (status, _) = get(create_test_app(url.clone(), secret_config()), "/v2/me", &token).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", case);
+    }
+}
+
+// JWKSの公開鍵で署名されたトークンを受け付けることのテスト
+#[tokio::test]
+async fn test_gotrue_token_signed_with_jwks_key() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let jwks_mock = mock_server
+        .mock("GET", "/auth/v1/.well-known/jwks.json")
+        .with_status(200)
+        .with_body(json!({ "keys": [{
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "use": "sig",
+            "alg": "EdDSA",
+            "kid": "project-key",
+            "x": URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+        }] }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some("project-key".to_string());
+    let token = encode(&header, &gotrue_claims(&mock_server.url()), &EncodingKey::from_ed_der(pkcs8.as_ref())).unwrap();
+    let _user_mock = setup_user_mock(&mut mock_server, Matcher::Exact(format!("Bearer {}", token))).await;
+
+    // JWTシークレットなし(JWKSのみ)、JWKSは2回目以降キャッシュを使う
+    let config = SupabaseAuthConfig { enabled: true, ..SupabaseAuthConfig::default() };
+    let app = create_test_app(mock_server.url(), config);
+    for _ in 0..2 {
+        let (status, body) = get(app.clone(), "/v2/auth/check", &token).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["id"], USER_ID);
+    }
+
+    // JWTシークレットがない場合、HS256のトークンは受け付けない
+    let token = sign_with_secret(&gotrue_claims(&mock_server.url()), PROJECT_SECRET);
+    let (status, _) = get(app, "/v2/auth/check", &token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    jwks_mock.assert_async().await;
+}
+
+// 未知の鍵(kid)のトークンでは、前回の取得から間隔をあけずにJWKSを取得し直さないことのテスト
+#[tokio::test]
+async fn test_unknown_kid_does_not_refetch_jwks_every_request() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let jwks_body = json!({ "keys": [{
+        "kty": "OKP",
+        "crv": "Ed25519",
+        "alg": "EdDSA",
+        "kid": "project-key",
+        "x": URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+    }] })
+    .to_string();
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some("unknown-key".to_string());
+    let token = encode(&header, &gotrue_claims(&mock_server.url()), &EncodingKey::from_ed_der(pkcs8.as_ref())).unwrap();
+
+    // 最初の取得のみで、取得し直さない
+    let jwks_mock = mock_server
+        .mock("GET", "/auth/v1/.well-known/jwks.json")
+        .with_status(200)
+        .with_body(jwks_body.clone())
+        .expect(1)
+        .create_async()
+        .await;
+    let config = SupabaseAuthConfig { enabled: true, ..SupabaseAuthConfig::default() };
+    let app = create_test_app(mock_server.url(), config);
+    for _ in 0..3 {
+        let (status, _) = get(app.clone(), "/v2/auth/check", &token).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+    jwks_mock.assert_async().await;
+    jwks_mock.remove_async().await;
+
+    // 間隔が過ぎていれば、鍵のローテーションに備えて1回だけ取得し直す
+    let jwks_mock = mock_server
+        .mock("GET", "/auth/v1/.well-known/jwks.json")
+        .with_status(200)
+        .with_body(jwks_body)
+        .expect(2)
+        .create_async()
+        .await;
+    let config = SupabaseAuthConfig {
+        enabled: true,
+        jwks_min_refresh_interval: Duration::ZERO,
+        ..SupabaseAuthConfig::default()
+    };
+    let (status, _) = get(create_test_app(mock_server.url(), config), "/v2/auth/check", &token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    jwks_mock.assert_async().await;
+}
+
+// JWKSの取得に失敗した後は、しばらく取得し直さずにすぐ401を返すことのテスト
+#[tokio::test]
+async fn test_jwks_failure_is_not_refetched_during_backoff() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some("project-key".to_string());
+    let token = encode(&header, &gotrue_claims(&mock_server.url()), &EncodingKey::from_ed_der(pkcs8.as_ref())).unwrap();
+
+    // 失敗した取得の後は、期間中に取得しない
+    let jwks_mock = mock_server
+        .mock("GET", "/auth/v1/.well-known/jwks.json")
+        .with_status(503)
+        .expect(1)
+        .create_async()
+        .await;
+    let config = SupabaseAuthConfig { enabled: true, ..SupabaseAuthConfig::default() };
+    let app = create_test_app(mock_server.url(), config);
+    for _ in 0..3 {
+        let (status, _) = get(app.clone(), "/v2/auth/check", &token).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+    jwks_mock.assert_async().await;
+    jwks_mock.remove_async().await;
+
+    // 期間がなければ、リクエストごとに取得し直す
+    let jwks_mock = mock_server
+        .mock("GET", "/auth/v1/.well-known/jwks.json")
+        .with_status(503)
+        .expect(2)
+        .create_async()
+        .await;
+    let config = SupabaseAuthConfig {
+        enabled: true,
+        jwks_failure_backoff: Duration::ZERO,
+        ..SupabaseAuthConfig::default()
+    };
+    let app = create_test_app(mock_server.url(), config);
+    for _ in 0..2 {
+        let (status, _) = get(app.clone(), "/v2/auth/check", &token).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+    jwks_mock.assert_async().await;
+}
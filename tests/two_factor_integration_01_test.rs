@@ -0,0 +1,354 @@
+// 必要なクレートのインポート
+use backend::{
+    models::auth::auth::Claims,
+    routes::create_routes,
+    services::auth::totp::Totp,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// テスト用のユーザーID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// テスト用のセッションのID
+const SESSION_ID: &str = "00000000-0000-0000-0000-0000000000e1";
+// 管理者のユーザーID
+const ADMIN_ID: &str = "00000000-0000-0000-0000-0000000000ad";
+// テスト用のユーザーのパスワード
+const PASSWORD: &str = "password123";
+// テスト用のTOTPの秘密鍵
+const SECRET: &str = "JBSWY3DPEHPK3PXP";
+
+// テスト用のユーザーのJSON(Supabaseが返す行)
+fn test_user_row(two_factor_enabled: bool) -> Value {
+    json!({
+        "id": USER_ID,
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": bcrypt::hash(PASSWORD, 4).unwrap(),
+        "two_factor_enabled": two_factor_enabled,
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    })
+}
+
+// ユーザーを返すモックを設定(IDとメールアドレスの両方)
+async fn setup_user_mocks(mock_server: &mut mockito::Server, two_factor_enabled: bool) -> Vec<mockito::Mock> {
+    let body = json!([test_user_row(two_factor_enabled)]).to_string();
+    let mut mocks = Vec::new();
+    for path in [
+        format!("/rest/v1/trans_users?id=eq.{}", USER_ID),
+        "/rest/v1/trans_users?email=eq.test%40example.com".to_string(),
+    ] {
+        mocks.push(mock_server
+            .mock("GET", path.as_str())
+            .with_status(200)
+            .with_body(body.clone())
+            .create_async()
+            .await);
+    }
+    mocks
+}
+
+// 二段階認証の設定を返すモックを設定
+async fn setup_two_factor_mock(mock_server: &mut mockito::Server, confirmed: bool) -> mockito::Mock {
+    mock_server
+        .mock("GET", format!("/rest/v1/trans_user_two_factor?user_id=eq.{}", USER_ID).as_str())
+        .with_status(200)
+        .with_body(json!([{
+            "user_id": USER_ID,
+            "secret": SECRET,
+            "last_used_step": null,
+            "confirmed_at": if confirmed { json!("2024-01-01T00:00:00") } else { Value::Null },
+        }]).to_string())
+        .create_async()
+        .await
+}
+
+// 行を返すRPCのモックを設定
+async fn setup_rpc_mock(mock_server: &mut mockito::Server, function: &str, expect: usize) -> mockito::Mock {
+    mock_server
+        .mock("POST", format!("/rest/v1/rpc/{}", function).as_str())
+        .match_body(mockito::Matcher::PartialJson(json!({ "p_user_id": USER_ID })))
+        .with_status(200)
+        .with_body(json!([{ "user_id": USER_ID }]).to_string())
+        .expect(expect)
+        .create_async()
+        .await
+}
+
+// 一致するリカバリーコードがない(行を返さない)RPCのモックを設定
+async fn setup_no_recovery_code_mock(mock_server: &mut mockito::Server, expect: usize) -> mockito::Mock {
+    mock_server
+        .mock("POST", "/rest/v1/rpc/use_two_factor_recovery_code")
+        .with_status(200)
+        .with_body("[]")
+        .expect(expect)
+        .create_async()
+        .await
+}
+
+// サインインで作成するセッションのモックを設定(作成・トークンの検証での取得・最終アクセス日時の更新)
+async fn setup_session_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let body = json!([{
+        "id": SESSION_ID,
+        "user_id": USER_ID,
+        "user_agent": null,
+        "ip_address": null,
+        "created_at": "2024-01-01T00:00:00",
+        "last_seen_at": "2024-01-01T00:00:00",
+        "expires_at": "2099-01-01T00:00:00",
+    }])
+    .to_string();
+    vec![
+        mock_server
+            .mock("POST", "/rest/v1/rpc/create_user_session")
+            .with_status(200)
+            .with_body(body.clone())
+            .create_async()
+            .await,
+        mock_server
+            .mock("GET", format!("/rest/v1/trans_user_sessions?id=eq.{}", SESSION_ID).as_str())
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await,
+        mock_server
+            .mock("POST", "/rest/v1/rpc/touch_user_session")
+            .with_status(200)
+            .with_body("[]")
+            .create_async()
+            .await,
+    ]
+}
+
+// テスト用のJWTシークレット
+const TEST_JWT_SECRET: &str = "test_secret";
+
+// テスト用のアプリケーションを作成(管理者を1人登録する)
+fn create_test_app(supabase_url: String) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.jwt_secret = TEST_JWT_SECRET.to_string();
+    state.admin_config.user_ids.insert(Uuid::parse_str(ADMIN_ID).unwrap());
+    create_routes(Arc::new(state))
+}
+
+// ユーザーのトークンを作成(テスト用のJWTシークレットで署名)
+fn token(user_id: &str) -> String {
+    let claims = Claims::new(&Uuid::parse_str(user_id).unwrap());
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes())).unwrap()
+}
+
+// 現在のコードを計算する関数
+fn current_code() -> String {
+    Totp::from_base32(SECRET).unwrap().code_at(chrono::Utc::now().timestamp())
+}
+
+// リクエストを送信し、ステータスとボディを返す
+async fn send(app: Router, method: &str, uri: &str, token: Option<&str>, body: Option<Value>) -> (StatusCode, Value) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let request = request
+        .body(body.map(|body| Body::from(body.to_string())).unwrap_or_else(Body::empty))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+// 設定の開始で秘密鍵を保存し、認証アプリのURIを返すことのテスト
+#[tokio::test]
+async fn test_setup_returns_otpauth_uri() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mocks = setup_user_mocks(&mut mock_server, false).await;
+    let rpc_mock = setup_rpc_mock(&mut mock_server, "begin_two_factor_setup", 2).await;
+
+    for uri in ["/me/2fa/setup", "/v2/me/2fa/setup"] {
+        let app = create_test_app(mock_server.url());
+        let (status, body) = send(app, "POST", uri, Some(&token(USER_ID)), None).await;
+        assert_eq!(status, StatusCode::OK, "{}", uri);
+
+        let secret = body["secret"].as_str().unwrap();
+        assert!(Totp::from_base32(secret).is_some());
+        assert_eq!(
+            body["otpauth_uri"].as_str().unwrap(),
+            Totp::from_base32(secret).unwrap().otpauth_uri("backend", "test@example.com")
+        );
+    }
+
+    rpc_mock.assert_async().await;
+}
+
+// 認証アプリのコードで有効にし、リカバリーコードを返すことのテスト
+#[tokio::test]
+async fn test_confirm_enables_two_factor() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _two_factor_mock = setup_two_factor_mock(&mut mock_server, false).await;
+    let rpc_mock = setup_rpc_mock(&mut mock_server, "enable_two_factor_with_audit", 1).await;
+
+    // 誤ったコードは書き込まずに拒否
+    let app = create_test_app(mock_server.url());
+    let (status, _) = send(app, "POST", "/v2/me/2fa/confirm", Some(&token(USER_ID)), Some(json!({ "code": "abcdef" }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let app = create_test_app(mock_server.url());
+    let body = Some(json!({ "code": current_code() }));
+    let (status, body) = send(app, "POST", "/v2/me/2fa/confirm", Some(&token(USER_ID)), body).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["recovery_codes"].as_array().unwrap().len(), 10);
+
+    rpc_mock.assert_async().await;
+}
+
+// 二段階認証が有効なユーザーのサインインはチャレンジを返し、コードの検証後にトークンを返すことのテスト
+#[tokio::test]
+async fn test_sign_in_requires_second_factor() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _session_mocks = setup_session_mocks(&mut mock_server).await;
+    let _user_mocks = setup_user_mocks(&mut mock_server, true).await;
+    let _two_factor_mock = setup_two_factor_mock(&mut mock_server, true).await;
+    let rpc_mock = setup_rpc_mock(&mut mock_server, "record_two_factor_step", 1).await;
+    let recovery_mock = setup_no_recovery_code_mock(&mut mock_server, 1).await;
+
+    // 同じ状態(チャレンジのストア)を使い回す
+    let app = create_test_app(mock_server.url());
+    let sign_in = Some(json!({ "email": "test@example.com", "password": PASSWORD }));
+    let (status, body) = send(app.clone(), "POST", "/v2/auth/signin", None, sign_in).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    // トークンは返さない
+    assert!(body.get("token").is_none());
+    let challenge = body["challenge_token"].as_str().unwrap().to_string();
+
+    // 誤ったコード
+    let wrong = Some(json!({ "challenge_token": challenge, "code": "000000x" }));
+    let (status, _) = send(app.clone(), "POST", "/v2/auth/2fa/verify", None, wrong).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let verify = Some(json!({ "challenge_token": challenge, "code": current_code() }));
+    let (status, body) = send(app.clone(), "POST", "/v2/auth/2fa/verify", None, verify.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["token"].is_string());
+
+    // 使用済みのチャレンジは無効
+    let (status, _) = send(app, "POST", "/v2/auth/2fa/verify", None, verify).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    rpc_mock.assert_async().await;
+    recovery_mock.assert_async().await;
+}
+
+// 試行回数を使い切ったチャレンジは正しいコードでも拒否することのテスト
+#[tokio::test]
+async fn test_challenge_attempts_are_limited() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mocks = setup_user_mocks(&mut mock_server, true).await;
+    let _two_factor_mock = setup_two_factor_mock(&mut mock_server, true).await;
+    // 誤ったコードはリカバリーコードとして照合し、認証アプリのコードの記録は行わない
+    let recovery_mock = setup_no_recovery_code_mock(&mut mock_server, 5).await;
+    let record_mock = setup_rpc_mock(&mut mock_server, "record_two_factor_step", 0).await;
+
+    let app = create_test_app(mock_server.url());
+    let sign_in = Some(json!({ "email": "test@example.com", "password": PASSWORD }));
+    let (status, body) = send(app.clone(), "POST", "/auth/signin", None, sign_in).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let challenge = body["challenge_token"].as_str().unwrap().to_string();
+
+    // 既定の試行回数(5回)を使い切る(リカバリーコードとしても一致しない値)
+    for _ in 0..5 {
+        let wrong = Some(json!({ "challenge_token": challenge, "code": "wrong-code" }));
+        let (status, _) = send(app.clone(), "POST", "/auth/2fa/verify", None, wrong).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    let verify = Some(json!({ "challenge_token": challenge, "code": current_code() }));
+    let (status, body) = send(app, "POST", "/auth/2fa/verify", None, verify).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["message"], "Invalid or expired challenge");
+
+    recovery_mock.assert_async().await;
+    record_mock.assert_async().await;
+}
+
+// コードの失敗はチャレンジをまたいで数え、多すぎる場合はチャレンジの発行と検証を拒否することのテスト
+#[tokio::test]
+async fn test_failures_across_challenges_lock_user() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _user_mocks = setup_user_mocks(&mut mock_server, true).await;
+    let _two_factor_mock = setup_two_factor_mock(&mut mock_server, true).await;
+    // 既定の上限(10回)までの誤ったコードのみ照合する
+    let recovery_mock = setup_no_recovery_code_mock(&mut mock_server, 10).await;
+    let record_mock = setup_rpc_mock(&mut mock_server, "record_two_factor_step", 0).await;
+
+    let app = create_test_app(mock_server.url());
+    let sign_in = Some(json!({ "email": "test@example.com", "password": PASSWORD }));
+    let mut challenges = Vec::new();
+    for _ in 0..3 {
+        let (status, body) = send(app.clone(), "POST", "/auth/signin", None, sign_in.clone()).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        challenges.push(body["challenge_token"].as_str().unwrap().to_string());
+    }
+
+    // 2つのチャレンジの試行回数(5回ずつ)を使い切る
+    for challenge in &challenges[..2] {
+        for _ in 0..5 {
+            let wrong = Some(json!({ "challenge_token": challenge, "code": "wrong-code" }));
+            let (status, _) = send(app.clone(), "POST", "/auth/2fa/verify", None, wrong).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    // 発行済みのチャレンジは正しいコードでも拒否する
+    let verify = Some(json!({ "challenge_token": challenges[2], "code": current_code() }));
+    let (status, _) = send(app.clone(), "POST", "/v2/auth/2fa/verify", None, verify).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    // 新しいチャレンジも発行しない
+    let (status, body) = send(app, "POST", "/v2/auth/signin", None, sign_in).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(body.get("challenge_token").is_none());
+
+    recovery_mock.assert_async().await;
+    record_mock.assert_async().await;
+}
+
+// 管理者のみが二段階認証をリセットできることのテスト
+#[tokio::test]
+async fn test_reset_requires_admin() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let rpc_mock = setup_rpc_mock(&mut mock_server, "reset_two_factor_with_audit", 2).await;
+
+    for uri in [format!("/users/{}/2fa", USER_ID), format!("/v2/users/{}/2fa", USER_ID)] {
+        // 管理者以外
+        let app = create_test_app(mock_server.url());
+        let (status, _) = send(app, "DELETE", &uri, Some(&token(USER_ID)), None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{}", uri);
+
+        // トークンなし
+        let app = create_test_app(mock_server.url());
+        let (status, _) = send(app, "DELETE", &uri, None, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", uri);
+
+        // 管理者
+        let app = create_test_app(mock_server.url());
+        let (status, _) = send(app, "DELETE", &uri, Some(&token(ADMIN_ID)), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT, "{}", uri);
+    }
+
+    rpc_mock.assert_async().await;
+}
@@ -0,0 +1,193 @@
+// 必要なクレートのインポート
+use backend::{
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+
+// 存在するユーザーのID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+// 存在しないユーザーのID
+const MISSING_ID: &str = "00000000-0000-0000-0000-000000000002";
+
+// テスト用のユーザーのJSON
+fn test_user() -> Value {
+    json!({
+        "id": USER_ID,
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": "hashed",
+        "created_at": "2024-01-01T00:00:00",
+        "updated_at": "2024-01-01T00:00:00",
+    })
+}
+
+// 正しい入力のJSON
+fn valid_input() -> Value {
+    json!({
+        "username": "test_user",
+        "email": "test@example.com",
+        "password": "password123",
+    })
+}
+
+// 更新の入力のJSON(パスワードは含めない)
+fn update_input() -> Value {
+    json!({
+        "username": "test_user",
+        "email": "test@example.com",
+    })
+}
+
+// Supabaseのモックを設定
+async fn setup_mocks(mock_server: &mut mockito::Server) -> Vec<mockito::Mock> {
+    let mut mocks = Vec::new();
+
+    // 一覧・1件の取得
+    for (path, body) in [
+        ("/rest/v1/trans_users".to_string(), json!([test_user()])),
+        (format!("/rest/v1/trans_users?id=eq.{}", USER_ID), json!([test_user()])),
+        (format!("/rest/v1/trans_users?id=eq.{}", MISSING_ID), json!([])),
+    ] {
+        mocks.push(mock_server
+            .mock("GET", path.as_str())
+            .with_status(200)
+            .with_body(body.to_string())
+            .create_async()
+            .await);
+    }
+
+    // 作成
+    mocks.push(mock_server
+        .mock("POST", "/rest/v1/rpc/create_user_with_audit")
+        .with_status(200)
+        .with_body(json!([test_user()]).to_string())
+        .create_async()
+        .await);
+
+    // 更新・削除(存在しないユーザーは空の配列)
+    for function in ["update_user_with_audit", "delete_user_with_audit"] {
+        for (id, body) in [(USER_ID, json!([test_user()])), (MISSING_ID, json!([]))] {
+            mocks.push(mock_server
+                .mock("POST", format!("/rest/v1/rpc/{}", function).as_str())
+                .match_body(mockito::Matcher::PartialJson(json!({ "p_id": id })))
+                .with_status(200)
+                .with_body(body.to_string())
+                .create_async()
+                .await);
+        }
+    }
+
+    mocks
+}
+
+// リクエストを送信し、ステータスとボディを返す(リクエストIDは比較しない)
+async fn send(supabase_url: &str, method: &str, uri: &str, body: Option<&Value>) -> (StatusCode, Value) {
+    // キャッシュの影響を受けないよう、リクエストごとにアプリケーションを作成
+    let app = create_routes(Arc::new(AppState::new(supabase_url.to_string(), "test_key".to_string())));
+
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(body.map(|body| Body::from(body.to_string())).unwrap_or_else(Body::empty))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let mut value: Value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    if let Some(object) = value.as_object_mut() {
+        object.remove("request_id");
+    }
+    (status, value)
+}
+
+// 不正な入力のJSON
+fn input_with(field: &str, value: &str) -> Value {
+    let mut input = valid_input();
+    input[field] = json!(value);
+    input
+}
+
+// 不正な更新の入力のJSON
+fn update_with(field: &str, value: &str) -> Value {
+    let mut input = update_input();
+    input[field] = json!(value);
+    input
+}
+
+// /usersと/di/usersが同じ結果を返すことのテスト
+#[tokio::test]
+async fn test_legacy_and_di_routes_are_identical() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mocks = setup_mocks(&mut mock_server).await;
+    let url = mock_server.url();
+
+    // 比較するリクエスト(メソッド、/usersからの相対パス、ボディ、期待するステータス)
+    let cases = vec![
+        ("GET", String::new(), None, StatusCode::OK),
+        ("GET", format!("/{}", USER_ID), None, StatusCode::OK),
+        ("GET", format!("/{}", MISSING_ID), None, StatusCode::NOT_FOUND),
+        ("POST", String::new(), Some(valid_input()), StatusCode::OK),
+        ("POST", String::new(), Some(input_with("username", "")), StatusCode::BAD_REQUEST),
+        ("POST", String::new(), Some(input_with("email", "invalid")), StatusCode::BAD_REQUEST),
+        ("POST", String::new(), Some(input_with("password", "short")), StatusCode::BAD_REQUEST),
+        ("PUT", format!("/{}", USER_ID), Some(update_input()), StatusCode::OK),
+        ("PUT", format!("/{}", USER_ID), Some(update_with("email", "invalid")), StatusCode::BAD_REQUEST),
+        ("PUT", format!("/{}", USER_ID), Some(valid_input()), StatusCode::UNPROCESSABLE_ENTITY),
+        ("PUT", format!("/{}", MISSING_ID), Some(update_input()), StatusCode::NOT_FOUND),
+        ("DELETE", format!("/{}", MISSING_ID), None, StatusCode::NOT_FOUND),
+    ];
+
+    for (method, suffix, body, expected) in cases {
+        let legacy = send(&url, method, &format!("/users{}", suffix), body.as_ref()).await;
+        let di = send(&url, method, &format!("/di/users{}", suffix), body.as_ref()).await;
+
+        assert_eq!(legacy.0, expected, "{} /users{}", method, suffix);
+        assert_eq!(legacy, di, "{} /users{}", method, suffix);
+    }
+}
+
+// 削除の成功だけは互換モードで以前のレスポンス形式を保つことのテスト
+#[tokio::test]
+async fn test_delete_keeps_legacy_response_shape() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mocks = setup_mocks(&mut mock_server).await;
+    let url = mock_server.url();
+
+    // /usersは200とメッセージのJSON
+    let legacy = send(&url, "DELETE", &format!("/users/{}", USER_ID), None).await;
+    assert_eq!(legacy, (StatusCode::OK, json!("User deleted successfully")));
+
+    // /di/usersは204でボディなし
+    let di = send(&url, "DELETE", &format!("/di/users/{}", USER_ID), None).await;
+    assert_eq!(di, (StatusCode::NO_CONTENT, Value::Null));
+}
+
+// 更新でパスワードを送ると、すべてのバージョンで拒否し、更新のRPCを呼ばないことのテスト
+#[tokio::test]
+async fn test_update_rejects_password() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let update_mock = mock_server
+        .mock("POST", "/rest/v1/rpc/update_user_with_audit")
+        .expect(0)
+        .create_async()
+        .await;
+    let url = mock_server.url();
+
+    for prefix in ["/users", "/di/users", "/v1/users", "/v2/users"] {
+        let uri = format!("{}/{}", prefix, USER_ID);
+        let (status, _) = send(&url, "PUT", &uri, Some(&input_with("password", "new_password456"))).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "PUT {}", uri);
+    }
+
+    update_mock.assert_async().await;
+}
@@ -1,7 +1,7 @@
 // 必要なクレートのインポート
 use backend::{
     create_app,
-    models::users::users::{User, NewUser},
+    models::users::users::{User, NewUser, UserUpdate},
 };
 // 必要なクレートのインポート
 use axum::{
@@ -95,10 +95,9 @@ async fn test_user_crud_operations() {
     // ------------------------------------------------------------------------
     // 4. UPDATE: ユーザー情報を更新
     // ------------------------------------------------------------------------
-    let updated_user = NewUser {
+    let updated_user = UserUpdate {
         username: "updated_user".to_string(),
         email: "updated@example.com".to_string(),
-        password: "newpassword123".to_string(),
     };
 
     // ユーザーを更新
@@ -1,7 +1,7 @@
 // 必要なクレートのインポート
 use backend::{
     create_app,
-    models::users::users::NewUser,
+    models::users::users::{NewUser, UserUpdate},
 };
 // 必要なクレートのインポート
 use axum::{
@@ -100,10 +100,9 @@ async fn test_invalid_user_operations() {
     // ------------------------------------------------------------------------
     // 5. 存在しないユーザーの更新テスト
     // ------------------------------------------------------------------------
-    let update_user = NewUser {
+    let update_user = UserUpdate {
         username: "updated".to_string(),
         email: "updated@example.com".to_string(),
-        password: "password123".to_string(),
     };
 
     // ユーザーを更新
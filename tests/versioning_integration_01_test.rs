@@ -0,0 +1,157 @@
+// 必要なクレートのインポート
+use backend::{
+    routes::create_routes,
+    state::AppState,
+};
+// 必要なクレートのインポート
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    response::Response,
+    Router,
+};
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::util::ServiceExt;
+
+// 存在するユーザーのID
+const USER_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+// ユーザーを返すモックを設定
+async fn setup_user_mock(mock_server: &mut mockito::Server) -> mockito::Mock {
+    mock_server
+        .mock("GET", format!("/rest/v1/trans_users?id=eq.{}", USER_ID).as_str())
+        .with_status(200)
+        .with_body(json!([{
+            "id": USER_ID,
+            "username": "test_user",
+            "email": "test@example.com",
+            "password": "hashed",
+            "created_at": "2024-01-01T00:00:00",
+            "updated_at": "2024-01-01T00:00:00",
+        }]).to_string())
+        .create_async()
+        .await
+}
+
+// テスト用のアプリケーションを作成
+fn create_test_app(supabase_url: String, negotiation_enabled: bool) -> Router {
+    let mut state = AppState::new(supabase_url, "test_key".to_string());
+    state.versioning_config.negotiation_enabled = negotiation_enabled;
+    state.versioning_config.v1_sunset = Some(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap());
+    create_routes(Arc::new(state))
+}
+
+// GETリクエストを送信
+async fn get(app: &Router, uri: &str, accept: Option<&str>) -> Response {
+    let mut request = Request::builder().uri(uri);
+    if let Some(accept) = accept {
+        request = request.header("Accept", accept);
+    }
+    app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap()
+}
+
+// Varyヘッダーにacceptが含まれるか確認
+fn varies_by_accept(response: &Response) -> bool {
+    response.headers().get_all("vary").iter().any(|value| value == "accept")
+}
+
+// レスポンスボディをJSONに変換
+async fn body_json(response: Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+// v1は以前の形式で非推奨のヘッダーを付け、v2はパスワードを含まないことのテスト
+#[tokio::test]
+async fn test_versioned_prefixes() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = setup_user_mock(&mut mock_server).await;
+    let app = create_test_app(mock_server.url(), true);
+
+    // v1と接頭辞のないパスは以前の形式で、非推奨のヘッダーを付ける
+    for uri in [format!("/v1/users/{}", USER_ID), format!("/users/{}", USER_ID)] {
+        let response = get(&app, &uri, None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["deprecation"], "true");
+        assert_eq!(response.headers()["sunset"], "Tue, 01 Jan 2030 00:00:00 GMT");
+        assert_eq!(
+            response.headers()["link"],
+            "</api-docs/v2/openapi.json>; rel=\"successor-version\""
+        );
+        assert_eq!(body_json(response).await["password"], "hashed");
+    }
+
+    // v2はパスワードを含まず、非推奨のヘッダーも付けない
+    let response = get(&app, &format!("/v2/users/{}", USER_ID), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("deprecation").is_none());
+    let body = body_json(response).await;
+    assert_eq!(body["id"], USER_ID);
+    assert!(body.get("password").is_none());
+}
+
+// Acceptヘッダーで接頭辞のないパスのバージョンを選択できることのテスト
+#[tokio::test]
+async fn test_accept_header_negotiation() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = setup_user_mock(&mut mock_server).await;
+    let app = create_test_app(mock_server.url(), true);
+    let uri = format!("/users/{}", USER_ID);
+
+    // v2を要求するとv2のレスポンスを返す
+    let response = get(&app, &uri, Some("application/vnd.backend.v2+json")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(varies_by_accept(&response));
+    assert!(response.headers().get("deprecation").is_none());
+    assert!(body_json(response).await.get("password").is_none());
+
+    // v1を要求するとv1のレスポンスを返す
+    let response = get(&app, &uri, Some("application/vnd.backend.v1+json")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_json(response).await["password"], "hashed");
+
+    // 未対応のバージョンは406
+    let response = get(&app, &uri, Some("application/vnd.backend.v9+json")).await;
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+
+    // 接頭辞のあるパスはAcceptヘッダーに関わらずそのバージョン
+    let response = get(&app, &format!("/v1/users/{}", USER_ID), Some("application/vnd.backend.v2+json")).await;
+    assert_eq!(body_json(response).await["password"], "hashed");
+}
+
+// バージョンの選択を無効にした場合はAcceptヘッダーを無視することのテスト
+#[tokio::test]
+async fn test_negotiation_can_be_disabled() {
+    let mut mock_server = mockito::Server::new_async().await;
+    let _mock = setup_user_mock(&mut mock_server).await;
+    let app = create_test_app(mock_server.url(), false);
+
+    let response = get(&app, &format!("/users/{}", USER_ID), Some("application/vnd.backend.v2+json")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!varies_by_accept(&response));
+    assert_eq!(body_json(response).await["password"], "hashed");
+}
+
+// バージョンごとのOpenAPIドキュメントのテスト
+#[tokio::test]
+async fn test_openapi_per_version() {
+    let app = create_test_app("http://127.0.0.1:9".to_string(), true);
+
+    // v1は/v1の接頭辞が付き、非推奨
+    let v1 = body_json(get(&app, "/api-docs/v1/openapi.json", None).await).await;
+    assert_eq!(v1["paths"]["/v1/users/{id}"]["get"]["deprecated"], true);
+    assert!(v1["paths"].get("/users/{id}").is_none());
+
+    // v2はパスワードを含まないスキーマ
+    let v2 = body_json(get(&app, "/api-docs/v2/openapi.json", None).await).await;
+    assert!(v2["paths"]["/v2/users/{id}"]["get"].get("deprecated").is_none());
+    let properties = &v2["components"]["schemas"]["UserResponse"]["properties"];
+    assert!(properties.get("email").is_some());
+    assert!(properties.get("password").is_none());
+
+    // 接頭辞のないドキュメントは以前のまま
+    let unversioned = body_json(get(&app, "/api-docs/openapi.json", None).await).await;
+    assert!(unversioned["paths"].get("/users/{id}").is_some());
+}